@@ -0,0 +1,202 @@
+//! `tipping-cli` (feature `cli`): admin and tipping commands against a deployment, signed by the keypair
+//! and sent to the RPC URL of the Solana CLI config (`solana config get`), either of which
+//! `--keypair` and `--url` override.
+
+use std::{error::Error, str::FromStr};
+
+use clap::{Parser, Subcommand};
+use solana_cli_config::{Config, CONFIG_FILE};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+use tip_program::{
+    client::{self, TipExtras, PROGRAM_ID},
+    state::{CreatorStats, TipConfig, TipperStats},
+    token,
+};
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+#[derive(Parser)]
+#[command(name = "tipping-cli", version, about = "Administer and use a tip program deployment")]
+struct Cli {
+    /// Solana CLI config file (default: the `solana` CLI's own)
+    #[arg(long, short = 'C', global = true)]
+    config: Option<String>,
+    /// RPC URL, instead of the config's `json_rpc_url`
+    #[arg(long, short = 'u', global = true)]
+    url: Option<String>,
+    /// Signer keypair file, instead of the config's `keypair_path`
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<String>,
+    #[arg(long, global = true, default_value_t = PROGRAM_ID)]
+    program_id: Pubkey,
+    /// Platform whose config the command acts on (0 = the deployment's original one)
+    #[arg(long, global = true, default_value_t = 0)]
+    platform: u64,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create the platform config, with the keypair as admin
+    Init {
+        /// Token account that collects the fees
+        #[arg(long)]
+        treasury: Pubkey,
+        #[arg(long)]
+        fee_bps: u16,
+    },
+    /// Schedule a new fee rate, or apply the scheduled one once its activation slot has passed
+    UpdateFee {
+        #[arg(required_unless_present = "apply")]
+        fee_bps: Option<u16>,
+        #[arg(long, conflicts_with = "fee_bps")]
+        apply: bool,
+    },
+    /// Print the platform config
+    ShowConfig,
+    /// Tip a creator from the keypair's associated token account, creating the creator's if missing
+    Tip {
+        creator: Pubkey,
+        /// In base units of the mint
+        amount: u64,
+        #[arg(long)]
+        mint: Pubkey,
+        #[arg(long, default_value = "")]
+        memo: String,
+    },
+    /// Print a creator's stats on the platform, or a tipper's with --tipper
+    Stats {
+        wallet: Pubkey,
+        #[arg(long)]
+        tipper: bool,
+    },
+}
+
+fn main() -> CliResult<()> {
+    let cli = Cli::parse();
+    let config = match cli.config.as_deref().or(CONFIG_FILE.as_deref()) {
+        Some(path) => Config::load(path).unwrap_or_default(),
+        None => Config::default(),
+    };
+    let url = cli.url.unwrap_or(config.json_rpc_url);
+    let rpc = RpcClient::new_with_commitment(url, CommitmentConfig::from_str(&config.commitment)?);
+    let keypair_path = cli.keypair.unwrap_or(config.keypair_path);
+    let signer = || read_keypair_file(&keypair_path).map_err(|e| format!("reading keypair {keypair_path}: {e}"));
+    let (program_id, platform_id) = (cli.program_id, cli.platform);
+
+    match cli.command {
+        Command::Init { treasury, fee_bps } => {
+            let admin = signer()?;
+            let ix = client::initialize(&program_id, platform_id, &treasury, &admin.pubkey(), fee_bps, None);
+            report(send(&rpc, &admin, &[ix])?);
+        }
+        Command::UpdateFee { fee_bps: Some(fee_bps), .. } => {
+            let admin = signer()?;
+            let ix = client::schedule_fee_update(&program_id, platform_id, &admin.pubkey(), fee_bps);
+            report(send(&rpc, &admin, &[ix])?);
+            let config = fetch_config(&rpc, &program_id, platform_id)?;
+            println!("{} bps applies from slot {}", config.pending_fee_bps, config.fee_activation_slot);
+        }
+        Command::UpdateFee { fee_bps: None, .. } => {
+            // Anyone may apply a due update; the keypair only pays for it
+            let payer = signer()?;
+            report(send(&rpc, &payer, &[client::apply_fee_update(&program_id, platform_id)])?);
+        }
+        Command::ShowConfig => print_config(&fetch_config(&rpc, &program_id, platform_id)?),
+        Command::Tip { creator, amount, mint, memo } => {
+            let tipper = signer()?;
+            let token_program = rpc.get_account(&mint)?.owner;
+            let config = fetch_config(&rpc, &program_id, platform_id)?;
+            let tipper_token = token::get_associated_token_address(&tipper.pubkey(), &mint, &token_program);
+            let creator_token = token::get_associated_token_address(&creator, &mint, &token_program);
+            let ixs = [
+                token::create_associated_token_account_idempotent(&tipper.pubkey(), &creator, &mint, &token_program),
+                client::tip(
+                    &program_id,
+                    platform_id,
+                    &tipper.pubkey(),
+                    &tipper_token,
+                    &creator,
+                    &creator_token,
+                    &config.treasury,
+                    &mint,
+                    &token_program,
+                    amount,
+                    &memo,
+                    TipExtras::default(),
+                ),
+            ];
+            report(send(&rpc, &tipper, &ixs)?);
+        }
+        Command::Stats { wallet, tipper: false } => {
+            let (address, _) = client::find_creator_stats_pda(&program_id, platform_id, &wallet);
+            let stats = CreatorStats::load(&rpc.get_account_data(&address)?)?;
+            println!("tips:           {}", stats.tip_count);
+            println!("received:       {}", stats.total_received);
+            println!("largest tip:    {}", stats.largest_tip);
+            println!("epoch volume:   {} (epoch {})", stats.epoch_volume, stats.epoch_index);
+            println!("usd received:   {}", stats.total_usd_received);
+            println!("staked:         {}", stats.total_staked);
+            println!("categories:     {:?}", stats.category_counts);
+            println!("tiers:          {:?}", stats.tier_counts);
+        }
+        Command::Stats { wallet, tipper: true } => {
+            let (address, _) = client::find_tipper_stats_pda(&program_id, platform_id, &wallet);
+            let stats = TipperStats::load(&rpc.get_account_data(&address)?)?;
+            println!("tips:           {}", stats.tip_count);
+            println!("sent:           {}", stats.total_volume);
+            println!("epoch volume:   {} (epoch {})", stats.epoch_volume, stats.epoch_index);
+            println!("streak:         {} days (longest {})", stats.current_streak_days, stats.longest_streak);
+            println!("nonce:          {}", stats.tip_nonce);
+        }
+    }
+    Ok(())
+}
+
+/// Configs of any layout version, so an outdated one can still be inspected before MigrateConfig
+fn fetch_config(rpc: &RpcClient, program_id: &Pubkey, platform_id: u64) -> CliResult<TipConfig> {
+    let (address, _) = client::find_config_pda(program_id, platform_id);
+    Ok(TipConfig::load_any_version(&rpc.get_account_data(&address)?)?)
+}
+
+fn send(rpc: &RpcClient, payer: &Keypair, ixs: &[Instruction]) -> CliResult<Signature> {
+    let blockhash = rpc.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &[payer], blockhash);
+    Ok(rpc.send_and_confirm_transaction(&tx)?)
+}
+
+fn report(signature: Signature) {
+    println!("Signature: {signature}");
+}
+
+fn print_config(config: &TipConfig) {
+    println!("version:        {}", config.version);
+    println!("admin:          {}", config.admin);
+    if config.pending_admin != Pubkey::default() {
+        println!("pending admin:  {}", config.pending_admin);
+    }
+    println!("treasury:       {}", config.treasury);
+    println!("fee:            {} bps", config.fee_bps);
+    if config.fee_activation_slot != 0 {
+        println!("pending fee:    {} bps from slot {}", config.pending_fee_bps, config.fee_activation_slot);
+    }
+    for i in 0..config.fee_tier_count as usize {
+        println!("fee tier:       {} bps from {}", config.fee_tier_bps[i], config.fee_tier_min_amounts[i]);
+    }
+    println!("referral:       {} bps of the fee", config.referral_bps);
+    println!("paused:         {}", config.paused);
+    println!("tips:           {}", config.total_tips);
+    println!("min/max tip:    {} / {}", config.min_tip_amount, config.max_tip_amount);
+    if config.multisig_threshold != 0 {
+        println!("multisig:       {} of {}", config.multisig_threshold, config.multisig_signer_count);
+    }
+}
@@ -0,0 +1,542 @@
+//! Campaigns and matching pools: a creator's fundraising goals, and a sponsor's pool that matches tips
+//! to creators. TipWithCampaign counts a tip toward a campaign and TipWithMatch pays a match on top of
+//! one; both are `tip` with their accounts ahead of Tip's.
+
+use super::*;
+
+/// Match a tip 1:1 from a sponsor's pool, capped by what is left in the pool vault.
+/// Matched tokens go to the creator without a fee.
+#[allow(clippy::too_many_arguments)]
+pub fn match_tip<'a>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'a>],
+    pool_acc: &AccountInfo<'a>,
+    pool_vault: &AccountInfo<'a>,
+    creator: &AccountInfo<'a>,
+    creator_token: &AccountInfo<'a>,
+    mint_acc: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    amount: u64,
+    hook_accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    if pool_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut pool = MatchingPool::unpack(&pool_acc.data.borrow())?;
+    if !pool.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let (pool_pda, pool_bump) = get_matching_pool_pda(program_id, &pool.sponsor, pool.pool_id);
+    if *pool_acc.key != pool_pda || *pool_vault.key != get_pool_vault_pda(program_id, &pool_pda).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if pool.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    if pool.creator != Pubkey::default() && pool.creator != *creator.key {
+        msg!("Matching pool is reserved for {}", pool.creator);
+        return Ok(());
+    }
+
+    let matched = amount.min(token::unpack_account(pool_vault)?.amount);
+    if matched == 0 {
+        msg!("Matching pool exhausted");
+        return Ok(());
+    }
+
+    let decimals = token::unpack_mint(mint_acc)?.decimals;
+    let received = transfer_tokens(
+        accounts,
+        token_program, pool_vault, mint_acc, creator_token, pool_acc, matched, decimals,
+        &[&[b"pool", pool.sponsor.as_ref(), &pool.pool_id.to_le_bytes(), &[pool_bump]]], hook_accounts,
+    )?;
+
+    pool.total_matched = pool.total_matched.checked_add(matched).ok_or(TipError::MathOverflow)?;
+    pool.pack(&mut pool_acc.data.borrow_mut())?;
+    msg!("Matched {} from pool {}", received, pool.pool_id);
+    TipMatched { pool: pool_pda, creator: *creator.key, amount: received }.emit(accounts)
+}
+
+/// Fee override of a campaign the tip references; the campaign itself is checked again when the
+/// tip is counted toward it
+pub fn load_campaign_fee(
+    program_id: &Pubkey,
+    campaign_acc: &AccountInfo,
+    creator: &Pubkey,
+) -> Result<Option<u16>, ProgramError> {
+    validation::program_owned(program_id, campaign_acc)?;
+    let campaign = Campaign::load(&campaign_acc.data.borrow())?;
+    if campaign.creator != *creator {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(campaign.has_fee_override.then_some(campaign.fee_override_bps))
+}
+
+/// How much of a tip of `amount` the campaign takes: all of it, unless it caps its overflow, in which
+/// case only what its target still needs
+pub fn campaign_tip_amount(
+    program_id: &Pubkey,
+    campaign_acc: &AccountInfo,
+    creator: &Pubkey,
+    amount: u64,
+) -> Result<u64, ProgramError> {
+    validation::program_owned(program_id, campaign_acc)?;
+    let campaign = Campaign::load(&campaign_acc.data.borrow())?;
+    if campaign.creator != *creator {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if campaign.overflow == CampaignOverflow::Accept {
+        return Ok(amount);
+    }
+    match campaign.target_amount.saturating_sub(campaign.raised) {
+        0 => {
+            msg!("Campaign {} has raised its target of {}", campaign.campaign_id, campaign.target_amount);
+            Err(TipError::CampaignGoalReached.into())
+        }
+        remaining => Ok(amount.min(remaining)),
+    }
+}
+
+/// Count a tip of `amount`, out of the `requested` amount `campaign_tip_amount` cut it from, toward one
+/// of the creator's open campaigns, logging each 25% milestone it crosses and any excess over the target
+#[allow(clippy::too_many_arguments)]
+pub fn contribute_to_campaign<'a>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    campaign_acc: &AccountInfo<'a>,
+    creator: &AccountInfo,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+    requested: u64,
+) -> ProgramResult {
+    if campaign_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut campaign = Campaign::load(&campaign_acc.data.borrow())?;
+    if !campaign.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let (pda, _) = get_campaign_pda(program_id, &campaign.creator, campaign.campaign_id);
+    if *campaign_acc.key != pda || campaign.creator != *creator.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if campaign.finalized || Clock::get()?.unix_timestamp >= campaign.deadline_ts {
+        msg!("Campaign {} is closed", campaign.campaign_id);
+        return Err(TipError::CampaignClosed.into());
+    }
+
+    let before = campaign.raised;
+    campaign.raised = campaign.raised.checked_add(amount).ok_or(TipError::MathOverflow)?;
+    // Under Cap the excess is what the tip was cut by; under Accept, what it raised past the target
+    let excess = match campaign.overflow {
+        CampaignOverflow::Cap => requested.saturating_sub(amount),
+        CampaignOverflow::Accept => campaign.raised.saturating_sub(campaign.target_amount.max(before)),
+    };
+    let slot = Clock::get()?.slot;
+    let mut reached = vec![];
+    for (quarter, milestone_slot) in (1..).zip(campaign.milestone_slots.iter_mut()) {
+        let threshold = (campaign.target_amount as u128 * quarter).div_ceil(4);
+        if *milestone_slot == 0 && campaign.raised as u128 >= threshold {
+            *milestone_slot = slot;
+            reached.push(25 * quarter as u8);
+        }
+    }
+
+    // Legacy accounts grow to hold the milestone slots
+    if campaign_acc.data_len() < CAMPAIGN_SIZE {
+        grow_account(campaign_acc, payer, system_program, CAMPAIGN_SIZE)?;
+    }
+    campaign.pack(&mut campaign_acc.data.borrow_mut())?;
+    msg!("Campaign {}: {} of {} raised", campaign.campaign_id, campaign.raised, campaign.target_amount);
+    for percent in reached {
+        MilestoneReached {
+            creator: campaign.creator,
+            campaign_id: campaign.campaign_id,
+            percent,
+            raised: campaign.raised,
+            slot,
+        }
+        .emit(accounts)?;
+    }
+    if excess > 0 {
+        GoalExceeded {
+            creator: campaign.creator,
+            campaign_id: campaign.campaign_id,
+            target_amount: campaign.target_amount,
+            raised: campaign.raised,
+            excess,
+            overflow: campaign.overflow,
+        }
+        .emit(accounts)?;
+    }
+    Ok(())
+}
+
+/// CreateCampaign: [campaign_id: u64, target_amount: u64, deadline_ts: i64]
+/// Accounts: [campaign (w), creator (s,w), system_program, payer (optional, s,w)]
+pub fn create_campaign(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    campaign_id: u64,
+    target_amount: u64,
+    deadline_ts: i64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let campaign_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[campaign_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let (pda, bump) = get_campaign_pda(program_id, creator.key, campaign_id);
+    if *campaign_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !campaign_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    if target_amount == 0 {
+        msg!("Campaign target must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if deadline_ts <= Clock::get()?.unix_timestamp {
+        msg!("Campaign deadline must be in the future");
+        return Err(TipError::InvalidExpiry.into());
+    }
+
+    create_pda_account(
+        payer,
+        campaign_acc,
+        system_program,
+        program_id,
+        CAMPAIGN_SIZE,
+        &[b"campaign", creator.key.as_ref(), &campaign_id.to_le_bytes(), &[bump]],
+    )?;
+
+    let campaign = Campaign {
+        is_initialized: true,
+        creator: *creator.key,
+        campaign_id,
+        target_amount,
+        raised: 0,
+        deadline_ts,
+        finalized: false,
+        goal_met: false,
+        milestone_slots: [0; 4],
+        has_fee_override: false,
+        fee_override_bps: 0,
+        overflow: CampaignOverflow::Accept,
+    };
+    campaign.pack(&mut campaign_acc.data.borrow_mut())?;
+
+    msg!("Campaign {} created: target {}", campaign_id, target_amount);
+    CampaignCreated { creator: *creator.key, campaign_id, target_amount, deadline_ts }.emit(accounts)
+}
+
+/// FinalizeCampaign: no data
+/// Accounts: [campaign (w)]
+/// Emits CampaignFinalized; permissionless once the deadline has passed
+pub fn finalize_campaign(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let campaign_acc = next_account_info(iter)?;
+
+    validation::writable(&[campaign_acc])?;
+
+    if campaign_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut campaign = Campaign::load(&campaign_acc.data.borrow())?;
+    if !campaign.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if campaign.finalized {
+        return Err(TipError::CampaignClosed.into());
+    }
+    if Clock::get()?.unix_timestamp < campaign.deadline_ts {
+        msg!("Campaign ends at {}", campaign.deadline_ts);
+        return Err(TipError::CampaignActive.into());
+    }
+
+    campaign.finalized = true;
+    campaign.goal_met = campaign.raised >= campaign.target_amount;
+    // A legacy account no tip has grown yet has no milestones to keep, so it stays at its old size
+    pack_prefix(&campaign, campaign_acc, CAMPAIGN_SIZE)?;
+
+    CampaignFinalized {
+        creator: campaign.creator,
+        campaign_id: campaign.campaign_id,
+        target_amount: campaign.target_amount,
+        raised: campaign.raised,
+        goal_met: campaign.goal_met,
+    }
+    .emit(accounts)
+}
+
+/// SetCampaignOverflow: [overflow: CampaignOverflow]
+/// Accounts: [campaign (w), creator (s,w), system_program, payer (optional, s,w)]
+pub fn set_campaign_overflow(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    overflow: CampaignOverflow,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let campaign_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[campaign_acc, creator])?;
+    validation::program_owned(program_id, campaign_acc)?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let mut campaign = Campaign::load(&campaign_acc.data.borrow())?;
+    if !campaign.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if campaign.creator != *creator.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if campaign.finalized {
+        return Err(TipError::CampaignClosed.into());
+    }
+
+    campaign.overflow = overflow;
+    // Legacy accounts grow to hold the setting
+    if campaign_acc.data_len() < CAMPAIGN_SIZE {
+        grow_account(campaign_acc, payer, system_program, CAMPAIGN_SIZE)?;
+    }
+    campaign.pack(&mut campaign_acc.data.borrow_mut())?;
+
+    msg!("Campaign {} overflow: {:?}", campaign.campaign_id, overflow);
+    CampaignOverflowSet { creator: campaign.creator, campaign_id: campaign.campaign_id, overflow }.emit(accounts)
+}
+
+/// CreateMatchingPool: [pool_id: u64, creator: Pubkey, amount: u64]
+/// Accounts: [pool (w), pool_vault (w), sponsor (s,w), sponsor_token (w), mint, token_program, allowed_mint,
+///            system_program, payer (optional, s,w)]
+/// The vault can be topped up later with a plain token transfer
+pub fn create_matching_pool(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pool_id: u64,
+    creator: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let pool_acc = next_account_info(iter)?;
+    let pool_vault = next_account_info(iter)?;
+    let sponsor = next_account_info(iter)?;
+    let sponsor_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !sponsor.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[pool_acc, pool_vault, sponsor, sponsor_token])?;
+    validation::token_accounts(&[sponsor_token])?;
+    let payer = next_rent_payer(program_id, iter, sponsor)?;
+
+    let (pool_pda, pool_bump) = get_matching_pool_pda(program_id, sponsor.key, pool_id);
+    if *pool_acc.key != pool_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (vault_pda, vault_bump) = get_pool_vault_pda(program_id, &pool_pda);
+    if *pool_vault.key != vault_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !pool_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    if amount == 0 {
+        msg!("Pool deposit must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    if load_allowed_mint(program_id, allowed_mint_acc, &[sponsor_token])?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+
+    create_pda_account(
+        payer,
+        pool_acc,
+        system_program,
+        program_id,
+        MATCHING_POOL_SIZE,
+        &[b"pool", sponsor.key.as_ref(), &pool_id.to_le_bytes(), &[pool_bump]],
+    )?;
+    create_vault(
+        payer,
+        pool_vault,
+        mint_acc,
+        token_program,
+        system_program,
+        &pool_pda,
+        &[b"pool_vault", pool_pda.as_ref(), &[vault_bump]],
+    )?;
+
+    invoke(
+        &token::transfer_checked(
+            token_program.key, sponsor_token.key, mint_acc.key, pool_vault.key, sponsor.key, amount, mint.decimals,
+        ),
+        &[sponsor_token.clone(), mint_acc.clone(), pool_vault.clone(), sponsor.clone()],
+    )?;
+
+    let pool = MatchingPool {
+        is_initialized: true,
+        sponsor: *sponsor.key,
+        pool_id,
+        mint: *mint_acc.key,
+        creator,
+        total_matched: 0,
+    };
+    pool.pack(&mut pool_acc.data.borrow_mut())?;
+
+    msg!("Matching pool {} funded with {}", pool_id, amount);
+    MatchingPoolCreated { sponsor: *sponsor.key, pool_id, mint: *mint_acc.key, creator, amount }.emit(accounts)
+}
+
+/// CloseMatchingPool: no data
+/// Accounts: [pool (w), pool_vault (w), sponsor (s,w), sponsor_token (w), mint, token_program]
+pub fn close_matching_pool(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let pool_acc = next_account_info(iter)?;
+    let pool_vault = next_account_info(iter)?;
+    let sponsor = next_account_info(iter)?;
+    let sponsor_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    if !sponsor.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[pool_acc, pool_vault, sponsor, sponsor_token])?;
+    validation::token_accounts(&[pool_vault, sponsor_token])?;
+
+    if pool_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let pool = MatchingPool::unpack(&pool_acc.data.borrow())?;
+    if !pool.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if pool.sponsor != *sponsor.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    let (pool_pda, pool_bump) = get_matching_pool_pda(program_id, &pool.sponsor, pool.pool_id);
+    if *pool_acc.key != pool_pda || *pool_vault.key != get_pool_vault_pda(program_id, &pool_pda).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if pool.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let signer_seeds: &[&[u8]] = &[b"pool", pool.sponsor.as_ref(), &pool.pool_id.to_le_bytes(), &[pool_bump]];
+    let remaining = token::unpack_account(pool_vault)?.amount;
+    if remaining > 0 {
+        invoke_signed(
+            &token::transfer_checked(
+                token_program.key, pool_vault.key, mint_acc.key, sponsor_token.key, &pool_pda, remaining, mint.decimals,
+            ),
+            &[pool_vault.clone(), mint_acc.clone(), sponsor_token.clone(), pool_acc.clone()],
+            &[signer_seeds],
+        )?;
+    }
+    invoke_signed(
+        &token::close_account(token_program.key, pool_vault.key, sponsor.key, &pool_pda),
+        &[pool_vault.clone(), sponsor.clone(), pool_acc.clone()],
+        &[signer_seeds],
+    )?;
+
+    close_pda(pool_acc, sponsor)?;
+
+    msg!("Matching pool {} closed: {} returned, {} matched", pool.pool_id, remaining, pool.total_matched);
+    MatchingPoolClosed {
+        sponsor: pool.sponsor,
+        pool_id: pool.pool_id,
+        returned: remaining,
+        total_matched: pool.total_matched,
+    }
+    .emit(accounts)
+}
+
+/// SetCampaignFee: [fee_bps: Option<u16>]
+/// Accounts: [campaign (w), authority (s,w), config, system_program,
+///            instructions_sysvar (if the admin signs and admin_isolation is on), payer (optional, s,w)]
+/// Signed by the campaign's creator or platform 0's admin; only the admin's changes go to the audit log
+pub fn set_campaign_fee(program_id: &Pubkey, accounts: &[AccountInfo], fee_bps: Option<u16>) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let campaign_acc = next_account_info(iter)?;
+    let authority = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[campaign_acc, authority])?;
+    validation::program_owned(program_id, campaign_acc)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let mut campaign = Campaign::load(&campaign_acc.data.borrow())?;
+    if !campaign.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    // Campaigns are shared by every platform, so only platform 0's admin may override them
+    let is_admin = config.admin == *authority.key && config.platform_id == 0;
+    if campaign.creator != *authority.key && !is_admin {
+        return Err(TipError::Unauthorized.into());
+    }
+    if is_admin {
+        check_admin_isolation(&config, iter)?;
+    }
+    let payer = next_rent_payer(program_id, iter, authority)?;
+
+    if fee_bps.is_some_and(|bps| bps > 1000) {
+        return Err(TipError::FeeTooHigh.into());
+    }
+    if campaign.finalized {
+        return Err(TipError::CampaignClosed.into());
+    }
+
+    let old_value = audit_words(&[campaign.has_fee_override as u64, campaign.fee_override_bps as u64]);
+    campaign.has_fee_override = fee_bps.is_some();
+    campaign.fee_override_bps = fee_bps.unwrap_or(0);
+    // Legacy accounts grow to hold the override
+    if campaign_acc.data_len() < CAMPAIGN_SIZE {
+        grow_account(campaign_acc, payer, system_program, CAMPAIGN_SIZE)?;
+    }
+    campaign.pack(&mut campaign_acc.data.borrow_mut())?;
+    if is_admin {
+        audit(
+            program_id,
+            accounts,
+            &config,
+            AdminAction::SetCampaignFee,
+            authority.key,
+            campaign_acc.key,
+            old_value,
+            audit_words(&[campaign.has_fee_override as u64, campaign.fee_override_bps as u64]),
+        )?;
+    }
+
+    msg!("Campaign {} fee: {:?} bps", campaign.campaign_id, fee_bps);
+    CampaignFeeSet { creator: campaign.creator, campaign_id: campaign.campaign_id, fee_bps }.emit(accounts)
+}
@@ -0,0 +1,281 @@
+//! Off-chain helpers for bots and backends (feature `client`).
+//!
+//! Instruction builders live in `crate::instruction` and are re-exported here. Account helpers
+//! decode raw account data, so any RPC client works: fetch with e.g. `RpcClient::get_account_data`
+//! and pass the bytes in. Helpers for a platform's PDAs take its `platform_id`; 0 is the
+//! deployment's original platform.
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+pub use crate::instruction::*;
+pub use crate::state::{
+    AccountType, BadgeTier, ClosableAccount, DelegatedTip, FeeTier, Role, SelfTipPolicy, SplitShare, TipConfig,
+};
+use crate::state::*;
+pub use crate::PROGRAM_ID;
+
+pub fn find_config_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    get_config_pda(program_id, platform_id)
+}
+
+pub fn find_creator_stats_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey) -> (Pubkey, u8) {
+    get_creator_stats_pda(program_id, platform_id, creator)
+}
+
+pub fn find_tipper_stats_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey) -> (Pubkey, u8) {
+    get_tipper_stats_pda(program_id, platform_id, tipper)
+}
+
+pub fn find_notify_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_notify_pda(program_id, creator)
+}
+
+pub fn find_allowed_mint_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    get_allowed_mint_pda(program_id, platform_id, mint)
+}
+
+pub fn find_fee_override_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey) -> (Pubkey, u8) {
+    get_fee_override_pda(program_id, platform_id, creator)
+}
+
+pub fn find_session_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey) -> (Pubkey, u8) {
+    get_session_pda(program_id, platform_id, tipper)
+}
+
+/// SPL delegate to approve on the tipper's token account for `tip_delegated`; its `Delegation::nonce`
+/// (0 before the first delegated tip) goes into the next DelegatedTip
+pub fn find_delegation_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey) -> (Pubkey, u8) {
+    get_delegation_pda(program_id, platform_id, tipper)
+}
+
+pub fn find_split_config_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_split_config_pda(program_id, creator)
+}
+
+pub fn find_collaborator_split_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_collaborator_split_pda(program_id, creator)
+}
+
+pub fn find_scheduled_tip_pda(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    schedule_id: u64,
+) -> (Pubkey, u8) {
+    get_scheduled_tip_pda(program_id, platform_id, tipper, creator, schedule_id)
+}
+
+pub fn find_subscription_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_subscription_pda(program_id, platform_id, tipper, creator)
+}
+
+pub fn find_escrow_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_escrow_pda(program_id, platform_id, tipper, creator)
+}
+
+pub fn find_campaign_pda(program_id: &Pubkey, creator: &Pubkey, campaign_id: u64) -> (Pubkey, u8) {
+    get_campaign_pda(program_id, creator, campaign_id)
+}
+
+pub fn find_matching_pool_pda(program_id: &Pubkey, sponsor: &Pubkey, pool_id: u64) -> (Pubkey, u8) {
+    get_matching_pool_pda(program_id, sponsor, pool_id)
+}
+
+pub fn find_tip_receipt_pda(program_id: &Pubkey, tipper: &Pubkey, creator: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    get_tip_receipt_pda(program_id, tipper, creator, nonce)
+}
+
+pub fn find_badge_config_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_badge_config_pda(program_id, creator)
+}
+
+pub fn find_supporter_stats_pda(program_id: &Pubkey, tipper: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_supporter_stats_pda(program_id, tipper, creator)
+}
+
+pub fn find_creator_profile_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_creator_profile_pda(program_id, creator)
+}
+
+/// Decode a TipSplit configuration of any size; `shares[..recipient_count]` are its entries
+pub fn decode_split_config(data: &[u8]) -> Result<SplitConfig, ProgramError> {
+    SplitConfig::load(data)
+}
+
+/// Decode a collaborator split of any size, e.g. to list `CollaboratorSplit::entries`
+pub fn decode_collaborator_split(data: &[u8]) -> Result<CollaboratorSplit, ProgramError> {
+    CollaboratorSplit::load(data)
+}
+
+/// Decode a tip receipt of either layout, e.g. to check its `external_ref`
+pub fn decode_tip_receipt(data: &[u8]) -> Result<TipReceipt, ProgramError> {
+    TipReceipt::load(data)
+}
+
+/// Decode a creator profile of either layout, e.g. to show its tip menu with `CreatorProfile::tip_menu`
+pub fn decode_creator_profile(data: &[u8]) -> Result<CreatorProfile, ProgramError> {
+    CreatorProfile::load(data)
+}
+
+pub fn find_creator_vault_pda(program_id: &Pubkey, creator: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    get_creator_vault_pda(program_id, creator, mint)
+}
+
+/// Token account to pass as `creator_token` once the creator has opened a vault for `mint`
+pub fn find_creator_vault_token(program_id: &Pubkey, creator: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_creator_vault_token_pda(program_id, &get_creator_vault_pda(program_id, creator, mint).0).0
+}
+
+pub fn find_vesting_pda(program_id: &Pubkey, creator: &Pubkey, mint: &Pubkey, index: u64) -> (Pubkey, u8) {
+    get_vesting_pda(program_id, &get_creator_vault_pda(program_id, creator, mint).0, index)
+}
+
+pub fn find_sol_vault_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_sol_vault_pda(program_id, creator)
+}
+
+pub fn find_multisig_authority_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    get_multisig_authority_pda(program_id, platform_id)
+}
+
+pub fn find_pending_action_pda(program_id: &Pubkey, platform_id: u64, nonce: u64) -> (Pubkey, u8) {
+    get_pending_action_pda(program_id, platform_id, nonce)
+}
+
+/// PDA of the epoch containing `unix_timestamp`
+pub fn find_epoch_stats_pda(program_id: &Pubkey, platform_id: u64, unix_timestamp: i64) -> (Pubkey, u8) {
+    get_epoch_stats_pda(program_id, platform_id, epoch_index(unix_timestamp))
+}
+
+/// Leaderboard PDA of the epoch containing `unix_timestamp`
+pub fn find_leaderboard_pda(program_id: &Pubkey, platform_id: u64, unix_timestamp: i64) -> (Pubkey, u8) {
+    get_leaderboard_pda(program_id, platform_id, epoch_index(unix_timestamp))
+}
+
+/// Tipper leaderboard PDA of the epoch containing `unix_timestamp`
+pub fn find_tipper_leaderboard_pda(program_id: &Pubkey, platform_id: u64, unix_timestamp: i64) -> (Pubkey, u8) {
+    get_tipper_leaderboard_pda(program_id, platform_id, epoch_index(unix_timestamp))
+}
+
+pub fn find_reward_pool_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    get_reward_pool_pda(program_id, platform_id)
+}
+
+pub fn find_audit_log_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    get_audit_log_pda(program_id, platform_id)
+}
+
+pub fn find_promo_code_pda(program_id: &Pubkey, platform_id: u64, code: &str) -> (Pubkey, u8) {
+    get_promo_code_pda(program_id, platform_id, &promo_code_hash(code))
+}
+
+pub fn find_emergency_withdrawal_pda(program_id: &Pubkey, platform_id: u64, vault: &Pubkey) -> (Pubkey, u8) {
+    get_emergency_withdrawal_pda(program_id, platform_id, vault)
+}
+
+/// Audit log entries still held by the ring buffer, oldest first
+pub fn decode_audit_log(data: &[u8]) -> Result<Vec<AuditEntry>, ProgramError> {
+    let log = AuditLog::load(data)?;
+    let count = log.count as usize;
+    let entries = log.entries;
+    let start = count.saturating_sub(AUDIT_LOG_ENTRIES);
+    Ok((start..count).map(|i| entries[i % AUDIT_LOG_ENTRIES]).collect())
+}
+
+/// Ranked creators (or tippers, on a tipper leaderboard) on a leaderboard account, highest volume first
+pub fn decode_leaderboard(data: &[u8]) -> Result<Vec<(Pubkey, u64)>, ProgramError> {
+    let board = Leaderboard::load(data)?;
+    let entries = board.entries;
+    Ok(entries.iter().filter(|e| { e.volume } > 0).map(|e| (e.creator, e.volume)).collect())
+}
+
+pub fn find_mint_stats_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    get_mint_stats_pda(program_id, platform_id, mint)
+}
+
+pub fn find_tipper_rebate_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    get_tipper_rebate_pda(program_id, platform_id, tipper, mint)
+}
+
+pub fn find_treasury_vault_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    get_treasury_vault_pda(program_id, platform_id, mint)
+}
+
+pub fn find_stake_position_pda(
+    program_id: &Pubkey,
+    platform_id: u64,
+    supporter: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+) -> (Pubkey, u8) {
+    get_stake_position_pda(program_id, platform_id, supporter, creator, mint)
+}
+
+pub fn find_stake_vault_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    get_stake_vault_pda(program_id, platform_id, creator, mint)
+}
+
+pub fn find_volume_window_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    get_volume_window_pda(program_id, platform_id)
+}
+
+pub fn find_fan_club_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_fan_club_pda(program_id, creator)
+}
+
+pub fn find_forward_rule_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    get_forward_rule_pda(program_id, creator)
+}
+
+/// Deposit address to hand out for plain SOL transfers; token transfers go to its ATA for the mint
+/// (see `find_tip_link_token`)
+pub fn find_tip_link_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey, link_id: u64) -> (Pubkey, u8) {
+    get_tip_link_pda(program_id, platform_id, creator, link_id)
+}
+
+pub fn find_tip_link_token(
+    program_id: &Pubkey,
+    platform_id: u64,
+    creator: &Pubkey,
+    link_id: u64,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    let (tip_link, _) = get_tip_link_pda(program_id, platform_id, creator, link_id);
+    crate::token::get_associated_token_address(&tip_link, mint, token_program)
+}
+
+pub fn find_stats_snapshot_pda(program_id: &Pubkey, platform_id: u64, epoch_index: u64) -> (Pubkey, u8) {
+    get_stats_snapshot_pda(program_id, platform_id, epoch_index)
+}
+
+pub fn find_creator_stats_snapshot_pda(
+    program_id: &Pubkey,
+    platform_id: u64,
+    creator: &Pubkey,
+    epoch_index: u64,
+) -> (Pubkey, u8) {
+    get_creator_stats_snapshot_pda(program_id, platform_id, creator, epoch_index)
+}
+
+pub fn find_charity_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    get_charity_pda(program_id, platform_id, mint)
+}
+
+/// Tip volume over the last 24 hours and the last 7 days as of `unix_timestamp`, from the
+/// volume window account
+pub fn decode_rolling_volume(data: &[u8], unix_timestamp: i64) -> Result<(u64, u64), ProgramError> {
+    let window = VolumeWindow::load(data)?;
+    Ok((window.volume(unix_timestamp, 24), window.volume(unix_timestamp, VOLUME_WINDOW_BUCKETS)))
+}
+
+/// Decode the config account; fails with `ConfigOutdated` until MigrateConfig has run
+pub fn decode_config(data: &[u8]) -> Result<TipConfig, ProgramError> {
+    TipConfig::load(data)
+}
+
+/// Decode any other program account, e.g. `decode_account::<CreatorStats>(&data)`
+pub fn decode_account<T: AccountType>(data: &[u8]) -> Result<T, ProgramError> {
+    T::unpack(data)
+}
@@ -0,0 +1,47 @@
+//! Thin layer over SPL Account Compression for compressed tip receipts. The program is built with
+//! Anchor, so each instruction is an 8-byte sighash, sha256("global:<name>")[..8], followed by the
+//! Borsh-encoded arguments.
+
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+
+pub const ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+/// Log wrapper the compression program emits changelogs through, for indexers
+pub const NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+const INIT_EMPTY_MERKLE_TREE: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+const APPEND: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+
+/// `merkle_tree` must already be allocated (owned by the compression program) with room for
+/// `max_depth` and `max_buffer_size`
+pub fn init_empty_merkle_tree(merkle_tree: &Pubkey, authority: &Pubkey, max_depth: u32, max_buffer_size: u32) -> Instruction {
+    let mut data = INIT_EMPTY_MERKLE_TREE.to_vec();
+    data.extend_from_slice(&max_depth.to_le_bytes());
+    data.extend_from_slice(&max_buffer_size.to_le_bytes());
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*merkle_tree, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(NOOP_ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn append(merkle_tree: &Pubkey, authority: &Pubkey, leaf: [u8; 32]) -> Instruction {
+    let mut data = APPEND.to_vec();
+    data.extend_from_slice(&leaf);
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*merkle_tree, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(NOOP_ID, false),
+        ],
+        data,
+    }
+}
@@ -0,0 +1,170 @@
+//! Typed wrappers for on-chain programs that call this one (feature `cpi`).
+//!
+//! `TippingProgram` wraps the tipping program's account and builds the account metas and data for
+//! each call, so callers only pass `AccountInfo`s. A tipper owned by the calling program signs with
+//! `signer_seeds`; pass `&[]` when the tipper signed the outer transaction. Reads check the account's
+//! owner and address before decoding, so a spoofed config or stats account is rejected.
+
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    msg,
+    program::{get_return_data, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::TipError;
+use crate::instruction::TipInstruction;
+use crate::state::{get_creator_stats_pda, CreatorStats, TipConfig};
+use crate::{check_config_address, validation, TipOutcome, PROGRAM_ID};
+
+/// The accounts of `Tip`, in its order; see `instruction::tip` for how each is derived
+pub struct TipAccounts<'a, 'info> {
+    pub config: &'a AccountInfo<'info>,
+    pub tipper: &'a AccountInfo<'info>,
+    pub tipper_token: &'a AccountInfo<'info>,
+    pub creator: &'a AccountInfo<'info>,
+    pub creator_token: &'a AccountInfo<'info>,
+    pub treasury_token: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub allowed_mint: &'a AccountInfo<'info>,
+    pub creator_stats: &'a AccountInfo<'info>,
+    pub tipper_stats: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub blocked_creator: &'a AccountInfo<'info>,
+    pub blocked_tipper: &'a AccountInfo<'info>,
+    pub mint_stats: &'a AccountInfo<'info>,
+    pub creator_notify: &'a AccountInfo<'info>,
+    /// Optional trailing accounts, already in `Tip`'s slot order; passed on with their own flags
+    pub remaining: &'a [AccountInfo<'info>],
+}
+
+pub struct TippingProgram<'a, 'info> {
+    program: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> TippingProgram<'a, 'info> {
+    /// Wrap the mainnet deployment at `PROGRAM_ID`
+    pub fn new(program: &'a AccountInfo<'info>) -> Result<Self, ProgramError> {
+        Self::with_program_id(program, &PROGRAM_ID)
+    }
+
+    /// Wrap another deployment, e.g. on devnet or a localnet validator
+    pub fn with_program_id(program: &'a AccountInfo<'info>, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if program.key != program_id || !program.executable {
+            msg!("{} is not the tipping program {}", program.key, program_id);
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        Ok(Self { program })
+    }
+
+    pub fn program_id(&self) -> &Pubkey {
+        self.program.key
+    }
+
+    /// CPI `Tip`, returning how it was split
+    pub fn tip(
+        &self,
+        accounts: &TipAccounts<'a, 'info>,
+        amount: u64,
+        memo: &str,
+        category: u8,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<TipOutcome, ProgramError> {
+        let fixed = [
+            AccountMeta::new(*accounts.config.key, false),
+            AccountMeta::new(*accounts.tipper.key, true),
+            AccountMeta::new(*accounts.tipper_token.key, false),
+            AccountMeta::new_readonly(*accounts.creator.key, false),
+            AccountMeta::new(*accounts.creator_token.key, false),
+            AccountMeta::new(*accounts.treasury_token.key, false),
+            AccountMeta::new_readonly(*accounts.mint.key, false),
+            AccountMeta::new_readonly(*accounts.token_program.key, false),
+            AccountMeta::new_readonly(*accounts.allowed_mint.key, false),
+            AccountMeta::new(*accounts.creator_stats.key, false),
+            AccountMeta::new(*accounts.tipper_stats.key, false),
+            AccountMeta::new_readonly(*accounts.system_program.key, false),
+            AccountMeta::new_readonly(*accounts.blocked_creator.key, false),
+            AccountMeta::new_readonly(*accounts.blocked_tipper.key, false),
+            AccountMeta::new(*accounts.mint_stats.key, false),
+            AccountMeta::new_readonly(*accounts.creator_notify.key, false),
+        ];
+        let metas = fixed.into_iter().chain(accounts.remaining.iter().map(|acc| AccountMeta {
+            pubkey: *acc.key,
+            is_signer: acc.is_signer,
+            is_writable: acc.is_writable,
+        }));
+        let ix = Instruction::new_with_borsh(
+            *self.program.key,
+            &TipInstruction::Tip { amount, memo: memo.to_string(), category },
+            metas.collect(),
+        );
+
+        let mut infos = vec![
+            accounts.config.clone(),
+            accounts.tipper.clone(),
+            accounts.tipper_token.clone(),
+            accounts.creator.clone(),
+            accounts.creator_token.clone(),
+            accounts.treasury_token.clone(),
+            accounts.mint.clone(),
+            accounts.token_program.clone(),
+            accounts.allowed_mint.clone(),
+            accounts.creator_stats.clone(),
+            accounts.tipper_stats.clone(),
+            accounts.system_program.clone(),
+            accounts.blocked_creator.clone(),
+            accounts.blocked_tipper.clone(),
+            accounts.mint_stats.clone(),
+            accounts.creator_notify.clone(),
+            self.program.clone(),
+        ];
+        infos.extend_from_slice(accounts.remaining);
+        invoke_signed(&ix, &infos, signer_seeds)?;
+
+        self.return_data()
+    }
+
+    /// Decode `config` after checking it is a config of this deployment; fails if it is uninitialized.
+    /// Reads the account directly, since a config is larger than the runtime's return data limit.
+    pub fn read_config(&self, config: &AccountInfo) -> Result<TipConfig, ProgramError> {
+        let decoded = TipConfig::load(&config.data.borrow())?;
+        check_config_address(self.program.key, config, decoded.platform_id, decoded.bump)?;
+        if !decoded.is_initialized {
+            return Err(TipError::Uninitialized.into());
+        }
+        Ok(decoded)
+    }
+
+    /// Decode `creator`'s stats on `platform_id`, as `GetCreatorStats` would return them: fields missing
+    /// from a legacy account at zero, and all zero but `creator` if they have never been tipped
+    pub fn read_creator_stats(
+        &self,
+        creator_stats: &AccountInfo,
+        creator: &Pubkey,
+        platform_id: u64,
+    ) -> Result<CreatorStats, ProgramError> {
+        if *creator_stats.key != get_creator_stats_pda(self.program.key, platform_id, creator).0 {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if creator_stats.data_is_empty() {
+            return Ok(CreatorStats { creator: *creator, ..CreatorStats::load(&[])? });
+        }
+        validation::program_owned(self.program.key, creator_stats)?;
+        CreatorStats::load(&creator_stats.data.borrow())
+    }
+
+    /// The `TipOutcome` the last call left, which must come from this program
+    fn return_data(&self) -> Result<TipOutcome, ProgramError> {
+        match get_return_data() {
+            Some((program_id, data)) if program_id == *self.program.key => Ok(TipOutcome::try_from_slice(&data)?),
+            _ => {
+                msg!("Tipping program returned no outcome");
+                Err(TipError::MissingReturnData.into())
+            }
+        }
+    }
+}
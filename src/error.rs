@@ -0,0 +1,199 @@
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+/// Surfaced to clients as `ProgramError::Custom(code)`; codes match idl.json
+#[derive(Error, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TipError {
+    #[error("Tip amount must be greater than zero")]
+    ZeroAmount = 6000,
+    #[error("Fee basis points cannot exceed 1000 (10%)")]
+    FeeTooHigh,
+    #[error("Memo cannot exceed 280 characters")]
+    MemoTooLong,
+    #[error("Account is not initialized")]
+    Uninitialized,
+    #[error("Signer is not authorized for this account")]
+    Unauthorized,
+    #[error("Treasury token account does not match config")]
+    TreasuryMismatch,
+    #[error("Treasury wallet does not own the treasury token account")]
+    TreasuryWalletMismatch,
+    #[error("Treasury must be an initialized SPL token account")]
+    InvalidTreasury,
+    #[error("Arithmetic overflow")]
+    MathOverflow,
+    #[error("Tip too small for the fee to be represented")]
+    FeeNotRepresentable,
+    #[error("Source token account is frozen")]
+    SourceAccountFrozen,
+    #[error("Instructions sysvar required while admin isolation is on")]
+    MissingInstructionsSysvar,
+    #[error("Admin instruction must be alone in its transaction")]
+    AdminNotIsolated,
+    #[error("Session expiry must be in the future")]
+    InvalidExpiry,
+    #[error("Session has expired")]
+    SessionExpired,
+    #[error("Tip exceeds the remaining session budget")]
+    SessionBudgetExhausted,
+    #[error("Token account does not match the session or subscription")]
+    SessionTokenMismatch,
+    #[error("Tipping is paused")]
+    Paused,
+    #[error("Token accounts must share one mint")]
+    MintMismatch,
+    #[error("Mint is not on the allowlist")]
+    MintNotAllowed,
+    #[error("Creator token account is not the creator's associated token account")]
+    CreatorTokenMismatch,
+    #[error("Split needs 1-5 distinct recipients whose shares sum to 10000 bps")]
+    InvalidSplit,
+    #[error("Subscription interval must be > 0")]
+    InvalidInterval,
+    #[error("Subscription payment is not due yet")]
+    PaymentNotDue,
+    #[error("Escrow timeout has not passed yet")]
+    EscrowNotExpired,
+    #[error("Campaign is closed")]
+    CampaignClosed,
+    #[error("Campaign deadline has not passed yet")]
+    CampaignActive,
+    #[error("Epoch stats account is not for the current epoch")]
+    WrongEpoch,
+    #[error("Tipper cannot be their own referrer")]
+    SelfReferral,
+    #[error("Tipping must be paused first")]
+    NotPaused,
+    #[error("Config uses an older layout; run MigrateConfig")]
+    ConfigOutdated,
+    #[error("Account discriminator does not match the expected account type")]
+    WrongAccountType,
+    #[error("Too many fee tiers, or tiers not sorted by strictly increasing min_amount")]
+    InvalidFeeTiers,
+    #[error("Tip amount is below the minimum")]
+    TipTooSmall,
+    #[error("Tip amount is above the maximum")]
+    TipTooLarge,
+    #[error("Tipper exceeded the rate limit for this window")]
+    RateLimited,
+    #[error("No fee update is scheduled")]
+    NoPendingFeeUpdate,
+    #[error("Scheduled fee update is still timelocked")]
+    FeeUpdateTimelocked,
+    #[error("Multisig needs 1-5 distinct signers and a threshold between 1 and the signer count")]
+    InvalidMultisig,
+    #[error("Signer is not in the multisig signer set")]
+    NotMultisigSigner,
+    #[error("Action must be a decodable non-multisig instruction of at most 512 bytes")]
+    InvalidAction,
+    #[error("Action does not have enough approvals yet")]
+    ThresholdNotMet,
+    #[error("Multisig signer set changed since the action was proposed")]
+    StaleAction,
+    #[error("NFT receipts are not configured")]
+    ReceiptNftDisabled,
+    #[error("Receipt URI template cannot exceed 128 bytes")]
+    UriTooLong,
+    #[error("Collection mint does not match config")]
+    CollectionMismatch,
+    #[error("Merkle tree does not match the config's receipt tree")]
+    ReceiptTreeMismatch,
+    #[error("Badge thresholds must be non-zero and strictly increasing")]
+    InvalidBadgeThresholds,
+    #[error("Tip volume does not reach a higher badge tier")]
+    NoBadgeUpgrade,
+    #[error("Tip is at or above the vesting threshold and must vest in the creator's vault through Tip")]
+    VestingRequired,
+    #[error("Nothing new has vested yet")]
+    NothingVested,
+    #[error("Tip is under dispute")]
+    TipDisputed,
+    #[error("Dispute window for this tip has closed")]
+    DisputeWindowClosed,
+    #[error("Tip is not under dispute")]
+    NotDisputed,
+    #[error("Anonymous tips cannot create receipts, supporter stats or vesting schedules")]
+    AnonymousTipLinked,
+    #[error("Creator is blocked")]
+    CreatorBlocked,
+    #[error("Tipper is blocked")]
+    TipperBlocked,
+    #[error("Display name cannot exceed 32 bytes or metadata URI 200 bytes")]
+    ProfileFieldTooLong,
+    #[error("Creator is not verified")]
+    CreatorNotVerified,
+    #[error("Tip category out of range")]
+    InvalidCategory,
+    #[error("Delegated tip needs the tipper's Ed25519 signature over its message")]
+    InvalidTipSignature,
+    #[error("Delegated tip authorization has expired")]
+    TipAuthorizationExpired,
+    #[error("Tip must be a top-level instruction, not a CPI")]
+    CpiNotAllowed,
+    #[error("Account must be writable")]
+    AccountNotWritable,
+    #[error("Price feed is not trading or its price is stale")]
+    StalePrice,
+    #[error("Price feed is not the one set for this mint")]
+    PriceFeedMismatch,
+    #[error("USD tips must pass the mint's price feed")]
+    PriceFeedRequired,
+    #[error("Tip converts to more than the maximum amount")]
+    SlippageExceeded,
+    #[error("Unstaked tokens are still unbonding")]
+    StillUnbonding,
+    #[error("Scheduled tip is not due yet")]
+    NotYetDue,
+    #[error("Epoch has not ended yet")]
+    EpochNotOver,
+    #[error("Reward pool needs between 1 and 10 winners")]
+    InvalidRewardWinners,
+    #[error("No upgrade authority was recorded at Initialize")]
+    UpgradeAuthorityNotRecorded,
+    #[error("Program upgrade authority does not match the one recorded at Initialize")]
+    UpgradeAuthorityMismatch,
+    #[error("Emergency withdrawal is still timelocked")]
+    EmergencyWithdrawTimelocked,
+    #[error("Audit log must be passed to admin instructions once opened")]
+    AuditLogRequired,
+    #[error("Promo code has expired")]
+    PromoCodeExpired,
+    #[error("Promo code has no uses left")]
+    PromoCodeExhausted,
+    #[error("Tip menu holds up to 6 amounts, none zero or below the creator's minimum")]
+    InvalidTipMenu,
+    #[error("Fan club discount cannot exceed 5000 bps")]
+    InvalidFanDiscount,
+    #[error("Token is not an NFT of the creator's verified fan club collection held by the tipper")]
+    NotFanClubMember,
+    #[error("Tip tiers need a tip mint and up to 4 non-zero, strictly increasing thresholds")]
+    InvalidTipTiers,
+    #[error("Donation needs the mint's charity and cannot exceed its max_donation_bps")]
+    InvalidDonation,
+    #[error("Forward rule needs a destination other than the creator and at most 10000 bps")]
+    InvalidForwardRule,
+    #[error("Tipper is the creator or controls the creator's payout account")]
+    SelfTipNotAllowed,
+    #[error("Swap needs the configured swap program, the creator's payout mint and ATA, and no vesting")]
+    InvalidSwapRoute,
+    #[error("Swap did not spend exactly the creator's share or returned less than the minimum")]
+    SwapBelowMinimum,
+    #[error("Tipping program returned no data to the caller")]
+    MissingReturnData,
+    #[error("Reward token account is not the ranked tipper's associated token account")]
+    RewardTokenMismatch,
+    #[error("Registered creators have reached the config's max_creators")]
+    CreatorCapReached,
+    #[error("Tip nonce is not the tipper's next nonce")]
+    TipNonceMismatch,
+    #[error("Tax withholding needs at most 10000 bps and a tax account while on")]
+    InvalidTaxWithholding,
+    #[error("Campaign has met its target and takes no more than it")]
+    CampaignGoalReached,
+}
+
+impl From<TipError> for ProgramError {
+    fn from(e: TipError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
@@ -0,0 +1,270 @@
+//! Escrowed tips held in a vault until the creator accepts or declines them, or the tipper reclaims
+//! them after ESCROW_TIMEOUT_SECS.
+
+use super::*;
+
+/// EscrowTip: [amount: u64]
+/// Accounts: [escrow (w), vault (w), tipper (s,w), tipper_token (w), creator, mint, token_program, allowed_mint,
+///            system_program, blocked_tipper, (config), payer (optional, s,w)]
+/// One pending escrow per (tipper, creator) on the config's platform (platform 0 without one), whose
+/// config alone can settle it; the tipper pays rent for both PDAs and gets it back on resolution
+pub fn escrow_tip(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let escrow_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[escrow_acc, vault, tipper, tipper_token])?;
+    validation::token_accounts(&[tipper_token])?;
+    let platform_id = optional_platform(program_id, iter)?;
+    let payer = next_rent_payer(program_id, iter, tipper)?;
+    check_tipper_not_blocked(program_id, platform_id, blocked_tipper_acc, tipper.key)?;
+
+    let (escrow_pda, escrow_bump) = get_escrow_pda(program_id, platform_id, tipper.key, creator.key);
+    if *escrow_acc.key != escrow_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (vault_pda, vault_bump) = get_escrow_vault_pda(program_id, &escrow_pda);
+    if *vault.key != vault_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !escrow_acc.data_is_empty() {
+        msg!("An escrowed tip to this creator is already pending");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    if amount == 0 {
+        msg!("Tip amount must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    if check_allowed_mint(program_id, platform_id, allowed_mint_acc, &[tipper_token])?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+
+    create_pda_account(
+        payer,
+        escrow_acc,
+        system_program,
+        program_id,
+        ESCROW_SIZE,
+        &[b"escrow", &platform_seed(platform_id), tipper.key.as_ref(), creator.key.as_ref(), &[escrow_bump]],
+    )?;
+    create_vault(
+        payer,
+        vault,
+        mint_acc,
+        token_program,
+        system_program,
+        &escrow_pda,
+        &[b"vault", escrow_pda.as_ref(), &[vault_bump]],
+    )?;
+
+    // The escrow holds what reaches the vault, which a Token-2022 transfer fee may have cut
+    let amount = transfer_tokens(
+        accounts, token_program, tipper_token, mint_acc, vault, tipper, amount, mint.decimals, &[], &[],
+    )?;
+
+    let escrow = Escrow {
+        is_initialized: true,
+        tipper: *tipper.key,
+        creator: *creator.key,
+        tipper_token: *tipper_token.key,
+        mint: *mint_acc.key,
+        amount,
+        created_ts: Clock::get()?.unix_timestamp,
+    };
+    escrow.pack(&mut escrow_acc.data.borrow_mut())?;
+
+    msg!("Tip escrowed: {} for {}", amount, creator.key);
+    TipEscrowed { tipper: *tipper.key, creator: *creator.key, mint: *mint_acc.key, amount }.emit(accounts)
+}
+
+/// Load and check an escrow of `platform_id` with its vault; returns the escrow and its bump
+pub fn load_escrow(
+    program_id: &Pubkey,
+    platform_id: u64,
+    escrow_acc: &AccountInfo,
+    vault: &AccountInfo,
+    mint_acc: &AccountInfo,
+    tipper: &AccountInfo,
+) -> Result<(Escrow, u8), ProgramError> {
+    if escrow_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let escrow = Escrow::unpack(&escrow_acc.data.borrow())?;
+    if !escrow.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let (escrow_pda, escrow_bump) = get_escrow_pda(program_id, platform_id, &escrow.tipper, &escrow.creator);
+    if *escrow_acc.key != escrow_pda || *vault.key != get_escrow_vault_pda(program_id, &escrow_pda).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if escrow.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    if escrow.tipper != *tipper.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    Ok((escrow, escrow_bump))
+}
+
+/// Close the vault and escrow, returning both rents to the tipper who paid them
+pub fn close_escrow<'a>(
+    escrow_acc: &AccountInfo<'a>,
+    vault: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    tipper: &AccountInfo<'a>,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    invoke_signed(
+        &token::close_account(token_program.key, vault.key, tipper.key, escrow_acc.key),
+        &[vault.clone(), tipper.clone(), escrow_acc.clone()],
+        &[signer_seeds],
+    )?;
+
+    close_pda(escrow_acc, tipper)
+}
+
+/// AcceptTip: no data
+/// Accounts: [config (w), escrow (w), vault (w), creator (s), creator_token (w), treasury_token (w), mint, token_program,
+///            tipper (w), blocked_creator, mint_stats (w), then per treasury split entry: split_token (w)]
+/// The fee is charged now, at the rate in force when the creator accepts; a blocked creator cannot
+/// accept, and the tipper reclaims the escrow after the timeout
+pub fn accept_tip(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let escrow_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let creator_token = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, escrow_acc, vault, creator_token, treasury_token, tipper])?;
+    validation::token_accounts(&[vault, creator_token, treasury_token])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+
+    // The escrow is seeded by its platform, so only that platform's config (and fee) can settle it
+    let (escrow, escrow_bump) = load_escrow(program_id, config.platform_id, escrow_acc, vault, mint_acc, tipper)?;
+    if escrow.creator != *creator.key {
+        return Err(TipError::Unauthorized.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+
+    let amount = escrow.amount;
+    let mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+    let platform = platform_seed(config.platform_id);
+    let signer_seeds: &[&[u8]] =
+        &[b"escrow", &platform, escrow.tipper.as_ref(), escrow.creator.as_ref(), &[escrow_bump]];
+
+    let creator_received = transfer_tokens(
+        accounts,
+        token_program, vault, mint_acc, creator_token, escrow_acc, creator_amount, mint.decimals, &[signer_seeds], &[],
+    )?;
+
+    let fee_received = pay_treasury_fee(
+        accounts,
+        config.treasury_split(), fee, iter, token_program, vault, mint_acc, treasury_token, escrow_acc, mint.decimals,
+        &[signer_seeds], &[],
+    )?;
+
+    close_escrow(escrow_acc, vault, token_program, tipper, signer_seeds)?;
+
+    let delivered = add_checked(creator_received, fee_received)?;
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, delivered, fee_received)?;
+    msg!("Escrowed tip accepted: {} to creator, {} fee", creator_received, fee_received);
+    EscrowResolved { tipper: escrow.tipper, creator: escrow.creator, amount, accepted: true }.emit(accounts)?;
+    emit_tip(accounts, &escrow.tipper, &escrow.creator, mint_acc.key, delivered, fee_received, 0, 0)
+}
+
+/// DeclineTip: no data
+/// Accounts: [escrow (w), vault (w), authority (s), tipper_token (w), mint, token_program, tipper (w),
+///            (config, for an escrow on another platform than 0)]
+/// The creator can decline at any time; the tipper can reclaim after ESCROW_TIMEOUT_SECS
+pub fn decline_tip(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let escrow_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let authority = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[escrow_acc, vault, tipper_token, tipper])?;
+    validation::token_accounts(&[vault, tipper_token])?;
+    let platform_id = optional_platform(program_id, iter)?;
+
+    let (escrow, escrow_bump) = load_escrow(program_id, platform_id, escrow_acc, vault, mint_acc, tipper)?;
+    if *authority.key == escrow.tipper {
+        if Clock::get()?.unix_timestamp < escrow.created_ts.saturating_add(ESCROW_TIMEOUT_SECS) {
+            msg!("Tipper can reclaim after {}", escrow.created_ts.saturating_add(ESCROW_TIMEOUT_SECS));
+            return Err(TipError::EscrowNotExpired.into());
+        }
+    } else if *authority.key != escrow.creator {
+        return Err(TipError::Unauthorized.into());
+    }
+    if escrow.tipper_token != *tipper_token.key {
+        return Err(TipError::SessionTokenMismatch.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let platform = platform_seed(platform_id);
+    let signer_seeds: &[&[u8]] =
+        &[b"escrow", &platform, escrow.tipper.as_ref(), escrow.creator.as_ref(), &[escrow_bump]];
+
+    invoke_signed(
+        &token::transfer_checked(
+            token_program.key, vault.key, mint_acc.key, tipper_token.key, escrow_acc.key, escrow.amount, mint.decimals,
+        ),
+        &[vault.clone(), mint_acc.clone(), tipper_token.clone(), escrow_acc.clone()],
+        &[signer_seeds],
+    )?;
+
+    close_escrow(escrow_acc, vault, token_program, tipper, signer_seeds)?;
+
+    msg!("Escrowed tip refunded: {}", escrow.amount);
+    EscrowResolved { tipper: escrow.tipper, creator: escrow.creator, amount: escrow.amount, accepted: false }
+        .emit(accounts)
+}
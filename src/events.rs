@@ -0,0 +1,1345 @@
+//! Borsh events logged via `sol_log_data` as `[name, borsh(event)]`, where `name` is the
+//! struct name in ASCII. Indexers match on the first field and decode the second.
+//!
+//! Logs are truncated once a transaction exceeds its log limit, so when the SPL Noop program is
+//! among an instruction's accounts every event is also CPI'd into it, Anchor `emit_cpi!` style:
+//! the inner instruction's data is `EVENT_IX_TAG`, `sha256("event:<name>")[..8]`, then
+//! `borsh(event)`, and is kept in the transaction metadata whatever the log size.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    hash::hashv,
+    instruction::Instruction,
+    log::sol_log_data,
+    program::invoke,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    compression::NOOP_ID,
+    state::{
+        BadgeTier, CampaignOverflow, ClosableAccount, EmergencyVault, FeeTier, ReceiptLeaf, Role, RoutingDestination,
+        SelfTipPolicy, SplitShare,
+    },
+};
+
+/// Anchor's marker for self-CPI'd events, little-endian
+pub const EVENT_IX_TAG: [u8; 8] = 0x1d9a_cb51_2ea5_45e4_u64.to_le_bytes();
+
+pub trait Event: BorshSerialize {
+    const NAME: &'static [u8];
+
+    /// Log the event, and CPI it into the Noop program if `accounts` hold it
+    fn emit(&self, accounts: &[AccountInfo]) -> ProgramResult {
+        let data = borsh::to_vec(self)?;
+        sol_log_data(&[Self::NAME, &data]);
+        if accounts.iter().any(|acc| *acc.key == NOOP_ID) {
+            let discriminator = hashv(&[b"event:", Self::NAME]).to_bytes();
+            let data = [&EVENT_IX_TAG[..], &discriminator[..8], &data].concat();
+            invoke(&Instruction { program_id: NOOP_ID, accounts: vec![], data }, &[])?;
+        }
+        Ok(())
+    }
+}
+
+/// Every completed tip, whichever instruction paid it. `mint` is default for native SOL;
+/// `fee` is the whole fee, including any referrer share.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipEvent {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub slot: u64,
+    /// `Tip`'s category; 0 for every other instruction
+    pub category: u8,
+    /// `Tip`'s superchat tier under the creator's thresholds when passed their profile (0 = none); 0
+    /// for every other instruction
+    pub tier: u8,
+}
+
+/// USD value of a `Tip` passed with its mint's price feed, in millionths of a dollar at the oracle price;
+/// `amount` is the `TipEvent` amount it values
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipValued {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub usd_micros: u64,
+}
+
+/// Tip to a creator who opted in with SetNotify
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CreatorNotified {
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub tipper: Pubkey,
+}
+
+/// Tip that carried a message
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipMemo {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub memo: String,
+}
+
+/// The tip that starts the fee holiday in `mint` (the default pubkey for SOL)
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FeeTargetReached {
+    pub total_fees: u64,
+    pub mint: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ReferralPaid {
+    pub referrer_token: Pubkey,
+    pub amount: u64,
+}
+
+/// Under `lenient_routing`, a tip went through without an optional destination that failed its
+/// checks; `account` is the destination's token account, or the default pubkey if none was passed
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RoutingSkipped {
+    pub creator: Pubkey,
+    pub destination: RoutingDestination,
+    pub account: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipMatched {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub platform_id: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FeeUpdateScheduled {
+    pub new_fee_bps: u16,
+    pub activation_slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FeeUpdatedEvent {
+    pub old_fee_bps: u16,
+    pub new_fee_bps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FeeTargetUpdated {
+    pub fee_target: u64,
+    pub mint: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ReferralBpsUpdated {
+    pub referral_bps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MintAllowlistUpdated {
+    pub mint: Pubkey,
+    pub allowed: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct PausedUpdated {
+    pub paused: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TreasuryUpdated {
+    pub treasury: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RebateUpdated {
+    pub rebate_threshold: u64,
+    pub rebate_bps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RebateClaimed {
+    pub tipper: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct Staked {
+    pub supporter: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// Supporter's stake behind the creator in this mint afterwards
+    pub staked: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct Unstaked {
+    pub supporter: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub unbonding_ends_at: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct StakeWithdrawn {
+    pub supporter: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FeesWithdrawn {
+    pub treasury_vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    /// Left in the vault after the withdrawal
+    pub remaining: u64,
+}
+
+/// One recipient's part of a DistributeFees payout
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FeesDistributed {
+    pub treasury_vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// A payout left a treasury vault below the config's `treasury_low_watermark`
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TreasuryLow {
+    pub treasury_vault: Pubkey,
+    pub balance: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TreasuryLowWatermarkUpdated {
+    pub watermark: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct AdminProposed {
+    pub pending_admin: Pubkey,
+}
+
+/// AcceptAdmin or HandoffOwnership
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct AdminChanged {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct AdminIsolationUpdated {
+    pub enabled: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RentSwept {
+    pub lamports: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct NotifyUpdated {
+    pub creator: Pubkey,
+    pub notify: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SessionOpened {
+    pub tipper: Pubkey,
+    pub session_key: Pubkey,
+    pub budget: u64,
+    pub expiry_ts: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SplitUpdated {
+    pub creator: Pubkey,
+    pub recipient_count: u8,
+}
+
+/// Logged after `TipEvent` for a TipGift, whose `tipper` paid for a tip credited to `attributed_tipper`
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipGifted {
+    pub tipper: Pubkey,
+    pub attributed_tipper: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipScheduled {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub schedule_id: u64,
+    pub amount: u64,
+    pub crank_reward: u64,
+    pub execute_at: i64,
+}
+
+/// Logged before the `TipEvent` of an executed scheduled tip
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ScheduledTipExecuted {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub schedule_id: u64,
+    pub amount: u64,
+    /// Token account the crank reward went to
+    pub cranker_token: Pubkey,
+    pub crank_reward: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ScheduledTipCancelled {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub schedule_id: u64,
+    /// Refunded to the tipper, crank reward included
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CollaboratorsUpdated {
+    pub creator: Pubkey,
+    pub shares: Vec<SplitShare>,
+}
+
+/// One collaborator's cut of a tip, paid alongside the creator's share
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CollaboratorPaid {
+    pub creator: Pubkey,
+    pub collaborator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Logged after the `TipEvent`s of a ProcessSubscriptionsBatch
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SubscriptionsBatchProcessed {
+    pub paid: u16,
+    /// Subscriptions passed that were not due or could not be paid
+    pub skipped: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SubscriptionCreated {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub interval_slots: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SubscriptionCancelled {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipEscrowed {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// AcceptTip (accepted) or DeclineTip (refunded)
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct EscrowResolved {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub accepted: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CampaignCreated {
+    pub creator: Pubkey,
+    pub campaign_id: u64,
+    pub target_amount: u64,
+    pub deadline_ts: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CampaignFinalized {
+    pub creator: Pubkey,
+    pub campaign_id: u64,
+    pub target_amount: u64,
+    pub raised: u64,
+    pub goal_met: bool,
+}
+
+/// A tip took the campaign to `percent` (25, 50, 75 or 100) of its target
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MilestoneReached {
+    pub creator: Pubkey,
+    pub campaign_id: u64,
+    pub percent: u8,
+    pub raised: u64,
+    pub slot: u64,
+}
+
+/// A tip went `excess` past the campaign's target: kept under `CampaignOverflow::Accept`, left with the
+/// tipper under `Cap`
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct GoalExceeded {
+    pub creator: Pubkey,
+    pub campaign_id: u64,
+    pub target_amount: u64,
+    pub raised: u64,
+    pub excess: u64,
+    pub overflow: CampaignOverflow,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CampaignOverflowSet {
+    pub creator: Pubkey,
+    pub campaign_id: u64,
+    pub overflow: CampaignOverflow,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MatchingPoolCreated {
+    pub sponsor: Pubkey,
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MatchingPoolClosed {
+    pub sponsor: Pubkey,
+    pub pool_id: u64,
+    pub returned: u64,
+    pub total_matched: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct EpochOpened {
+    pub epoch_index: u64,
+}
+
+/// `platform` is whether this call took the platform snapshot; `creators` counts the creator snapshots
+/// it took, leaving out those the epoch already had
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct StatsSnapshotTaken {
+    pub epoch_index: u64,
+    pub platform: bool,
+    pub creators: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct LeaderboardOpened {
+    pub epoch_index: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipperLeaderboardOpened {
+    pub epoch_index: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RewardPoolFunded {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub reward_per_epoch: u64,
+    pub winners: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct EpochRewardPaid {
+    pub epoch_index: u64,
+    pub tipper: Pubkey,
+    pub amount: u64,
+}
+
+/// Logged after the `EpochRewardPaid`s of a DistributeEpochRewards
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct EpochRewardsDistributed {
+    pub epoch_index: u64,
+    pub mint: Pubkey,
+    pub total: u64,
+    pub winners: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct DeploymentVerified {
+    pub upgrade_authority: Pubkey,
+    /// Slot the current program code was deployed at
+    pub last_deploy_slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct EmergencyWithdrawScheduled {
+    pub kind: EmergencyVault,
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_slot: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct EmergencyWithdrawExecuted {
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct EmergencyWithdrawCancelled {
+    pub vault: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct PromoCodeCreated {
+    pub promo_code: Pubkey,
+    pub discount_bps: u16,
+    pub max_uses: u64,
+    pub expiry_ts: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct PromoCodeClosed {
+    pub promo_code: Pubkey,
+    pub remaining_uses: u64,
+}
+
+/// `fee_waived` is the part of the tip's fee the code took off
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct PromoCodeRedeemed {
+    pub promo_code: Pubkey,
+    pub tipper: Pubkey,
+    pub fee_waived: u64,
+    pub remaining_uses: u64,
+}
+
+/// `fee_bps` is None once the campaign is back on the global rate
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CampaignFeeSet {
+    pub creator: Pubkey,
+    pub campaign_id: u64,
+    pub fee_bps: Option<u16>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct AuditLogOpened {
+    pub audit_log: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct VolumeWindowOpened {
+    pub volume_window: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ConfigClosed {
+    pub recipient: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct AccountClosed {
+    pub account: Pubkey,
+    pub kind: ClosableAccount,
+    pub recipient: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ConfigMigrated {
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// Extra treasury fee recipients; the treasury keeps whatever the shares leave
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TreasurySplitUpdated {
+    pub shares: Vec<SplitShare>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FeeTiersUpdated {
+    pub tiers: Vec<FeeTier>,
+}
+
+/// `fee_bps` is None once the override is cleared
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FeeOverrideUpdated {
+    pub creator: Pubkey,
+    pub fee_bps: Option<u16>,
+}
+
+/// Logged by both RegisterCreator and UpdateCreator
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CreatorProfileUpdated {
+    pub creator: Pubkey,
+    pub display_name: String,
+    pub metadata_uri: String,
+    pub payout_token: Pubkey,
+}
+
+/// Amounts are in `mint`'s base units
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipMenuUpdated {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub min_tip: u64,
+    pub tip_menu: Vec<u64>,
+}
+
+/// Thresholds are in `mint`'s base units, lowest first
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipTiersUpdated {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub thresholds: Vec<u64>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CreatorVerified {
+    pub creator: Pubkey,
+    pub verified: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TaxWithholdingSet {
+    pub creator: Pubkey,
+    pub tax_bps: u16,
+    pub tax_account: Pubkey,
+}
+
+/// Part of a tip's creator share paid to the creator's tax account
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TaxWithheld {
+    pub creator: Pubkey,
+    pub tax_account: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RequireVerifiedUpdated {
+    pub required: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MaxCreatorsUpdated {
+    pub max_creators: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RequireTopLevelUpdated {
+    pub required: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct LenientRoutingUpdated {
+    pub enabled: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SelfTipPolicyUpdated {
+    pub policy: SelfTipPolicy,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CreatorBlockUpdated {
+    pub creator: Pubkey,
+    pub blocked: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipperBlockUpdated {
+    pub tipper: Pubkey,
+    pub blocked: bool,
+}
+
+/// `mint` is None for the config-wide minimum
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MinTipAmountUpdated {
+    pub mint: Option<Pubkey>,
+    pub min_tip_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct PriceFeedUpdated {
+    pub mint: Pubkey,
+    pub price_feed: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MaxTipAmountUpdated {
+    pub max_tip_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RateLimitUpdated {
+    pub window_secs: u64,
+    pub max_tips: u64,
+    pub max_volume: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MultisigUpdated {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ActionProposed {
+    pub action: Pubkey,
+    pub nonce: u64,
+    pub proposer: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ActionApproved {
+    pub action: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ActionExecuted {
+    pub action: Pubkey,
+    pub nonce: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ActionCancelled {
+    pub action: Pubkey,
+    pub nonce: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct RoleUpdated {
+    pub role: Role,
+    pub authority: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ReceiptNftUpdated {
+    pub collection: Pubkey,
+    pub uri_template: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ReceiptNftMinted {
+    pub tipper: Pubkey,
+    pub mint: Pubkey,
+    pub collection: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ReceiptTreeInitialized {
+    pub tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ReceiptAppended {
+    pub tree: Pubkey,
+    pub leaf: ReceiptLeaf,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct BadgeThresholdsUpdated {
+    pub creator: Pubkey,
+    pub thresholds: [u64; 3],
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct BadgeClaimed {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub tier: BadgeTier,
+    pub total_volume: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct VaultOpened {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub vault_token: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipsClaimed {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+/// Logged after the `TipsClaimed` of each vault a PayoutBatch swept
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct PayoutBatchProcessed {
+    pub caller: Pubkey,
+    pub mint: Pubkey,
+    pub paid: u16,
+    /// Vaults passed below `min_amount` or without a creator ATA
+    pub skipped: u16,
+    /// Paid to the caller across all vaults
+    pub incentive: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FanClubUpdated {
+    pub creator: Pubkey,
+    pub collection: Pubkey,
+    pub discount_bps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct CharitySet {
+    pub mint: Pubkey,
+    pub charity_token: Pubkey,
+    pub max_donation_bps: u16,
+}
+
+/// Part of a TipWithDonation's creator share sent to the mint's charity; `amount` is what the
+/// charity received
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct DonationMade {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub charity_token: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ForwardRuleSet {
+    pub creator: Pubkey,
+    pub destination: Pubkey,
+    pub forward_bps: u16,
+}
+
+/// Part of the creator's share of a tip sent to their forward rule's destination; `amount` is what
+/// the destination received
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipForwarded {
+    pub creator: Pubkey,
+    pub destination: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SwapProgramSet {
+    pub swap_program: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct PayoutMintSet {
+    pub creator: Pubkey,
+    pub payout_mint: Pubkey,
+}
+
+/// The creator's share of a TipWithSwap converted through the swap program; `amount_out` is what
+/// reached the creator's payout ATA
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipSwapped {
+    pub creator: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipLinkCreated {
+    pub creator: Pubkey,
+    pub tip_link: Pubkey,
+    pub link_id: u64,
+}
+
+/// The `external_ref` a TipWithRef bound to the tip, logged after its TipEvent
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipExternalRef {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub external_ref: [u8; 32],
+}
+
+/// A sweep of a tip link; `mint` is the default pubkey for SOL, `amount` what was swept before the fee
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipLinkClaimed {
+    pub creator: Pubkey,
+    pub tip_link: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+/// A tip the platform's `SelfTipPolicy::Flag` let through although the tipper is the creator or
+/// controls the account being paid; `tipper` is the default pubkey for `TipAnonymous`
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SelfTip {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// A tipper proved they hold an NFT of the creator's fan club collection
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct FanDiscountApplied {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub collection: Pubkey,
+    pub fee_waived: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SolVaultOpened {
+    pub creator: Pubkey,
+    pub sol_vault: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct SolWithdrawn {
+    pub creator: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct VestingUpdated {
+    pub threshold: u64,
+    pub cliff_secs: u64,
+    pub duration_secs: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipVested {
+    pub creator: Pubkey,
+    pub tipper: Pubkey,
+    pub schedule: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct VestedReleased {
+    pub schedule: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub remaining: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct DisputeWindowUpdated {
+    pub window_slots: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipDisputed {
+    pub schedule: Pubkey,
+    pub tipper: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct DisputeResolved {
+    pub schedule: Pubkey,
+    pub refunded: bool,
+    pub amount: u64,
+}
+
+impl Event for TipEvent {
+    const NAME: &'static [u8] = b"TipEvent";
+}
+impl Event for TipValued {
+    const NAME: &'static [u8] = b"TipValued";
+}
+impl Event for CreatorNotified {
+    const NAME: &'static [u8] = b"CreatorNotified";
+}
+impl Event for TipMemo {
+    const NAME: &'static [u8] = b"TipMemo";
+}
+impl Event for FeeTargetReached {
+    const NAME: &'static [u8] = b"FeeTargetReached";
+}
+impl Event for ReferralPaid {
+    const NAME: &'static [u8] = b"ReferralPaid";
+}
+impl Event for RoutingSkipped {
+    const NAME: &'static [u8] = b"RoutingSkipped";
+}
+impl Event for TipMatched {
+    const NAME: &'static [u8] = b"TipMatched";
+}
+impl Event for ConfigInitialized {
+    const NAME: &'static [u8] = b"ConfigInitialized";
+}
+impl Event for FeeUpdateScheduled {
+    const NAME: &'static [u8] = b"FeeUpdateScheduled";
+}
+impl Event for FeeUpdatedEvent {
+    const NAME: &'static [u8] = b"FeeUpdatedEvent";
+}
+impl Event for FeeTargetUpdated {
+    const NAME: &'static [u8] = b"FeeTargetUpdated";
+}
+impl Event for ReferralBpsUpdated {
+    const NAME: &'static [u8] = b"ReferralBpsUpdated";
+}
+impl Event for MintAllowlistUpdated {
+    const NAME: &'static [u8] = b"MintAllowlistUpdated";
+}
+impl Event for PausedUpdated {
+    const NAME: &'static [u8] = b"PausedUpdated";
+}
+impl Event for TreasuryUpdated {
+    const NAME: &'static [u8] = b"TreasuryUpdated";
+}
+impl Event for FeesWithdrawn {
+    const NAME: &'static [u8] = b"FeesWithdrawn";
+}
+impl Event for FeesDistributed {
+    const NAME: &'static [u8] = b"FeesDistributed";
+}
+impl Event for TreasuryLow {
+    const NAME: &'static [u8] = b"TreasuryLow";
+}
+impl Event for TreasuryLowWatermarkUpdated {
+    const NAME: &'static [u8] = b"TreasuryLowWatermarkUpdated";
+}
+impl Event for RebateUpdated {
+    const NAME: &'static [u8] = b"RebateUpdated";
+}
+impl Event for RebateClaimed {
+    const NAME: &'static [u8] = b"RebateClaimed";
+}
+impl Event for TipGifted {
+    const NAME: &'static [u8] = b"TipGifted";
+}
+impl Event for SubscriptionsBatchProcessed {
+    const NAME: &'static [u8] = b"SubscriptionsBatchProcessed";
+}
+impl Event for TipScheduled {
+    const NAME: &'static [u8] = b"TipScheduled";
+}
+impl Event for ScheduledTipExecuted {
+    const NAME: &'static [u8] = b"ScheduledTipExecuted";
+}
+impl Event for ScheduledTipCancelled {
+    const NAME: &'static [u8] = b"ScheduledTipCancelled";
+}
+impl Event for CollaboratorsUpdated {
+    const NAME: &'static [u8] = b"CollaboratorsUpdated";
+}
+impl Event for CollaboratorPaid {
+    const NAME: &'static [u8] = b"CollaboratorPaid";
+}
+impl Event for Staked {
+    const NAME: &'static [u8] = b"Staked";
+}
+impl Event for Unstaked {
+    const NAME: &'static [u8] = b"Unstaked";
+}
+impl Event for StakeWithdrawn {
+    const NAME: &'static [u8] = b"StakeWithdrawn";
+}
+impl Event for AdminProposed {
+    const NAME: &'static [u8] = b"AdminProposed";
+}
+impl Event for AdminChanged {
+    const NAME: &'static [u8] = b"AdminChanged";
+}
+impl Event for AdminIsolationUpdated {
+    const NAME: &'static [u8] = b"AdminIsolationUpdated";
+}
+impl Event for RentSwept {
+    const NAME: &'static [u8] = b"RentSwept";
+}
+impl Event for NotifyUpdated {
+    const NAME: &'static [u8] = b"NotifyUpdated";
+}
+impl Event for SessionOpened {
+    const NAME: &'static [u8] = b"SessionOpened";
+}
+impl Event for SplitUpdated {
+    const NAME: &'static [u8] = b"SplitUpdated";
+}
+impl Event for SubscriptionCreated {
+    const NAME: &'static [u8] = b"SubscriptionCreated";
+}
+impl Event for SubscriptionCancelled {
+    const NAME: &'static [u8] = b"SubscriptionCancelled";
+}
+impl Event for TipEscrowed {
+    const NAME: &'static [u8] = b"TipEscrowed";
+}
+impl Event for EscrowResolved {
+    const NAME: &'static [u8] = b"EscrowResolved";
+}
+impl Event for CampaignCreated {
+    const NAME: &'static [u8] = b"CampaignCreated";
+}
+impl Event for CampaignFinalized {
+    const NAME: &'static [u8] = b"CampaignFinalized";
+}
+impl Event for MilestoneReached {
+    const NAME: &'static [u8] = b"MilestoneReached";
+}
+impl Event for GoalExceeded {
+    const NAME: &'static [u8] = b"GoalExceeded";
+}
+impl Event for CampaignOverflowSet {
+    const NAME: &'static [u8] = b"CampaignOverflowSet";
+}
+impl Event for MatchingPoolCreated {
+    const NAME: &'static [u8] = b"MatchingPoolCreated";
+}
+impl Event for MatchingPoolClosed {
+    const NAME: &'static [u8] = b"MatchingPoolClosed";
+}
+impl Event for EpochOpened {
+    const NAME: &'static [u8] = b"EpochOpened";
+}
+impl Event for ConfigClosed {
+    const NAME: &'static [u8] = b"ConfigClosed";
+}
+impl Event for AccountClosed {
+    const NAME: &'static [u8] = b"AccountClosed";
+}
+impl Event for ConfigMigrated {
+    const NAME: &'static [u8] = b"ConfigMigrated";
+}
+impl Event for TreasurySplitUpdated {
+    const NAME: &'static [u8] = b"TreasurySplitUpdated";
+}
+impl Event for FeeTiersUpdated {
+    const NAME: &'static [u8] = b"FeeTiersUpdated";
+}
+impl Event for FeeOverrideUpdated {
+    const NAME: &'static [u8] = b"FeeOverrideUpdated";
+}
+impl Event for MinTipAmountUpdated {
+    const NAME: &'static [u8] = b"MinTipAmountUpdated";
+}
+impl Event for PriceFeedUpdated {
+    const NAME: &'static [u8] = b"PriceFeedUpdated";
+}
+impl Event for MaxTipAmountUpdated {
+    const NAME: &'static [u8] = b"MaxTipAmountUpdated";
+}
+impl Event for RateLimitUpdated {
+    const NAME: &'static [u8] = b"RateLimitUpdated";
+}
+impl Event for MultisigUpdated {
+    const NAME: &'static [u8] = b"MultisigUpdated";
+}
+impl Event for ActionProposed {
+    const NAME: &'static [u8] = b"ActionProposed";
+}
+impl Event for ActionApproved {
+    const NAME: &'static [u8] = b"ActionApproved";
+}
+impl Event for ActionExecuted {
+    const NAME: &'static [u8] = b"ActionExecuted";
+}
+impl Event for ActionCancelled {
+    const NAME: &'static [u8] = b"ActionCancelled";
+}
+impl Event for RoleUpdated {
+    const NAME: &'static [u8] = b"RoleUpdated";
+}
+impl Event for ReceiptNftUpdated {
+    const NAME: &'static [u8] = b"ReceiptNftUpdated";
+}
+impl Event for ReceiptNftMinted {
+    const NAME: &'static [u8] = b"ReceiptNftMinted";
+}
+impl Event for ReceiptTreeInitialized {
+    const NAME: &'static [u8] = b"ReceiptTreeInitialized";
+}
+impl Event for ReceiptAppended {
+    const NAME: &'static [u8] = b"ReceiptAppended";
+}
+impl Event for BadgeThresholdsUpdated {
+    const NAME: &'static [u8] = b"BadgeThresholdsUpdated";
+}
+impl Event for BadgeClaimed {
+    const NAME: &'static [u8] = b"BadgeClaimed";
+}
+impl Event for VaultOpened {
+    const NAME: &'static [u8] = b"VaultOpened";
+}
+impl Event for TipsClaimed {
+    const NAME: &'static [u8] = b"TipsClaimed";
+}
+impl Event for SolVaultOpened {
+    const NAME: &'static [u8] = b"SolVaultOpened";
+}
+impl Event for SolWithdrawn {
+    const NAME: &'static [u8] = b"SolWithdrawn";
+}
+impl Event for VestingUpdated {
+    const NAME: &'static [u8] = b"VestingUpdated";
+}
+impl Event for TipVested {
+    const NAME: &'static [u8] = b"TipVested";
+}
+impl Event for VestedReleased {
+    const NAME: &'static [u8] = b"VestedReleased";
+}
+impl Event for DisputeWindowUpdated {
+    const NAME: &'static [u8] = b"DisputeWindowUpdated";
+}
+impl Event for TipDisputed {
+    const NAME: &'static [u8] = b"TipDisputed";
+}
+impl Event for DisputeResolved {
+    const NAME: &'static [u8] = b"DisputeResolved";
+}
+impl Event for CreatorBlockUpdated {
+    const NAME: &'static [u8] = b"CreatorBlockUpdated";
+}
+impl Event for TipperBlockUpdated {
+    const NAME: &'static [u8] = b"TipperBlockUpdated";
+}
+impl Event for CreatorProfileUpdated {
+    const NAME: &'static [u8] = b"CreatorProfileUpdated";
+}
+impl Event for CreatorVerified {
+    const NAME: &'static [u8] = b"CreatorVerified";
+}
+impl Event for TaxWithholdingSet {
+    const NAME: &'static [u8] = b"TaxWithholdingSet";
+}
+impl Event for TaxWithheld {
+    const NAME: &'static [u8] = b"TaxWithheld";
+}
+impl Event for RequireVerifiedUpdated {
+    const NAME: &'static [u8] = b"RequireVerifiedUpdated";
+}
+impl Event for MaxCreatorsUpdated {
+    const NAME: &'static [u8] = b"MaxCreatorsUpdated";
+}
+impl Event for RequireTopLevelUpdated {
+    const NAME: &'static [u8] = b"RequireTopLevelUpdated";
+}
+impl Event for LenientRoutingUpdated {
+    const NAME: &'static [u8] = b"LenientRoutingUpdated";
+}
+impl Event for LeaderboardOpened {
+    const NAME: &'static [u8] = b"LeaderboardOpened";
+}
+impl Event for TipperLeaderboardOpened {
+    const NAME: &'static [u8] = b"TipperLeaderboardOpened";
+}
+impl Event for VolumeWindowOpened {
+    const NAME: &'static [u8] = b"VolumeWindowOpened";
+}
+impl Event for RewardPoolFunded {
+    const NAME: &'static [u8] = b"RewardPoolFunded";
+}
+impl Event for EpochRewardPaid {
+    const NAME: &'static [u8] = b"EpochRewardPaid";
+}
+impl Event for DeploymentVerified {
+    const NAME: &'static [u8] = b"DeploymentVerified";
+}
+impl Event for EmergencyWithdrawScheduled {
+    const NAME: &'static [u8] = b"EmergencyWithdrawScheduled";
+}
+impl Event for EmergencyWithdrawExecuted {
+    const NAME: &'static [u8] = b"EmergencyWithdrawExecuted";
+}
+impl Event for EmergencyWithdrawCancelled {
+    const NAME: &'static [u8] = b"EmergencyWithdrawCancelled";
+}
+impl Event for EpochRewardsDistributed {
+    const NAME: &'static [u8] = b"EpochRewardsDistributed";
+}
+impl Event for AuditLogOpened {
+    const NAME: &'static [u8] = b"AuditLogOpened";
+}
+impl Event for PromoCodeCreated {
+    const NAME: &'static [u8] = b"PromoCodeCreated";
+}
+impl Event for PromoCodeClosed {
+    const NAME: &'static [u8] = b"PromoCodeClosed";
+}
+impl Event for PromoCodeRedeemed {
+    const NAME: &'static [u8] = b"PromoCodeRedeemed";
+}
+impl Event for CampaignFeeSet {
+    const NAME: &'static [u8] = b"CampaignFeeSet";
+}
+impl Event for TipMenuUpdated {
+    const NAME: &'static [u8] = b"TipMenuUpdated";
+}
+impl Event for PayoutBatchProcessed {
+    const NAME: &'static [u8] = b"PayoutBatchProcessed";
+}
+impl Event for FanClubUpdated {
+    const NAME: &'static [u8] = b"FanClubUpdated";
+}
+impl Event for TipTiersUpdated {
+    const NAME: &'static [u8] = b"TipTiersUpdated";
+}
+impl Event for CharitySet {
+    const NAME: &'static [u8] = b"CharitySet";
+}
+impl Event for DonationMade {
+    const NAME: &'static [u8] = b"DonationMade";
+}
+impl Event for ForwardRuleSet {
+    const NAME: &'static [u8] = b"ForwardRuleSet";
+}
+impl Event for TipForwarded {
+    const NAME: &'static [u8] = b"TipForwarded";
+}
+impl Event for StatsSnapshotTaken {
+    const NAME: &'static [u8] = b"StatsSnapshotTaken";
+}
+impl Event for FanDiscountApplied {
+    const NAME: &'static [u8] = b"FanDiscountApplied";
+}
+impl Event for SelfTipPolicyUpdated {
+    const NAME: &'static [u8] = b"SelfTipPolicyUpdated";
+}
+impl Event for SelfTip {
+    const NAME: &'static [u8] = b"SelfTip";
+}
+impl Event for SwapProgramSet {
+    const NAME: &'static [u8] = b"SwapProgramSet";
+}
+impl Event for PayoutMintSet {
+    const NAME: &'static [u8] = b"PayoutMintSet";
+}
+impl Event for TipSwapped {
+    const NAME: &'static [u8] = b"TipSwapped";
+}
+impl Event for TipLinkCreated {
+    const NAME: &'static [u8] = b"TipLinkCreated";
+}
+impl Event for TipLinkClaimed {
+    const NAME: &'static [u8] = b"TipLinkClaimed";
+}
+impl Event for TipExternalRef {
+    const NAME: &'static [u8] = b"TipExternalRef";
+}
@@ -0,0 +1,195 @@
+//! Fee and split arithmetic shared by every tip path, free of accounts so it can be checked
+//! off-chain (feature `test-utils`). Every amount the program divides rounds each part down and
+//! gives the dust to one party: the treasury when a fee is divided (treasury split entries, referral
+//! share, promo discount, rebates), the largest part when a payout is (TipSplit recipients,
+//! collaborators against the creator, epoch reward winners). The parts always sum to exactly the
+//! amount, in whatever order recipients are listed.
+
+use solana_program::{msg, program_error::ProgramError};
+
+use crate::{error::TipError, state::FeeSchedule};
+
+/// Treasury fee for a tip of `amount` under the current config, at the rate of its fee tier
+pub fn compute_fee(schedule: FeeSchedule, amount: u64) -> Result<u64, ProgramError> {
+    let fee_holiday = schedule.fee_target > 0 && schedule.total_fees >= schedule.fee_target;
+    let fee_bps = schedule.bps_for(amount);
+    // Widened so large tips cannot overflow the product; the quotient is at most `amount`
+    let mut fee = u64::try_from(amount as u128 * fee_bps as u128 / 10_000).map_err(|_| TipError::MathOverflow)?;
+    // Small tips (common with 0-decimal mints) can round the fee down to nothing
+    if fee == 0 && fee_bps > 0 && !fee_holiday {
+        msg!("Tip too small to carry a fee: min {} base units", 10_000u64.div_ceil(fee_bps as u64));
+        return Err(TipError::FeeNotRepresentable.into());
+    }
+    // Stop collecting once the fee target is met, charging only what's left on the crossing tip
+    if schedule.fee_target > 0 {
+        fee = fee.min(schedule.fee_target.saturating_sub(schedule.total_fees));
+    }
+    Ok(fee)
+}
+
+/// `bps` of `amount`, rounded down
+pub fn bps_share(amount: u64, bps: u64) -> u64 {
+    (amount as u128 * bps as u128 / 10_000) as u64
+}
+
+/// `amount` divided in proportion to `weights`, each part rounded down, with the dust added to the
+/// part of the largest weight (the first of equal ones)
+pub fn split_by_weight(amount: u64, weights: &[u128]) -> Result<Vec<u64>, ProgramError> {
+    let total: u128 = weights.iter().sum();
+    if total == 0 {
+        return Ok(vec![0; weights.len()]);
+    }
+    let mut parts: Vec<u64> = weights.iter().map(|&weight| (amount as u128 * weight / total) as u64).collect();
+    let dust = amount.checked_sub(parts.iter().sum()).ok_or(TipError::MathOverflow)?;
+    let largest = weights.iter().enumerate().rev().max_by_key(|(_, &weight)| weight).map_or(0, |(i, _)| i);
+    parts[largest] = parts[largest].checked_add(dust).ok_or(TipError::MathOverflow)?;
+    Ok(parts)
+}
+
+/// `fee` less a promo code's or fan club's `discount_bps` of it; returns the discounted fee and the
+/// amount waived
+pub fn apply_discount(fee: u64, discount_bps: u16) -> Result<(u64, u64), ProgramError> {
+    let waived = bps_share(fee, discount_bps as u64);
+    Ok((fee.checked_sub(waived).ok_or(TipError::MathOverflow)?, waived))
+}
+
+/// Each treasury split entry's `share_bps` of `fee`; returns the shares and what is left for the
+/// treasury itself
+pub fn split_treasury_fee(fee: u64, share_bps: &[u16]) -> Result<(Vec<u64>, u64), ProgramError> {
+    let shares: Vec<u64> = share_bps.iter().map(|&bps| bps_share(fee, bps as u64)).collect();
+    let paid = shares.iter().try_fold(0u64, |paid, &share| paid.checked_add(share)).ok_or(TipError::MathOverflow)?;
+    Ok((shares, fee.checked_sub(paid).ok_or(TipError::MathOverflow)?))
+}
+
+/// Each collaborator's cut of `creator_amount` by its `share_bps`, with the creator's own part
+/// (whatever the shares leave of 10000 bps) last
+pub fn collaborator_cuts(creator_amount: u64, share_bps: &[u16]) -> Result<Vec<u64>, ProgramError> {
+    let mut weights: Vec<u128> = share_bps.iter().map(|&bps| bps as u128).collect();
+    let collaborator_bps: u128 = weights.iter().sum();
+    weights.push(10_000u128.checked_sub(collaborator_bps).ok_or(TipError::InvalidSplit)?);
+    split_by_weight(creator_amount, &weights)
+}
+
+/// The rates that divide a tip once its fee is known, as Tip reads them from its accounts; zero (or
+/// empty) for a part the tip does not have
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TipRates<'a> {
+    /// The config's referral share of the fee, when the tip names a referrer
+    pub referral_bps: u16,
+    pub donation_bps: u16,
+    pub collaborator_bps: &'a [u16],
+    /// The tax withholding on the creator's profile, when the tip passes it
+    pub tax_bps: u16,
+    /// The creator's forward rule, unless the tip vests
+    pub forward_bps: u16,
+}
+
+/// A tip's amount as Tip pays it out; the parts sum to exactly the amount
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TipParts {
+    pub referral: u64,
+    /// The fee less the referral, before any treasury split
+    pub treasury: u64,
+    pub donated: u64,
+    pub collaborators: Vec<u64>,
+    /// Withheld for the creator's tax account
+    pub withheld: u64,
+    pub forwarded: u64,
+    pub creator: u64,
+}
+
+/// Divide a tip of `amount` carrying `fee` (after any discounts): the referrer's share comes out of
+/// the fee, and the creator's share gives up the donation first, then the collaborators' cuts, then
+/// the tax withholding and the forward rule's share of what is left
+pub fn split_tip(amount: u64, fee: u64, rates: TipRates) -> Result<TipParts, ProgramError> {
+    let referral = bps_share(fee, rates.referral_bps as u64);
+    let treasury = fee.checked_sub(referral).ok_or(TipError::MathOverflow)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+
+    let donated = bps_share(creator_amount, rates.donation_bps as u64);
+    let creator_amount = creator_amount.checked_sub(donated).ok_or(TipError::MathOverflow)?;
+    let mut collaborators = collaborator_cuts(creator_amount, rates.collaborator_bps)?;
+    let creator_amount = collaborators.pop().unwrap_or(creator_amount);
+    let withheld = bps_share(creator_amount, rates.tax_bps as u64);
+    let creator_amount = creator_amount.checked_sub(withheld).ok_or(TipError::MathOverflow)?;
+    let forwarded = bps_share(creator_amount, rates.forward_bps as u64);
+    let creator = creator_amount.checked_sub(forwarded).ok_or(TipError::MathOverflow)?;
+    Ok(TipParts { referral, treasury, donated, collaborators, withheld, forwarded, creator })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::MAX_FEE_TIERS;
+
+    fn flat(fee_bps: u16) -> FeeSchedule {
+        FeeSchedule {
+            fee_bps,
+            fee_target: 0,
+            total_fees: 0,
+            tier_count: 0,
+            tier_min_amounts: [0; MAX_FEE_TIERS],
+            tier_bps: [0; MAX_FEE_TIERS],
+        }
+    }
+
+    #[test]
+    fn compute_fee_at_u64_max() {
+        // The widened product cannot overflow, and the fee never exceeds the amount
+        assert_eq!(compute_fee(flat(1000), u64::MAX), Ok(u64::MAX / 10));
+        assert_eq!(compute_fee(flat(10_000), u64::MAX), Ok(u64::MAX));
+        assert_eq!(compute_fee(flat(0), u64::MAX), Ok(0));
+    }
+
+    #[test]
+    fn compute_fee_target_at_u64_max() {
+        let schedule = FeeSchedule { fee_target: u64::MAX, total_fees: u64::MAX - 1, ..flat(1000) };
+        assert_eq!(compute_fee(schedule, u64::MAX), Ok(1));
+        let met = FeeSchedule { total_fees: u64::MAX, ..schedule };
+        assert_eq!(compute_fee(met, u64::MAX), Ok(0));
+    }
+
+    // A 0-decimal mint's base unit is a whole token, so a small tip's fee can round to nothing
+    #[test]
+    fn compute_fee_zero_decimal_mint_rounds_down() {
+        assert_eq!(compute_fee(flat(250), 100), Ok(2));
+        assert_eq!(compute_fee(flat(250), 40), Ok(1));
+    }
+
+    #[test]
+    fn compute_fee_zero_decimal_mint_rejects_unrepresentable_fee() {
+        assert_eq!(compute_fee(flat(250), 39), Err(TipError::FeeNotRepresentable.into()));
+        assert_eq!(compute_fee(flat(250), 1), Err(TipError::FeeNotRepresentable.into()));
+        // A fee-free schedule, or one in its fee holiday, has nothing to round away
+        assert_eq!(compute_fee(flat(0), 1), Ok(0));
+        let holiday = FeeSchedule { fee_target: 10, total_fees: 10, ..flat(250) };
+        assert_eq!(compute_fee(holiday, 1), Ok(0));
+    }
+
+    #[test]
+    fn bps_share_at_u64_max() {
+        assert_eq!(bps_share(u64::MAX, 10_000), u64::MAX);
+        assert_eq!(bps_share(u64::MAX, 5_000), u64::MAX / 2);
+        assert_eq!(bps_share(u64::MAX, 0), 0);
+    }
+
+    #[test]
+    fn split_by_weight_at_u64_max() {
+        let parts = split_by_weight(u64::MAX, &[1, 1, 1]).unwrap();
+        assert_eq!(parts.iter().map(|&p| p as u128).sum::<u128>(), u64::MAX as u128);
+        let parts = split_by_weight(u64::MAX, &[u64::MAX as u128, u64::MAX as u128]).unwrap();
+        assert_eq!(parts, vec![u64::MAX / 2 + 1, u64::MAX / 2]);
+    }
+
+    #[test]
+    fn split_tip_withholds_tax_from_the_creator_share_after_the_fee() {
+        let parts = split_tip(10_000, 500, TipRates { tax_bps: 2_000, ..TipRates::default() }).unwrap();
+        assert_eq!((parts.treasury, parts.withheld, parts.creator), (500, 1_900, 7_600));
+    }
+
+    #[test]
+    fn split_tip_without_withholding_pays_the_creator_in_full() {
+        let parts = split_tip(10_000, 500, TipRates::default()).unwrap();
+        assert_eq!((parts.withheld, parts.creator), (0, 9_500));
+    }
+}
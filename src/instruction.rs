@@ -0,0 +1,4736 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use shank::ShankInstruction;
+use solana_program::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+use crate::{compression, metadata, state::*, token};
+
+/// Wire format: one tag byte (the variant index) followed by the Borsh-encoded fields. Tags are stable:
+/// new variants are only ever appended
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, ShankInstruction)]
+pub enum TipInstruction {
+    /// Accounts: [config (w), treasury, admin (s,w), system_program, payer (optional, s,w)]
+    /// `upgrade_authority`, if set, is recorded for VerifyDeployment to check
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "treasury")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, name = "system_program")]
+    #[account(4, writable, signer, optional, name = "payer")]
+    Initialize { fee_bps: u16, upgrade_authority: Option<Pubkey> },
+    /// Accounts: [config (w), tipper (s,w), tipper_token (w), creator, creator_token (w), treasury_token (w), mint, token_program,
+    ///            allowed_mint, creator_stats (w), tipper_stats (w), system_program, blocked_creator, blocked_tipper,
+    ///            mint_stats (w), creator_notify (optional), epoch_stats (optional, w), referrer_token (optional, w),
+    ///            fee_override (optional), receipt (optional, w), receipt_tree (optional, w), tree_authority,
+    ///            compression_program, noop_program, supporter_stats (optional, w), creator_profile (optional),
+    ///            leaderboard (optional, w), payer (optional, s,w), instructions_sysvar (optional),
+    ///            volume_window (optional, w), price_feed (optional), rebate (optional, w),
+    ///            collaborators (optional), associated_token_program (optional), promo_code (optional, w),
+    ///            fan_club (optional), nft_token, nft_metadata, charity (optional, w), charity_token (w),
+    ///            forward_rule (optional), forward_token (w), gifter_stats (optional, w),
+    ///            tipper_leaderboard (optional, w), then per treasury split entry: split_token (w),
+    ///            then per collaborator: collaborator_token (w), then tax_token (w) while the creator's
+    ///            profile withholds tax, then transfer-hook accounts]
+    /// Pass the program id in an optional slot to skip it (for the receipt tree, in place of all its
+    /// accounts); with a treasury split set or tax withheld, every optional slot must be present ahead of
+    /// the trailing accounts. Tips of at least `vesting_threshold` must be sent as TipWithVesting; campaigns, matching
+    /// pools and swaps each have their own variant too, taking its accounts ahead of these. While
+    /// `require_top_level` is set the instructions sysvar is required and the tip fails when invoked via CPI
+    /// `memo` is an optional message (empty = none), up to 280 characters; `category` (below TIP_CATEGORIES,
+    /// 0 = none) is counted in the creator's stats and reported in TipEvent. Every Tip variant returns
+    /// the `TipOutcome` split via return data
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, optional, name = "creator_notify")]
+    #[account(16, writable, optional, name = "epoch_stats")]
+    #[account(17, writable, optional, name = "referrer_token")]
+    #[account(18, optional, name = "fee_override")]
+    #[account(19, writable, optional, name = "receipt")]
+    #[account(20, writable, optional, name = "receipt_tree")]
+    #[account(21, optional, name = "tree_authority")]
+    #[account(22, optional, name = "compression_program")]
+    #[account(23, optional, name = "noop_program")]
+    #[account(24, writable, optional, name = "supporter_stats")]
+    #[account(25, optional, name = "creator_profile")]
+    #[account(26, writable, optional, name = "leaderboard")]
+    #[account(27, writable, signer, optional, name = "payer")]
+    #[account(28, optional, name = "instructions_sysvar")]
+    #[account(29, writable, optional, name = "volume_window")]
+    #[account(30, optional, name = "price_feed")]
+    #[account(31, writable, optional, name = "rebate")]
+    #[account(32, optional, name = "collaborators")]
+    #[account(33, optional, name = "associated_token_program")]
+    #[account(34, writable, optional, name = "promo_code")]
+    #[account(35, optional, name = "fan_club")]
+    #[account(36, optional, name = "nft_token")]
+    #[account(37, optional, name = "nft_metadata")]
+    #[account(38, writable, optional, name = "charity")]
+    #[account(39, writable, optional, name = "charity_token")]
+    #[account(40, optional, name = "forward_rule")]
+    #[account(41, writable, optional, name = "forward_token")]
+    #[account(42, writable, optional, name = "gifter_stats")]
+    #[account(43, writable, optional, name = "tipper_leaderboard")]
+    Tip { amount: u64, memo: String, category: u8 },
+    /// Accounts: [config (w), admin, fee_manager or governance_authority (s), instructions_sysvar (if admin_isolation)]
+    /// The original fee update, kept at its tag for existing clients; it now schedules the rate exactly
+    /// like ScheduleFeeUpdate
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    UpdateFee { new_fee_bps: u16 },
+    /// Accounts: [(config), (mint)]; returns the platform's ProgramPdas for the current epoch, with the
+    /// mint's PDAs if one is passed, via return data
+    #[account(0, optional, name = "config")]
+    #[account(1, optional, name = "mint")]
+    DerivePdas,
+    /// Accounts: [creator_notify (w), creator (s,w), system_program, payer (optional, s,w)]
+    #[account(0, writable, name = "creator_notify")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetNotify { notify: bool },
+    /// Accounts: [config, admin (s,w), mint_stats (w), mint, system_program, instructions_sysvar (if admin_isolation),
+    ///            payer (optional, s,w)]
+    /// Fee holiday in `mint` (the default pubkey for SOL) once its MintStats count `fee_target` in fees
+    #[account(0, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, name = "mint_stats")]
+    #[account(3, name = "mint")]
+    #[account(4, name = "system_program")]
+    #[account(5, optional, name = "instructions_sysvar")]
+    #[account(6, writable, signer, optional, name = "payer")]
+    SetFeeTarget { fee_target: u64 },
+    /// Accounts: [config (w), admin (s,w), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SweepExcessRent,
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetAdminIsolation { enabled: bool },
+    /// Accounts: [session (w), tipper (s,w), tipper_token (w), session_key, token_program, system_program,
+    ///            blocked_tipper, config (optional), payer (optional, s,w)]
+    /// The session only tips on the config's platform (platform 0 without one)
+    #[account(0, writable, name = "session")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "session_key")]
+    #[account(4, name = "token_program")]
+    #[account(5, name = "system_program")]
+    #[account(6, name = "blocked_tipper")]
+    #[account(7, optional, name = "config")]
+    #[account(8, writable, signer, optional, name = "payer")]
+    OpenSession { budget: u64, expiry_ts: i64 },
+    /// Accounts: [config (w), session (w), session_key (s), tipper_token (w), creator, creator_token (w), treasury_token (w), mint,
+    ///            token_program, allowed_mint, tipper_stats (w), blocked_creator, blocked_tipper, mint_stats (w),
+    ///            then per treasury split entry: split_token (w)]
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, name = "session")]
+    #[account(2, signer, name = "session_key")]
+    #[account(3, writable, name = "tipper_token")]
+    #[account(4, name = "creator")]
+    #[account(5, writable, name = "creator_token")]
+    #[account(6, writable, name = "treasury_token")]
+    #[account(7, name = "mint")]
+    #[account(8, name = "token_program")]
+    #[account(9, name = "allowed_mint")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "blocked_creator")]
+    #[account(12, name = "blocked_tipper")]
+    #[account(13, writable, name = "mint_stats")]
+    SessionTip { amount: u64 },
+    /// Accounts: [config (w), admin (s), new_admin, new_treasury, instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, name = "new_admin")]
+    #[account(3, name = "new_treasury")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    HandoffOwnership,
+    /// Accounts: [config (w), tipper (s,w), creator (w), treasury_token, treasury_wallet (w), system_program,
+    ///            tipper_stats (w), blocked_creator, blocked_tipper, mint_stats (w), sol_vault (optional, w)]
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "creator")]
+    #[account(3, name = "treasury_token")]
+    #[account(4, writable, name = "treasury_wallet")]
+    #[account(5, name = "system_program")]
+    #[account(6, writable, name = "tipper_stats")]
+    #[account(7, name = "blocked_creator")]
+    #[account(8, name = "blocked_tipper")]
+    #[account(9, writable, name = "mint_stats")]
+    #[account(10, writable, optional, name = "sol_vault")]
+    TipSol { amount: u64 },
+    /// Accounts: [config (w), admin (s), new_admin, instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, name = "new_admin")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    ProposeAdmin,
+    /// Accounts: [config (w), new_admin (s)]
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "new_admin")]
+    AcceptAdmin,
+    /// Accounts: [config (w), admin, treasury_manager or governance_authority (s), new_treasury, allowed_mint,
+    ///            instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, name = "new_treasury")]
+    #[account(3, name = "allowed_mint")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    UpdateTreasury,
+    /// Accounts: [config (w), admin or pauser (s), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    Pause,
+    /// Accounts: [config (w), admin or pauser (s), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    Unpause,
+    /// Accounts: [allowed_mint (w), config, admin (s,w), mint, system_program,
+    ///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    #[account(0, writable, name = "allowed_mint")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, name = "mint")]
+    #[account(4, name = "system_program")]
+    #[account(5, optional, name = "instructions_sysvar")]
+    #[account(6, writable, signer, optional, name = "payer")]
+    AllowMint,
+    /// Accounts: [allowed_mint (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "allowed_mint")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    DisallowMint,
+    /// Accounts: [config (w), tipper (s,w), tipper_token (w), treasury_token (w), mint, token_program, allowed_mint,
+    ///            tipper_stats (w), system_program, blocked_tipper, mint_stats (w),
+    ///            then per amount: creator, creator_token (w), creator_stats (w), blocked_creator,
+    ///            then per treasury split entry: split_token (w), then payer (optional, s,w)]
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, writable, name = "treasury_token")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "token_program")]
+    #[account(6, name = "allowed_mint")]
+    #[account(7, writable, name = "tipper_stats")]
+    #[account(8, name = "system_program")]
+    #[account(9, name = "blocked_tipper")]
+    #[account(10, writable, name = "mint_stats")]
+    #[account(11, writable, signer, optional, name = "payer")]
+    TipMany { amounts: Vec<u64> },
+    /// Accounts: [split (w), creator (s,w), system_program, payer (optional, s,w)]
+    /// Replaces the creator's split; shares must sum to 10000 bps
+    #[account(0, writable, name = "split")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetSplit { shares: Vec<SplitShare> },
+    /// Accounts: [config (w), tipper (s,w), tipper_token (w), creator, split, treasury_token (w), mint, token_program,
+    ///            allowed_mint, creator_stats (w), tipper_stats (w), system_program, blocked_creator, blocked_tipper,
+    ///            mint_stats (w), then per share: recipient_token (w), then per treasury split entry: split_token (w),
+    ///            then payer (optional, s,w)]
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, name = "split")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, writable, signer, optional, name = "payer")]
+    TipSplit { amount: u64 },
+    /// Accounts: [sub (w), tipper (s,w), tipper_token (w), creator, token_program, system_program, blocked_tipper,
+    ///            config (optional), payer (optional, s,w)]
+    /// Approves the subscription PDA for `allowance`; the first payment is due immediately, on the config's
+    /// platform
+    #[account(0, writable, name = "sub")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, name = "token_program")]
+    #[account(5, name = "system_program")]
+    #[account(6, name = "blocked_tipper")]
+    #[account(7, optional, name = "config")]
+    #[account(8, writable, signer, optional, name = "payer")]
+    CreateSubscription { amount: u64, interval_slots: u64, allowance: u64 },
+    /// Accounts: [sub (w), tipper (s,w), tipper_token (w), token_program]
+    #[account(0, writable, name = "sub")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "token_program")]
+    CancelSubscription,
+    /// Accounts: [config (w), sub (w), tipper_token (w), creator, creator_token (w), treasury_token (w), mint, token_program,
+    ///            allowed_mint, tipper_stats (w), blocked_creator, blocked_tipper, mint_stats (w),
+    ///            then per treasury split entry: split_token (w)]
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, name = "sub")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "tipper_stats")]
+    #[account(10, name = "blocked_creator")]
+    #[account(11, name = "blocked_tipper")]
+    #[account(12, writable, name = "mint_stats")]
+    ProcessSubscription,
+    /// Accounts: [escrow (w), vault (w), tipper (s,w), tipper_token (w), creator, mint, token_program, allowed_mint,
+    ///            system_program, blocked_tipper, config (optional), payer (optional, s,w)]
+    #[account(0, writable, name = "escrow")]
+    #[account(1, writable, name = "vault")]
+    #[account(2, writable, signer, name = "tipper")]
+    #[account(3, writable, name = "tipper_token")]
+    #[account(4, name = "creator")]
+    #[account(5, name = "mint")]
+    #[account(6, name = "token_program")]
+    #[account(7, name = "allowed_mint")]
+    #[account(8, name = "system_program")]
+    #[account(9, name = "blocked_tipper")]
+    #[account(10, optional, name = "config")]
+    #[account(11, writable, signer, optional, name = "payer")]
+    EscrowTip { amount: u64 },
+    /// Accounts: [config (w), escrow (w), vault (w), creator (s), creator_token (w), treasury_token (w), mint, token_program,
+    ///            tipper (w), blocked_creator, mint_stats (w), then per treasury split entry: split_token (w)]
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, name = "escrow")]
+    #[account(2, writable, name = "vault")]
+    #[account(3, signer, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, writable, name = "tipper")]
+    #[account(9, name = "blocked_creator")]
+    #[account(10, writable, name = "mint_stats")]
+    AcceptTip,
+    /// Accounts: [escrow (w), vault (w), authority (s), tipper_token (w), mint, token_program, tipper (w),
+    ///            config (optional)]
+    /// `authority` is the creator, or the tipper once the escrow timeout has passed
+    #[account(0, writable, name = "escrow")]
+    #[account(1, writable, name = "vault")]
+    #[account(2, signer, name = "authority")]
+    #[account(3, writable, name = "tipper_token")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "token_program")]
+    #[account(6, writable, name = "tipper")]
+    #[account(7, optional, name = "config")]
+    DeclineTip,
+    /// Accounts: [campaign (w), creator (s,w), system_program, payer (optional, s,w)]
+    #[account(0, writable, name = "campaign")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    CreateCampaign { campaign_id: u64, target_amount: u64, deadline_ts: i64 },
+    /// Accounts: [campaign (w)]
+    /// Permissionless once the deadline has passed
+    #[account(0, writable, name = "campaign")]
+    FinalizeCampaign,
+    /// Accounts: [pool (w), pool_vault (w), sponsor (s,w), sponsor_token (w), mint, token_program, allowed_mint,
+    ///            system_program, payer (optional, s,w)]
+    /// `creator` restricts matching to one creator (default = any); `amount` is the initial deposit
+    #[account(0, writable, name = "pool")]
+    #[account(1, writable, name = "pool_vault")]
+    #[account(2, writable, signer, name = "sponsor")]
+    #[account(3, writable, name = "sponsor_token")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "token_program")]
+    #[account(6, name = "allowed_mint")]
+    #[account(7, name = "system_program")]
+    #[account(8, writable, signer, optional, name = "payer")]
+    CreateMatchingPool { pool_id: u64, creator: Pubkey, amount: u64 },
+    /// Accounts: [pool (w), pool_vault (w), sponsor (s,w), sponsor_token (w), mint, token_program]
+    /// Returns the unmatched balance and both rents to the sponsor
+    #[account(0, writable, name = "pool")]
+    #[account(1, writable, name = "pool_vault")]
+    #[account(2, writable, signer, name = "sponsor")]
+    #[account(3, writable, name = "sponsor_token")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "token_program")]
+    CloseMatchingPool,
+    /// Accounts: [epoch_stats (w), payer (s,w), system_program, (config)]
+    /// Permissionless: opens the current epoch's stats account
+    #[account(0, writable, name = "epoch_stats")]
+    #[account(1, writable, signer, name = "payer")]
+    #[account(2, name = "system_program")]
+    #[account(3, optional, name = "config")]
+    RolloverEpoch,
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetReferralBps { referral_bps: u16 },
+    /// Accounts: [config (w), admin (s), recipient (w), instructions_sysvar (if admin_isolation)]
+    /// Only while paused
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, writable, name = "recipient")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    CloseConfig,
+    /// Accounts: [account (w), authority (s), recipient (w), (config, for stats accounts)]
+    #[account(0, writable, name = "account")]
+    #[account(1, signer, name = "authority")]
+    #[account(2, writable, name = "recipient")]
+    #[account(3, optional, name = "config")]
+    CloseAccount { kind: ClosableAccount },
+    /// Accounts: [config (w), admin (s,w), system_program, payer (optional, s,w)]
+    /// Grows the config to the current layout; the admin tops up rent
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    MigrateConfig,
+
+    /// Accounts: [config (w), admin or treasury_manager (s), treasury, allowed_mint,
+    ///            then per share: recipient_token, instructions_sysvar (if admin_isolation)]
+    /// Empty `shares` sends the whole fee to the treasury again
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, name = "treasury")]
+    #[account(3, name = "allowed_mint")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    SetTreasurySplit { shares: Vec<SplitShare> },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// Tips of at least a tier's `min_amount` pay its `fee_bps`; empty `tiers` restores the flat fee
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetFeeTiers { tiers: Vec<FeeTier> },
+
+    /// Accounts: [fee_override (w), config, admin (s,w), creator, system_program,
+    ///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    /// `Tip` charges this creator `fee_bps` instead of the tiers or global rate when passed the override
+    #[account(0, writable, name = "fee_override")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, name = "creator")]
+    #[account(4, name = "system_program")]
+    #[account(5, optional, name = "instructions_sysvar")]
+    #[account(6, writable, signer, optional, name = "payer")]
+    SetFeeOverride { fee_bps: u16 },
+
+    /// Accounts: [fee_override (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "fee_override")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    ClearFeeOverride,
+
+    /// Accounts: [config (w), admin (s,w), allowed_mint (optional, w), system_program,
+    ///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    /// Pass the program id as allowed_mint to set the config-wide minimum instead of one mint's
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, optional, name = "allowed_mint")]
+    #[account(3, name = "system_program")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    #[account(5, writable, signer, optional, name = "payer")]
+    SetMinTipAmount { min_tip_amount: u64 },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// 0 removes the cap
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetMaxTipAmount { max_tip_amount: u64 },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// `window_secs` 0 turns rate limiting off; a 0 max leaves that dimension unlimited
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetRateLimit { window_secs: u64, max_tips: u64, max_volume: u64 },
+
+    /// Accounts: [config (w)]
+    /// Permissionless: commits the scheduled fee once its activation slot is reached
+    #[account(0, writable, name = "config")]
+    ApplyFeeUpdate,
+
+    /// Accounts: [config (w), admin (s), new_admin (only when threshold is 0),
+    ///            instructions_sysvar (if admin_isolation)]
+    /// A non-zero threshold makes the multisig authority PDA the admin; 0 hands admin to new_admin
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "new_admin")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    SetMultisig { signers: Vec<Pubkey>, threshold: u8 },
+
+    /// Accounts: [pending_action (w), config, proposer (s,w), system_program, payer (optional, s,w)]
+    /// `data` is the Borsh-encoded admin instruction to run as the multisig authority
+    #[account(0, writable, name = "pending_action")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "proposer")]
+    #[account(3, name = "system_program")]
+    #[account(4, writable, signer, optional, name = "payer")]
+    ProposeAction { nonce: u64, data: Vec<u8> },
+
+    /// Accounts: [pending_action (w), config, signer (s)]
+    #[account(0, writable, name = "pending_action")]
+    #[account(1, name = "config")]
+    #[account(2, signer, name = "signer")]
+    ApproveAction,
+
+    /// Accounts: [pending_action (w), config, proposer (w), multisig_authority, tip_program,
+    ///            then the action's own accounts]
+    #[account(0, writable, name = "pending_action")]
+    #[account(1, name = "config")]
+    #[account(2, writable, name = "proposer")]
+    #[account(3, name = "multisig_authority")]
+    #[account(4, name = "tip_program")]
+    ExecuteAction,
+
+    /// Accounts: [pending_action (w), proposer (s,w)]
+    #[account(0, writable, name = "pending_action")]
+    #[account(1, writable, signer, name = "proposer")]
+    CancelAction,
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// `authority` Pubkey::default() revokes the role
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetRole { role: Role, authority: Pubkey },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// `uri_template` (max 128 bytes) may contain `{mint}`; Pubkey::default() turns NFT receipts off
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetReceiptNft { collection: Pubkey, uri_template: String },
+
+    /// Accounts: [nft_mint (s,w), nft_token (w), nft_metadata (w), nft_master_edition (w), receipt_authority,
+    ///            collection_mint, collection_metadata (w), collection_master_edition, token_metadata_program,
+    ///            spl_token_program, associated_token_program, then the accounts of `Tip`]
+    /// `Tip` plus a commemorative NFT minted to the tipper's ATA; `nft_mint` is a fresh keypair
+    #[account(0, writable, signer, name = "nft_mint")]
+    #[account(1, writable, name = "nft_token")]
+    #[account(2, writable, name = "nft_metadata")]
+    #[account(3, writable, name = "nft_master_edition")]
+    #[account(4, name = "receipt_authority")]
+    #[account(5, name = "collection_mint")]
+    #[account(6, writable, name = "collection_metadata")]
+    #[account(7, name = "collection_master_edition")]
+    #[account(8, name = "token_metadata_program")]
+    #[account(9, name = "spl_token_program")]
+    #[account(10, name = "associated_token_program")]
+    TipWithReceiptNft { amount: u64, memo: String, category: u8 },
+
+    /// Accounts: [config (w), admin (s), merkle_tree (w), tree_authority, compression_program, noop_program,
+    ///            instructions_sysvar (if admin_isolation)]
+    /// Initializes a pre-allocated concurrent Merkle tree and makes it the receipt tree
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, writable, name = "merkle_tree")]
+    #[account(3, name = "tree_authority")]
+    #[account(4, name = "compression_program")]
+    #[account(5, name = "noop_program")]
+    #[account(6, optional, name = "instructions_sysvar")]
+    InitReceiptTree { max_depth: u32, max_buffer_size: u32 },
+
+    /// Accounts: [badge_config (w), creator (s,w), system_program, payer (optional, s,w)]
+    /// Bronze, silver and gold thresholds of cumulative tip volume, strictly increasing
+    #[account(0, writable, name = "badge_config")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetBadgeThresholds { thresholds: [u64; 3] },
+
+    /// Accounts: [supporter_stats (w), badge_config, tipper (s), stake (optional)]
+    #[account(0, writable, name = "supporter_stats")]
+    #[account(1, name = "badge_config")]
+    #[account(2, signer, name = "tipper")]
+    #[account(3, optional, name = "stake")]
+    ClaimBadge,
+
+    /// Accounts: [creator_vault (w), vault_token (w), creator (s,w), mint, token_program, system_program,
+    ///            payer (optional, s,w)]
+    /// Opens the creator's pull-based vault for `mint`; `vault_token` is then accepted as `creator_token`
+    #[account(0, writable, name = "creator_vault")]
+    #[account(1, writable, name = "vault_token")]
+    #[account(2, writable, signer, name = "creator")]
+    #[account(3, name = "mint")]
+    #[account(4, name = "token_program")]
+    #[account(5, name = "system_program")]
+    #[account(6, writable, signer, optional, name = "payer")]
+    OpenVault,
+
+    /// Accounts: [creator_vault (w), vault_token (w), creator (s), destination (w), mint, token_program,
+    ///            blocked_creator (platform 0's)]
+    /// Withdraws `amount` (0 = everything not vesting) to any token account of the vault's mint
+    #[account(0, writable, name = "creator_vault")]
+    #[account(1, writable, name = "vault_token")]
+    #[account(2, signer, name = "creator")]
+    #[account(3, writable, name = "destination")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "token_program")]
+    #[account(6, name = "blocked_creator")]
+    Claim { amount: u64 },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// Tips of at least `threshold` base units (0 = off) vest in the creator's vault: nothing
+    /// before `cliff_secs`, then linearly over `duration_secs` (0 = all at the cliff)
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetVesting { threshold: u64, cliff_secs: u64, duration_secs: u64 },
+
+    /// Accounts: [vesting (w), creator_vault (w), tipper (w)]
+    /// Permissionless: unlock what has vested; closes the schedule once fully released
+    #[account(0, writable, name = "vesting")]
+    #[account(1, writable, name = "creator_vault")]
+    #[account(2, writable, name = "tipper")]
+    ReleaseVested,
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// Slots after a vesting tip during which its tipper may dispute it (0 = no disputes)
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetDisputeWindow { window_slots: u64 },
+
+    /// Accounts: [vesting (w), tipper (s)]
+    /// Freezes the unreleased part of a vesting tip until ResolveDispute
+    #[account(0, writable, name = "vesting")]
+    #[account(1, signer, name = "tipper")]
+    DisputeTip,
+
+    /// Accounts: [config, arbiter (s), vesting (w), creator_vault (w), vault_token (w), tipper_token (w), mint,
+    ///            token_program, tipper (w), instructions_sysvar (if admin_isolation)]
+    /// Admin or arbiter: refund the unreleased amount to the tipper, or unlock it for the creator
+    #[account(0, name = "config")]
+    #[account(1, signer, name = "arbiter")]
+    #[account(2, writable, name = "vesting")]
+    #[account(3, writable, name = "creator_vault")]
+    #[account(4, writable, name = "vault_token")]
+    #[account(5, writable, name = "tipper_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, writable, name = "tipper")]
+    #[account(9, optional, name = "instructions_sysvar")]
+    ResolveDispute { refund: bool },
+
+    /// Accounts: the same as `Tip`; the receipt, receipt tree and supporter_stats slots must be skipped
+    /// `Tip` with the default pubkey in place of the tipper in events; TipperStats only counts it
+    /// toward the rate limit
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, optional, name = "creator_notify")]
+    #[account(16, writable, optional, name = "epoch_stats")]
+    #[account(17, writable, optional, name = "referrer_token")]
+    #[account(18, optional, name = "fee_override")]
+    #[account(19, writable, optional, name = "receipt")]
+    #[account(20, writable, optional, name = "receipt_tree")]
+    #[account(21, optional, name = "tree_authority")]
+    #[account(22, optional, name = "compression_program")]
+    #[account(23, optional, name = "noop_program")]
+    #[account(24, writable, optional, name = "supporter_stats")]
+    #[account(25, optional, name = "creator_profile")]
+    #[account(26, writable, optional, name = "leaderboard")]
+    #[account(27, writable, signer, optional, name = "payer")]
+    #[account(28, optional, name = "instructions_sysvar")]
+    #[account(29, writable, optional, name = "volume_window")]
+    #[account(30, optional, name = "price_feed")]
+    #[account(31, writable, optional, name = "rebate")]
+    #[account(32, optional, name = "collaborators")]
+    #[account(33, optional, name = "associated_token_program")]
+    #[account(34, writable, optional, name = "promo_code")]
+    #[account(35, optional, name = "fan_club")]
+    #[account(36, optional, name = "nft_token")]
+    #[account(37, optional, name = "nft_metadata")]
+    #[account(38, writable, optional, name = "charity")]
+    #[account(39, writable, optional, name = "charity_token")]
+    #[account(40, optional, name = "forward_rule")]
+    #[account(41, writable, optional, name = "forward_token")]
+    #[account(42, writable, optional, name = "gifter_stats")]
+    #[account(43, writable, optional, name = "tipper_leaderboard")]
+    TipAnonymous { amount: u64, memo: String, category: u8 },
+
+    /// Accounts: the same as `Tip`, with the PDA ["wsol", tipper] as tipper_token, the native mint and SPL Token
+    /// Wraps `amount` of the tipper's SOL into a temporary wSOL account, tips from it and closes it
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "wsol_account")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, optional, name = "creator_notify")]
+    #[account(16, writable, optional, name = "epoch_stats")]
+    #[account(17, writable, optional, name = "referrer_token")]
+    #[account(18, optional, name = "fee_override")]
+    #[account(19, writable, optional, name = "receipt")]
+    #[account(20, writable, optional, name = "receipt_tree")]
+    #[account(21, optional, name = "tree_authority")]
+    #[account(22, optional, name = "compression_program")]
+    #[account(23, optional, name = "noop_program")]
+    #[account(24, writable, optional, name = "supporter_stats")]
+    #[account(25, optional, name = "creator_profile")]
+    #[account(26, writable, optional, name = "leaderboard")]
+    #[account(27, writable, signer, optional, name = "payer")]
+    #[account(28, optional, name = "instructions_sysvar")]
+    #[account(29, writable, optional, name = "volume_window")]
+    #[account(30, optional, name = "price_feed")]
+    #[account(31, writable, optional, name = "rebate")]
+    #[account(32, optional, name = "collaborators")]
+    #[account(33, optional, name = "associated_token_program")]
+    #[account(34, writable, optional, name = "promo_code")]
+    #[account(35, optional, name = "fan_club")]
+    #[account(36, optional, name = "nft_token")]
+    #[account(37, optional, name = "nft_metadata")]
+    #[account(38, writable, optional, name = "charity")]
+    #[account(39, writable, optional, name = "charity_token")]
+    #[account(40, optional, name = "forward_rule")]
+    #[account(41, writable, optional, name = "forward_token")]
+    #[account(42, writable, optional, name = "gifter_stats")]
+    #[account(43, writable, optional, name = "tipper_leaderboard")]
+    TipWrappedSol { amount: u64, memo: String, category: u8 },
+
+    /// Accounts: [blocked_creator (w), config, admin (s,w), creator, system_program,
+    ///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    #[account(0, writable, name = "blocked_creator")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, name = "creator")]
+    #[account(4, name = "system_program")]
+    #[account(5, optional, name = "instructions_sysvar")]
+    #[account(6, writable, signer, optional, name = "payer")]
+    BlockCreator,
+
+    /// Accounts: [blocked_creator (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "blocked_creator")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    UnblockCreator,
+
+    /// Accounts: [blocked_tipper (w), config, admin (s,w), tipper, system_program,
+    ///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    #[account(0, writable, name = "blocked_tipper")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, name = "tipper")]
+    #[account(4, name = "system_program")]
+    #[account(5, optional, name = "instructions_sysvar")]
+    #[account(6, writable, signer, optional, name = "payer")]
+    BlockTipper,
+
+    /// Accounts: [blocked_tipper (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "blocked_tipper")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    UnblockTipper,
+
+    /// Accounts: [profile (w), creator (s,w), payout_token, system_program, config (w), payer (optional, s,w)]
+    /// `payout_token` must belong to the creator, directly or through one of their vaults. `config` is
+    /// platform 0's, which counts profiles against `max_creators`
+    #[account(0, writable, name = "profile")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "payout_token")]
+    #[account(3, name = "system_program")]
+    #[account(4, writable, name = "config")]
+    #[account(5, writable, signer, optional, name = "payer")]
+    RegisterCreator { display_name: String, metadata_uri: String },
+
+    /// Accounts: [profile (w), creator (s), payout_token]
+    /// Replaces every field of the profile but `verified`
+    #[account(0, writable, name = "profile")]
+    #[account(1, signer, name = "creator")]
+    #[account(2, name = "payout_token")]
+    UpdateCreator { display_name: String, metadata_uri: String },
+
+    /// Accounts: [profile (w), config, admin or verifier (s), instructions_sysvar (if admin_isolation)]
+    #[account(0, writable, name = "profile")]
+    #[account(1, name = "config")]
+    #[account(2, signer, name = "verifier")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    SetCreatorVerified { verified: bool },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// While set, `Tip` must pass the creator's verified profile and the other tip paths are closed
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetRequireVerified { required: bool },
+
+    /// Accounts: [leaderboard (w), payer (s,w), system_program, (config)]
+    /// Permissionless: opens the current epoch's top-creator leaderboard
+    #[account(0, writable, name = "leaderboard")]
+    #[account(1, writable, signer, name = "payer")]
+    #[account(2, name = "system_program")]
+    #[account(3, optional, name = "config")]
+    OpenLeaderboard,
+
+    /// Accounts: [config (w), relayer (s,w), tipper, delegation (w), tipper_token (w), creator, creator_token (w),
+    ///            treasury_token (w), mint, token_program, allowed_mint, system_program, instructions_sysvar,
+    ///            tipper_stats (w), blocked_creator, blocked_tipper, mint_stats (w),
+    ///            then per treasury split entry: split_token (w)]
+    /// Submitted by a relayer, paid from the tipper's approval of the delegation PDA; the previous instruction
+    /// must be an Ed25519 verification of the tipper's signature over the matching DelegatedTip
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "relayer")]
+    #[account(2, name = "tipper")]
+    #[account(3, writable, name = "delegation")]
+    #[account(4, writable, name = "tipper_token")]
+    #[account(5, name = "creator")]
+    #[account(6, writable, name = "creator_token")]
+    #[account(7, writable, name = "treasury_token")]
+    #[account(8, name = "mint")]
+    #[account(9, name = "token_program")]
+    #[account(10, name = "allowed_mint")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "instructions_sysvar")]
+    #[account(13, writable, name = "tipper_stats")]
+    #[account(14, name = "blocked_creator")]
+    #[account(15, name = "blocked_tipper")]
+    #[account(16, writable, name = "mint_stats")]
+    TipDelegated { amount: u64, nonce: u64, expiry_ts: i64 },
+
+    /// Accounts: [sol_vault (w), creator (s,w), system_program, payer (optional, s,w)]
+    /// Opens the creator's lamport vault; `TipSol` then pays into it when passed the vault
+    #[account(0, writable, name = "sol_vault")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    OpenSolVault,
+
+    /// Accounts: [sol_vault (w), creator (s), destination (w)]
+    /// Withdraws `amount` lamports (0 = everything above rent) to any account
+    #[account(0, writable, name = "sol_vault")]
+    #[account(1, signer, name = "creator")]
+    #[account(2, writable, name = "destination")]
+    WithdrawSol { amount: u64 },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// While `required`, `Tip` (and the variants built on it) must pass the instructions sysvar and fails
+    /// when invoked via CPI
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, name = "instructions_sysvar")]
+    SetRequireTopLevel { required: bool },
+
+    /// Accounts: [volume_window (w), payer (s,w), system_program, (config)]
+    /// Permissionless: opens the hourly volume ring buffer that `Tip` updates when passed it
+    #[account(0, writable, name = "volume_window")]
+    #[account(1, writable, signer, name = "payer")]
+    #[account(2, name = "system_program")]
+    #[account(3, optional, name = "config")]
+    OpenVolumeWindow,
+
+    /// Accounts: [config, admin (s,w), allowed_mint (w), system_program,
+    ///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    /// Pyth price account `Tip` values this mint with when passed it; the default pubkey turns valuation off
+    #[account(0, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, name = "allowed_mint")]
+    #[account(3, name = "system_program")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    #[account(5, writable, signer, optional, name = "payer")]
+    SetPriceFeed { price_feed: Pubkey },
+
+    /// Accounts: the accounts of `Tip`, with the mint's `price_feed` slot required
+    /// Tip worth `usd_cents` at the oracle price, converted to base units (rounded up); fails with
+    /// `SlippageExceeded` if that is more than `max_amount`
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, optional, name = "creator_notify")]
+    #[account(16, writable, optional, name = "epoch_stats")]
+    #[account(17, writable, optional, name = "referrer_token")]
+    #[account(18, optional, name = "fee_override")]
+    #[account(19, writable, optional, name = "receipt")]
+    #[account(20, writable, optional, name = "receipt_tree")]
+    #[account(21, optional, name = "tree_authority")]
+    #[account(22, optional, name = "compression_program")]
+    #[account(23, optional, name = "noop_program")]
+    #[account(24, writable, optional, name = "supporter_stats")]
+    #[account(25, optional, name = "creator_profile")]
+    #[account(26, writable, optional, name = "leaderboard")]
+    #[account(27, writable, signer, optional, name = "payer")]
+    #[account(28, optional, name = "instructions_sysvar")]
+    #[account(29, writable, optional, name = "volume_window")]
+    #[account(30, name = "price_feed")]
+    #[account(31, writable, optional, name = "rebate")]
+    #[account(32, optional, name = "collaborators")]
+    #[account(33, optional, name = "associated_token_program")]
+    #[account(34, writable, optional, name = "promo_code")]
+    #[account(35, optional, name = "fan_club")]
+    #[account(36, optional, name = "nft_token")]
+    #[account(37, optional, name = "nft_metadata")]
+    #[account(38, writable, optional, name = "charity")]
+    #[account(39, writable, optional, name = "charity_token")]
+    #[account(40, optional, name = "forward_rule")]
+    #[account(41, writable, optional, name = "forward_token")]
+    #[account(42, writable, optional, name = "gifter_stats")]
+    #[account(43, writable, optional, name = "tipper_leaderboard")]
+    TipUsd { usd_cents: u64, max_amount: u64, memo: String, category: u8 },
+
+    /// Accounts: [config (w), admin or treasury_manager (s,w), treasury_vault (w), mint, token_program,
+    ///            allowed_mint, system_program, instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    /// Creates the program-held treasury vault for an allowed mint and makes it the config's treasury
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, name = "treasury_vault")]
+    #[account(3, name = "mint")]
+    #[account(4, name = "token_program")]
+    #[account(5, name = "allowed_mint")]
+    #[account(6, name = "system_program")]
+    #[account(7, optional, name = "instructions_sysvar")]
+    #[account(8, writable, signer, optional, name = "payer")]
+    OpenTreasuryVault,
+
+    /// Accounts: [config, admin or treasury_manager (s), treasury_vault (w), destination (w), mint,
+    ///            token_program, instructions_sysvar (if admin_isolation)]
+    /// Moves `amount` of accrued fees out of a treasury vault
+    #[account(0, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, writable, name = "treasury_vault")]
+    #[account(3, writable, name = "destination")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "token_program")]
+    #[account(6, optional, name = "instructions_sysvar")]
+    WithdrawFees { amount: u64 },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// Tippers whose volume in one mint over a 30-day period reaches `rebate_threshold` accrue `rebate_bps`
+    /// of the treasury's fee on each further tip that passes their rebate account (0 threshold = off)
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetRebate { rebate_threshold: u64, rebate_bps: u16 },
+
+    /// Accounts: [config, tipper (s), rebate (w), treasury_vault (w), tipper_token (w), mint, token_program]
+    #[account(0, name = "config")]
+    #[account(1, signer, name = "tipper")]
+    #[account(2, writable, name = "rebate")]
+    #[account(3, writable, name = "treasury_vault")]
+    #[account(4, writable, name = "tipper_token")]
+    #[account(5, name = "mint")]
+    #[account(6, name = "token_program")]
+    ClaimRebate,
+
+    /// Accounts: [config, supporter (s,w), supporter_token (w), creator, stake (w), stake_vault (w), mint,
+    ///            token_program, allowed_mint, creator_stats (w), system_program, payer (optional, s,w)]
+    #[account(0, name = "config")]
+    #[account(1, writable, signer, name = "supporter")]
+    #[account(2, writable, name = "supporter_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "stake")]
+    #[account(5, writable, name = "stake_vault")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, name = "system_program")]
+    #[account(11, writable, signer, optional, name = "payer")]
+    Stake { amount: u64 },
+
+    /// Accounts: [supporter (s), stake (w), creator_stats (w), (config)]
+    #[account(0, signer, name = "supporter")]
+    #[account(1, writable, name = "stake")]
+    #[account(2, writable, name = "creator_stats")]
+    #[account(3, optional, name = "config")]
+    Unstake { amount: u64 },
+
+    /// Accounts: [config, supporter (s,w), stake (w), stake_vault (w), supporter_token (w), mint, token_program]
+    #[account(0, name = "config")]
+    #[account(1, writable, signer, name = "supporter")]
+    #[account(2, writable, name = "stake")]
+    #[account(3, writable, name = "stake_vault")]
+    #[account(4, writable, name = "supporter_token")]
+    #[account(5, name = "mint")]
+    #[account(6, name = "token_program")]
+    WithdrawStake,
+
+    /// Accounts: [collaborators (w), creator (s,w), system_program, payer (optional, s,w)]
+    #[account(0, writable, name = "collaborators")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetCollaborators { shares: Vec<SplitShare> },
+
+    /// Accounts: the accounts of `Tip`, with the attributed tipper's `tipper_stats`, `receipt` and
+    /// `supporter_stats`
+    /// `Tip` paid by the signing tipper but credited to `attributed_tipper`'s stats, receipts and badges
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, optional, name = "creator_notify")]
+    #[account(16, writable, optional, name = "epoch_stats")]
+    #[account(17, writable, optional, name = "referrer_token")]
+    #[account(18, optional, name = "fee_override")]
+    #[account(19, writable, optional, name = "receipt")]
+    #[account(20, writable, optional, name = "receipt_tree")]
+    #[account(21, optional, name = "tree_authority")]
+    #[account(22, optional, name = "compression_program")]
+    #[account(23, optional, name = "noop_program")]
+    #[account(24, writable, optional, name = "supporter_stats")]
+    #[account(25, optional, name = "creator_profile")]
+    #[account(26, writable, optional, name = "leaderboard")]
+    #[account(27, writable, signer, optional, name = "payer")]
+    #[account(28, optional, name = "instructions_sysvar")]
+    #[account(29, writable, optional, name = "volume_window")]
+    #[account(30, optional, name = "price_feed")]
+    #[account(31, writable, optional, name = "rebate")]
+    #[account(32, optional, name = "collaborators")]
+    #[account(33, optional, name = "associated_token_program")]
+    #[account(34, writable, optional, name = "promo_code")]
+    #[account(35, optional, name = "fan_club")]
+    #[account(36, optional, name = "nft_token")]
+    #[account(37, optional, name = "nft_metadata")]
+    #[account(38, writable, optional, name = "charity")]
+    #[account(39, writable, optional, name = "charity_token")]
+    #[account(40, optional, name = "forward_rule")]
+    #[account(41, writable, optional, name = "forward_token")]
+    #[account(42, writable, optional, name = "gifter_stats")]
+    #[account(43, writable, optional, name = "tipper_leaderboard")]
+    TipGift { amount: u64, memo: String, category: u8, attributed_tipper: Pubkey },
+
+    /// Accounts: [scheduled_tip (w), vault (w), tipper (s,w), tipper_token (w), creator, mint, token_program,
+    ///            allowed_mint, system_program, blocked_tipper, config (optional), payer (optional, s,w)]
+    #[account(0, writable, name = "scheduled_tip")]
+    #[account(1, writable, name = "vault")]
+    #[account(2, writable, signer, name = "tipper")]
+    #[account(3, writable, name = "tipper_token")]
+    #[account(4, name = "creator")]
+    #[account(5, name = "mint")]
+    #[account(6, name = "token_program")]
+    #[account(7, name = "allowed_mint")]
+    #[account(8, name = "system_program")]
+    #[account(9, name = "blocked_tipper")]
+    #[account(10, optional, name = "config")]
+    #[account(11, writable, signer, optional, name = "payer")]
+    ScheduleTip { schedule_id: u64, amount: u64, crank_reward: u64, execute_at: i64 },
+
+    /// Accounts: [config (w), scheduled_tip (w), vault (w), creator, creator_token (w), treasury_token (w), mint,
+    ///            token_program, allowed_mint, tipper (w), cranker_token (w), tipper_stats (w), blocked_creator,
+    ///            blocked_tipper, mint_stats (w), then per treasury split entry: split_token (w)]
+    /// Permissionless once `execute_at` has passed; `cranker_token` receives the crank reward
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, name = "scheduled_tip")]
+    #[account(2, writable, name = "vault")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "tipper")]
+    #[account(10, writable, name = "cranker_token")]
+    #[account(11, writable, name = "tipper_stats")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    ExecuteScheduledTip,
+
+    /// Accounts: [scheduled_tip (w), vault (w), tipper (s,w), tipper_token (w), mint, token_program, config (optional)]
+    #[account(0, writable, name = "scheduled_tip")]
+    #[account(1, writable, name = "vault")]
+    #[account(2, writable, signer, name = "tipper")]
+    #[account(3, writable, name = "tipper_token")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "token_program")]
+    #[account(6, optional, name = "config")]
+    CancelScheduledTip,
+
+    /// Accounts: [config (w), treasury_token (w), mint, token_program, allowed_mint, mint_stats (w),
+    ///            then per subscription: sub (w), tipper_token (w), creator, creator_token (w), tipper_stats (w),
+    ///            blocked_creator, blocked_tipper, then per treasury split entry: split_token (w)]
+    /// Permissionless; subscriptions that cannot be paid right now are skipped
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, name = "treasury_token")]
+    #[account(2, name = "mint")]
+    #[account(3, name = "token_program")]
+    #[account(4, name = "allowed_mint")]
+    #[account(5, writable, name = "mint_stats")]
+    ProcessSubscriptionsBatch,
+
+    /// Accounts: [config, admin (s,w), admin_token (w), reward_pool (w), reward_vault (w), mint, token_program,
+    ///            allowed_mint, system_program, instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    /// Admin-only; `amount` may be 0 to only change the payout settings
+    #[account(0, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, name = "admin_token")]
+    #[account(3, writable, name = "reward_pool")]
+    #[account(4, writable, name = "reward_vault")]
+    #[account(5, name = "mint")]
+    #[account(6, name = "token_program")]
+    #[account(7, name = "allowed_mint")]
+    #[account(8, name = "system_program")]
+    #[account(9, optional, name = "instructions_sysvar")]
+    #[account(10, writable, signer, optional, name = "payer")]
+    FundRewardPool { amount: u64, reward_per_epoch: u64, winners: u8 },
+
+    /// Accounts: [config, reward_pool (w), reward_vault (w), tipper_leaderboard (w), mint, token_program,
+    ///            cranker (s,w), then per paid leaderboard entry: tipper_token (w)]
+    /// Permissionless once the tipper leaderboard's epoch has ended; pays its top tippers and closes it to
+    /// the cranker
+    #[account(0, name = "config")]
+    #[account(1, writable, name = "reward_pool")]
+    #[account(2, writable, name = "reward_vault")]
+    #[account(3, writable, name = "tipper_leaderboard")]
+    #[account(4, name = "mint")]
+    #[account(5, name = "token_program")]
+    #[account(6, writable, signer, name = "cranker")]
+    DistributeEpochRewards,
+
+    /// Accounts: [config, program, program_data]
+    /// Permissionless; fails unless the program's upgrade authority is the one recorded at Initialize
+    #[account(0, name = "config")]
+    #[account(1, name = "program")]
+    #[account(2, name = "program_data")]
+    VerifyDeployment,
+
+    /// Accounts: [config, admin (s,w), withdrawal (w), vault_owner, vault, destination, system_program,
+    ///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    /// `vault_owner` is the escrow, matching pool or config that owns `vault`; executable after
+    /// EMERGENCY_WITHDRAW_DELAY_SLOTS
+    #[account(0, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, name = "withdrawal")]
+    #[account(3, name = "vault_owner")]
+    #[account(4, name = "vault")]
+    #[account(5, name = "destination")]
+    #[account(6, name = "system_program")]
+    #[account(7, optional, name = "instructions_sysvar")]
+    #[account(8, writable, signer, optional, name = "payer")]
+    ScheduleEmergencyWithdraw { kind: EmergencyVault, amount: u64 },
+
+    /// Accounts: [config, admin (s,w), withdrawal (w), vault_owner (w), vault (w), destination (w), mint, token_program,
+    ///            instructions_sysvar (if admin_isolation)]
+    #[account(0, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, name = "withdrawal")]
+    #[account(3, writable, name = "vault_owner")]
+    #[account(4, writable, name = "vault")]
+    #[account(5, writable, name = "destination")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, optional, name = "instructions_sysvar")]
+    ExecuteEmergencyWithdraw,
+
+    /// Accounts: [config, admin (s,w), withdrawal (w), instructions_sysvar (if admin_isolation)]
+    #[account(0, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, name = "withdrawal")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    CancelEmergencyWithdraw,
+
+    /// Accounts: [config (w), admin (s,w), audit_log (w), system_program, instructions_sysvar (if admin_isolation),
+    ///            payer (optional, s,w)]
+    /// Admin-only and permanent; afterwards every admin instruction appends to the audit log, which
+    /// must follow its other accounts (see `with_audit_log`)
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "admin")]
+    #[account(2, writable, name = "audit_log")]
+    #[account(3, name = "system_program")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    #[account(5, writable, signer, optional, name = "payer")]
+    OpenAuditLog,
+
+    /// Accounts: [config]
+    /// Returns the Borsh-encoded TipConfig via return data
+    #[account(0, name = "config")]
+    GetConfig,
+
+    /// Accounts: [creator_stats, creator, (config)]
+    /// Returns the Borsh-encoded CreatorStats via return data (all zero but `creator` if never tipped)
+    #[account(0, name = "creator_stats")]
+    #[account(1, name = "creator")]
+    #[account(2, optional, name = "config")]
+    GetCreatorStats,
+
+    /// Accounts: [promo_code (w), config, admin (s,w), system_program, instructions_sysvar (if admin_isolation),
+    ///            payer (optional, s,w)]
+    /// Admin-only; `code_hash` is `promo_code_hash(code)`. Tips passing the PromoCode before `expiry_ts`
+    /// pay `discount_bps` (1-10000) less of their fee, up to `max_uses` times
+    #[account(0, writable, name = "promo_code")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, name = "system_program")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    #[account(5, writable, signer, optional, name = "payer")]
+    CreatePromoCode { code_hash: [u8; 32], discount_bps: u16, max_uses: u64, expiry_ts: i64 },
+
+    /// Accounts: [promo_code (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+    /// Admin-only; rent goes to the admin
+    #[account(0, writable, name = "promo_code")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, optional, name = "instructions_sysvar")]
+    ClosePromoCode,
+
+    /// Accounts: [campaign (w), authority (s,w), config, system_program,
+    ///            instructions_sysvar (if the admin signs and admin_isolation is on), payer (optional, s,w)]
+    /// The campaign's creator or platform 0's admin; tips referencing the campaign then pay `fee_bps` (max 1000)
+    /// instead of the global rate, or the global rate again for None
+    #[account(0, writable, name = "campaign")]
+    #[account(1, writable, signer, name = "authority")]
+    #[account(2, name = "config")]
+    #[account(3, name = "system_program")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    #[account(5, writable, signer, optional, name = "payer")]
+    SetCampaignFee { fee_bps: Option<u16> },
+
+    /// Accounts: [config (w), treasury, admin (s,w), system_program, payer (optional, s,w)]
+    /// Initialize for another platform on the same deployment, with its own config at
+    /// ["config", platform_id] and its own platform PDAs; the first caller becomes its admin
+    #[account(0, writable, name = "config")]
+    #[account(1, name = "treasury")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, name = "system_program")]
+    #[account(4, writable, signer, optional, name = "payer")]
+    InitializePlatform { platform_id: u64, fee_bps: u16, upgrade_authority: Option<Pubkey> },
+
+    /// Accounts: [profile (w), creator (s,w), mint, system_program, payer (optional, s,w)]
+    /// The creator's minimum tip and up to MAX_TIP_MENU suggested amounts, in `mint`'s base units;
+    /// Tip rejects tips in that mint below `min_tip` when passed the creator's profile
+    #[account(0, writable, name = "profile")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "mint")]
+    #[account(3, name = "system_program")]
+    #[account(4, writable, signer, optional, name = "payer")]
+    SetTipMenu { min_tip: u64, tip_menu: Vec<u64> },
+
+    /// Accounts: [caller (s), caller_token (w), mint, token_program,
+    ///            then per vault: creator_vault (w), vault_token (w), creator_token (w), blocked_creator (platform 0's)]
+    /// Permissionless; sweeps each vault's claimable balance to the creator's ATA, paying the caller
+    /// PAYOUT_INCENTIVE_BPS of it. Vaults below `min_amount` or without an ATA are skipped
+    #[account(0, signer, name = "caller")]
+    #[account(1, writable, name = "caller_token")]
+    #[account(2, name = "mint")]
+    #[account(3, name = "token_program")]
+    PayoutBatch { min_amount: u64 },
+
+    /// Accounts: [fan_club (w), creator (s,w), system_program, payer (optional, s,w)]
+    /// Tips from holders of an NFT in the verified Metaplex `collection` waive `discount_bps` of the fee
+    /// (at most MAX_FAN_DISCOUNT_BPS); 0 bps turns the discount off
+    #[account(0, writable, name = "fan_club")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetFanClub { collection: Pubkey, discount_bps: u16 },
+
+    /// Accounts: [profile (w), creator (s,w), system_program, payer (optional, s,w)]
+    /// Up to MAX_TIP_TIERS superchat thresholds in the profile's `tip_mint`, strictly increasing; Tip
+    /// reports and counts the tier of tips passing the profile. Empty thresholds turn tiers off
+    #[account(0, writable, name = "profile")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetTipTiers { thresholds: Vec<u64> },
+
+    /// Accounts: [charity (w), config, admin (s,w), charity_token, system_program,
+    ///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+    /// Admin-only; registers `charity_token` as the charity for tips in its mint, which
+    /// TipWithDonation may give up to `max_donation_bps` of the creator's share (0 = donations off)
+    #[account(0, writable, name = "charity")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "admin")]
+    #[account(3, name = "charity_token")]
+    #[account(4, name = "system_program")]
+    #[account(5, optional, name = "instructions_sysvar")]
+    #[account(6, writable, signer, optional, name = "payer")]
+    SetCharity { max_donation_bps: u16 },
+
+    /// Accounts: the accounts of `Tip`, with the mint's `charity` and its `charity_token`
+    /// `Tip` that sends `donation_bps` of the creator's share, after the fee and before collaborators'
+    /// cuts, to the mint's charity
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, optional, name = "creator_notify")]
+    #[account(16, writable, optional, name = "epoch_stats")]
+    #[account(17, writable, optional, name = "referrer_token")]
+    #[account(18, optional, name = "fee_override")]
+    #[account(19, writable, optional, name = "receipt")]
+    #[account(20, writable, optional, name = "receipt_tree")]
+    #[account(21, optional, name = "tree_authority")]
+    #[account(22, optional, name = "compression_program")]
+    #[account(23, optional, name = "noop_program")]
+    #[account(24, writable, optional, name = "supporter_stats")]
+    #[account(25, optional, name = "creator_profile")]
+    #[account(26, writable, optional, name = "leaderboard")]
+    #[account(27, writable, signer, optional, name = "payer")]
+    #[account(28, optional, name = "instructions_sysvar")]
+    #[account(29, writable, optional, name = "volume_window")]
+    #[account(30, optional, name = "price_feed")]
+    #[account(31, writable, optional, name = "rebate")]
+    #[account(32, optional, name = "collaborators")]
+    #[account(33, optional, name = "associated_token_program")]
+    #[account(34, writable, optional, name = "promo_code")]
+    #[account(35, optional, name = "fan_club")]
+    #[account(36, optional, name = "nft_token")]
+    #[account(37, optional, name = "nft_metadata")]
+    #[account(38, writable, optional, name = "charity")]
+    #[account(39, writable, optional, name = "charity_token")]
+    #[account(40, optional, name = "forward_rule")]
+    #[account(41, writable, optional, name = "forward_token")]
+    #[account(42, writable, optional, name = "gifter_stats")]
+    #[account(43, writable, optional, name = "tipper_leaderboard")]
+    TipWithDonation { amount: u64, memo: String, category: u8, donation_bps: u16 },
+
+    /// Accounts: [forward_rule (w), creator (s,w), system_program, payer (optional, s,w)]
+    /// Tips passing the rule send `forward_bps` of what the creator would receive to `destination`'s
+    /// ATA for the tip's mint; 0 bps turns forwarding off
+    #[account(0, writable, name = "forward_rule")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetForwardRule { destination: Pubkey, forward_bps: u16 },
+
+    /// Accounts: [snapshot (w), config, payer (s,w), system_program,
+    ///            then per creator: creator_stats, creator_snapshot (w)]
+    /// Permissionless; freezes the platform's totals and each creator's stats for the current epoch.
+    /// Snapshots the epoch already has are left alone, so several calls can cover many creators
+    #[account(0, writable, name = "snapshot")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "payer")]
+    #[account(3, name = "system_program")]
+    SnapshotStats,
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// Whether `Tip` (and the variants built on it) allows, flags or rejects tips from the creator or a
+    /// wallet controlling the account being paid
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, name = "instructions_sysvar")]
+    SetSelfTipPolicy { policy: SelfTipPolicy },
+
+    /// Accounts: [swap_program, payout_token (w), payout_mint, then the swap route's `route_accounts` accounts,
+    ///            then the accounts of `Tip` with the creator's profile]
+    /// `Tip` whose creator share, after collaborators and forwarding, is converted to the creator's payout
+    /// mint by CPI to the config's swap program with `swap_data`. The swap must spend exactly that share
+    /// and pay `payout_token`, the creator's ATA for their profile's `payout_mint`, at least `min_out`
+    #[account(0, name = "swap_program")]
+    #[account(1, writable, name = "payout_token")]
+    #[account(2, name = "payout_mint")]
+    #[account(3, writable, name = "config")]
+    #[account(4, writable, signer, name = "tipper")]
+    #[account(5, writable, name = "tipper_token")]
+    #[account(6, name = "creator")]
+    #[account(7, writable, name = "creator_token")]
+    #[account(8, writable, name = "treasury_token")]
+    #[account(9, name = "mint")]
+    #[account(10, name = "token_program")]
+    #[account(11, name = "allowed_mint")]
+    #[account(12, writable, name = "creator_stats")]
+    #[account(13, writable, name = "tipper_stats")]
+    #[account(14, name = "system_program")]
+    #[account(15, name = "blocked_creator")]
+    #[account(16, name = "blocked_tipper")]
+    #[account(17, writable, name = "mint_stats")]
+    #[account(18, optional, name = "creator_notify")]
+    #[account(19, writable, optional, name = "epoch_stats")]
+    #[account(20, writable, optional, name = "referrer_token")]
+    #[account(21, optional, name = "fee_override")]
+    #[account(22, writable, optional, name = "receipt")]
+    #[account(23, writable, optional, name = "receipt_tree")]
+    #[account(24, optional, name = "tree_authority")]
+    #[account(25, optional, name = "compression_program")]
+    #[account(26, optional, name = "noop_program")]
+    #[account(27, writable, optional, name = "supporter_stats")]
+    #[account(28, optional, name = "creator_profile")]
+    #[account(29, writable, optional, name = "leaderboard")]
+    #[account(30, writable, signer, optional, name = "payer")]
+    #[account(31, optional, name = "instructions_sysvar")]
+    #[account(32, writable, optional, name = "volume_window")]
+    #[account(33, optional, name = "price_feed")]
+    #[account(34, writable, optional, name = "rebate")]
+    #[account(35, optional, name = "collaborators")]
+    #[account(36, optional, name = "associated_token_program")]
+    #[account(37, writable, optional, name = "promo_code")]
+    #[account(38, optional, name = "fan_club")]
+    #[account(39, optional, name = "nft_token")]
+    #[account(40, optional, name = "nft_metadata")]
+    #[account(41, writable, optional, name = "charity")]
+    #[account(42, writable, optional, name = "charity_token")]
+    #[account(43, optional, name = "forward_rule")]
+    #[account(44, writable, optional, name = "forward_token")]
+    #[account(45, writable, optional, name = "gifter_stats")]
+    #[account(46, writable, optional, name = "tipper_leaderboard")]
+    TipWithSwap { amount: u64, memo: String, category: u8, min_out: u64, route_accounts: u8, swap_data: Vec<u8> },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// Admin-only; the swap program (e.g. an AMM or aggregator) TipWithSwap may call (default = swaps off)
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, name = "instructions_sysvar")]
+    SetSwapProgram { swap_program: Pubkey },
+
+    /// Accounts: [profile (w), creator (s,w), system_program, payer (optional, s,w)]
+    /// Mint TipWithSwap converts the creator's share into (default = none); legacy profiles grow to hold it
+    #[account(0, writable, name = "profile")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetPayoutMint { payout_mint: Pubkey },
+
+    /// Accounts: [tip_link (w), config, creator (s,w), system_program, payer (optional, s,w)]
+    /// Opens the creator's tip link `link_id` on the config's platform, a deposit address for plain SOL
+    /// transfers (and, through its ATAs, token transfers); an address already funded is taken over
+    #[account(0, writable, name = "tip_link")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "creator")]
+    #[account(3, name = "system_program")]
+    #[account(4, writable, signer, optional, name = "payer")]
+    CreateTipLink { link_id: u64 },
+
+    /// Accounts: [config (w), tip_link (w), link_token (w), creator (s), creator_token (w), treasury_token (w),
+    ///            mint, token_program, allowed_mint, blocked_creator, mint_stats (w),
+    ///            then per treasury split entry: split_token (w)]
+    /// Sweeps everything in `link_token`, the tip link's token account for an allowed mint, to the creator
+    /// less the fee at the current rate
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, name = "tip_link")]
+    #[account(2, writable, name = "link_token")]
+    #[account(3, signer, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, name = "blocked_creator")]
+    #[account(10, writable, name = "mint_stats")]
+    ClaimTipLink,
+
+    /// Accounts: [config (w), tip_link (w), creator (s,w), treasury_token, treasury_wallet (w), blocked_creator,
+    ///            mint_stats (w)]
+    /// Sweeps the tip link's lamports above rent to the creator less the fee, which goes to the treasury
+    /// wallet as with TipSol
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, name = "tip_link")]
+    #[account(2, writable, signer, name = "creator")]
+    #[account(3, name = "treasury_token")]
+    #[account(4, writable, name = "treasury_wallet")]
+    #[account(5, name = "blocked_creator")]
+    #[account(6, writable, name = "mint_stats")]
+    ClaimTipLinkSol,
+
+    /// Accounts: the accounts of `Tip`
+    /// `Tip` bound to off-chain content: a non-zero `external_ref` (e.g. the hash of a chat message, a video
+    /// timestamp or an order id) is stored in the tip's receipt and logged as TipExternalRef
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, optional, name = "creator_notify")]
+    #[account(16, writable, optional, name = "epoch_stats")]
+    #[account(17, writable, optional, name = "referrer_token")]
+    #[account(18, optional, name = "fee_override")]
+    #[account(19, writable, optional, name = "receipt")]
+    #[account(20, writable, optional, name = "receipt_tree")]
+    #[account(21, optional, name = "tree_authority")]
+    #[account(22, optional, name = "compression_program")]
+    #[account(23, optional, name = "noop_program")]
+    #[account(24, writable, optional, name = "supporter_stats")]
+    #[account(25, optional, name = "creator_profile")]
+    #[account(26, writable, optional, name = "leaderboard")]
+    #[account(27, writable, signer, optional, name = "payer")]
+    #[account(28, optional, name = "instructions_sysvar")]
+    #[account(29, writable, optional, name = "volume_window")]
+    #[account(30, optional, name = "price_feed")]
+    #[account(31, writable, optional, name = "rebate")]
+    #[account(32, optional, name = "collaborators")]
+    #[account(33, optional, name = "associated_token_program")]
+    #[account(34, writable, optional, name = "promo_code")]
+    #[account(35, optional, name = "fan_club")]
+    #[account(36, optional, name = "nft_token")]
+    #[account(37, optional, name = "nft_metadata")]
+    #[account(38, writable, optional, name = "charity")]
+    #[account(39, writable, optional, name = "charity_token")]
+    #[account(40, optional, name = "forward_rule")]
+    #[account(41, writable, optional, name = "forward_token")]
+    #[account(42, writable, optional, name = "gifter_stats")]
+    #[account(43, writable, optional, name = "tipper_leaderboard")]
+    TipWithRef { amount: u64, memo: String, category: u8, external_ref: [u8; 32] },
+
+    /// Accounts: [tipper_leaderboard (w), payer (s,w), system_program, (config)]
+    /// Permissionless: opens the current epoch's top-tipper leaderboard, which DistributeEpochRewards pays
+    #[account(0, writable, name = "tipper_leaderboard")]
+    #[account(1, writable, signer, name = "payer")]
+    #[account(2, name = "system_program")]
+    #[account(3, optional, name = "config")]
+    OpenTipperLeaderboard,
+
+    /// Accounts: [config (w), admin, fee_manager or governance_authority (s), instructions_sysvar (if admin_isolation)]
+    /// Takes effect through ApplyFeeUpdate once FEE_UPDATE_DELAY_SLOTS have passed
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    ScheduleFeeUpdate { new_fee_bps: u16 },
+
+    /// Accounts: [config]
+    /// Returns one byte via return data, 1 while the platform is paused and 0 otherwise; needs no signer
+    #[account(0, name = "config")]
+    IsPaused,
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// Admin of platform 0: caps the creator profiles RegisterCreator may open (0 = unlimited)
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetMaxCreators { max_creators: u32 },
+
+    /// Accounts: [config (w), admin or treasury_manager (s), instructions_sysvar (if admin_isolation)]
+    /// Payouts leaving a treasury vault below `watermark` base units emit TreasuryLow (0 = off)
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetTreasuryLowWatermark { watermark: u64 },
+
+    /// Accounts: [config, admin or treasury_manager (s), treasury_vault (w), mint, token_program,
+    ///            then per share: destination_token (w), then instructions_sysvar (if admin_isolation)]
+    /// Pays out the treasury vault's whole balance, each share's `recipient` (a token account of the
+    /// vault's mint) receiving its `share_bps`; 1-5 distinct recipients whose shares sum to 10000 bps
+    #[account(0, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, writable, name = "treasury_vault")]
+    #[account(3, name = "mint")]
+    #[account(4, name = "token_program")]
+    #[account(5, optional, name = "instructions_sysvar")]
+    DistributeFees { shares: Vec<SplitShare> },
+
+    /// Accounts: [campaign (w), then the accounts of `Tip`]
+    /// `Tip` counted toward one of the creator's open campaigns, at the campaign's fee rate if it has one.
+    /// A campaign set to `CampaignOverflow::Cap` takes only what its target still needs, charging the tipper
+    /// no more than that; a tip going past the target emits GoalExceeded either way
+    #[account(0, writable, name = "campaign")]
+    #[account(1, writable, name = "config")]
+    #[account(2, writable, signer, name = "tipper")]
+    #[account(3, writable, name = "tipper_token")]
+    #[account(4, name = "creator")]
+    #[account(5, writable, name = "creator_token")]
+    #[account(6, writable, name = "treasury_token")]
+    #[account(7, name = "mint")]
+    #[account(8, name = "token_program")]
+    #[account(9, name = "allowed_mint")]
+    #[account(10, writable, name = "creator_stats")]
+    #[account(11, writable, name = "tipper_stats")]
+    #[account(12, name = "system_program")]
+    #[account(13, name = "blocked_creator")]
+    #[account(14, name = "blocked_tipper")]
+    #[account(15, writable, name = "mint_stats")]
+    #[account(16, optional, name = "creator_notify")]
+    #[account(17, writable, optional, name = "epoch_stats")]
+    #[account(18, writable, optional, name = "referrer_token")]
+    #[account(19, optional, name = "fee_override")]
+    #[account(20, writable, optional, name = "receipt")]
+    #[account(21, writable, optional, name = "receipt_tree")]
+    #[account(22, optional, name = "tree_authority")]
+    #[account(23, optional, name = "compression_program")]
+    #[account(24, optional, name = "noop_program")]
+    #[account(25, writable, optional, name = "supporter_stats")]
+    #[account(26, optional, name = "creator_profile")]
+    #[account(27, writable, optional, name = "leaderboard")]
+    #[account(28, writable, signer, optional, name = "payer")]
+    #[account(29, optional, name = "instructions_sysvar")]
+    #[account(30, writable, optional, name = "volume_window")]
+    #[account(31, optional, name = "price_feed")]
+    #[account(32, writable, optional, name = "rebate")]
+    #[account(33, optional, name = "collaborators")]
+    #[account(34, optional, name = "associated_token_program")]
+    #[account(35, writable, optional, name = "promo_code")]
+    #[account(36, optional, name = "fan_club")]
+    #[account(37, optional, name = "nft_token")]
+    #[account(38, optional, name = "nft_metadata")]
+    #[account(39, writable, optional, name = "charity")]
+    #[account(40, writable, optional, name = "charity_token")]
+    #[account(41, optional, name = "forward_rule")]
+    #[account(42, writable, optional, name = "forward_token")]
+    #[account(43, writable, optional, name = "gifter_stats")]
+    #[account(44, writable, optional, name = "tipper_leaderboard")]
+    TipWithCampaign { amount: u64, memo: String, category: u8 },
+
+    /// Accounts: [matching_pool (w), pool_vault (w), then the accounts of `Tip`]
+    /// `Tip` that the sponsor's pool matches 1:1 out of `pool_vault`, as far as the vault holds out; the
+    /// match goes to the creator without a fee
+    #[account(0, writable, name = "matching_pool")]
+    #[account(1, writable, name = "pool_vault")]
+    #[account(2, writable, name = "config")]
+    #[account(3, writable, signer, name = "tipper")]
+    #[account(4, writable, name = "tipper_token")]
+    #[account(5, name = "creator")]
+    #[account(6, writable, name = "creator_token")]
+    #[account(7, writable, name = "treasury_token")]
+    #[account(8, name = "mint")]
+    #[account(9, name = "token_program")]
+    #[account(10, name = "allowed_mint")]
+    #[account(11, writable, name = "creator_stats")]
+    #[account(12, writable, name = "tipper_stats")]
+    #[account(13, name = "system_program")]
+    #[account(14, name = "blocked_creator")]
+    #[account(15, name = "blocked_tipper")]
+    #[account(16, writable, name = "mint_stats")]
+    #[account(17, optional, name = "creator_notify")]
+    #[account(18, writable, optional, name = "epoch_stats")]
+    #[account(19, writable, optional, name = "referrer_token")]
+    #[account(20, optional, name = "fee_override")]
+    #[account(21, writable, optional, name = "receipt")]
+    #[account(22, writable, optional, name = "receipt_tree")]
+    #[account(23, optional, name = "tree_authority")]
+    #[account(24, optional, name = "compression_program")]
+    #[account(25, optional, name = "noop_program")]
+    #[account(26, writable, optional, name = "supporter_stats")]
+    #[account(27, optional, name = "creator_profile")]
+    #[account(28, writable, optional, name = "leaderboard")]
+    #[account(29, writable, signer, optional, name = "payer")]
+    #[account(30, optional, name = "instructions_sysvar")]
+    #[account(31, writable, optional, name = "volume_window")]
+    #[account(32, optional, name = "price_feed")]
+    #[account(33, writable, optional, name = "rebate")]
+    #[account(34, optional, name = "collaborators")]
+    #[account(35, optional, name = "associated_token_program")]
+    #[account(36, writable, optional, name = "promo_code")]
+    #[account(37, optional, name = "fan_club")]
+    #[account(38, optional, name = "nft_token")]
+    #[account(39, optional, name = "nft_metadata")]
+    #[account(40, writable, optional, name = "charity")]
+    #[account(41, writable, optional, name = "charity_token")]
+    #[account(42, optional, name = "forward_rule")]
+    #[account(43, writable, optional, name = "forward_token")]
+    #[account(44, writable, optional, name = "gifter_stats")]
+    #[account(45, writable, optional, name = "tipper_leaderboard")]
+    TipWithMatch { amount: u64, memo: String, category: u8 },
+
+    /// Accounts: [creator_vault (w), vesting (w), then the accounts of `Tip`]
+    /// `Tip` of the config's `vesting_threshold` or more, paid into the creator's vault (`creator_token` is
+    /// its token account) and locked in `vesting`, the vault's next schedule
+    #[account(0, writable, name = "creator_vault")]
+    #[account(1, writable, name = "vesting")]
+    #[account(2, writable, name = "config")]
+    #[account(3, writable, signer, name = "tipper")]
+    #[account(4, writable, name = "tipper_token")]
+    #[account(5, name = "creator")]
+    #[account(6, writable, name = "creator_token")]
+    #[account(7, writable, name = "treasury_token")]
+    #[account(8, name = "mint")]
+    #[account(9, name = "token_program")]
+    #[account(10, name = "allowed_mint")]
+    #[account(11, writable, name = "creator_stats")]
+    #[account(12, writable, name = "tipper_stats")]
+    #[account(13, name = "system_program")]
+    #[account(14, name = "blocked_creator")]
+    #[account(15, name = "blocked_tipper")]
+    #[account(16, writable, name = "mint_stats")]
+    #[account(17, optional, name = "creator_notify")]
+    #[account(18, writable, optional, name = "epoch_stats")]
+    #[account(19, writable, optional, name = "referrer_token")]
+    #[account(20, optional, name = "fee_override")]
+    #[account(21, writable, optional, name = "receipt")]
+    #[account(22, writable, optional, name = "receipt_tree")]
+    #[account(23, optional, name = "tree_authority")]
+    #[account(24, optional, name = "compression_program")]
+    #[account(25, optional, name = "noop_program")]
+    #[account(26, writable, optional, name = "supporter_stats")]
+    #[account(27, optional, name = "creator_profile")]
+    #[account(28, writable, optional, name = "leaderboard")]
+    #[account(29, writable, signer, optional, name = "payer")]
+    #[account(30, optional, name = "instructions_sysvar")]
+    #[account(31, writable, optional, name = "volume_window")]
+    #[account(32, optional, name = "price_feed")]
+    #[account(33, writable, optional, name = "rebate")]
+    #[account(34, optional, name = "collaborators")]
+    #[account(35, optional, name = "associated_token_program")]
+    #[account(36, writable, optional, name = "promo_code")]
+    #[account(37, optional, name = "fan_club")]
+    #[account(38, optional, name = "nft_token")]
+    #[account(39, optional, name = "nft_metadata")]
+    #[account(40, writable, optional, name = "charity")]
+    #[account(41, writable, optional, name = "charity_token")]
+    #[account(42, optional, name = "forward_rule")]
+    #[account(43, writable, optional, name = "forward_token")]
+    #[account(44, writable, optional, name = "gifter_stats")]
+    #[account(45, writable, optional, name = "tipper_leaderboard")]
+    TipWithVesting { amount: u64, memo: String, category: u8 },
+
+    /// Accounts: the accounts of `Tip`
+    /// `Tip` that only goes through while `nonce` is the tipper's `TipperStats::tip_nonce`, which it then
+    /// increments, so a tipper's nonced tips land in the order they were signed and a replayed or
+    /// reordered one fails with TipNonceMismatch. Plain tips neither check nor advance the nonce
+    #[account(0, writable, name = "config")]
+    #[account(1, writable, signer, name = "tipper")]
+    #[account(2, writable, name = "tipper_token")]
+    #[account(3, name = "creator")]
+    #[account(4, writable, name = "creator_token")]
+    #[account(5, writable, name = "treasury_token")]
+    #[account(6, name = "mint")]
+    #[account(7, name = "token_program")]
+    #[account(8, name = "allowed_mint")]
+    #[account(9, writable, name = "creator_stats")]
+    #[account(10, writable, name = "tipper_stats")]
+    #[account(11, name = "system_program")]
+    #[account(12, name = "blocked_creator")]
+    #[account(13, name = "blocked_tipper")]
+    #[account(14, writable, name = "mint_stats")]
+    #[account(15, optional, name = "creator_notify")]
+    #[account(16, writable, optional, name = "epoch_stats")]
+    #[account(17, writable, optional, name = "referrer_token")]
+    #[account(18, optional, name = "fee_override")]
+    #[account(19, writable, optional, name = "receipt")]
+    #[account(20, writable, optional, name = "receipt_tree")]
+    #[account(21, optional, name = "tree_authority")]
+    #[account(22, optional, name = "compression_program")]
+    #[account(23, optional, name = "noop_program")]
+    #[account(24, writable, optional, name = "supporter_stats")]
+    #[account(25, optional, name = "creator_profile")]
+    #[account(26, writable, optional, name = "leaderboard")]
+    #[account(27, writable, signer, optional, name = "payer")]
+    #[account(28, optional, name = "instructions_sysvar")]
+    #[account(29, writable, optional, name = "volume_window")]
+    #[account(30, optional, name = "price_feed")]
+    #[account(31, writable, optional, name = "rebate")]
+    #[account(32, optional, name = "collaborators")]
+    #[account(33, optional, name = "associated_token_program")]
+    #[account(34, writable, optional, name = "promo_code")]
+    #[account(35, optional, name = "fan_club")]
+    #[account(36, optional, name = "nft_token")]
+    #[account(37, optional, name = "nft_metadata")]
+    #[account(38, writable, optional, name = "charity")]
+    #[account(39, writable, optional, name = "charity_token")]
+    #[account(40, optional, name = "forward_rule")]
+    #[account(41, writable, optional, name = "forward_token")]
+    #[account(42, writable, optional, name = "gifter_stats")]
+    #[account(43, writable, optional, name = "tipper_leaderboard")]
+    TipWithNonce { amount: u64, memo: String, category: u8, nonce: u64 },
+
+    /// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+    /// While `enabled`, `Tip` (and the variants built on it) skips a referral, charity or forward
+    /// destination that fails its checks, emitting RoutingSkipped, and still pays the creator and treasury
+    #[account(0, writable, name = "config")]
+    #[account(1, signer, name = "admin")]
+    #[account(2, optional, name = "instructions_sysvar")]
+    SetLenientRouting { enabled: bool },
+
+    /// Accounts: [profile (w), config, admin or verifier (s,w), system_program,
+    ///            instructions_sysvar (if admin_isolation)]
+    /// Has `Tip` withhold `tax_bps` of the creator's own share, after the fee, donation and collaborators'
+    /// cuts, for `tax_account`'s ATA in the tip's mint (0 = off); legacy profiles grow to hold it at the
+    /// signer's expense
+    #[account(0, writable, name = "profile")]
+    #[account(1, name = "config")]
+    #[account(2, writable, signer, name = "verifier")]
+    #[account(3, name = "system_program")]
+    #[account(4, optional, name = "instructions_sysvar")]
+    SetTaxWithholding { tax_bps: u16, tax_account: Pubkey },
+
+    /// Accounts: [campaign (w), creator (s,w), system_program, payer (optional, s,w)]
+    /// Creator-only, until the campaign is finalized: whether TipWithCampaign keeps the part of a tip beyond
+    /// the target or takes only what the target still needs (default = accept); legacy campaigns grow to
+    /// hold it
+    #[account(0, writable, name = "campaign")]
+    #[account(1, writable, signer, name = "creator")]
+    #[account(2, name = "system_program")]
+    #[account(3, writable, signer, optional, name = "payer")]
+    SetCampaignOverflow { overflow: CampaignOverflow },
+}
+
+impl TipInstruction {
+    /// Decode instruction data, also accepting the Tip payload from before memos and categories,
+    /// `[1, amount: u64]`, as a Tip with an empty memo and category 0
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        if let Some((&1, amount)) = data.split_first() {
+            if let Ok(amount) = <[u8; 8]>::try_from(amount) {
+                return Ok(Self::Tip { amount: u64::from_le_bytes(amount), memo: String::new(), category: 0 });
+            }
+        }
+        Self::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
+
+/// Conversion of the creator's share for `tip` (see `TipWithSwap`)
+#[derive(Clone, Copy, Debug)]
+pub struct SwapRoute<'a> {
+    /// The config's swap program
+    pub swap_program: &'a Pubkey,
+    /// The creator profile's payout mint, and the token program owning it
+    pub payout_mint: &'a Pubkey,
+    pub payout_token_program: &'a Pubkey,
+    /// Least the creator's payout ATA must receive
+    pub min_out: u64,
+    /// Data and accounts of the swap instruction, as the swap program's SDK builds it for the tipper
+    /// swapping the creator's share out of `tipper_token` into the creator's payout ATA
+    pub data: &'a [u8],
+    pub accounts: &'a [AccountMeta],
+}
+
+/// Optional trailing accounts for `tip`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TipExtras<'a> {
+    /// Send as `TipWithCampaign`, counting the tip toward this campaign of the creator's
+    pub campaign: Option<&'a Pubkey>,
+    /// Send as `TipWithMatch`, matched out of this sponsor's pool
+    pub matching_pool: Option<&'a Pubkey>,
+    /// Current epoch index (see `epoch_index`); its EpochStats must already exist
+    pub epoch_index: Option<u64>,
+    /// Token account that receives `referral_bps` of the fee
+    pub referrer_token: Option<&'a Pubkey>,
+    /// Pass the creator's FeeOverride PDA so a negotiated rate applies
+    pub fee_override: bool,
+    /// The config's treasury split recipients, in order (empty if no split is set)
+    pub treasury_split: &'a [Pubkey],
+    /// The tipper's (or attributed tipper's) current `TipperStats::tip_count` (0 before their first
+    /// tip) to create a TipReceipt for this tip
+    pub receipt_nonce: Option<u64>,
+    /// The config's receipt tree, to append a compressed receipt instead
+    pub receipt_tree: Option<&'a Pubkey>,
+    /// Count the tip toward the tipper's supporter badge with this creator
+    pub supporter_stats: bool,
+    /// Send as `TipWithVesting` with the creator vault's current `vesting_count`, for tips of at least the
+    /// config's `vesting_threshold`; `creator_token` must then be the vault token account
+    pub vesting_index: Option<u64>,
+    /// Send as `TipAnonymous`, which rejects receipts, supporter stats and vesting
+    pub anonymous: bool,
+    /// Send as `TipGift`, crediting this wallet's stats, receipt and supporter badge instead of the tipper's;
+    /// the tipper's own TipperStats is passed too, for the rate limit
+    pub attributed_tipper: Option<&'a Pubkey>,
+    /// Pass the creator's CreatorProfile, required while the config's `require_verified` is set; it also
+    /// applies their minimum tip and superchat tiers
+    pub creator_profile: bool,
+    /// Current epoch index (see `epoch_index`); re-ranks the creator on its Leaderboard, which must already exist
+    pub leaderboard_epoch: Option<u64>,
+    /// Current epoch index; re-ranks the tipper (or attributed tipper) on its tipper Leaderboard, which must
+    /// already exist
+    pub tipper_leaderboard_epoch: Option<u64>,
+    /// Signer that pays rent for every account the tip creates or grows, instead of the tipper
+    pub payer: Option<&'a Pubkey>,
+    /// Pass the instructions sysvar, required while the config's `require_top_level` is set
+    pub instructions_sysvar: bool,
+    /// Add the tip to the hourly VolumeWindow, which must already exist
+    pub volume_window: bool,
+    /// The mint's Pyth price account, as set with SetPriceFeed, to value the tip in USD
+    pub price_feed: Option<&'a Pubkey>,
+    /// Count the tip toward the tipper's rebate for this mint, created on first use
+    pub rebate: bool,
+    /// The creator's collaborator wallets, in order (empty if they have no collaborator split)
+    pub collaborators: &'a [Pubkey],
+    /// The `tax_account` of the creator's profile while it withholds tax (see `SetTaxWithholding`), whose ATA
+    /// for the mint receives the withheld share; needs `creator_profile`
+    pub tax_account: Option<&'a Pubkey>,
+    /// Pass the ATA program so a `creator_token` that does not exist yet is created as the creator's
+    /// ATA (see `tip_to_creator`)
+    pub create_creator_ata: bool,
+    /// Promo code to take off the fee (see `CreatePromoCode`)
+    pub promo_code: Option<&'a str>,
+    /// Mint of an NFT the tipper holds (in their ATA) from the creator's fan club collection, for its
+    /// fee discount (see `SetFanClub`)
+    pub fan_nft: Option<&'a Pubkey>,
+    /// Send as `TipWithDonation`, giving this share of the creator's share to the mint's charity, whose
+    /// `charity_token` must be passed too; cannot be combined with `anonymous` or `attributed_tipper`
+    pub donation_bps: u16,
+    /// Token account the mint's Charity registered (see `SetCharity`)
+    pub charity_token: Option<&'a Pubkey>,
+    /// Destination of the creator's ForwardRule, whose ATA for the mint receives the forwarded share
+    /// (see `SetForwardRule`)
+    pub forward_to: Option<&'a Pubkey>,
+    /// Send as `TipWithSwap`, converting the creator's share to their payout mint; needs `creator_profile`.
+    /// `campaign`, `matching_pool`, `vesting_index` and `swap` each pick their own variant, so at most one
+    /// can be set, and none with `anonymous`, `attributed_tipper`, `donation_bps` or `external_ref`
+    pub swap: Option<SwapRoute<'a>>,
+    /// Send as `TipWithRef`, binding this reference to the tip's receipt and events; cannot be combined
+    /// with `anonymous`, `attributed_tipper` or `donation_bps`
+    pub external_ref: Option<[u8; 32]>,
+    /// Send as `TipWithNonce` with the tipper's current `TipperStats::tip_nonce` (0 before their first);
+    /// cannot be combined with any other variant
+    pub nonce: Option<u64>,
+    /// Extra accounts a Token-2022 transfer-hook mint needs, appended after the treasury split and
+    /// collaborator token accounts
+    pub hook_accounts: &'a [AccountMeta],
+    /// Tip category below TIP_CATEGORIES (0 = none)
+    pub category: u8,
+}
+
+/// Append the treasury split recipients that every fee-paying instruction takes last. `tip` takes
+/// them through `TipExtras::treasury_split` instead.
+pub fn with_treasury_split(mut ix: Instruction, split_tokens: &[Pubkey]) -> Instruction {
+    ix.accounts.extend(split_tokens.iter().map(|token| AccountMeta::new(*token, false)));
+    ix
+}
+
+/// Append a `payer` that funds the rent of whatever the instruction creates or grows, in place of its
+/// authority (tipper, creator, sponsor or admin). It must sign and fills the slot right after the
+/// instruction's own accounts, so add it after `with_treasury_split` and before `with_audit_log` or
+/// `with_event_cpi`; `tip` takes it through `TipExtras::payer` instead.
+pub fn with_rent_payer(mut ix: Instruction, payer: &Pubkey) -> Instruction {
+    ix.accounts.push(AccountMeta::new(*payer, true));
+    ix
+}
+
+/// Append the SPL Noop program so the instruction also CPIs each event into it, where it survives log
+/// truncation (see `events`). Add it last.
+pub fn with_event_cpi(mut ix: Instruction) -> Instruction {
+    ix.accounts.push(AccountMeta::new_readonly(compression::NOOP_ID, false));
+    ix
+}
+
+/// Append the audit log that every admin instruction takes once OpenAuditLog has run. It is found by
+/// address; add it after any rent payer.
+pub fn with_audit_log(mut ix: Instruction, program_id: &Pubkey, platform_id: u64) -> Instruction {
+    ix.accounts.push(AccountMeta::new(get_audit_log_pda(program_id, platform_id).0, false));
+    ix
+}
+
+fn build(program_id: &Pubkey, data: TipInstruction, accounts: Vec<AccountMeta>) -> Instruction {
+    Instruction::new_with_borsh(*program_id, &data, accounts)
+}
+
+// Admin builders always pass the instructions sysvar; it is ignored unless admin_isolation is on.
+// Builders for instructions without a config of their own pass the platform's config last, after
+// which the platform's PDAs are derived; it does not need to exist for platform 0.
+
+/// Creates the config of `platform_id`; platform 0 is the deployment's original config
+pub fn initialize(
+    program_id: &Pubkey,
+    platform_id: u64,
+    treasury: &Pubkey,
+    admin: &Pubkey,
+    fee_bps: u16,
+    upgrade_authority: Option<&Pubkey>,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::InitializePlatform { platform_id, fee_bps, upgrade_authority: upgrade_authority.copied() },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*treasury, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `creator` is the wallet owning `creator_token`; `mint` is the mint of all three token accounts
+/// and `token_program` its owner (SPL Token or Token-2022)
+#[allow(clippy::too_many_arguments)]
+pub fn tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    memo: &str,
+    extras: TipExtras,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    let (creator_stats, _) = get_creator_stats_pda(program_id, platform_id, creator);
+    let credited = extras.attributed_tipper.unwrap_or(tipper);
+    let (tipper_stats, _) = get_tipper_stats_pda(program_id, platform_id, credited);
+    let (creator_notify, _) = get_notify_pda(program_id, creator);
+    // The variant's own accounts come ahead of Tip's
+    let mut accounts = Vec::new();
+    if let Some(campaign) = extras.campaign {
+        accounts.push(AccountMeta::new(*campaign, false));
+    }
+    if let Some(pool) = extras.matching_pool {
+        let (pool_vault, _) = get_pool_vault_pda(program_id, pool);
+        accounts.extend([AccountMeta::new(*pool, false), AccountMeta::new(pool_vault, false)]);
+    }
+    if let Some(index) = extras.vesting_index {
+        let (creator_vault, _) = get_creator_vault_pda(program_id, creator, mint);
+        let (vesting, _) = get_vesting_pda(program_id, &creator_vault, index);
+        accounts.extend([AccountMeta::new(creator_vault, false), AccountMeta::new(vesting, false)]);
+    }
+    if let Some(route) = extras.swap {
+        let payout_token = token::get_associated_token_address(creator, route.payout_mint, route.payout_token_program);
+        accounts.extend([
+            AccountMeta::new_readonly(*route.swap_program, false),
+            AccountMeta::new(payout_token, false),
+            AccountMeta::new_readonly(*route.payout_mint, false),
+        ]);
+        accounts.extend_from_slice(route.accounts);
+    }
+    accounts.extend([
+        AccountMeta::new(config, false),
+        AccountMeta::new(*tipper, true),
+        AccountMeta::new(*tipper_token, false),
+        AccountMeta::new_readonly(*creator, false),
+        AccountMeta::new(*creator_token, false),
+        AccountMeta::new(*treasury_token, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(allowed_mint, false),
+        AccountMeta::new(creator_stats, false),
+        AccountMeta::new(tipper_stats, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+        AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+        AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+        AccountMeta::new_readonly(creator_notify, false),
+    ]);
+    let slots = [
+        extras.epoch_index.map(|index| {
+            vec![AccountMeta::new(get_epoch_stats_pda(program_id, platform_id, index).0, false)]
+        }),
+        extras.referrer_token.map(|token| vec![AccountMeta::new(*token, false)]),
+        extras.fee_override.then(|| {
+            vec![AccountMeta::new_readonly(get_fee_override_pda(program_id, platform_id, creator).0, false)]
+        }),
+        extras.receipt_nonce.map(|nonce| {
+            vec![AccountMeta::new(get_tip_receipt_pda(program_id, credited, creator, nonce).0, false)]
+        }),
+        extras.receipt_tree.map(|tree| {
+            vec![
+                AccountMeta::new(*tree, false),
+                AccountMeta::new_readonly(get_tree_authority_pda(program_id, platform_id).0, false),
+                AccountMeta::new_readonly(compression::ID, false),
+                AccountMeta::new_readonly(compression::NOOP_ID, false),
+            ]
+        }),
+        extras.supporter_stats.then(|| {
+            vec![AccountMeta::new(get_supporter_stats_pda(program_id, credited, creator).0, false)]
+        }),
+        extras.creator_profile.then(|| {
+            vec![AccountMeta::new_readonly(get_creator_profile_pda(program_id, creator).0, false)]
+        }),
+        extras.leaderboard_epoch.map(|index| {
+            vec![AccountMeta::new(get_leaderboard_pda(program_id, platform_id, index).0, false)]
+        }),
+        extras.payer.map(|payer| vec![AccountMeta::new(*payer, true)]),
+        extras.instructions_sysvar.then(|| vec![AccountMeta::new_readonly(sysvar::instructions::id(), false)]),
+        extras.volume_window.then(|| vec![AccountMeta::new(get_volume_window_pda(program_id, platform_id).0, false)]),
+        extras.price_feed.map(|feed| vec![AccountMeta::new_readonly(*feed, false)]),
+        extras.rebate.then(|| {
+            vec![AccountMeta::new(get_tipper_rebate_pda(program_id, platform_id, tipper, mint).0, false)]
+        }),
+        (!extras.collaborators.is_empty())
+            .then(|| vec![AccountMeta::new_readonly(get_collaborator_split_pda(program_id, creator).0, false)]),
+        extras.create_creator_ata.then(|| vec![AccountMeta::new_readonly(token::ASSOCIATED_TOKEN_PROGRAM_ID, false)]),
+        extras.promo_code.map(|code| {
+            vec![AccountMeta::new(get_promo_code_pda(program_id, platform_id, &promo_code_hash(code)).0, false)]
+        }),
+        extras.fan_nft.map(|nft| {
+            vec![
+                AccountMeta::new_readonly(get_fan_club_pda(program_id, creator).0, false),
+                AccountMeta::new_readonly(token::get_associated_token_address(tipper, nft, &spl_token::id()), false),
+                AccountMeta::new_readonly(metadata::find_metadata_pda(nft), false),
+            ]
+        }),
+        extras.charity_token.map(|charity_token| {
+            vec![
+                AccountMeta::new(get_charity_pda(program_id, platform_id, mint).0, false),
+                AccountMeta::new(*charity_token, false),
+            ]
+        }),
+        extras.forward_to.map(|destination| {
+            vec![
+                AccountMeta::new_readonly(get_forward_rule_pda(program_id, creator).0, false),
+                AccountMeta::new(token::get_associated_token_address(destination, mint, token_program), false),
+            ]
+        }),
+        extras.attributed_tipper.map(|_| {
+            vec![AccountMeta::new(get_tipper_stats_pda(program_id, platform_id, tipper).0, false)]
+        }),
+        extras.tipper_leaderboard_epoch.map(|index| {
+            vec![AccountMeta::new(get_tipper_leaderboard_pda(program_id, platform_id, index).0, false)]
+        }),
+    ];
+    let used = match (extras.treasury_split, extras.tax_account, extras.hook_accounts) {
+        ([], None, []) => slots.iter().rposition(Option::is_some).map_or(0, |i| i + 1),
+        _ => slots.len(),
+    };
+    for slot in slots.into_iter().take(used) {
+        accounts.extend(slot.unwrap_or_else(|| vec![AccountMeta::new_readonly(*program_id, false)]));
+    }
+    accounts.extend(extras.treasury_split.iter().map(|token| AccountMeta::new(*token, false)));
+    accounts.extend(extras.collaborators.iter().map(|collaborator| {
+        AccountMeta::new(token::get_associated_token_address(collaborator, mint, token_program), false)
+    }));
+    assert!(extras.tax_account.is_none() || extras.creator_profile, "`tax_account` needs `creator_profile`");
+    accounts.extend(extras.tax_account.map(|tax_account| {
+        AccountMeta::new(token::get_associated_token_address(tax_account, mint, token_program), false)
+    }));
+    accounts.extend_from_slice(extras.hook_accounts);
+    let memo = memo.to_string();
+    let category = extras.category;
+    assert!(extras.swap.is_none() || extras.creator_profile, "`swap` needs `creator_profile`");
+    let variant = (extras.attributed_tipper, extras.anonymous, extras.donation_bps, extras.external_ref);
+    let data = match variant {
+        (Some(attributed_tipper), _, 0, None) => {
+            TipInstruction::TipGift { amount, memo, category, attributed_tipper: *attributed_tipper }
+        }
+        (None, true, 0, None) => TipInstruction::TipAnonymous { amount, memo, category },
+        (None, false, 0, None) => TipInstruction::Tip { amount, memo, category },
+        (None, false, donation_bps, None) => TipInstruction::TipWithDonation { amount, memo, category, donation_bps },
+        (None, false, 0, Some(external_ref)) => TipInstruction::TipWithRef { amount, memo, category, external_ref },
+        _ => panic!(
+            "`donation_bps` and `external_ref` cannot be combined with each other, `anonymous` or `attributed_tipper`"
+        ),
+    };
+    let route = (extras.campaign, extras.matching_pool, extras.vesting_index, extras.swap);
+    let data = match (data, route, extras.nonce) {
+        (data, (None, None, None, None), None) => data,
+        (TipInstruction::Tip { amount, memo, category }, (None, None, None, None), Some(nonce)) => {
+            TipInstruction::TipWithNonce { amount, memo, category, nonce }
+        }
+        (TipInstruction::Tip { amount, memo, category }, (Some(_), None, None, None), None) => {
+            TipInstruction::TipWithCampaign { amount, memo, category }
+        }
+        (TipInstruction::Tip { amount, memo, category }, (None, Some(_), None, None), None) => {
+            TipInstruction::TipWithMatch { amount, memo, category }
+        }
+        (TipInstruction::Tip { amount, memo, category }, (None, None, Some(_), None), None) => {
+            TipInstruction::TipWithVesting { amount, memo, category }
+        }
+        (TipInstruction::Tip { amount, memo, category }, (None, None, None, Some(route)), None) => {
+            TipInstruction::TipWithSwap {
+                amount,
+                memo,
+                category,
+                min_out: route.min_out,
+                route_accounts: route.accounts.len() as u8,
+                swap_data: route.data.to_vec(),
+            }
+        }
+        _ => panic!(
+            "`campaign`, `matching_pool`, `vesting_index`, `swap` and `nonce` each send their own variant, and \
+             cannot be combined with each other or the other variants"
+        ),
+    };
+    build(program_id, data, accounts)
+}
+
+/// `tip` to the creator's wallet: `creator_token` is their ATA for `mint`, created by the tip if they
+/// have never held the mint
+#[allow(clippy::too_many_arguments)]
+pub fn tip_to_creator(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    memo: &str,
+    extras: TipExtras,
+) -> Instruction {
+    let creator_token = token::get_associated_token_address(creator, mint, token_program);
+    let extras = TipExtras { create_creator_ata: true, ..extras };
+    tip(
+        program_id, platform_id, tipper, tipper_token, creator, &creator_token, treasury_token, mint, token_program,
+        amount, memo, extras,
+    )
+}
+
+pub fn schedule_fee_update(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, new_fee_bps: u16) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::ScheduleFeeUpdate { new_fee_bps },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn derive_pdas(program_id: &Pubkey, platform_id: u64, mint: Option<&Pubkey>) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let mut accounts = vec![AccountMeta::new_readonly(config, false)];
+    accounts.extend(mint.map(|mint| AccountMeta::new_readonly(*mint, false)));
+    build(program_id, TipInstruction::DerivePdas, accounts)
+}
+
+pub fn set_notify(program_id: &Pubkey, creator: &Pubkey, notify: bool) -> Instruction {
+    let (creator_notify, _) = get_notify_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetNotify { notify },
+        vec![
+            AccountMeta::new(creator_notify, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `mint` is the default pubkey for native SOL
+pub fn set_fee_target(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    fee_target: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetFeeTarget { fee_target },
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn sweep_excess_rent(program_id: &Pubkey, platform_id: u64, admin: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SweepExcessRent,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_admin_isolation(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, enabled: bool) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetAdminIsolation { enabled },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// The session only tips on `platform_id`
+#[allow(clippy::too_many_arguments)]
+pub fn open_session(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    session_key: &Pubkey,
+    token_program: &Pubkey,
+    budget: u64,
+    expiry_ts: i64,
+) -> Instruction {
+    let (session, _) = get_session_pda(program_id, platform_id, tipper);
+    build(
+        program_id,
+        TipInstruction::OpenSession { budget, expiry_ts },
+        vec![
+            AccountMeta::new(session, false),
+            AccountMeta::new(*tipper, true),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*session_key, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn session_tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    session_key: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    let (session, _) = get_session_pda(program_id, platform_id, tipper);
+    build(
+        program_id,
+        TipInstruction::SessionTip { amount },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(session, false),
+            AccountMeta::new_readonly(*session_key, true),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new(*creator_token, false),
+            AccountMeta::new(*treasury_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(allowed_mint, false),
+            AccountMeta::new(get_tipper_stats_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+        ],
+    )
+}
+
+/// The Ed25519 program instruction that must precede `tip_delegated`, for a `signature` the tipper
+/// made over `borsh(DelegatedTip)` with any wallet that can sign messages
+pub fn verify_delegated_tip(tipper: &Pubkey, signature: &[u8; 64], message: &DelegatedTip) -> Instruction {
+    let message = borsh::to_vec(message).expect("in-memory Borsh encoding cannot fail");
+    // One signature; its offsets (u16 LE) point into this instruction's data (index u16::MAX)
+    const HEADER: u16 = 2 + 14;
+    let offsets = [HEADER + 32, u16::MAX, HEADER, u16::MAX, HEADER + 96, message.len() as u16, u16::MAX];
+    let mut data = vec![1, 0];
+    data.extend(offsets.iter().flat_map(|offset| offset.to_le_bytes()));
+    data.extend_from_slice(tipper.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(&message);
+    Instruction { program_id: solana_program::ed25519_program::id(), accounts: vec![], data }
+}
+
+/// Send right after `verify_delegated_tip` for the same `message`; `relayer` pays the transaction
+/// and, on the tipper's first delegated tip, the Delegation rent. The tip goes through the config of
+/// `message.platform_id`
+pub fn tip_delegated(
+    program_id: &Pubkey,
+    relayer: &Pubkey,
+    tipper: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    token_program: &Pubkey,
+    message: &DelegatedTip,
+) -> Instruction {
+    let platform_id = message.platform_id;
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, &message.mint);
+    let (delegation, _) = get_delegation_pda(program_id, platform_id, tipper);
+    build(
+        program_id,
+        TipInstruction::TipDelegated { amount: message.amount, nonce: message.nonce, expiry_ts: message.expiry_ts },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*relayer, true),
+            AccountMeta::new_readonly(*tipper, false),
+            AccountMeta::new(delegation, false),
+            AccountMeta::new(message.tipper_token, false),
+            AccountMeta::new_readonly(message.creator, false),
+            AccountMeta::new(*creator_token, false),
+            AccountMeta::new(*treasury_token, false),
+            AccountMeta::new_readonly(message.mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(allowed_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new(get_tipper_stats_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, &message.creator).0, false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, &message.mint).0, false),
+        ],
+    )
+}
+
+pub fn handoff_ownership(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    new_admin: &Pubkey,
+    new_treasury: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::HandoffOwnership,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*new_admin, false),
+            AccountMeta::new_readonly(*new_treasury, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn tip_sol(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    treasury_token: &Pubkey,
+    treasury_wallet: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::TipSol { amount },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*tipper, true),
+            AccountMeta::new(*creator, false),
+            AccountMeta::new_readonly(*treasury_token, false),
+            AccountMeta::new(*treasury_wallet, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(get_tipper_stats_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, &Pubkey::default()).0, false),
+        ],
+    )
+}
+
+/// `tip_sol` that pays the creator's share into their SolVault, which must be open
+pub fn tip_sol_to_vault(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    treasury_token: &Pubkey,
+    treasury_wallet: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (sol_vault, _) = get_sol_vault_pda(program_id, creator);
+    let mut ix = tip_sol(program_id, platform_id, tipper, creator, treasury_token, treasury_wallet, amount);
+    ix.accounts.push(AccountMeta::new(sol_vault, false));
+    ix
+}
+
+pub fn propose_admin(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::ProposeAdmin,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*new_admin, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn accept_admin(program_id: &Pubkey, platform_id: u64, new_admin: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::AcceptAdmin,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*new_admin, true),
+        ],
+    )
+}
+
+pub fn update_treasury(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    new_treasury: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    build(
+        program_id,
+        TipInstruction::UpdateTreasury,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(*new_treasury, false),
+            AccountMeta::new_readonly(allowed_mint, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn pause(program_id: &Pubkey, platform_id: u64, admin: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::Pause,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn unpause(program_id: &Pubkey, platform_id: u64, admin: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::Unpause,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn allow_mint(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    build(
+        program_id,
+        TipInstruction::AllowMint,
+        vec![
+            AccountMeta::new(allowed_mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn disallow_mint(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, mint: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    build(
+        program_id,
+        TipInstruction::DisallowMint,
+        vec![
+            AccountMeta::new(allowed_mint, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// One tip per `(creator, creator_token, amount)`; all token accounts must share `mint`
+#[allow(clippy::too_many_arguments)]
+pub fn tip_many(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    tips: &[(Pubkey, Pubkey, u64)],
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    let (tipper_stats, _) = get_tipper_stats_pda(program_id, platform_id, tipper);
+    let mut accounts = vec![
+        AccountMeta::new(config, false),
+        AccountMeta::new(*tipper, true),
+        AccountMeta::new(*tipper_token, false),
+        AccountMeta::new(*treasury_token, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(allowed_mint, false),
+        AccountMeta::new(tipper_stats, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+        AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+    ];
+    for (creator, creator_token, _) in tips {
+        let (creator_stats, _) = get_creator_stats_pda(program_id, platform_id, creator);
+        accounts.push(AccountMeta::new_readonly(*creator, false));
+        accounts.push(AccountMeta::new(*creator_token, false));
+        accounts.push(AccountMeta::new(creator_stats, false));
+        accounts.push(AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false));
+    }
+    let amounts = tips.iter().map(|(_, _, amount)| *amount).collect();
+    build(program_id, TipInstruction::TipMany { amounts }, accounts)
+}
+
+pub fn set_split(program_id: &Pubkey, creator: &Pubkey, shares: Vec<SplitShare>) -> Instruction {
+    let (split, _) = get_split_config_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetSplit { shares },
+        vec![
+            AccountMeta::new(split, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `recipient_tokens` must follow the order of the creator's `SplitConfig` shares
+#[allow(clippy::too_many_arguments)]
+pub fn tip_split(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    recipient_tokens: &[Pubkey],
+    amount: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (split, _) = get_split_config_pda(program_id, creator);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    let (creator_stats, _) = get_creator_stats_pda(program_id, platform_id, creator);
+    let (tipper_stats, _) = get_tipper_stats_pda(program_id, platform_id, tipper);
+    let mut accounts = vec![
+        AccountMeta::new(config, false),
+        AccountMeta::new(*tipper, true),
+        AccountMeta::new(*tipper_token, false),
+        AccountMeta::new_readonly(*creator, false),
+        AccountMeta::new_readonly(split, false),
+        AccountMeta::new(*treasury_token, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(allowed_mint, false),
+        AccountMeta::new(creator_stats, false),
+        AccountMeta::new(tipper_stats, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+        AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+        AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+    ];
+    accounts.extend(recipient_tokens.iter().map(|token| AccountMeta::new(*token, false)));
+    build(program_id, TipInstruction::TipSplit { amount }, accounts)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_subscription(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    interval_slots: u64,
+    allowance: u64,
+) -> Instruction {
+    let (sub, _) = get_subscription_pda(program_id, platform_id, tipper, creator);
+    build(
+        program_id,
+        TipInstruction::CreateSubscription { amount, interval_slots, allowance },
+        vec![
+            AccountMeta::new(sub, false),
+            AccountMeta::new(*tipper, true),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+pub fn cancel_subscription(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (sub, _) = get_subscription_pda(program_id, platform_id, tipper, creator);
+    build(
+        program_id,
+        TipInstruction::CancelSubscription,
+        vec![
+            AccountMeta::new(sub, false),
+            AccountMeta::new(*tipper, true),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn process_subscription(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (sub, _) = get_subscription_pda(program_id, platform_id, tipper, creator);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    build(
+        program_id,
+        TipInstruction::ProcessSubscription,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(sub, false),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new(*creator_token, false),
+            AccountMeta::new(*treasury_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(allowed_mint, false),
+            AccountMeta::new(get_tipper_stats_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+        ],
+    )
+}
+
+/// Pays every due subscription in `subscriptions`, given as (tipper, tipper_token, creator,
+/// creator_token), all in `mint`
+pub fn process_subscriptions_batch(
+    program_id: &Pubkey,
+    platform_id: u64,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    subscriptions: &[(Pubkey, Pubkey, Pubkey, Pubkey)],
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    let mut accounts = vec![
+        AccountMeta::new(config, false),
+        AccountMeta::new(*treasury_token, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(allowed_mint, false),
+        AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+    ];
+    for (tipper, tipper_token, creator, creator_token) in subscriptions {
+        let (sub, _) = get_subscription_pda(program_id, platform_id, tipper, creator);
+        accounts.push(AccountMeta::new(sub, false));
+        accounts.push(AccountMeta::new(*tipper_token, false));
+        accounts.push(AccountMeta::new_readonly(*creator, false));
+        accounts.push(AccountMeta::new(*creator_token, false));
+        accounts.push(AccountMeta::new(get_tipper_stats_pda(program_id, platform_id, tipper).0, false));
+        accounts.push(AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false));
+        accounts.push(AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false));
+    }
+    build(program_id, TipInstruction::ProcessSubscriptionsBatch, accounts)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn escrow_tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (escrow, _) = get_escrow_pda(program_id, platform_id, tipper, creator);
+    let (vault, _) = get_escrow_vault_pda(program_id, &escrow);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    build(
+        program_id,
+        TipInstruction::EscrowTip { amount },
+        vec![
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new(*tipper, true),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(allowed_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn accept_tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (escrow, _) = get_escrow_pda(program_id, platform_id, tipper, creator);
+    let (vault, _) = get_escrow_vault_pda(program_id, &escrow);
+    build(
+        program_id,
+        TipInstruction::AcceptTip,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(*creator_token, false),
+            AccountMeta::new(*treasury_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(*tipper, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+        ],
+    )
+}
+
+/// `authority` is the creator, or the tipper once the escrow has timed out
+#[allow(clippy::too_many_arguments)]
+pub fn decline_tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    authority: &Pubkey,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (escrow, _) = get_escrow_pda(program_id, platform_id, tipper, creator);
+    let (vault, _) = get_escrow_vault_pda(program_id, &escrow);
+    build(
+        program_id,
+        TipInstruction::DeclineTip,
+        vec![
+            AccountMeta::new(escrow, false),
+            AccountMeta::new(vault, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(*tipper, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+pub fn create_campaign(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    campaign_id: u64,
+    target_amount: u64,
+    deadline_ts: i64,
+) -> Instruction {
+    let (campaign, _) = get_campaign_pda(program_id, creator, campaign_id);
+    build(
+        program_id,
+        TipInstruction::CreateCampaign { campaign_id, target_amount, deadline_ts },
+        vec![
+            AccountMeta::new(campaign, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn set_campaign_overflow(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    campaign_id: u64,
+    overflow: CampaignOverflow,
+) -> Instruction {
+    build(
+        program_id,
+        TipInstruction::SetCampaignOverflow { overflow },
+        vec![
+            AccountMeta::new(get_campaign_pda(program_id, creator, campaign_id).0, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn finalize_campaign(program_id: &Pubkey, creator: &Pubkey, campaign_id: u64) -> Instruction {
+    let (campaign, _) = get_campaign_pda(program_id, creator, campaign_id);
+    build(program_id, TipInstruction::FinalizeCampaign, vec![AccountMeta::new(campaign, false)])
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_matching_pool(
+    program_id: &Pubkey,
+    platform_id: u64,
+    sponsor: &Pubkey,
+    sponsor_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    pool_id: u64,
+    creator: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (pool, _) = get_matching_pool_pda(program_id, sponsor, pool_id);
+    let (pool_vault, _) = get_pool_vault_pda(program_id, &pool);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    build(
+        program_id,
+        TipInstruction::CreateMatchingPool { pool_id, creator: *creator, amount },
+        vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new(pool_vault, false),
+            AccountMeta::new(*sponsor, true),
+            AccountMeta::new(*sponsor_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(allowed_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn close_matching_pool(
+    program_id: &Pubkey,
+    sponsor: &Pubkey,
+    sponsor_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    pool_id: u64,
+) -> Instruction {
+    let (pool, _) = get_matching_pool_pda(program_id, sponsor, pool_id);
+    let (pool_vault, _) = get_pool_vault_pda(program_id, &pool);
+    build(
+        program_id,
+        TipInstruction::CloseMatchingPool,
+        vec![
+            AccountMeta::new(pool, false),
+            AccountMeta::new(pool_vault, false),
+            AccountMeta::new(*sponsor, true),
+            AccountMeta::new(*sponsor_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    )
+}
+
+pub fn rollover_epoch(program_id: &Pubkey, platform_id: u64, payer: &Pubkey, epoch_index: u64) -> Instruction {
+    let (epoch_stats, _) = get_epoch_stats_pda(program_id, platform_id, epoch_index);
+    build(
+        program_id,
+        TipInstruction::RolloverEpoch,
+        vec![
+            AccountMeta::new(epoch_stats, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+/// Snapshot the platform and `creators` for the epoch `epoch_index`, which must be the current one
+pub fn snapshot_stats(
+    program_id: &Pubkey,
+    platform_id: u64,
+    payer: &Pubkey,
+    epoch_index: u64,
+    creators: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(get_stats_snapshot_pda(program_id, platform_id, epoch_index).0, false),
+        AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for creator in creators {
+        let (snapshot, _) = get_creator_stats_snapshot_pda(program_id, platform_id, creator, epoch_index);
+        accounts.push(AccountMeta::new_readonly(get_creator_stats_pda(program_id, platform_id, creator).0, false));
+        accounts.push(AccountMeta::new(snapshot, false));
+    }
+    build(program_id, TipInstruction::SnapshotStats, accounts)
+}
+
+pub fn open_leaderboard(program_id: &Pubkey, platform_id: u64, payer: &Pubkey, epoch_index: u64) -> Instruction {
+    let (leaderboard, _) = get_leaderboard_pda(program_id, platform_id, epoch_index);
+    build(
+        program_id,
+        TipInstruction::OpenLeaderboard,
+        vec![
+            AccountMeta::new(leaderboard, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+pub fn open_tipper_leaderboard(program_id: &Pubkey, platform_id: u64, payer: &Pubkey, epoch_index: u64) -> Instruction {
+    let (tipper_leaderboard, _) = get_tipper_leaderboard_pda(program_id, platform_id, epoch_index);
+    build(
+        program_id,
+        TipInstruction::OpenTipperLeaderboard,
+        vec![
+            AccountMeta::new(tipper_leaderboard, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+pub fn set_referral_bps(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, referral_bps: u16) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetReferralBps { referral_bps },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn close_config(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, recipient: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::CloseConfig,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*recipient, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `account` is the PDA to close; `authority` is its creator or tipper (see `ClosableAccount`)
+pub fn close_account(
+    program_id: &Pubkey,
+    platform_id: u64,
+    account: &Pubkey,
+    authority: &Pubkey,
+    recipient: &Pubkey,
+    kind: ClosableAccount,
+) -> Instruction {
+    build(
+        program_id,
+        TipInstruction::CloseAccount { kind },
+        vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new(*recipient, false),
+            // A closed profile frees its slot in platform 0's creator count
+            match kind {
+                ClosableAccount::CreatorProfile => AccountMeta::new(get_config_pda(program_id, 0).0, false),
+                _ => AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+            },
+        ],
+    )
+}
+
+pub fn migrate_config(program_id: &Pubkey, platform_id: u64, admin: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::MigrateConfig,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `shares` recipients are token accounts in the treasury's mint; empty clears the split
+pub fn set_treasury_split(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    treasury: &Pubkey,
+    mint: &Pubkey,
+    shares: Vec<SplitShare>,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (allowed_mint, _) = get_allowed_mint_pda(program_id, platform_id, mint);
+    let mut accounts = vec![
+        AccountMeta::new(config, false),
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new_readonly(*treasury, false),
+        AccountMeta::new_readonly(allowed_mint, false),
+    ];
+    accounts.extend(shares.iter().map(|share| AccountMeta::new_readonly(share.recipient, false)));
+    accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    build(program_id, TipInstruction::SetTreasurySplit { shares }, accounts)
+}
+
+pub fn set_fee_tiers(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, tiers: Vec<FeeTier>) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetFeeTiers { tiers },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_fee_override(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    creator: &Pubkey,
+    fee_bps: u16,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (fee_override, _) = get_fee_override_pda(program_id, platform_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetFeeOverride { fee_bps },
+        vec![
+            AccountMeta::new(fee_override, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn clear_fee_override(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, creator: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (fee_override, _) = get_fee_override_pda(program_id, platform_id, creator);
+    build(
+        program_id,
+        TipInstruction::ClearFeeOverride,
+        vec![
+            AccountMeta::new(fee_override, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn block_creator(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, creator: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (blocked_creator, _) = get_blocked_creator_pda(program_id, platform_id, creator);
+    build(
+        program_id,
+        TipInstruction::BlockCreator,
+        vec![
+            AccountMeta::new(blocked_creator, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn unblock_creator(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, creator: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (blocked_creator, _) = get_blocked_creator_pda(program_id, platform_id, creator);
+    build(
+        program_id,
+        TipInstruction::UnblockCreator,
+        vec![
+            AccountMeta::new(blocked_creator, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn register_creator(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    payout_token: &Pubkey,
+    display_name: &str,
+    metadata_uri: &str,
+) -> Instruction {
+    let (profile, _) = get_creator_profile_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::RegisterCreator {
+            display_name: display_name.to_string(),
+            metadata_uri: metadata_uri.to_string(),
+        },
+        vec![
+            AccountMeta::new(profile, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(*payout_token, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(get_config_pda(program_id, 0).0, false),
+        ],
+    )
+}
+
+pub fn update_creator(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    payout_token: &Pubkey,
+    display_name: &str,
+    metadata_uri: &str,
+) -> Instruction {
+    let (profile, _) = get_creator_profile_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::UpdateCreator {
+            display_name: display_name.to_string(),
+            metadata_uri: metadata_uri.to_string(),
+        },
+        vec![
+            AccountMeta::new(profile, false),
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new_readonly(*payout_token, false),
+        ],
+    )
+}
+
+pub fn set_tip_menu(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    min_tip: u64,
+    tip_menu: &[u64],
+) -> Instruction {
+    let (profile, _) = get_creator_profile_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetTipMenu { min_tip, tip_menu: tip_menu.to_vec() },
+        vec![
+            AccountMeta::new(profile, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn set_fan_club(program_id: &Pubkey, creator: &Pubkey, collection: &Pubkey, discount_bps: u16) -> Instruction {
+    let (fan_club, _) = get_fan_club_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetFanClub { collection: *collection, discount_bps },
+        vec![
+            AccountMeta::new(fan_club, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn set_forward_rule(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    destination: &Pubkey,
+    forward_bps: u16,
+) -> Instruction {
+    let (forward_rule, _) = get_forward_rule_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetForwardRule { destination: *destination, forward_bps },
+        vec![
+            AccountMeta::new(forward_rule, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn set_tip_tiers(program_id: &Pubkey, creator: &Pubkey, thresholds: &[u64]) -> Instruction {
+    let (profile, _) = get_creator_profile_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetTipTiers { thresholds: thresholds.to_vec() },
+        vec![
+            AccountMeta::new(profile, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn set_payout_mint(program_id: &Pubkey, creator: &Pubkey, payout_mint: &Pubkey) -> Instruction {
+    let (profile, _) = get_creator_profile_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetPayoutMint { payout_mint: *payout_mint },
+        vec![
+            AccountMeta::new(profile, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `verifier` is the admin or the config's verifier
+pub fn set_tax_withholding(
+    program_id: &Pubkey,
+    platform_id: u64,
+    verifier: &Pubkey,
+    creator: &Pubkey,
+    tax_bps: u16,
+    tax_account: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (profile, _) = get_creator_profile_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetTaxWithholding { tax_bps, tax_account: *tax_account },
+        vec![
+            AccountMeta::new(profile, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*verifier, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `verifier` is the admin or the config's verifier
+pub fn set_creator_verified(
+    program_id: &Pubkey,
+    platform_id: u64,
+    verifier: &Pubkey,
+    creator: &Pubkey,
+    verified: bool,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (profile, _) = get_creator_profile_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetCreatorVerified { verified },
+        vec![
+            AccountMeta::new(profile, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*verifier, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_require_verified(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, required: bool) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetRequireVerified { required },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_max_creators(program_id: &Pubkey, admin: &Pubkey, max_creators: u32) -> Instruction {
+    let (config, _) = get_config_pda(program_id, 0);
+    build(
+        program_id,
+        TipInstruction::SetMaxCreators { max_creators },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_treasury_low_watermark(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, watermark: u64) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetTreasuryLowWatermark { watermark },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `shares` name the destination token accounts, which are passed in the same order
+pub fn distribute_fees(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    shares: Vec<SplitShare>,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(config, false),
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(get_treasury_vault_pda(program_id, platform_id, mint).0, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    accounts.extend(shares.iter().map(|share| AccountMeta::new(share.recipient, false)));
+    accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    build(program_id, TipInstruction::DistributeFees { shares }, accounts)
+}
+
+pub fn block_tipper(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, tipper: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (blocked_tipper, _) = get_blocked_tipper_pda(program_id, platform_id, tipper);
+    build(
+        program_id,
+        TipInstruction::BlockTipper,
+        vec![
+            AccountMeta::new(blocked_tipper, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*tipper, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn unblock_tipper(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, tipper: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (blocked_tipper, _) = get_blocked_tipper_pda(program_id, platform_id, tipper);
+    build(
+        program_id,
+        TipInstruction::UnblockTipper,
+        vec![
+            AccountMeta::new(blocked_tipper, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `mint` sets that mint's own minimum (in its base units); None sets the config-wide one
+pub fn set_min_tip_amount(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    mint: Option<&Pubkey>,
+    min_tip_amount: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let allowed_mint = match mint {
+        Some(mint) => AccountMeta::new(get_allowed_mint_pda(program_id, platform_id, mint).0, false),
+        None => AccountMeta::new_readonly(*program_id, false),
+    };
+    build(
+        program_id,
+        TipInstruction::SetMinTipAmount { min_tip_amount },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*admin, true),
+            allowed_mint,
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_max_tip_amount(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, max_tip_amount: u64) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetMaxTipAmount { max_tip_amount },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_rate_limit(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    window_secs: u64,
+    max_tips: u64,
+    max_volume: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetRateLimit { window_secs, max_tips, max_volume },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn apply_fee_update(program_id: &Pubkey, platform_id: u64) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(program_id, TipInstruction::ApplyFeeUpdate, vec![AccountMeta::new(config, false)])
+}
+
+/// `new_admin` is required when `threshold` is 0
+pub fn set_multisig(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+    new_admin: Option<&Pubkey>,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let mut accounts = vec![AccountMeta::new(config, false), AccountMeta::new_readonly(*admin, true)];
+    if let Some(new_admin) = new_admin {
+        accounts.push(AccountMeta::new_readonly(*new_admin, false));
+    }
+    accounts.push(AccountMeta::new_readonly(sysvar::instructions::id(), false));
+    build(program_id, TipInstruction::SetMultisig { signers, threshold }, accounts)
+}
+
+/// `action` is any admin instruction built with the multisig authority as its admin
+pub fn propose_action(
+    program_id: &Pubkey,
+    platform_id: u64,
+    proposer: &Pubkey,
+    nonce: u64,
+    action: &Instruction,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (pending_action, _) = get_pending_action_pda(program_id, platform_id, nonce);
+    build(
+        program_id,
+        TipInstruction::ProposeAction { nonce, data: action.data.clone() },
+        vec![
+            AccountMeta::new(pending_action, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*proposer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn approve_action(program_id: &Pubkey, platform_id: u64, signer: &Pubkey, nonce: u64) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (pending_action, _) = get_pending_action_pda(program_id, platform_id, nonce);
+    build(
+        program_id,
+        TipInstruction::ApproveAction,
+        vec![
+            AccountMeta::new(pending_action, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*signer, true),
+        ],
+    )
+}
+
+/// `action` must be the instruction passed to `propose_action`; its accounts are appended with
+/// the multisig authority's signer flag cleared (the program signs for it)
+pub fn execute_action(
+    program_id: &Pubkey,
+    platform_id: u64,
+    proposer: &Pubkey,
+    nonce: u64,
+    action: &Instruction,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (pending_action, _) = get_pending_action_pda(program_id, platform_id, nonce);
+    let (authority, _) = get_multisig_authority_pda(program_id, platform_id);
+    let mut accounts = vec![
+        AccountMeta::new(pending_action, false),
+        AccountMeta::new_readonly(config, false),
+        AccountMeta::new(*proposer, false),
+        AccountMeta::new_readonly(authority, false),
+        AccountMeta::new_readonly(*program_id, false),
+    ];
+    accounts.extend(action.accounts.iter().map(|meta| AccountMeta {
+        is_signer: meta.is_signer && meta.pubkey != authority,
+        ..meta.clone()
+    }));
+    build(program_id, TipInstruction::ExecuteAction, accounts)
+}
+
+pub fn cancel_action(program_id: &Pubkey, platform_id: u64, proposer: &Pubkey, nonce: u64) -> Instruction {
+    let (pending_action, _) = get_pending_action_pda(program_id, platform_id, nonce);
+    build(
+        program_id,
+        TipInstruction::CancelAction,
+        vec![AccountMeta::new(pending_action, false), AccountMeta::new(*proposer, true)],
+    )
+}
+
+pub fn set_role(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, role: Role, authority: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetRole { role, authority: *authority },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_receipt_nft(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    collection: &Pubkey,
+    uri_template: &str,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetReceiptNft { collection: *collection, uri_template: uri_template.to_string() },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// Wrap a `tip` instruction so it also mints a receipt NFT from the fresh `nft_mint` keypair,
+/// which must sign the transaction. `collection_mint` must be the config's receipt collection.
+/// Budget extra compute units: the mint and three Token Metadata CPIs run after the tip.
+/// Panics if `tip_ix` was not built by `tip`.
+pub fn tip_with_receipt_nft(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tip_ix: Instruction,
+    tipper: &Pubkey,
+    nft_mint: &Pubkey,
+    collection_mint: &Pubkey,
+) -> Instruction {
+    let (receipt_authority, _) = get_receipt_authority_pda(program_id, platform_id);
+    let data = match TipInstruction::unpack(&tip_ix.data) {
+        Ok(TipInstruction::Tip { amount, memo, category }) => {
+            TipInstruction::TipWithReceiptNft { amount, memo, category }
+        }
+        _ => panic!("tip_with_receipt_nft expects an instruction built by `tip`"),
+    };
+    let mut accounts = vec![
+        AccountMeta::new(*nft_mint, true),
+        AccountMeta::new(token::get_associated_token_address(tipper, nft_mint, &spl_token::id()), false),
+        AccountMeta::new(metadata::find_metadata_pda(nft_mint), false),
+        AccountMeta::new(metadata::find_master_edition_pda(nft_mint), false),
+        AccountMeta::new_readonly(receipt_authority, false),
+        AccountMeta::new_readonly(*collection_mint, false),
+        AccountMeta::new(metadata::find_metadata_pda(collection_mint), false),
+        AccountMeta::new_readonly(metadata::find_master_edition_pda(collection_mint), false),
+        AccountMeta::new_readonly(metadata::ID, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(token::ASSOCIATED_TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(tip_ix.accounts);
+    build(program_id, data, accounts)
+}
+
+/// `merkle_tree` must already be allocated by the caller, owned by the compression program
+pub fn init_receipt_tree(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    merkle_tree: &Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::InitReceiptTree { max_depth, max_buffer_size },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(*merkle_tree, false),
+            AccountMeta::new_readonly(get_tree_authority_pda(program_id, platform_id).0, false),
+            AccountMeta::new_readonly(compression::ID, false),
+            AccountMeta::new_readonly(compression::NOOP_ID, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_badge_thresholds(program_id: &Pubkey, creator: &Pubkey, thresholds: [u64; 3]) -> Instruction {
+    let (badge_config, _) = get_badge_config_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetBadgeThresholds { thresholds },
+        vec![
+            AccountMeta::new(badge_config, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn claim_badge(program_id: &Pubkey, tipper: &Pubkey, creator: &Pubkey) -> Instruction {
+    let (supporter_stats, _) = get_supporter_stats_pda(program_id, tipper, creator);
+    let (badge_config, _) = get_badge_config_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::ClaimBadge,
+        vec![
+            AccountMeta::new(supporter_stats, false),
+            AccountMeta::new_readonly(badge_config, false),
+            AccountMeta::new_readonly(*tipper, true),
+        ],
+    )
+}
+
+/// `claim_badge` that also counts the tipper's stake behind `creator` in `mint`
+pub fn claim_badge_with_stake(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+) -> Instruction {
+    let mut ix = claim_badge(program_id, tipper, creator);
+    let (stake, _) = get_stake_position_pda(program_id, platform_id, tipper, creator, mint);
+    ix.accounts.push(AccountMeta::new_readonly(stake, false));
+    ix
+}
+
+pub fn open_vault(program_id: &Pubkey, creator: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Instruction {
+    let (creator_vault, _) = get_creator_vault_pda(program_id, creator, mint);
+    let (vault_token, _) = get_creator_vault_token_pda(program_id, &creator_vault);
+    build(
+        program_id,
+        TipInstruction::OpenVault,
+        vec![
+            AccountMeta::new(creator_vault, false),
+            AccountMeta::new(vault_token, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `amount` 0 claims everything not locked by vesting
+pub fn claim(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    destination: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (creator_vault, _) = get_creator_vault_pda(program_id, creator, mint);
+    let (vault_token, _) = get_creator_vault_token_pda(program_id, &creator_vault);
+    build(
+        program_id,
+        TipInstruction::Claim { amount },
+        vec![
+            AccountMeta::new(creator_vault, false),
+            AccountMeta::new(vault_token, false),
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, 0, creator).0, false),
+        ],
+    )
+}
+
+/// Sweep the `mint` vaults of `creators` to their ATAs
+pub fn payout_batch(
+    program_id: &Pubkey,
+    caller: &Pubkey,
+    caller_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    creators: &[Pubkey],
+    min_amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*caller, true),
+        AccountMeta::new(*caller_token, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    for creator in creators {
+        let (creator_vault, _) = get_creator_vault_pda(program_id, creator, mint);
+        accounts.push(AccountMeta::new(creator_vault, false));
+        accounts.push(AccountMeta::new(get_creator_vault_token_pda(program_id, &creator_vault).0, false));
+        accounts.push(AccountMeta::new(token::get_associated_token_address(creator, mint, token_program), false));
+        accounts.push(AccountMeta::new_readonly(get_blocked_creator_pda(program_id, 0, creator).0, false));
+    }
+    build(program_id, TipInstruction::PayoutBatch { min_amount }, accounts)
+}
+
+pub fn set_vesting(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    threshold: u64,
+    cliff_secs: u64,
+    duration_secs: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetVesting { threshold, cliff_secs, duration_secs },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `tipper` is the schedule's stored tipper, who gets its rent back once it is fully released
+pub fn release_vested(program_id: &Pubkey, creator: &Pubkey, mint: &Pubkey, index: u64, tipper: &Pubkey) -> Instruction {
+    let (creator_vault, _) = get_creator_vault_pda(program_id, creator, mint);
+    let (vesting, _) = get_vesting_pda(program_id, &creator_vault, index);
+    build(
+        program_id,
+        TipInstruction::ReleaseVested,
+        vec![
+            AccountMeta::new(vesting, false),
+            AccountMeta::new(creator_vault, false),
+            AccountMeta::new(*tipper, false),
+        ],
+    )
+}
+
+pub fn set_dispute_window(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, window_slots: u64) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetDisputeWindow { window_slots },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn dispute_tip(program_id: &Pubkey, tipper: &Pubkey, creator: &Pubkey, mint: &Pubkey, index: u64) -> Instruction {
+    let (creator_vault, _) = get_creator_vault_pda(program_id, creator, mint);
+    let (vesting, _) = get_vesting_pda(program_id, &creator_vault, index);
+    build(
+        program_id,
+        TipInstruction::DisputeTip,
+        vec![AccountMeta::new(vesting, false), AccountMeta::new_readonly(*tipper, true)],
+    )
+}
+
+/// `tipper_token` must belong to the tipper; it is only paid when `refund` is set
+#[allow(clippy::too_many_arguments)]
+pub fn resolve_dispute(
+    program_id: &Pubkey,
+    platform_id: u64,
+    arbiter: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    index: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    refund: bool,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (creator_vault, _) = get_creator_vault_pda(program_id, creator, mint);
+    let (vault_token, _) = get_creator_vault_token_pda(program_id, &creator_vault);
+    let (vesting, _) = get_vesting_pda(program_id, &creator_vault, index);
+    build(
+        program_id,
+        TipInstruction::ResolveDispute { refund },
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*arbiter, true),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new(creator_vault, false),
+            AccountMeta::new(vault_token, false),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new(*tipper, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `tip` paid in SOL that the program wraps on the fly; the native mint must be allowed.
+/// `extras.anonymous` is ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn tip_wrapped_sol(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    amount: u64,
+    memo: &str,
+    extras: TipExtras,
+) -> Instruction {
+    let (wsol, _) = get_wsol_pda(program_id, tipper);
+    let mut ix = tip(
+        program_id, platform_id, tipper, &wsol, creator, creator_token, treasury_token, &token::NATIVE_MINT,
+        &spl_token::id(), amount, memo, extras,
+    );
+    ix.data = borsh::to_vec(&TipInstruction::TipWrappedSol { amount, memo: memo.to_string(), category: extras.category })
+        .expect("in-memory Borsh encoding cannot fail");
+    ix
+}
+
+pub fn open_sol_vault(program_id: &Pubkey, creator: &Pubkey) -> Instruction {
+    let (sol_vault, _) = get_sol_vault_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::OpenSolVault,
+        vec![
+            AccountMeta::new(sol_vault, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `amount` 0 withdraws everything above the vault's rent-exempt minimum
+pub fn withdraw_sol(program_id: &Pubkey, creator: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    let (sol_vault, _) = get_sol_vault_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::WithdrawSol { amount },
+        vec![
+            AccountMeta::new(sol_vault, false),
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(*destination, false),
+        ],
+    )
+}
+
+pub fn set_require_top_level(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, required: bool) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetRequireTopLevel { required },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_lenient_routing(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, enabled: bool) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetLenientRouting { enabled },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_self_tip_policy(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, policy: SelfTipPolicy) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetSelfTipPolicy { policy },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_swap_program(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, swap_program: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetSwapProgram { swap_program: *swap_program },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn create_tip_link(program_id: &Pubkey, platform_id: u64, creator: &Pubkey, link_id: u64) -> Instruction {
+    build(
+        program_id,
+        TipInstruction::CreateTipLink { link_id },
+        vec![
+            AccountMeta::new(get_tip_link_pda(program_id, platform_id, creator, link_id).0, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Sweep the tip link's ATA for `mint` into the creator's; add the treasury split with
+/// `with_treasury_split`
+#[allow(clippy::too_many_arguments)]
+pub fn claim_tip_link(
+    program_id: &Pubkey,
+    platform_id: u64,
+    creator: &Pubkey,
+    link_id: u64,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (tip_link, _) = get_tip_link_pda(program_id, platform_id, creator, link_id);
+    build(
+        program_id,
+        TipInstruction::ClaimTipLink,
+        vec![
+            AccountMeta::new(get_config_pda(program_id, platform_id).0, false),
+            AccountMeta::new(tip_link, false),
+            AccountMeta::new(token::get_associated_token_address(&tip_link, mint, token_program), false),
+            AccountMeta::new_readonly(*creator, true),
+            AccountMeta::new(token::get_associated_token_address(creator, mint, token_program), false),
+            AccountMeta::new(*treasury_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(get_allowed_mint_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+        ],
+    )
+}
+
+pub fn claim_tip_link_sol(
+    program_id: &Pubkey,
+    platform_id: u64,
+    creator: &Pubkey,
+    link_id: u64,
+    treasury_token: &Pubkey,
+    treasury_wallet: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        TipInstruction::ClaimTipLinkSol,
+        vec![
+            AccountMeta::new(get_config_pda(program_id, platform_id).0, false),
+            AccountMeta::new(get_tip_link_pda(program_id, platform_id, creator, link_id).0, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(*treasury_token, false),
+            AccountMeta::new(*treasury_wallet, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, &Pubkey::default()).0, false),
+        ],
+    )
+}
+
+/// `payer` funds the rent; anyone may open it, once
+pub fn open_volume_window(program_id: &Pubkey, platform_id: u64, payer: &Pubkey) -> Instruction {
+    let (volume_window, _) = get_volume_window_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::OpenVolumeWindow,
+        vec![
+            AccountMeta::new(volume_window, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+pub fn set_price_feed(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    price_feed: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetPriceFeed { price_feed: *price_feed },
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(get_allowed_mint_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `Tip` for a USD amount; quote `max_amount` from the same feed with some slippage headroom
+#[allow(clippy::too_many_arguments)]
+pub fn tip_usd(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    price_feed: &Pubkey,
+    usd_cents: u64,
+    max_amount: u64,
+    memo: &str,
+    extras: TipExtras,
+) -> Instruction {
+    let extras = TipExtras { price_feed: Some(price_feed), ..extras };
+    let mut ix = tip(
+        program_id, platform_id, tipper, tipper_token, creator, creator_token, treasury_token, mint, token_program,
+        max_amount, memo, extras,
+    );
+    let memo = memo.to_string();
+    ix.data = borsh::to_vec(&TipInstruction::TipUsd { usd_cents, max_amount, memo, category: extras.category })
+        .expect("in-memory Borsh encoding cannot fail");
+    ix
+}
+
+pub fn open_treasury_vault(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::OpenTreasuryVault,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(get_treasury_vault_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(get_allowed_mint_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `destination` is any token account of `mint`
+pub fn withdraw_fees(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    destination: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::WithdrawFees { amount },
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(get_treasury_vault_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn set_rebate(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    rebate_threshold: u64,
+    rebate_bps: u16,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetRebate { rebate_threshold, rebate_bps },
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn claim_rebate(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::ClaimRebate,
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*tipper, true),
+            AccountMeta::new(get_tipper_rebate_pda(program_id, platform_id, tipper, mint).0, false),
+            AccountMeta::new(get_treasury_vault_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    )
+}
+
+/// The supporter's token account must hold `mint`; `supporter` also pays rent for the position and vault
+#[allow(clippy::too_many_arguments)]
+pub fn stake(
+    program_id: &Pubkey,
+    platform_id: u64,
+    supporter: &Pubkey,
+    supporter_token: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::Stake { amount },
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*supporter, true),
+            AccountMeta::new(*supporter_token, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new(get_stake_position_pda(program_id, platform_id, supporter, creator, mint).0, false),
+            AccountMeta::new(get_stake_vault_pda(program_id, platform_id, creator, mint).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(get_allowed_mint_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new(get_creator_stats_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+pub fn unstake(
+    program_id: &Pubkey,
+    platform_id: u64,
+    supporter: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        TipInstruction::Unstake { amount },
+        vec![
+            AccountMeta::new_readonly(*supporter, true),
+            AccountMeta::new(get_stake_position_pda(program_id, platform_id, supporter, creator, mint).0, false),
+            AccountMeta::new(get_creator_stats_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+pub fn withdraw_stake(
+    program_id: &Pubkey,
+    platform_id: u64,
+    supporter: &Pubkey,
+    supporter_token: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::WithdrawStake,
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*supporter, true),
+            AccountMeta::new(get_stake_position_pda(program_id, platform_id, supporter, creator, mint).0, false),
+            AccountMeta::new(get_stake_vault_pda(program_id, platform_id, creator, mint).0, false),
+            AccountMeta::new(*supporter_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    )
+}
+
+/// Empty `shares` clears the split
+pub fn set_collaborators(program_id: &Pubkey, creator: &Pubkey, shares: Vec<SplitShare>) -> Instruction {
+    let (collaborators, _) = get_collaborator_split_pda(program_id, creator);
+    build(
+        program_id,
+        TipInstruction::SetCollaborators { shares },
+        vec![
+            AccountMeta::new(collaborators, false),
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// `execute_at` is a Unix timestamp; `crank_reward` is escrowed on top of `amount`
+#[allow(clippy::too_many_arguments)]
+pub fn schedule_tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    schedule_id: u64,
+    amount: u64,
+    crank_reward: u64,
+    execute_at: i64,
+) -> Instruction {
+    let (scheduled_tip, _) = get_scheduled_tip_pda(program_id, platform_id, tipper, creator, schedule_id);
+    build(
+        program_id,
+        TipInstruction::ScheduleTip { schedule_id, amount, crank_reward, execute_at },
+        vec![
+            AccountMeta::new(scheduled_tip, false),
+            AccountMeta::new(get_scheduled_tip_vault_pda(program_id, &scheduled_tip).0, false),
+            AccountMeta::new(*tipper, true),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(get_allowed_mint_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+/// Append the treasury split recipients with `with_treasury_split` if the config has a split
+#[allow(clippy::too_many_arguments)]
+pub fn execute_scheduled_tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    schedule_id: u64,
+    cranker_token: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (scheduled_tip, _) = get_scheduled_tip_pda(program_id, platform_id, tipper, creator, schedule_id);
+    build(
+        program_id,
+        TipInstruction::ExecuteScheduledTip,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(scheduled_tip, false),
+            AccountMeta::new(get_scheduled_tip_vault_pda(program_id, &scheduled_tip).0, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new(*creator_token, false),
+            AccountMeta::new(*treasury_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(get_allowed_mint_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new(*tipper, false),
+            AccountMeta::new(*cranker_token, false),
+            AccountMeta::new(get_tipper_stats_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new_readonly(get_blocked_creator_pda(program_id, platform_id, creator).0, false),
+            AccountMeta::new_readonly(get_blocked_tipper_pda(program_id, platform_id, tipper).0, false),
+            AccountMeta::new(get_mint_stats_pda(program_id, platform_id, mint).0, false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn cancel_scheduled_tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    schedule_id: u64,
+) -> Instruction {
+    let (scheduled_tip, _) = get_scheduled_tip_pda(program_id, platform_id, tipper, creator, schedule_id);
+    build(
+        program_id,
+        TipInstruction::CancelScheduledTip,
+        vec![
+            AccountMeta::new(scheduled_tip, false),
+            AccountMeta::new(get_scheduled_tip_vault_pda(program_id, &scheduled_tip).0, false),
+            AccountMeta::new(*tipper, true),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn fund_reward_pool(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    admin_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    reward_per_epoch: u64,
+    winners: u8,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::FundRewardPool { amount, reward_per_epoch, winners },
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(*admin_token, false),
+            AccountMeta::new(get_reward_pool_pda(program_id, platform_id).0, false),
+            AccountMeta::new(get_reward_vault_pda(program_id, platform_id).0, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(get_allowed_mint_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `tippers` are the paid tipper leaderboard entries in rank order (see `client::decode_leaderboard`);
+/// their ATAs receive the rewards
+pub fn distribute_epoch_rewards(
+    program_id: &Pubkey,
+    platform_id: u64,
+    cranker: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    epoch_index: u64,
+    tippers: &[Pubkey],
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(config, false),
+        AccountMeta::new(get_reward_pool_pda(program_id, platform_id).0, false),
+        AccountMeta::new(get_reward_vault_pda(program_id, platform_id).0, false),
+        AccountMeta::new(get_tipper_leaderboard_pda(program_id, platform_id, epoch_index).0, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new(*cranker, true),
+    ];
+    for tipper in tippers {
+        accounts.push(AccountMeta::new(token::get_associated_token_address(tipper, mint, token_program), false));
+    }
+    build(program_id, TipInstruction::DistributeEpochRewards, accounts)
+}
+
+pub fn verify_deployment(program_id: &Pubkey, platform_id: u64) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let (program_data, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    build(
+        program_id,
+        TipInstruction::VerifyDeployment,
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(*program_id, false),
+            AccountMeta::new_readonly(program_data, false),
+        ],
+    )
+}
+
+/// `vault_owner` is the escrow or matching pool PDA owning `vault`, or the config PDA for a treasury
+/// vault
+#[allow(clippy::too_many_arguments)]
+pub fn schedule_emergency_withdraw(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    kind: EmergencyVault,
+    vault_owner: &Pubkey,
+    vault: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::ScheduleEmergencyWithdraw { kind, amount },
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(get_emergency_withdrawal_pda(program_id, platform_id, vault).0, false),
+            AccountMeta::new_readonly(*vault_owner, false),
+            AccountMeta::new_readonly(*vault, false),
+            AccountMeta::new_readonly(*destination, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_emergency_withdraw(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    vault_owner: &Pubkey,
+    vault: &Pubkey,
+    destination: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::ExecuteEmergencyWithdraw,
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(get_emergency_withdrawal_pda(program_id, platform_id, vault).0, false),
+            AccountMeta::new(*vault_owner, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn cancel_emergency_withdraw(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, vault: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::CancelEmergencyWithdraw,
+        vec![
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(get_emergency_withdrawal_pda(program_id, platform_id, vault).0, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn open_audit_log(program_id: &Pubkey, platform_id: u64, admin: &Pubkey) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::OpenAuditLog,
+        vec![
+            AccountMeta::new(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(get_audit_log_pda(program_id, platform_id).0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn get_config(program_id: &Pubkey, platform_id: u64) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(program_id, TipInstruction::GetConfig, vec![AccountMeta::new_readonly(config, false)])
+}
+
+pub fn is_paused(program_id: &Pubkey, platform_id: u64) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(program_id, TipInstruction::IsPaused, vec![AccountMeta::new_readonly(config, false)])
+}
+
+pub fn get_creator_stats(program_id: &Pubkey, platform_id: u64, creator: &Pubkey) -> Instruction {
+    let (creator_stats, _) = get_creator_stats_pda(program_id, platform_id, creator);
+    build(
+        program_id,
+        TipInstruction::GetCreatorStats,
+        vec![
+            AccountMeta::new_readonly(creator_stats, false),
+            AccountMeta::new_readonly(*creator, false),
+            AccountMeta::new_readonly(get_config_pda(program_id, platform_id).0, false),
+        ],
+    )
+}
+
+/// `code` is the promo code clients will pass to `tip`; only its hash goes on-chain
+pub fn create_promo_code(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    code: &str,
+    discount_bps: u16,
+    max_uses: u64,
+    expiry_ts: i64,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    let code_hash = promo_code_hash(code);
+    build(
+        program_id,
+        TipInstruction::CreatePromoCode { code_hash, discount_bps, max_uses, expiry_ts },
+        vec![
+            AccountMeta::new(get_promo_code_pda(program_id, platform_id, &code_hash).0, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// Register `charity_token` as the charity for tips in `mint`
+pub fn set_charity(
+    program_id: &Pubkey,
+    platform_id: u64,
+    admin: &Pubkey,
+    mint: &Pubkey,
+    charity_token: &Pubkey,
+    max_donation_bps: u16,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetCharity { max_donation_bps },
+        vec![
+            AccountMeta::new(get_charity_pda(program_id, platform_id, mint).0, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(*charity_token, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+pub fn close_promo_code(program_id: &Pubkey, platform_id: u64, admin: &Pubkey, code: &str) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::ClosePromoCode,
+        vec![
+            AccountMeta::new(get_promo_code_pda(program_id, platform_id, &promo_code_hash(code)).0, false),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// `authority` is the campaign's creator or platform 0's admin
+pub fn set_campaign_fee(
+    program_id: &Pubkey,
+    platform_id: u64,
+    authority: &Pubkey,
+    creator: &Pubkey,
+    campaign_id: u64,
+    fee_bps: Option<u16>,
+) -> Instruction {
+    let (config, _) = get_config_pda(program_id, platform_id);
+    build(
+        program_id,
+        TipInstruction::SetCampaignFee { fee_bps },
+        vec![
+            AccountMeta::new(get_campaign_pda(program_id, creator, campaign_id).0, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
@@ -1,207 +1,9916 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
-    entrypoint,
+    bpf_loader_upgradeable,
+    clock::Clock,
     entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
     msg,
-    program::{invoke, invoke_signed},
+    program::{invoke, invoke_signed, set_return_data},
     program_error::ProgramError,
+    pubkey,
     pubkey::Pubkey,
     rent::Rent,
     system_instruction,
-    sysvar::Sysvar,
+    sysvar::{self, Sysvar},
 };
 
-entrypoint!(process_instruction);
+#[cfg(all(feature = "client", not(target_os = "solana")))]
+pub mod client;
+pub mod compression;
+#[cfg(feature = "cpi")]
+pub mod cpi;
+pub mod error;
+pub mod events;
+pub mod instruction;
+pub mod metadata;
+pub mod oracle;
+#[cfg(all(feature = "solana-pay", not(target_os = "solana")))]
+pub mod solana_pay;
+pub mod state;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+#[cfg(all(feature = "testing", not(target_os = "solana")))]
+pub mod testing;
+pub mod token;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub mod wasm;
+mod campaign;
+mod escrow;
+mod fees;
+mod subscription;
+mod swap;
+mod tip_link;
+mod validation;
+mod vault;
+
+use campaign::{
+    campaign_tip_amount, close_matching_pool, contribute_to_campaign, create_campaign, create_matching_pool,
+    finalize_campaign, load_campaign_fee, match_tip, set_campaign_fee, set_campaign_overflow,
+};
+use error::TipError;
+use escrow::{accept_tip, close_escrow, decline_tip, escrow_tip};
+use events::*;
+use fees::{apply_discount, bps_share, compute_fee, split_by_weight, split_tip, split_treasury_fee, TipRates};
+use instruction::TipInstruction;
+use state::*;
+use subscription::{cancel_subscription, create_subscription, process_subscription, process_subscriptions_batch};
+use swap::{check_swap_route, set_swap_program, swap_to_payout, TipSwap};
+use tip_link::{claim_tip_link, claim_tip_link_sol, create_tip_link};
+use vault::{
+    check_not_vesting, claim, dispute_tip, load_sol_vault, load_vesting_vault, open_sol_vault, open_vault,
+    payout_batch, release_vested, resolve_dispute, set_dispute_window, set_vesting, withdraw_sol,
+};
+
+/// Mainnet deployment
+pub const PROGRAM_ID: Pubkey = solana_program::pubkey!("HTJhkCtgwugSJyurUo3Gv7tqXJwtSGX4UyrCVfttMi3a");
+
+#[cfg(all(feature = "program", not(feature = "no-entrypoint")))]
+solana_program::entrypoint!(process_instruction);
 
-/// Instructions: 0=Initialize, 1=Tip, 2=UpdateFee
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let (tag, rest) = instruction_data
-        .split_first()
-        .ok_or(ProgramError::InvalidInstructionData)?;
+    let instruction = TipInstruction::unpack(instruction_data)?;
 
-    match tag {
-        0 => initialize(program_id, accounts, rest),
-        1 => tip(program_id, accounts, rest),
-        2 => update_fee(program_id, accounts, rest),
-        _ => Err(ProgramError::InvalidInstructionData),
+    match instruction {
+        TipInstruction::Initialize { fee_bps, upgrade_authority } => {
+            initialize(program_id, accounts, 0, fee_bps, upgrade_authority)
+        }
+        TipInstruction::Tip { amount, memo, category } => {
+            let amount = TipAmount::Tokens(amount);
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Plain, [0; 32], TipCredit::Tipper, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::UpdateFee { new_fee_bps } | TipInstruction::ScheduleFeeUpdate { new_fee_bps } => {
+            schedule_fee_update(program_id, accounts, new_fee_bps)
+        }
+        TipInstruction::DerivePdas => derive_pdas_ix(program_id, accounts),
+        TipInstruction::SetNotify { notify } => set_notify(program_id, accounts, notify),
+        TipInstruction::SetFeeTarget { fee_target } => set_fee_target(program_id, accounts, fee_target),
+        TipInstruction::SweepExcessRent => sweep_excess_rent(program_id, accounts),
+        TipInstruction::SetAdminIsolation { enabled } => set_admin_isolation(program_id, accounts, enabled),
+        TipInstruction::OpenSession { budget, expiry_ts } => {
+            open_session(program_id, accounts, budget, expiry_ts)
+        }
+        TipInstruction::SessionTip { amount } => session_tip(program_id, accounts, amount),
+        TipInstruction::HandoffOwnership => handoff_ownership(program_id, accounts),
+        TipInstruction::TipSol { amount } => tip_sol(program_id, accounts, amount),
+        TipInstruction::ProposeAdmin => propose_admin(program_id, accounts),
+        TipInstruction::AcceptAdmin => accept_admin(program_id, accounts),
+        TipInstruction::UpdateTreasury => update_treasury(program_id, accounts),
+        TipInstruction::Pause => set_paused(program_id, accounts, true),
+        TipInstruction::Unpause => set_paused(program_id, accounts, false),
+        TipInstruction::AllowMint => allow_mint(program_id, accounts),
+        TipInstruction::DisallowMint => disallow_mint(program_id, accounts),
+        TipInstruction::TipMany { amounts } => tip_many(program_id, accounts, amounts),
+        TipInstruction::SetSplit { shares } => set_split(program_id, accounts, shares),
+        TipInstruction::TipSplit { amount } => tip_split(program_id, accounts, amount),
+        TipInstruction::CreateSubscription { amount, interval_slots, allowance } => {
+            create_subscription(program_id, accounts, amount, interval_slots, allowance)
+        }
+        TipInstruction::CancelSubscription => cancel_subscription(program_id, accounts),
+        TipInstruction::ProcessSubscription => process_subscription(program_id, accounts),
+        TipInstruction::EscrowTip { amount } => escrow_tip(program_id, accounts, amount),
+        TipInstruction::AcceptTip => accept_tip(program_id, accounts),
+        TipInstruction::DeclineTip => decline_tip(program_id, accounts),
+        TipInstruction::CreateCampaign { campaign_id, target_amount, deadline_ts } => {
+            create_campaign(program_id, accounts, campaign_id, target_amount, deadline_ts)
+        }
+        TipInstruction::FinalizeCampaign => finalize_campaign(program_id, accounts),
+        TipInstruction::CreateMatchingPool { pool_id, creator, amount } => {
+            create_matching_pool(program_id, accounts, pool_id, creator, amount)
+        }
+        TipInstruction::CloseMatchingPool => close_matching_pool(program_id, accounts),
+        TipInstruction::RolloverEpoch => rollover_epoch(program_id, accounts),
+        TipInstruction::SetReferralBps { referral_bps } => set_referral_bps(program_id, accounts, referral_bps),
+        TipInstruction::CloseConfig => close_config(program_id, accounts),
+        TipInstruction::CloseAccount { kind } => close_account(program_id, accounts, kind),
+        TipInstruction::MigrateConfig => migrate_config(program_id, accounts),
+        TipInstruction::SetTreasurySplit { shares } => set_treasury_split(program_id, accounts, shares),
+        TipInstruction::SetFeeTiers { tiers } => set_fee_tiers(program_id, accounts, tiers),
+        TipInstruction::SetFeeOverride { fee_bps } => set_fee_override(program_id, accounts, fee_bps),
+        TipInstruction::ClearFeeOverride => clear_fee_override(program_id, accounts),
+        TipInstruction::SetMinTipAmount { min_tip_amount } => set_min_tip_amount(program_id, accounts, min_tip_amount),
+        TipInstruction::SetMaxTipAmount { max_tip_amount } => set_max_tip_amount(program_id, accounts, max_tip_amount),
+        TipInstruction::SetRateLimit { window_secs, max_tips, max_volume } => {
+            set_rate_limit(program_id, accounts, RateLimit { window_secs, max_tips, max_volume })
+        }
+        TipInstruction::ApplyFeeUpdate => apply_fee_update(program_id, accounts),
+        TipInstruction::SetMultisig { signers, threshold } => set_multisig(program_id, accounts, signers, threshold),
+        TipInstruction::ProposeAction { nonce, data } => propose_action(program_id, accounts, nonce, data),
+        TipInstruction::ApproveAction => approve_action(program_id, accounts),
+        TipInstruction::ExecuteAction => execute_action(program_id, accounts),
+        TipInstruction::CancelAction => cancel_action(program_id, accounts),
+        TipInstruction::SetRole { role, authority } => set_role(program_id, accounts, role, authority),
+        TipInstruction::SetReceiptNft { collection, uri_template } => {
+            set_receipt_nft(program_id, accounts, collection, uri_template)
+        }
+        TipInstruction::TipWithReceiptNft { amount, memo, category } => {
+            tip_with_receipt_nft(program_id, accounts, amount, memo, category)
+        }
+        TipInstruction::InitReceiptTree { max_depth, max_buffer_size } => {
+            init_receipt_tree(program_id, accounts, max_depth, max_buffer_size)
+        }
+        TipInstruction::SetBadgeThresholds { thresholds } => set_badge_thresholds(program_id, accounts, thresholds),
+        TipInstruction::ClaimBadge => claim_badge(program_id, accounts),
+        TipInstruction::OpenVault => open_vault(program_id, accounts),
+        TipInstruction::Claim { amount } => claim(program_id, accounts, amount),
+        TipInstruction::SetVesting { threshold, cliff_secs, duration_secs } => {
+            set_vesting(program_id, accounts, threshold, cliff_secs, duration_secs)
+        }
+        TipInstruction::ReleaseVested => release_vested(program_id, accounts),
+        TipInstruction::SetDisputeWindow { window_slots } => set_dispute_window(program_id, accounts, window_slots),
+        TipInstruction::DisputeTip => dispute_tip(program_id, accounts),
+        TipInstruction::ResolveDispute { refund } => resolve_dispute(program_id, accounts, refund),
+        TipInstruction::TipAnonymous { amount, memo, category } => {
+            let amount = TipAmount::Tokens(amount);
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Plain, [0; 32], TipCredit::Anonymous, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::TipWrappedSol { amount, memo, category } => {
+            tip_wrapped_sol(program_id, accounts, amount, memo, category)
+        }
+        TipInstruction::BlockCreator => block_creator(program_id, accounts),
+        TipInstruction::UnblockCreator => unblock_creator(program_id, accounts),
+        TipInstruction::BlockTipper => block_tipper(program_id, accounts),
+        TipInstruction::UnblockTipper => unblock_tipper(program_id, accounts),
+        TipInstruction::RegisterCreator { display_name, metadata_uri } => {
+            register_creator(program_id, accounts, display_name, metadata_uri)
+        }
+        TipInstruction::UpdateCreator { display_name, metadata_uri } => {
+            update_creator(program_id, accounts, display_name, metadata_uri)
+        }
+        TipInstruction::SetCreatorVerified { verified } => set_creator_verified(program_id, accounts, verified),
+        TipInstruction::SetRequireVerified { required } => set_require_verified(program_id, accounts, required),
+        TipInstruction::OpenLeaderboard => open_leaderboard(program_id, accounts, false),
+        TipInstruction::TipDelegated { amount, nonce, expiry_ts } => {
+            tip_delegated(program_id, accounts, amount, nonce, expiry_ts)
+        }
+        TipInstruction::OpenSolVault => open_sol_vault(program_id, accounts),
+        TipInstruction::WithdrawSol { amount } => withdraw_sol(program_id, accounts, amount),
+        TipInstruction::SetRequireTopLevel { required } => set_require_top_level(program_id, accounts, required),
+        TipInstruction::OpenVolumeWindow => open_volume_window(program_id, accounts),
+        TipInstruction::SetPriceFeed { price_feed } => set_price_feed(program_id, accounts, price_feed),
+        TipInstruction::TipUsd { usd_cents, max_amount, memo, category } => {
+            let amount = TipAmount::UsdCents { usd_cents, max_amount };
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Plain, [0; 32], TipCredit::Tipper, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::OpenTreasuryVault => open_treasury_vault(program_id, accounts),
+        TipInstruction::WithdrawFees { amount } => withdraw_fees(program_id, accounts, amount),
+        TipInstruction::SetRebate { rebate_threshold, rebate_bps } => {
+            set_rebate(program_id, accounts, rebate_threshold, rebate_bps)
+        }
+        TipInstruction::ClaimRebate => claim_rebate(program_id, accounts),
+        TipInstruction::Stake { amount } => stake(program_id, accounts, amount),
+        TipInstruction::Unstake { amount } => unstake(program_id, accounts, amount),
+        TipInstruction::WithdrawStake => withdraw_stake(program_id, accounts),
+        TipInstruction::SetCollaborators { shares } => set_collaborators(program_id, accounts, shares),
+        TipInstruction::TipGift { amount, memo, category, attributed_tipper } => {
+            let credit = TipCredit::Gift(attributed_tipper);
+            let amount = TipAmount::Tokens(amount);
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Plain, [0; 32], credit, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::ScheduleTip { schedule_id, amount, crank_reward, execute_at } => {
+            schedule_tip(program_id, accounts, schedule_id, amount, crank_reward, execute_at)
+        }
+        TipInstruction::ExecuteScheduledTip => execute_scheduled_tip(program_id, accounts),
+        TipInstruction::CancelScheduledTip => cancel_scheduled_tip(program_id, accounts),
+        TipInstruction::ProcessSubscriptionsBatch => process_subscriptions_batch(program_id, accounts),
+        TipInstruction::FundRewardPool { amount, reward_per_epoch, winners } => {
+            fund_reward_pool(program_id, accounts, amount, reward_per_epoch, winners)
+        }
+        TipInstruction::DistributeEpochRewards => distribute_epoch_rewards(program_id, accounts),
+        TipInstruction::VerifyDeployment => verify_deployment(program_id, accounts),
+        TipInstruction::ScheduleEmergencyWithdraw { kind, amount } => {
+            schedule_emergency_withdraw(program_id, accounts, kind, amount)
+        }
+        TipInstruction::ExecuteEmergencyWithdraw => execute_emergency_withdraw(program_id, accounts),
+        TipInstruction::CancelEmergencyWithdraw => cancel_emergency_withdraw(program_id, accounts),
+        TipInstruction::OpenAuditLog => open_audit_log(program_id, accounts),
+        TipInstruction::GetConfig => get_config(program_id, accounts),
+        TipInstruction::GetCreatorStats => get_creator_stats(program_id, accounts),
+        TipInstruction::CreatePromoCode { code_hash, discount_bps, max_uses, expiry_ts } => {
+            create_promo_code(program_id, accounts, code_hash, discount_bps, max_uses, expiry_ts)
+        }
+        TipInstruction::ClosePromoCode => close_promo_code(program_id, accounts),
+        TipInstruction::SetCampaignFee { fee_bps } => set_campaign_fee(program_id, accounts, fee_bps),
+        TipInstruction::InitializePlatform { platform_id, fee_bps, upgrade_authority } => {
+            initialize(program_id, accounts, platform_id, fee_bps, upgrade_authority)
+        }
+        TipInstruction::SetTipMenu { min_tip, tip_menu } => set_tip_menu(program_id, accounts, min_tip, tip_menu),
+        TipInstruction::PayoutBatch { min_amount } => payout_batch(program_id, accounts, min_amount),
+        TipInstruction::SetFanClub { collection, discount_bps } => {
+            set_fan_club(program_id, accounts, collection, discount_bps)
+        }
+        TipInstruction::SetTipTiers { thresholds } => set_tip_tiers(program_id, accounts, thresholds),
+        TipInstruction::SetCharity { max_donation_bps } => set_charity(program_id, accounts, max_donation_bps),
+        TipInstruction::SetForwardRule { destination, forward_bps } => {
+            set_forward_rule(program_id, accounts, destination, forward_bps)
+        }
+        TipInstruction::SnapshotStats => snapshot_stats(program_id, accounts),
+        TipInstruction::SetSelfTipPolicy { policy } => set_self_tip_policy(program_id, accounts, policy),
+        TipInstruction::TipWithDonation { amount, memo, category, donation_bps } => {
+            let amount = TipAmount::Tokens(amount);
+            let (route, credit) = (TipRoute::Plain, TipCredit::Tipper);
+            tip(program_id, accounts, amount, memo, category, donation_bps, route, [0; 32], credit, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::TipWithSwap { amount, memo, category, min_out, route_accounts, swap_data } => {
+            let amount = TipAmount::Tokens(amount);
+            let swap = TipSwap { min_out, route_accounts, data: swap_data };
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Swap(swap), [0; 32], TipCredit::Tipper, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::SetSwapProgram { swap_program } => set_swap_program(program_id, accounts, swap_program),
+        TipInstruction::SetPayoutMint { payout_mint } => set_payout_mint(program_id, accounts, payout_mint),
+        TipInstruction::CreateTipLink { link_id } => create_tip_link(program_id, accounts, link_id),
+        TipInstruction::ClaimTipLink => claim_tip_link(program_id, accounts),
+        TipInstruction::ClaimTipLinkSol => claim_tip_link_sol(program_id, accounts),
+        TipInstruction::TipWithRef { amount, memo, category, external_ref } => {
+            let amount = TipAmount::Tokens(amount);
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Plain, external_ref, TipCredit::Tipper, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::OpenTipperLeaderboard => open_leaderboard(program_id, accounts, true),
+        TipInstruction::IsPaused => is_paused(program_id, accounts),
+        TipInstruction::SetMaxCreators { max_creators } => set_max_creators(program_id, accounts, max_creators),
+        TipInstruction::SetTreasuryLowWatermark { watermark } => {
+            set_treasury_low_watermark(program_id, accounts, watermark)
+        }
+        TipInstruction::DistributeFees { shares } => distribute_fees(program_id, accounts, shares),
+        TipInstruction::TipWithCampaign { amount, memo, category } => {
+            let amount = TipAmount::Tokens(amount);
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Campaign, [0; 32], TipCredit::Tipper, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::TipWithMatch { amount, memo, category } => {
+            let amount = TipAmount::Tokens(amount);
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Matched, [0; 32], TipCredit::Tipper, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::TipWithVesting { amount, memo, category } => {
+            let amount = TipAmount::Tokens(amount);
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Vested, [0; 32], TipCredit::Tipper, None)
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::TipWithNonce { amount, memo, category, nonce } => {
+            let (amount, credit) = (TipAmount::Tokens(amount), TipCredit::Tipper);
+            tip(program_id, accounts, amount, memo, category, 0, TipRoute::Plain, [0; 32], credit, Some(nonce))
+                .and_then(return_tip_outcome)
+        }
+        TipInstruction::SetLenientRouting { enabled } => set_lenient_routing(program_id, accounts, enabled),
+        TipInstruction::SetTaxWithholding { tax_bps, tax_account } => {
+            set_tax_withholding(program_id, accounts, tax_bps, tax_account)
+        }
+        TipInstruction::SetCampaignOverflow { overflow } => set_campaign_overflow(program_id, accounts, overflow),
     }
 }
 
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct TipConfig {
-    pub is_initialized: bool,
-    pub admin: Pubkey,
-    pub treasury: Pubkey,
-    pub fee_bps: u16,
-    pub total_tips: u64,
-    pub total_volume: u64,
+const COMPUTE_BUDGET_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// With admin_isolation on, the next account must be the instructions sysvar and the
+/// transaction may hold nothing besides this instruction and compute-budget settings
+fn check_admin_isolation<'a, 'b: 'a>(
+    config: &TipConfig,
+    iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+) -> ProgramResult {
+    if !config.admin_isolation {
+        return Ok(());
+    }
+
+    let ix_sysvar = next_account_info(iter)?;
+    if *ix_sysvar.key != sysvar::instructions::ID {
+        msg!("Admin isolation: instructions sysvar required");
+        return Err(TipError::MissingInstructionsSysvar.into());
+    }
+
+    let current = sysvar::instructions::load_current_index_checked(ix_sysvar)? as usize;
+    let mut index = 0;
+    while let Ok(ix) = sysvar::instructions::load_instruction_at_checked(index, ix_sysvar) {
+        if index != current && ix.program_id != COMPUTE_BUDGET_ID {
+            msg!("Admin isolation: admin instruction must be standalone");
+            return Err(TipError::AdminNotIsolated.into());
+        }
+        index += 1;
+    }
+    Ok(())
 }
 
-const CONFIG_SIZE: usize = 1 + 32 + 32 + 2 + 8 + 8; // 83 bytes
+pub const MAX_MEMO_CHARS: usize = 280;
 
-fn get_config_pda(program_id: &Pubkey) -> (Pubkey, u8) {
-    Pubkey::find_program_address(&[b"config"], program_id)
+/// Every platform-wide PDA of a platform with its bump, those of epoch `epoch_index` and, when a mint
+/// is given, those keyed by the mint. PDAs keyed by a creator, tipper or nonce are derived with the
+/// `get_*_pda` helpers.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct ProgramPdas {
+    pub config: Pubkey,
+    pub config_bump: u8,
+    pub reward_pool: Pubkey,
+    pub reward_pool_bump: u8,
+    pub reward_vault: Pubkey,
+    pub reward_vault_bump: u8,
+    pub volume_window: Pubkey,
+    pub volume_window_bump: u8,
+    pub audit_log: Pubkey,
+    pub audit_log_bump: u8,
+    pub tree_authority: Pubkey,
+    pub tree_authority_bump: u8,
+    pub receipt_authority: Pubkey,
+    pub receipt_authority_bump: u8,
+    pub multisig_authority: Pubkey,
+    pub multisig_authority_bump: u8,
+    pub epoch_index: u64,
+    pub epoch_stats: Pubkey,
+    pub epoch_stats_bump: u8,
+    pub leaderboard: Pubkey,
+    pub leaderboard_bump: u8,
+    pub tipper_leaderboard: Pubkey,
+    pub tipper_leaderboard_bump: u8,
+    pub stats_snapshot: Pubkey,
+    pub stats_snapshot_bump: u8,
+    pub mint: Option<MintPdas>,
 }
 
-/// Initialize: [fee_bps: u16]
-/// Accounts: [config (w), treasury, admin (s,w), system_program]
-fn initialize(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    data: &[u8],
-) -> ProgramResult {
-    let iter = &mut accounts.iter();
-    let config_acc = next_account_info(iter)?;
-    let treasury = next_account_info(iter)?;
-    let admin = next_account_info(iter)?;
-    let system_program = next_account_info(iter)?;
+/// The PDAs a platform keys by `mint`, with their bumps
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct MintPdas {
+    pub mint: Pubkey,
+    pub allowed_mint: Pubkey,
+    pub allowed_mint_bump: u8,
+    pub treasury_vault: Pubkey,
+    pub treasury_vault_bump: u8,
+    pub mint_stats: Pubkey,
+    pub mint_stats_bump: u8,
+    pub charity: Pubkey,
+    pub charity_bump: u8,
+}
 
-    if !admin.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+pub fn derive_pdas(program_id: &Pubkey, platform_id: u64, epoch_index: u64, mint: Option<&Pubkey>) -> ProgramPdas {
+    let (config, config_bump) = get_config_pda(program_id, platform_id);
+    let (reward_pool, reward_pool_bump) = get_reward_pool_pda(program_id, platform_id);
+    let (reward_vault, reward_vault_bump) = get_reward_vault_pda(program_id, platform_id);
+    let (volume_window, volume_window_bump) = get_volume_window_pda(program_id, platform_id);
+    let (audit_log, audit_log_bump) = get_audit_log_pda(program_id, platform_id);
+    let (tree_authority, tree_authority_bump) = get_tree_authority_pda(program_id, platform_id);
+    let (receipt_authority, receipt_authority_bump) = get_receipt_authority_pda(program_id, platform_id);
+    let (multisig_authority, multisig_authority_bump) = get_multisig_authority_pda(program_id, platform_id);
+    let (epoch_stats, epoch_stats_bump) = get_epoch_stats_pda(program_id, platform_id, epoch_index);
+    let (leaderboard, leaderboard_bump) = get_leaderboard_pda(program_id, platform_id, epoch_index);
+    let (tipper_leaderboard, tipper_leaderboard_bump) = get_tipper_leaderboard_pda(program_id, platform_id, epoch_index);
+    let (stats_snapshot, stats_snapshot_bump) = get_stats_snapshot_pda(program_id, platform_id, epoch_index);
+    let mint = mint.map(|mint| {
+        let (allowed_mint, allowed_mint_bump) = get_allowed_mint_pda(program_id, platform_id, mint);
+        let (treasury_vault, treasury_vault_bump) = get_treasury_vault_pda(program_id, platform_id, mint);
+        let (mint_stats, mint_stats_bump) = get_mint_stats_pda(program_id, platform_id, mint);
+        let (charity, charity_bump) = get_charity_pda(program_id, platform_id, mint);
+        MintPdas {
+            mint: *mint,
+            allowed_mint,
+            allowed_mint_bump,
+            treasury_vault,
+            treasury_vault_bump,
+            mint_stats,
+            mint_stats_bump,
+            charity,
+            charity_bump,
+        }
+    });
+    ProgramPdas {
+        config,
+        config_bump,
+        reward_pool,
+        reward_pool_bump,
+        reward_vault,
+        reward_vault_bump,
+        volume_window,
+        volume_window_bump,
+        audit_log,
+        audit_log_bump,
+        tree_authority,
+        tree_authority_bump,
+        receipt_authority,
+        receipt_authority_bump,
+        multisig_authority,
+        multisig_authority_bump,
+        epoch_index,
+        epoch_stats,
+        epoch_stats_bump,
+        leaderboard,
+        leaderboard_bump,
+        tipper_leaderboard,
+        tipper_leaderboard_bump,
+        stats_snapshot,
+        stats_snapshot_bump,
+        mint,
+    }
+}
+
+/// How a tip was split, returned by every `Tip` variant via return data. Amounts are what arrived,
+/// net of any Token-2022 transfer fee.
+#[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct TipOutcome {
+    /// The creator's share, including their collaborators' cuts and any part that vests
+    pub creator_amount: u64,
+    /// The whole fee, including `referral_amount`
+    pub fee: u64,
+    pub referral_amount: u64,
+}
+
+/// Set the return data last: every CPI clears it
+fn return_tip_outcome(outcome: TipOutcome) -> ProgramResult {
+    set_return_data(&borsh::to_vec(&outcome)?);
+    Ok(())
+}
+
+/// The config must be owned by this program and sit at ["config", platform, bump] for the platform
+/// and bump stored at Initialize; cheaper than re-running find_program_address on every instruction
+fn check_config_address(program_id: &Pubkey, config_acc: &AccountInfo, platform_id: u64, bump: u8) -> ProgramResult {
+    validation::program_owned(program_id, config_acc)?;
+    match Pubkey::create_program_address(&[b"config", &platform_seed(platform_id), &[bump]], program_id) {
+        Ok(pda) if pda == *config_acc.key => Ok(()),
+        _ => Err(ProgramError::InvalidSeeds),
     }
+}
 
-    let fee_bps = u16::from_le_bytes(data[..2].try_into().map_err(|_| ProgramError::InvalidInstructionData)?);
-    if fee_bps > 1000 {
-        msg!("Fee too high: max 1000 bps (10%)");
-        return Err(ProgramError::InvalidArgument);
+/// Admin and role powers over accounts every platform shares (escrows, matching pools, profiles and
+/// vesting schedules) stay with platform 0, the deployment's own config
+fn check_shared_authority(config: &TipConfig) -> ProgramResult {
+    if config.platform_id != 0 {
+        msg!("Only platform 0 may act on shared accounts");
+        return Err(TipError::Unauthorized.into());
     }
+    Ok(())
+}
 
-    let (pda, bump) = get_config_pda(program_id);
-    if *config_acc.key != pda {
-        return Err(ProgramError::InvalidSeeds);
+/// Platform 0's config, which counts creator profiles (shared by every platform) against `max_creators`
+fn creator_registry(program_id: &Pubkey, config_acc: &AccountInfo) -> Result<TipConfig, ProgramError> {
+    validation::writable(&[config_acc])?;
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
     }
+    check_shared_authority(&config)?;
+    Ok(config)
+}
 
-    let rent = Rent::get()?;
-    let lamports = rent.minimum_balance(CONFIG_SIZE);
+/// Platform of an instruction that takes no config of its own: an optional platform config may be
+/// passed after its accounts, and without one the instruction acts on platform 0
+fn optional_platform<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    iter: &mut (impl Iterator<Item = &'a AccountInfo<'b>> + Clone),
+) -> Result<u64, ProgramError> {
+    let Some(config_acc) = iter.clone().next().filter(|acc| acc.owner == program_id) else {
+        return Ok(0);
+    };
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    iter.next();
+    Ok(config.platform_id)
+}
 
+/// Create a program-owned PDA funded by `payer` at the rent-exempt minimum for `size`. An address
+/// someone already sent lamports to is topped up, allocated and assigned instead, since
+/// `create_account` refuses it.
+fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    size: usize,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    if account.lamports() == 0 {
+        let lamports = Rent::get()?.minimum_balance(size);
+        check_rent_payer(payer, lamports)?;
+        return invoke_signed(
+            &system_instruction::create_account(payer.key, account.key, lamports, size as u64, program_id),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[seeds],
+        );
+    }
+    top_up_rent(account, payer, system_program, size)?;
     invoke_signed(
-        &system_instruction::create_account(
-            admin.key, &pda, lamports, CONFIG_SIZE as u64, program_id,
-        ),
-        &[admin.clone(), config_acc.clone(), system_program.clone()],
-        &[&[b"config", &[bump]]],
+        &system_instruction::allocate(account.key, size as u64),
+        &[account.clone(), system_program.clone()],
+        &[seeds],
     )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, program_id),
+        &[account.clone(), system_program.clone()],
+        &[seeds],
+    )
+}
 
-    let config = TipConfig {
-        is_initialized: true,
-        admin: *admin.key,
-        treasury: *treasury.key,
-        fee_bps,
-        total_tips: 0,
-        total_volume: 0,
-    };
+/// Account that funds rent for whatever an instruction creates or grows: `payer` when the caller
+/// passed one (a backend sponsoring its users, say), which must sign, or else `authority`
+fn rent_payer<'a, 'b>(
+    payer: Option<&'a AccountInfo<'b>>,
+    authority: &'a AccountInfo<'b>,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    match payer {
+        Some(payer) if !payer.is_signer => Err(ProgramError::MissingRequiredSignature),
+        Some(payer) => Ok(payer),
+        None => Ok(authority),
+    }
+}
 
-    config.serialize(&mut &mut config_acc.data.borrow_mut()[..])?;
-    Ok(())
+/// `rent_payer` for the optional `payer` slot right after an instruction's own accounts. Only an
+/// instructions sysvar that `check_admin_isolation` left unread may come between them; an audit log
+/// or Noop program in the slot means no payer was passed, and any other account there is the payer.
+fn next_rent_payer<'a, 'b: 'a>(
+    program_id: &Pubkey,
+    iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+    authority: &'a AccountInfo<'b>,
+) -> Result<&'a AccountInfo<'b>, ProgramError> {
+    let mut slot = iter.next();
+    if slot.is_some_and(|acc| *acc.key == sysvar::instructions::id()) {
+        slot = iter.next();
+    }
+    rent_payer(slot.filter(|acc| acc.owner != program_id && *acc.key != compression::NOOP_ID), authority)
 }
 
-/// Tip: [amount: u64]
-/// Accounts: [config (w), tipper (s), tipper_token (w), creator_token (w), treasury_token (w), token_program]
-fn tip(
+/// Record an admin action in the audit log once it has been opened. The log may be passed anywhere
+/// after the instruction's own accounts and is found by address.
+#[allow(clippy::too_many_arguments)]
+fn audit(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    data: &[u8],
+    config: &TipConfig,
+    action: AdminAction,
+    actor: &Pubkey,
+    target: &Pubkey,
+    old_value: [u8; 32],
+    new_value: [u8; 32],
 ) -> ProgramResult {
-    let iter = &mut accounts.iter();
-    let config_acc = next_account_info(iter)?;
-    let tipper = next_account_info(iter)?;
-    let tipper_token = next_account_info(iter)?;
-    let creator_token = next_account_info(iter)?;
-    let treasury_token = next_account_info(iter)?;
-    let token_program = next_account_info(iter)?;
+    if !config.audit_log_enabled {
+        return Ok(());
+    }
+    let (pda, _) = get_audit_log_pda(program_id, config.platform_id);
+    let Some(log_acc) = accounts.iter().find(|acc| *acc.key == pda) else {
+        msg!("Audit log {} must be passed", pda);
+        return Err(TipError::AuditLogRequired.into());
+    };
+    validation::writable(&[log_acc])?;
+    validation::program_owned(program_id, log_acc)?;
+    let slot = Clock::get()?.slot;
+    let mut data = log_acc.data.borrow_mut();
+    AuditLog::load_mut(&mut data)?.append(AuditEntry {
+        action: action as u8,
+        actor: *actor,
+        target: *target,
+        slot,
+        old_value,
+        new_value,
+    });
+    Ok(())
+}
 
-    if !tipper.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+/// Audit value of up to four numbers, as consecutive little-endian u64s
+fn audit_words(words: &[u64]) -> [u8; 32] {
+    let mut value = [0; 32];
+    for (chunk, word) in value.chunks_exact_mut(8).zip(words) {
+        chunk.copy_from_slice(&word.to_le_bytes());
     }
+    value
+}
 
-    let (pda, _) = get_config_pda(program_id);
-    if *config_acc.key != pda {
-        return Err(ProgramError::InvalidSeeds);
+/// Audit value of a setting too large for one entry (fee tiers, treasury split, multisig signers)
+fn audit_hash(value: &impl BorshSerialize) -> Result<[u8; 32], ProgramError> {
+    Ok(solana_program::hash::hash(&borsh::to_vec(value)?).to_bytes())
+}
+
+/// Treasury must be an initialized SPL Token or Token-2022 account
+fn validate_treasury(treasury: &AccountInfo) -> ProgramResult {
+    if token::unpack_account(treasury).is_err() {
+        msg!("New treasury must be an initialized token account");
+        return Err(TipError::InvalidTreasury.into());
     }
+    Ok(())
+}
 
-    let mut config = TipConfig::try_from_slice(&config_acc.data.borrow())?;
-    if !config.is_initialized {
-        return Err(ProgramError::UninitializedAccount);
+/// All token accounts must share one mint, and that mint must have an AllowedMint entry on the
+/// platform, which is returned
+fn check_allowed_mint(
+    program_id: &Pubkey,
+    platform_id: u64,
+    allowed_mint_acc: &AccountInfo,
+    token_accounts: &[&AccountInfo],
+) -> Result<AllowedMint, ProgramError> {
+    let allowed = load_allowed_mint(program_id, allowed_mint_acc, token_accounts)?;
+    if *allowed_mint_acc.key != get_allowed_mint_pda(program_id, platform_id, &allowed.mint).0 {
+        msg!("Mint {} is not allowed on platform {}", allowed.mint, platform_id);
+        return Err(TipError::MintNotAllowed.into());
     }
+    Ok(allowed)
+}
 
-    let amount = u64::from_le_bytes(data[..8].try_into().map_err(|_| ProgramError::InvalidInstructionData)?);
-    if amount == 0 {
-        msg!("Tip amount must be > 0");
-        return Err(ProgramError::InvalidArgument);
+/// `check_allowed_mint` on any platform, for matching pools, which are created without a config and
+/// pay out under whichever platform settles them
+fn load_allowed_mint(
+    program_id: &Pubkey,
+    allowed_mint_acc: &AccountInfo,
+    token_accounts: &[&AccountInfo],
+) -> Result<AllowedMint, ProgramError> {
+    let mut mint = None;
+    for acc in token_accounts {
+        let acc_mint = token::unpack_account(acc)?.mint;
+        if *mint.get_or_insert(acc_mint) != acc_mint {
+            msg!("Token accounts must share one mint");
+            return Err(TipError::MintMismatch.into());
+        }
+    }
+    let mint = mint.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    // AllowedMint accounts are only ever created at their PDA, so a program-owned one
+    // naming this mint is the right address without re-deriving it
+    let allowed = if allowed_mint_acc.owner == program_id {
+        AllowedMint::load(&allowed_mint_acc.data.borrow()).ok()
+    } else {
+        None
+    };
+    match allowed {
+        Some(allowed) if allowed.is_initialized && allowed.mint == mint => Ok(allowed),
+        _ => {
+            msg!("Mint {} is not allowed", mint);
+            Err(TipError::MintNotAllowed.into())
+        }
+    }
+}
+
+/// Reject dust below `min_tip_amount` and tips above `max_tip_amount`, both in base units (0 = no limit)
+fn check_tip_limits(min_tip_amount: u64, max_tip_amount: u64, amount: u64) -> ProgramResult {
+    if amount < min_tip_amount {
+        msg!("Tip below the minimum of {} base units", min_tip_amount);
+        return Err(TipError::TipTooSmall.into());
+    }
+    if max_tip_amount > 0 && amount > max_tip_amount {
+        msg!("Tip above the maximum of {} base units", max_tip_amount);
+        return Err(TipError::TipTooLarge.into());
     }
+    Ok(())
+}
 
-    let fee = amount.checked_mul(config.fee_bps as u64).unwrap() / 10_000;
-    let creator_amount = amount.checked_sub(fee).unwrap();
+/// Recipients must use their associated token account, or their creator vault for the mint, so a
+/// frontend can't swap in its own
+fn check_creator_ata(
+    program_id: &Pubkey,
+    creator: &AccountInfo,
+    creator_token: &AccountInfo,
+    mint_acc: &AccountInfo,
+    token_program: &AccountInfo,
+) -> ProgramResult {
+    let expected = token::get_associated_token_address(creator.key, mint_acc.key, token_program.key);
+    if *creator_token.key == expected {
+        return Ok(());
+    }
+    let (creator_vault, _) = get_creator_vault_pda(program_id, creator.key, mint_acc.key);
+    if *creator_token.key != get_creator_vault_token_pda(program_id, &creator_vault).0 {
+        msg!("Creator token account must be the ATA or vault of {}", creator.key);
+        return Err(TipError::CreatorTokenMismatch.into());
+    }
+    Ok(())
+}
 
-    // Transfer to creator
+/// Create the creator's ATA for a tip to a creator who has never held the mint, paid by `payer`. The
+/// ATA program derives the address itself and fails unless `creator_token` is it.
+fn create_creator_ata<'a>(
+    payer: &AccountInfo<'a>,
+    creator: &AccountInfo<'a>,
+    creator_token: &AccountInfo<'a>,
+    mint_acc: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    ata_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if *ata_program.key != token::ASSOCIATED_TOKEN_PROGRAM_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
     invoke(
-        &spl_token::instruction::transfer(
-            token_program.key, tipper_token.key, creator_token.key, tipper.key, &[], creator_amount,
-        )?,
-        &[tipper_token.clone(), creator_token.clone(), tipper.clone()],
+        &token::create_associated_token_account_idempotent(payer.key, creator.key, mint_acc.key, token_program.key),
+        &[
+            payer.clone(),
+            creator_token.clone(),
+            creator.clone(),
+            mint_acc.clone(),
+            system_program.clone(),
+            token_program.clone(),
+        ],
     )?;
+    msg!("Created the token account of {}", creator.key);
+    Ok(())
+}
 
-    // Transfer fee to treasury
-    if fee > 0 {
-        if *treasury_token.key != config.treasury {
-            msg!("Treasury mismatch");
-            return Err(ProgramError::InvalidArgument);
-        }
-        invoke(
-            &spl_token::instruction::transfer(
-                token_program.key, tipper_token.key, treasury_token.key, tipper.key, &[], fee,
-            )?,
-            &[tipper_token.clone(), treasury_token.clone(), tipper.clone()],
-        )?;
+/// Fail before anything is written if `payer` cannot fund `rent_due` lamports
+fn check_rent_payer(payer: &AccountInfo, rent_due: u64) -> ProgramResult {
+    if !payer.is_signer || !payer.is_writable {
+        msg!("Rent payer {} must sign and be writable", payer.key);
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if payer.lamports() < rent_due {
+        msg!("Rent payer holds {} lamports of the {} due", payer.lamports(), rent_due);
+        return Err(ProgramError::InsufficientFunds);
+    }
+    Ok(())
+}
+
+/// Move whatever `account` lacks to be rent-exempt at `size` over from `payer`. Every instruction that
+/// creates, grows or migrates an account calls this (through `create_pda_account`, `grow_account`
+/// or `resize_account`) before writing the new layout, so a short balance fails up front.
+fn top_up_rent<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    size: usize,
+) -> ProgramResult {
+    let rent_due = Rent::get()?.minimum_balance(size).saturating_sub(account.lamports());
+    if rent_due == 0 {
+        return Ok(());
     }
+    check_rent_payer(payer, rent_due)?;
+    invoke(
+        &system_instruction::transfer(payer.key, account.key, rent_due),
+        &[payer.clone(), account.clone(), system_program.clone()],
+    )
+}
 
-    config.total_tips += 1;
-    config.total_volume += amount;
-    config.serialize(&mut &mut config_acc.data.borrow_mut()[..])?;
+/// Realloc a program-owned account to `size`, with `payer` topping up its rent
+fn grow_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    size: usize,
+) -> ProgramResult {
+    top_up_rent(account, payer, system_program, size)?;
+    account.realloc(size, true)
+}
 
-    msg!("Tip: {} to creator, {} fee", creator_amount, fee);
+/// Realloc a program-owned account to `size` either way: `payer` tops up the rent of a larger size,
+/// and `refund_to` gets back what a smaller one no longer needs
+fn resize_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    refund_to: &AccountInfo,
+    system_program: &AccountInfo<'a>,
+    size: usize,
+) -> ProgramResult {
+    if size >= account.data_len() {
+        return grow_account(account, payer, system_program, size);
+    }
+    account.realloc(size, false)?;
+    let excess = account.lamports().saturating_sub(Rent::get()?.minimum_balance(size));
+    **account.try_borrow_mut_lamports()? -= excess;
+    **refund_to.try_borrow_mut_lamports()? += excess;
+    Ok(())
+}
+
+/// Pack `value` into an account holding only the first bytes of its `full_size` layout, for
+/// accounts that store just their used entries or predate trailing fields
+fn pack_prefix<T: AccountType>(value: &T, account: &AccountInfo, full_size: usize) -> ProgramResult {
+    let mut packed = vec![0; full_size];
+    value.pack(&mut packed)?;
+    let mut data = account.data.borrow_mut();
+    let len = data.len();
+    data.copy_from_slice(packed.get(..len).ok_or(ProgramError::InvalidAccountData)?);
+    Ok(())
+}
+
+/// Zero a program-owned account and move all its lamports to `recipient`
+fn close_pda(account: &AccountInfo, recipient: &AccountInfo) -> ProgramResult {
+    let lamports = account.lamports();
+    **account.try_borrow_mut_lamports()? = 0;
+    **recipient.try_borrow_mut_lamports()? += lamports;
+    account.data.borrow_mut().fill(0);
     Ok(())
 }
 
-/// UpdateFee: [new_fee_bps: u16]
-/// Accounts: [config (w), admin (s)]
-fn update_fee(
+/// Create a token account PDA (base layout only) for `mint` whose owner is `owner`
+fn create_vault<'a>(
+    payer: &AccountInfo<'a>,
+    vault: &AccountInfo<'a>,
+    mint_acc: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    create_pda_account(payer, vault, system_program, token_program.key, token::ACCOUNT_LEN, seeds)?;
+    invoke(
+        &token::initialize_account3(token_program.key, vault.key, mint_acc.key, owner),
+        &[vault.clone(), mint_acc.clone()],
+    )
+}
+
+/// The mint must be owned by the passed token program, which must be SPL Token or Token-2022
+fn check_token_program(
+    mint_acc: &AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<spl_token::state::Mint, ProgramError> {
+    if !token::is_token_program(token_program.key) || mint_acc.owner != token_program.key {
+        msg!("Token program does not own the mint");
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    token::unpack_mint(mint_acc)
+}
+
+/// Most account infos a CPI may be handed on runtimes without the raised transaction account limit
+const MAX_CPI_ACCOUNT_INFOS: usize = 64;
+
+/// CPI handing the callee the instruction's own `accounts` as they are, instead of copying out the
+/// ones `ix` names; only an account list too long for the runtime to accept falls back to copies
+fn invoke_with_accounts(ix: &Instruction, accounts: &[AccountInfo], signer_seeds: &[&[&[u8]]]) -> ProgramResult {
+    if accounts.len() <= MAX_CPI_ACCOUNT_INFOS {
+        return invoke_signed(ix, accounts, signer_seeds);
+    }
+    let named = |acc: &&AccountInfo| acc.key == &ix.program_id || ix.accounts.iter().any(|meta| meta.pubkey == *acc.key);
+    let infos: Vec<AccountInfo> = accounts.iter().filter(named).cloned().collect();
+    invoke_signed(ix, &infos, signer_seeds)
+}
+
+/// Move `amount` from `source` to `destination`, pinning the mint's Token-2022 transfer fee when it charges
+/// one; returns what `destination` actually receives. `hook_accounts` are appended to the CPI for mints with
+/// a transfer hook: the hook program, its extra-account-metas PDA and whatever extra accounts that lists.
+/// `accounts` are the instruction's accounts, which the CPI borrows (see `invoke_with_accounts`).
+#[allow(clippy::too_many_arguments)]
+fn transfer_tokens<'a>(
+    accounts: &[AccountInfo<'a>],
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint_acc: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+    hook_accounts: &[AccountInfo<'a>],
+) -> Result<u64, ProgramError> {
+    let withheld = token::transfer_fee(mint_acc, Clock::get()?.epoch, amount)?;
+    let mut ix = if withheld > 0 {
+        token::transfer_checked_with_fee(
+            token_program.key, source.key, mint_acc.key, destination.key, authority.key, amount, decimals, withheld,
+        )
+    } else {
+        token::transfer_checked(
+            token_program.key, source.key, mint_acc.key, destination.key, authority.key, amount, decimals,
+        )
+    };
+    ix.accounts.extend(hook_accounts.iter().map(|acc| AccountMeta {
+        pubkey: *acc.key,
+        is_signer: false,
+        is_writable: acc.is_writable,
+    }));
+    invoke_with_accounts(&ix, accounts, signer_seeds)?;
+    amount.checked_sub(withheld).ok_or_else(|| TipError::MathOverflow.into())
+}
+
+/// Pay the treasury's part of a fee from `source`: each treasury split entry gets floor(fee * bps / 10000),
+/// `treasury_token` the rest. Split recipients are the next accounts in `iter`, one per entry. Returns what
+/// the recipients receive in total, which is less than `fee` for mints with a transfer fee.
+#[allow(clippy::too_many_arguments)]
+fn pay_treasury_fee<'a, 'b: 'a>(
+    accounts: &[AccountInfo<'b>],
+    split: TreasurySplit,
+    fee: u64,
+    iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+    token_program: &AccountInfo<'b>,
+    source: &AccountInfo<'b>,
+    mint_acc: &AccountInfo<'b>,
+    treasury_token: &AccountInfo<'b>,
+    authority: &AccountInfo<'b>,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+    hook_accounts: &[AccountInfo<'b>],
+) -> Result<u64, ProgramError> {
+    if fee == 0 {
+        return Ok(0);
+    }
+    if *treasury_token.key != split.treasury {
+        msg!("Treasury mismatch");
+        return Err(TipError::TreasuryMismatch.into());
+    }
+
+    let share_bps: Vec<u16> = split.entries().map(|(_, share_bps)| share_bps).collect();
+    let (shares, remaining) = split_treasury_fee(fee, &share_bps)?;
+    let mut received = 0;
+    for ((recipient, _), share) in split.entries().zip(shares) {
+        let recipient_token = next_account_info(iter)?;
+        if *recipient_token.key != recipient {
+            msg!("Treasury split recipient mismatch: expected {}", recipient);
+            return Err(TipError::TreasuryMismatch.into());
+        }
+        if share == 0 {
+            continue;
+        }
+        let delivered = transfer_tokens(
+            accounts,
+            token_program, source, mint_acc, recipient_token, authority, share, decimals, signer_seeds, hook_accounts,
+        )?;
+        received = add_checked(received, delivered)?;
+    }
+
+    if remaining > 0 {
+        let delivered = transfer_tokens(
+            accounts,
+            token_program, source, mint_acc, treasury_token, authority, remaining, decimals, signer_seeds, hook_accounts,
+        )?;
+        received = add_checked(received, delivered)?;
+    }
+    Ok(received)
+}
+
+/// The creator's collaborator split, if they have set one. An existing split is checked by its
+/// stored creator; only a missing one needs the PDA derived.
+fn load_collaborators(
     program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    data: &[u8],
+    collaborators_acc: &AccountInfo,
+    creator: &Pubkey,
+) -> Result<Option<CollaboratorSplit>, ProgramError> {
+    if collaborators_acc.owner == program_id && !collaborators_acc.data_is_empty() {
+        let split = CollaboratorSplit::load(&collaborators_acc.data.borrow())?;
+        if !split.is_initialized || split.creator != *creator {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        return Ok(Some(split));
+    }
+    if *collaborators_acc.key != get_collaborator_split_pda(program_id, creator).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(None)
+}
+
+/// Pay each collaborator its cut (from `split_tip`); returns the total that arrived. Every token
+/// account must be its collaborator's ATA.
+#[allow(clippy::too_many_arguments)]
+fn pay_collaborators<'a>(
+    accounts: &[AccountInfo<'a>],
+    shares: &[SplitShare],
+    cuts: &[u64],
+    collaborator_tokens: &[AccountInfo<'a>],
+    creator: &Pubkey,
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint_acc: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    decimals: u8,
+    hook_accounts: &[AccountInfo<'a>],
+) -> Result<u64, ProgramError> {
+    let mut received = 0;
+    for ((share, collaborator_token), &cut) in shares.iter().zip(collaborator_tokens).zip(cuts) {
+        let expected = token::get_associated_token_address(&share.recipient, mint_acc.key, token_program.key);
+        if *collaborator_token.key != expected {
+            msg!("Collaborator token account must be the ATA of {}", share.recipient);
+            return Err(TipError::CreatorTokenMismatch.into());
+        }
+        if cut == 0 {
+            continue;
+        }
+        let delivered = transfer_tokens(
+            accounts,
+            token_program, source, mint_acc, collaborator_token, authority, cut, decimals, &[], hook_accounts,
+        )?;
+        received = add_checked(received, delivered)?;
+        CollaboratorPaid { creator: *creator, collaborator: share.recipient, mint: *mint_acc.key, amount: delivered }
+            .emit(accounts)?;
+    }
+    Ok(received)
+}
+
+/// Send the tax withheld from the creator's share (from `split_tip`) to `tax_token`, which must be the
+/// ATA of the profile's `tax_account`
+#[allow(clippy::too_many_arguments)]
+fn withhold_tax<'a>(
+    accounts: &[AccountInfo<'a>],
+    tax_account: &Pubkey,
+    tax_token: &AccountInfo<'a>,
+    creator: &Pubkey,
+    amount: u64,
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint_acc: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    decimals: u8,
+    hook_accounts: &[AccountInfo<'a>],
 ) -> ProgramResult {
-    let iter = &mut accounts.iter();
-    let config_acc = next_account_info(iter)?;
-    let admin = next_account_info(iter)?;
+    if *tax_token.key != token::get_associated_token_address(tax_account, mint_acc.key, token_program.key) {
+        msg!("Tax token account must be the ATA of {}", tax_account);
+        return Err(TipError::InvalidTaxWithholding.into());
+    }
+    if amount == 0 {
+        return Ok(());
+    }
+    let delivered = transfer_tokens(
+        accounts, token_program, source, mint_acc, tax_token, authority, amount, decimals, &[], hook_accounts,
+    )?;
+    TaxWithheld { creator: *creator, tax_account: *tax_account, mint: *mint_acc.key, amount: delivered }.emit(accounts)
+}
 
-    if !admin.is_signer {
-        return Err(ProgramError::MissingRequiredSignature);
+/// The mint's registered charity, checked to take `charity_token` and a donation of `donation_bps`
+fn load_charity<'a>(
+    program_id: &Pubkey,
+    platform_id: u64,
+    charity_acc: &AccountInfo<'a>,
+    charity_token: &AccountInfo<'a>,
+    mint: &Pubkey,
+    donation_bps: u16,
+) -> Result<Charity, ProgramError> {
+    validation::program_owned(program_id, charity_acc)?;
+    validation::writable(&[charity_acc, charity_token])?;
+    if *charity_acc.key != get_charity_pda(program_id, platform_id, mint).0 {
+        return Err(ProgramError::InvalidSeeds);
     }
+    let charity = Charity::unpack(&charity_acc.data.borrow())?;
+    if !charity.is_initialized || charity.charity_token != *charity_token.key {
+        msg!("Charity token account must be {}", charity.charity_token);
+        return Err(TipError::InvalidDonation.into());
+    }
+    if donation_bps > charity.max_donation_bps {
+        msg!("Donations in this mint are capped at {} bps", charity.max_donation_bps);
+        return Err(TipError::InvalidDonation.into());
+    }
+    Ok(charity)
+}
 
-    let (pda, _) = get_config_pda(program_id);
-    if *config_acc.key != pda {
+/// Send the donated `amount` (from `split_tip`) to the charity loaded by `load_charity` and count it
+/// there
+#[allow(clippy::too_many_arguments)]
+fn donate<'a>(
+    accounts: &[AccountInfo<'a>],
+    charity_acc: &AccountInfo<'a>,
+    mut charity: Charity,
+    charity_token: &AccountInfo<'a>,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    amount: u64,
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint_acc: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    decimals: u8,
+    hook_accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    if amount == 0 {
+        return Ok(());
+    }
+    let received = transfer_tokens(
+        accounts,
+        token_program, source, mint_acc, charity_token, authority, amount, decimals, &[], hook_accounts,
+    )?;
+    charity.total_donated = add_checked(charity.total_donated, received)?;
+    charity.donation_count = add_checked(charity.donation_count, 1)?;
+    charity.pack(&mut charity_acc.data.borrow_mut())?;
+    DonationMade {
+        tipper: *tipper,
+        creator: *creator,
+        mint: *mint_acc.key,
+        charity_token: *charity_token.key,
+        amount: received,
+    }
+    .emit(accounts)
+}
+
+/// The creator's forward rule, checked to send to `forward_token`, the destination's ATA
+fn load_forward_rule(
+    program_id: &Pubkey,
+    forward_rule_acc: &AccountInfo,
+    forward_token: &AccountInfo,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Result<ForwardRule, ProgramError> {
+    validation::program_owned(program_id, forward_rule_acc)?;
+    if *forward_rule_acc.key != get_forward_rule_pda(program_id, creator).0 {
         return Err(ProgramError::InvalidSeeds);
     }
+    let rule = ForwardRule::unpack(&forward_rule_acc.data.borrow())?;
+    if !rule.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let expected = token::get_associated_token_address(&rule.destination, mint, token_program);
+    if *forward_token.key != expected {
+        msg!("Forward token account must be the ATA of {}", rule.destination);
+        return Err(TipError::CreatorTokenMismatch.into());
+    }
+    Ok(rule)
+}
 
-    let mut config = TipConfig::try_from_slice(&config_acc.data.borrow())?;
-    if !config.is_initialized {
-        return Err(ProgramError::UninitializedAccount);
+/// A `referrer_token` must hold the tip's mint and not belong to the tipper
+fn check_referrer(referrer_token: &AccountInfo, tipper: &Pubkey, mint: &Pubkey) -> ProgramResult {
+    let account = token::unpack_account(referrer_token)?;
+    if account.owner == *tipper {
+        return Err(TipError::SelfReferral.into());
     }
-    if config.admin != *admin.key {
-        return Err(ProgramError::IllegalOwner);
+    if account.mint != *mint {
+        return Err(TipError::MintMismatch.into());
     }
+    validation::writable(&[referrer_token])
+}
 
-    let new_fee = u16::from_le_bytes(data[..2].try_into().map_err(|_| ProgramError::InvalidInstructionData)?);
-    if new_fee > 1000 {
-        return Err(ProgramError::InvalidArgument);
+/// Pass an optional destination's checks through, or under `lenient_routing` drop the destination
+/// with a RoutingSkipped event so the tip still goes through
+fn route_or_skip<T>(
+    lenient: bool,
+    accounts: &[AccountInfo],
+    creator: &Pubkey,
+    destination: RoutingDestination,
+    account: &Pubkey,
+    checked: Result<T, ProgramError>,
+) -> Result<Option<T>, ProgramError> {
+    match checked {
+        Ok(routed) => Ok(Some(routed)),
+        Err(err) if lenient => {
+            msg!("Skipping {:?} destination {}: {}", destination, account, err);
+            RoutingSkipped { creator: *creator, destination, account: *account }.emit(accounts)?;
+            Ok(None)
+        }
+        Err(err) => Err(err),
     }
+}
 
-    config.fee_bps = new_fee;
-    config.serialize(&mut &mut config_acc.data.borrow_mut()[..])?;
-    Ok(())
+/// Send the forwarded `amount` (from `split_tip`) to the rule's destination; returns the amount received
+#[allow(clippy::too_many_arguments)]
+fn forward_tip<'a>(
+    accounts: &[AccountInfo<'a>],
+    rule: &ForwardRule,
+    forward_token: &AccountInfo<'a>,
+    creator: &Pubkey,
+    amount: u64,
+    token_program: &AccountInfo<'a>,
+    source: &AccountInfo<'a>,
+    mint_acc: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    decimals: u8,
+    hook_accounts: &[AccountInfo<'a>],
+) -> Result<u64, ProgramError> {
+    if amount == 0 {
+        return Ok(0);
+    }
+    validation::writable(&[forward_token])?;
+    let received = transfer_tokens(
+        accounts,
+        token_program, source, mint_acc, forward_token, authority, amount, decimals, &[], hook_accounts,
+    )?;
+    TipForwarded { creator: *creator, destination: rule.destination, mint: *mint_acc.key, amount: received }
+        .emit(accounts)?;
+    Ok(received)
+}
+
+/// Refuse a creator the admin has blocked; `blocked_acc` must be their BlockedCreator PDA, which
+/// only exists while they are blocked
+fn check_not_blocked(
+    program_id: &Pubkey,
+    platform_id: u64,
+    blocked_acc: &AccountInfo,
+    creator: &Pubkey,
+) -> ProgramResult {
+    if *blocked_acc.key != get_blocked_creator_pda(program_id, platform_id, creator).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if blocked_acc.owner == program_id && !blocked_acc.data_is_empty() {
+        msg!("Creator {} is blocked", creator);
+        return Err(TipError::CreatorBlocked.into());
+    }
+    Ok(())
+}
+
+
+/// Refuse a wallet the admin has blocked from tipping; `blocked_acc` must be its BlockedTipper PDA,
+/// which only exists while it is blocked
+fn check_tipper_not_blocked(
+    program_id: &Pubkey,
+    platform_id: u64,
+    blocked_acc: &AccountInfo,
+    tipper: &Pubkey,
+) -> ProgramResult {
+    if *blocked_acc.key != get_blocked_tipper_pda(program_id, platform_id, tipper).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if blocked_acc.owner == program_id && !blocked_acc.data_is_empty() {
+        msg!("Tipper {} is blocked", tipper);
+        return Err(TipError::TipperBlocked.into());
+    }
+    Ok(())
+}
+
+/// `check_not_blocked` for batches, which skip a blocked creator rather than fail
+fn creator_blocked(
+    program_id: &Pubkey,
+    platform_id: u64,
+    blocked_acc: &AccountInfo,
+    creator: &Pubkey,
+) -> Result<bool, ProgramError> {
+    match check_not_blocked(program_id, platform_id, blocked_acc, creator) {
+        Err(err) if err == TipError::CreatorBlocked.into() => Ok(true),
+        result => result.map(|()| false),
+    }
+}
+
+/// `check_tipper_not_blocked` for batches, which skip a blocked tipper rather than fail
+fn tipper_blocked(
+    program_id: &Pubkey,
+    platform_id: u64,
+    blocked_acc: &AccountInfo,
+    tipper: &Pubkey,
+) -> Result<bool, ProgramError> {
+    match check_tipper_not_blocked(program_id, platform_id, blocked_acc, tipper) {
+        Err(err) if err == TipError::TipperBlocked.into() => Ok(true),
+        result => result.map(|()| false),
+    }
+}
+
+/// Apply the config's SelfTipPolicy (stored as its index). A tip is a self-tip when the tipper or the
+/// wallet it is credited to is the creator or owns `creator_token`, or when the tipper pays from the
+/// `payout_token` of the creator's profile; `profile_acc` must already be checked to be that profile.
+#[allow(clippy::too_many_arguments)]
+fn check_self_tip(
+    policy: u8,
+    accounts: &[AccountInfo],
+    tipper: &Pubkey,
+    credited: &Pubkey,
+    public_tipper: &Pubkey,
+    tipper_token: &AccountInfo,
+    creator: &Pubkey,
+    creator_token: &AccountInfo,
+    profile_acc: Option<&AccountInfo>,
+    mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    if policy == SelfTipPolicy::Allow as u8 {
+        return Ok(());
+    }
+    let payee = token::unpack_account(creator_token)?.owner;
+    let payout_token = match profile_acc {
+        Some(profile_acc) => Some(CreatorProfile::load(&profile_acc.data.borrow())?.payout_token),
+        None => None,
+    };
+    let self_tip = [tipper, credited].iter().any(|wallet| *wallet == creator || **wallet == payee)
+        || payout_token == Some(*tipper_token.key);
+    apply_self_tip_policy(policy, accounts, self_tip, public_tipper, creator, mint, amount)
+}
+
+/// Flag or reject a tip already found to be a self-tip, as the config's SelfTipPolicy index says
+fn apply_self_tip_policy(
+    policy: u8,
+    accounts: &[AccountInfo],
+    self_tip: bool,
+    public_tipper: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    if !self_tip || policy == SelfTipPolicy::Allow as u8 {
+        return Ok(());
+    }
+    if policy == SelfTipPolicy::Reject as u8 {
+        msg!("Self-tips to {} are not allowed", creator);
+        return Err(TipError::SelfTipNotAllowed.into());
+    }
+    msg!("Self-tip flagged");
+    SelfTip { tipper: *public_tipper, creator: *creator, mint: *mint, amount }.emit(accounts)
+}
+
+/// Require `profile_acc` to be the creator's verified CreatorProfile
+fn check_verified(program_id: &Pubkey, profile_acc: Option<&AccountInfo>, creator: &AccountInfo) -> ProgramResult {
+    let Some(profile_acc) = profile_acc else {
+        msg!("Only verified creators can be tipped; pass the creator's profile");
+        return Err(TipError::CreatorNotVerified.into());
+    };
+    if profile_acc.owner != program_id || *profile_acc.key != get_creator_profile_pda(program_id, creator.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !CreatorProfile::load(&profile_acc.data.borrow())?.verified {
+        msg!("Creator {} is not verified", creator.key);
+        return Err(TipError::CreatorNotVerified.into());
+    }
+    Ok(())
+}
+
+/// Hold a tip to the creator's own minimum when their CreatorProfile sets one for `mint`, and return
+/// its superchat tier under the profile's thresholds
+fn creator_tip_tier(
+    program_id: &Pubkey,
+    profile_acc: &AccountInfo,
+    creator: &AccountInfo,
+    mint: &Pubkey,
+    amount: u64,
+) -> Result<u8, ProgramError> {
+    if profile_acc.owner != program_id || *profile_acc.key != get_creator_profile_pda(program_id, creator.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let profile = CreatorProfile::load(&profile_acc.data.borrow())?;
+    if profile.tip_mint == *mint && amount < profile.min_tip {
+        msg!("Tip below the creator's minimum of {} base units", profile.min_tip);
+        return Err(TipError::TipTooSmall.into());
+    }
+    Ok(profile.tip_tier(mint, amount))
+}
+
+/// The instruction the runtime is executing at the top level must be this program's, so a wrapper
+/// program reaching it via CPI is refused
+fn check_top_level(program_id: &Pubkey, ix_sysvar: Option<&AccountInfo>) -> ProgramResult {
+    let Some(ix_sysvar) = ix_sysvar.filter(|acc| *acc.key == sysvar::instructions::ID) else {
+        msg!("Top-level only: instructions sysvar required");
+        return Err(TipError::MissingInstructionsSysvar.into());
+    };
+    let current = sysvar::instructions::load_current_index_checked(ix_sysvar)? as usize;
+    if sysvar::instructions::load_instruction_at_checked(current, ix_sysvar)?.program_id != *program_id {
+        msg!("Tip must be a top-level instruction");
+        return Err(TipError::CpiNotAllowed.into());
+    }
+    Ok(())
+}
+
+/// Tip paths that take no creator profile cannot check verification, so they refuse while it is required
+fn check_verification_not_required(config: &TipConfig) -> ProgramResult {
+    if config.require_verified {
+        msg!("Only verified creators can be tipped; use Tip with the creator's profile");
+        return Err(TipError::CreatorNotVerified.into());
+    }
+    Ok(())
+}
+
+/// Emit the TipEvent every tip path ends with
+#[allow(clippy::too_many_arguments)]
+fn emit_tip(
+    accounts: &[AccountInfo],
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+    fee: u64,
+    category: u8,
+    tier: u8,
+) -> ProgramResult {
+    let slot = Clock::get()?.slot;
+    TipEvent { tipper: *tipper, creator: *creator, mint: *mint, amount, fee, slot, category, tier }.emit(accounts)
+}
+
+/// `a + b`, failing with MathOverflow instead of wrapping or panicking
+fn add_checked(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_add(b).ok_or_else(|| TipError::MathOverflow.into())
+}
+
+fn sub_checked(a: u64, b: u64) -> Result<u64, ProgramError> {
+    a.checked_sub(b).ok_or_else(|| TipError::MathOverflow.into())
+}
+
+/// Bump the config's tip count and last-tip time for `count` tips
+fn add_tips(config: &mut TipConfig, count: u64) -> ProgramResult {
+    config.total_tips = add_checked(config.total_tips, count)?;
+    let clock = Clock::get()?;
+    config.last_tip_slot = clock.slot;
+    config.last_tip_unix_ts = clock.unix_timestamp;
+    Ok(())
+}
+
+/// The mint's stats if `mint_stats_acc`, which must be their PDA, holds them yet. Native SOL is counted
+/// under the default pubkey.
+fn load_mint_stats(
+    program_id: &Pubkey,
+    platform_id: u64,
+    mint: &Pubkey,
+    mint_stats_acc: &AccountInfo,
+) -> Result<Option<MintStats>, ProgramError> {
+    validation::writable(&[mint_stats_acc])?;
+    if *mint_stats_acc.key != get_mint_stats_pda(program_id, platform_id, mint).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if mint_stats_acc.owner != program_id || mint_stats_acc.data_is_empty() {
+        return Ok(None);
+    }
+    let stats = MintStats::load(&mint_stats_acc.data.borrow())?;
+    match stats.is_initialized {
+        true if stats.mint != *mint => Err(ProgramError::InvalidSeeds),
+        true => Ok(Some(stats)),
+        false => Ok(None),
+    }
+}
+
+/// Count one tip of `volume`, including both fees, in the mint's stats; true if this tip started the
+/// mint's fee holiday
+fn add_mint_tip(stats: &mut MintStats, volume: u64, treasury_fee: u64, referral_fee: u64) -> Result<bool, ProgramError> {
+    let target_was_open = stats.total_fees < stats.fee_target;
+    stats.tip_count = add_checked(stats.tip_count, 1)?;
+    stats.total_volume = add_checked(stats.total_volume, volume)?;
+    stats.total_treasury_fees = add_checked(stats.total_treasury_fees, treasury_fee)?;
+    stats.total_referral_fees = add_checked(stats.total_referral_fees, referral_fee)?;
+    stats.total_fees = add_checked(stats.total_fees, add_checked(treasury_fee, referral_fee)?)?;
+    Ok(target_was_open && stats.total_fees >= stats.fee_target)
+}
+
+/// Persist the mint's stats in the account's own layout, announcing the mint's fee holiday if a tip
+/// just started it. A legacy account keeps only its own fields until `SetFeeTarget` or a `Tip` grows it;
+/// it cannot have a fee target before then.
+fn save_mint_stats(
+    accounts: &[AccountInfo],
+    stats: &MintStats,
+    mint_stats_acc: &AccountInfo,
+    target_reached: bool,
+) -> ProgramResult {
+    pack_prefix(stats, mint_stats_acc, MINT_STATS_SIZE)?;
+
+    if target_reached {
+        FeeTargetReached { total_fees: stats.total_fees, mint: stats.mint }.emit(accounts)?;
+    }
+    Ok(())
+}
+
+/// Count one tip of `volume` including `fee`, all of it the treasury's, in the config and, once it
+/// exists, the mint's stats
+fn record_tip(
+    accounts: &[AccountInfo],
+    config: &mut TipConfig,
+    config_acc: &AccountInfo,
+    mint_stats_acc: &AccountInfo,
+    mint_stats: Option<MintStats>,
+    volume: u64,
+    fee: u64,
+) -> ProgramResult {
+    add_tips(config, 1)?;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    let Some(mut stats) = mint_stats else {
+        return Ok(());
+    };
+    let target_reached = add_mint_tip(&mut stats, volume, fee, 0)?;
+    save_mint_stats(accounts, &stats, mint_stats_acc, target_reached)
+}
+
+/// Create the creator's stats PDA on first use (rent paid by `payer`) and count this tip under
+/// `category` and its superchat `tier` (0 = none), with `usd_received` the USD value of `creator_amount`
+/// (0 when unpriced); returns the creator's volume in the current leaderboard epoch
+#[allow(clippy::too_many_arguments)]
+fn bump_creator_stats<'a>(
+    program_id: &Pubkey,
+    platform_id: u64,
+    payer: &AccountInfo<'a>,
+    creator: &AccountInfo<'a>,
+    creator_stats_acc: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+    creator_amount: u64,
+    category: u8,
+    tier: u8,
+    usd_received: u64,
+) -> Result<u64, ProgramError> {
+    let mut stats =
+        load_or_create_creator_stats(program_id, platform_id, payer, creator, creator_stats_acc, system_program)?;
+    let clock = Clock::get()?;
+    stats.tip_count = add_checked(stats.tip_count, 1)?;
+    stats.total_received = add_checked(stats.total_received, creator_amount)?;
+    stats.total_usd_received = add_checked(stats.total_usd_received, usd_received)?;
+    stats.largest_tip = stats.largest_tip.max(amount);
+    stats.last_tip_slot = clock.slot;
+    stats.last_tip_unix_ts = clock.unix_timestamp;
+    let epoch = epoch_index(clock.unix_timestamp);
+    if stats.epoch_index != epoch {
+        stats.epoch_index = epoch;
+        stats.epoch_volume = 0;
+    }
+    stats.epoch_volume = stats.epoch_volume.saturating_add(amount);
+    let count = &mut stats.category_counts[category as usize];
+    *count = add_checked(*count, 1)?;
+    if let Some(count) = (tier as usize).checked_sub(1).map(|i| &mut stats.tier_counts[i]) {
+        *count = add_checked(*count, 1)?;
+    }
+
+    save_creator_stats(&stats, creator_stats_acc, payer, system_program)?;
+    Ok(stats.epoch_volume)
+}
+
+/// The creator's stats, creating the PDA on first use (rent paid by `payer`)
+fn load_or_create_creator_stats<'a>(
+    program_id: &Pubkey,
+    platform_id: u64,
+    payer: &AccountInfo<'a>,
+    creator: &AccountInfo<'a>,
+    creator_stats_acc: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<CreatorStats, ProgramError> {
+    // The stored key is the same on every platform, so the address is what ties stats to this one
+    let (pda, bump) = get_creator_stats_pda(program_id, platform_id, creator.key);
+    if *creator_stats_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let existing = if creator_stats_acc.owner == program_id && !creator_stats_acc.data_is_empty() {
+        Some(CreatorStats::load(&creator_stats_acc.data.borrow())?).filter(|stats| stats.is_initialized)
+    } else {
+        None
+    };
+    let stats = match existing {
+        Some(stats) if stats.creator == *creator.key => stats,
+        Some(_) => return Err(ProgramError::InvalidSeeds),
+        None => {
+            if creator_stats_acc.data_is_empty() {
+                create_pda_account(
+                    payer,
+                    creator_stats_acc,
+                    system_program,
+                    program_id,
+                    CREATOR_STATS_SIZE,
+                    &[b"creator", &platform_seed(platform_id), creator.key.as_ref(), &[bump]],
+                )?;
+            }
+            let mut stats = CreatorStats::unpack_or_zeroed(&creator_stats_acc.data.borrow())?;
+            stats.is_initialized = true;
+            stats.creator = *creator.key;
+            stats.created_at = Clock::get()?.unix_timestamp;
+            stats
+        }
+    };
+    Ok(stats)
+}
+
+/// Pack creator stats, first growing a legacy account to the current layout (paid by `payer`)
+fn save_creator_stats<'a>(
+    stats: &CreatorStats,
+    creator_stats_acc: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if creator_stats_acc.data_len() < CREATOR_STATS_SIZE {
+        grow_account(creator_stats_acc, payer, system_program, CREATOR_STATS_SIZE)?;
+    }
+    stats.pack(&mut creator_stats_acc.data.borrow_mut())
+}
+
+/// Create the tipper's stats PDA on first use (rent paid by `payer`), add `count` tips totalling `volume`
+/// and extend the daily streak, rejecting the batch if it pushes the tipper past the rate limit. Returns the
+/// tip count before the batch, which numbers `Tip` receipts. `anonymous` batches only count toward the rate limit.
+#[allow(clippy::too_many_arguments)]
+fn bump_tipper_stats<'a>(
+    program_id: &Pubkey,
+    platform_id: u64,
+    payer: &AccountInfo<'a>,
+    tipper: &Pubkey,
+    tipper_stats_acc: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    count: u64,
+    volume: u64,
+    limit: RateLimit,
+    anonymous: bool,
+    nonce: Option<u64>,
+) -> Result<u64, ProgramError> {
+    let (pda, bump) = get_tipper_stats_pda(program_id, platform_id, tipper);
+    if *tipper_stats_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let existing = if tipper_stats_acc.owner == program_id && !tipper_stats_acc.data_is_empty() {
+        Some(TipperStats::load(&tipper_stats_acc.data.borrow())?).filter(|stats| stats.is_initialized)
+    } else {
+        None
+    };
+    let mut stats = match existing {
+        Some(stats) if stats.tipper == *tipper => stats,
+        Some(_) => return Err(ProgramError::InvalidSeeds),
+        None => {
+            if tipper_stats_acc.data_is_empty() {
+                create_pda_account(
+                    payer,
+                    tipper_stats_acc,
+                    system_program,
+                    program_id,
+                    TIPPER_STATS_SIZE,
+                    &[b"tipper", &platform_seed(platform_id), tipper.as_ref(), &[bump]],
+                )?;
+            }
+            let mut stats = TipperStats::unpack_or_zeroed(&tipper_stats_acc.data.borrow())?;
+            stats.is_initialized = true;
+            stats.tipper = *tipper;
+            stats
+        }
+    };
+    if let Some(nonce) = nonce {
+        if nonce != stats.tip_nonce {
+            msg!("Tip nonce {} is not the tipper's next nonce {}", nonce, { stats.tip_nonce });
+            return Err(TipError::TipNonceMismatch.into());
+        }
+        stats.tip_nonce = add_checked(stats.tip_nonce, 1)?;
+    }
+    let prior_count = stats.tip_count;
+    let now = Clock::get()?.unix_timestamp;
+    let day = now.div_euclid(SECONDS_PER_DAY);
+    if !anonymous {
+        stats.tip_count = add_checked(stats.tip_count, count)?;
+        stats.total_volume = add_checked(stats.total_volume, volume)?;
+        let epoch = epoch_index(now);
+        if stats.epoch_index != epoch {
+            stats.epoch_index = epoch;
+            stats.epoch_volume = 0;
+        }
+        stats.epoch_volume = stats.epoch_volume.saturating_add(volume);
+    }
+    if !anonymous && (stats.current_streak_days == 0 || day != stats.last_tip_day) {
+        stats.current_streak_days = if stats.current_streak_days > 0 && day == stats.last_tip_day + 1 {
+            stats.current_streak_days + 1
+        } else {
+            1
+        };
+        stats.last_tip_day = day;
+        stats.longest_streak = stats.longest_streak.max(stats.current_streak_days);
+    }
+
+    charge_rate_window(&mut stats, now, count, volume, limit)?;
+
+    // Legacy accounts grow to hold the rate-limit window, streak, epoch volume and nonce
+    if tipper_stats_acc.data_len() < TIPPER_STATS_SIZE {
+        grow_account(tipper_stats_acc, payer, system_program, TIPPER_STATS_SIZE)?;
+    }
+    stats.pack(&mut tipper_stats_acc.data.borrow_mut())?;
+    Ok(prior_count)
+}
+
+/// Add `count` tips totalling `volume` to the tipper's current rate-limit window, opening a new one once
+/// it has run out; fails if that passes the limit. No-op without a limit
+fn charge_rate_window(stats: &mut TipperStats, now: i64, count: u64, volume: u64, limit: RateLimit) -> ProgramResult {
+    if limit.window_secs == 0 {
+        return Ok(());
+    }
+    if now.saturating_sub(stats.window_start_ts) >= limit.window_secs as i64 {
+        stats.window_start_ts = now;
+        stats.window_tip_count = 0;
+        stats.window_volume = 0;
+    }
+    stats.window_tip_count = add_checked(stats.window_tip_count, count)?;
+    stats.window_volume = stats.window_volume.saturating_add(volume);
+    if (limit.max_tips > 0 && stats.window_tip_count > limit.max_tips)
+        || (limit.max_volume > 0 && stats.window_volume > limit.max_volume)
+    {
+        msg!("Rate limit: {} tips / {} volume per {}s", limit.max_tips, limit.max_volume, limit.window_secs);
+        return Err(TipError::RateLimited.into());
+    }
+    Ok(())
+}
+
+/// Charge tips sent on the tipper's behalf by a session key or a crank to their rate-limit window,
+/// without counting them in their stats. Nobody on those paths pays rent, so while a limit is set the
+/// tipper needs stats with a rate-limit window on the platform already, which any `Tip` of theirs creates
+fn charge_rate_limit(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_stats_acc: &AccountInfo,
+    volume: u64,
+    limit: RateLimit,
+) -> ProgramResult {
+    if limit.window_secs == 0 {
+        return Ok(());
+    }
+    if *tipper_stats_acc.key != get_tipper_stats_pda(program_id, platform_id, tipper).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if tipper_stats_acc.owner != program_id || tipper_stats_acc.data_len() < RATE_LIMIT_TIPPER_STATS_SIZE {
+        msg!("A rate limit is set: {} needs tipper stats on this platform, which any Tip creates", tipper);
+        return Err(TipError::Uninitialized.into());
+    }
+    let mut stats = TipperStats::load(&tipper_stats_acc.data.borrow())?;
+    if !stats.is_initialized || stats.tipper != *tipper {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    charge_rate_window(&mut stats, Clock::get()?.unix_timestamp, 1, volume, limit)?;
+    pack_prefix(&stats, tipper_stats_acc, TIPPER_STATS_SIZE)
+}
+
+/// Create the (tipper, creator) supporter PDA on first use (rent paid by `payer`) and add `amount`
+fn bump_supporter_stats<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    tipper: &Pubkey,
+    creator: &AccountInfo<'a>,
+    supporter_acc: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    amount: u64,
+) -> ProgramResult {
+    let existing = if supporter_acc.owner == program_id && !supporter_acc.data_is_empty() {
+        Some(SupporterStats::unpack(&supporter_acc.data.borrow())?).filter(|stats| stats.is_initialized)
+    } else {
+        None
+    };
+    let mut stats = match existing {
+        Some(stats) if stats.tipper == *tipper && stats.creator == *creator.key => stats,
+        Some(_) => return Err(ProgramError::InvalidSeeds),
+        None => {
+            let (pda, bump) = get_supporter_stats_pda(program_id, tipper, creator.key);
+            if *supporter_acc.key != pda {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            create_pda_account(
+                payer,
+                supporter_acc,
+                system_program,
+                program_id,
+                SUPPORTER_STATS_SIZE,
+                &[b"supporter", tipper.as_ref(), creator.key.as_ref(), &[bump]],
+            )?;
+            SupporterStats {
+                is_initialized: true,
+                tipper: *tipper,
+                creator: *creator.key,
+                total_volume: 0,
+                badge: BadgeTier::None,
+            }
+        }
+    };
+    stats.total_volume = stats.total_volume.saturating_add(amount);
+    stats.pack(&mut supporter_acc.data.borrow_mut())?;
+    Ok(())
+}
+
+/// Config rebate settings, read once per tip
+#[derive(Clone, Copy)]
+struct RebateTerms {
+    threshold: u64,
+    bps: u16,
+}
+
+/// Create the tipper's rebate PDA for this mint on first use (rent paid by `payer`), add `amount` to the
+/// current period's volume and, once that reaches the threshold, accrue `bps` of `vault_fee`, the part of
+/// the fee that reached the mint's treasury vault
+#[allow(clippy::too_many_arguments)]
+fn accrue_rebate<'a>(
+    program_id: &Pubkey,
+    platform_id: u64,
+    payer: &AccountInfo<'a>,
+    tipper: &AccountInfo<'a>,
+    mint: &Pubkey,
+    rebate_acc: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    terms: RebateTerms,
+    amount: u64,
+    vault_fee: u64,
+) -> ProgramResult {
+    validation::writable(&[rebate_acc])?;
+    let (pda, bump) = get_tipper_rebate_pda(program_id, platform_id, tipper.key, mint);
+    if *rebate_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let existing = if rebate_acc.owner == program_id && !rebate_acc.data_is_empty() {
+        Some(TipperRebate::unpack(&rebate_acc.data.borrow())?).filter(|rebate| rebate.is_initialized)
+    } else {
+        None
+    };
+    let mut rebate = match existing {
+        Some(rebate) if rebate.tipper == *tipper.key && rebate.mint == *mint => rebate,
+        Some(_) => return Err(ProgramError::InvalidSeeds),
+        None => {
+            create_pda_account(
+                payer,
+                rebate_acc,
+                system_program,
+                program_id,
+                TIPPER_REBATE_SIZE,
+                &[b"rebate", &platform_seed(platform_id), tipper.key.as_ref(), mint.as_ref(), &[bump]],
+            )?;
+            TipperRebate {
+                is_initialized: true,
+                tipper: *tipper.key,
+                mint: *mint,
+                period: 0,
+                period_volume: 0,
+                accrued: 0,
+                total_claimed: 0,
+            }
+        }
+    };
+    let period = rebate_period(Clock::get()?.unix_timestamp);
+    if rebate.period != period {
+        rebate.period = period;
+        rebate.period_volume = 0;
+    }
+    rebate.period_volume = add_checked(rebate.period_volume, amount)?;
+    if terms.threshold > 0 && rebate.period_volume >= terms.threshold {
+        let earned = bps_share(vault_fee, terms.bps as u64);
+        rebate.accrued = add_checked(rebate.accrued, earned)?;
+    }
+    rebate.pack(&mut rebate_acc.data.borrow_mut())?;
+    Ok(())
+}
+
+/// The mint's stats as `load_mint_stats` found them, creating the PDA on first use or growing a legacy
+/// one (rent paid by `payer`)
+fn open_mint_stats<'a>(
+    program_id: &Pubkey,
+    platform_id: u64,
+    payer: &AccountInfo<'a>,
+    mint: &Pubkey,
+    mint_stats_acc: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    stats: Option<MintStats>,
+) -> Result<MintStats, ProgramError> {
+    if let Some(stats) = stats {
+        if mint_stats_acc.data_len() < MINT_STATS_SIZE {
+            grow_account(mint_stats_acc, payer, system_program, MINT_STATS_SIZE)?;
+        }
+        return Ok(stats);
+    }
+    let (_, bump) = get_mint_stats_pda(program_id, platform_id, mint);
+    create_pda_account(
+        payer,
+        mint_stats_acc,
+        system_program,
+        program_id,
+        MINT_STATS_SIZE,
+        &[b"mint_stats", &platform_seed(platform_id), mint.as_ref(), &[bump]],
+    )?;
+    Ok(MintStats {
+        is_initialized: true,
+        mint: *mint,
+        tip_count: 0,
+        total_volume: 0,
+        total_fees: 0,
+        total_treasury_fees: 0,
+        total_referral_fees: 0,
+        fee_target: 0,
+    })
+}
+
+/// Initialize: [fee_bps: u16, upgrade_authority: Option<Pubkey>]
+/// InitializePlatform: [platform_id: u64, fee_bps: u16, upgrade_authority: Option<Pubkey>]
+/// Accounts: [config (w), treasury, admin (s,w), system_program, payer (optional, s,w)]
+/// Initialize sets up platform 0; the first caller for a platform becomes its admin
+fn initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    platform_id: u64,
+    fee_bps: u16,
+    upgrade_authority: Option<Pubkey>,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let treasury = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, admin])?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    if fee_bps > 1000 {
+        msg!("Fee too high: max 1000 bps (10%)");
+        return Err(TipError::FeeTooHigh.into());
+    }
+
+    let (pda, bump) = get_config_pda(program_id, platform_id);
+    if *config_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    create_pda_account(
+        payer,
+        config_acc,
+        system_program,
+        program_id,
+        CONFIG_SIZE,
+        &[b"config", &platform_seed(platform_id), &[bump]],
+    )?;
+
+    let config = TipConfig {
+        is_initialized: true,
+        admin: *admin.key,
+        treasury: *treasury.key,
+        fee_bps,
+        total_tips: 0,
+        total_volume: 0,
+        total_fees: 0,
+        fee_target: 0,
+        admin_isolation: false,
+        pending_admin: Pubkey::default(),
+        paused: false,
+        referral_bps: 0,
+        total_treasury_fees: 0,
+        total_referral_fees: 0,
+        version: CONFIG_VERSION,
+        bump,
+        treasury_split_count: 0,
+        treasury_split_recipients: [Pubkey::default(); MAX_TREASURY_SPLITS],
+        treasury_split_bps: [0; MAX_TREASURY_SPLITS],
+        fee_tier_count: 0,
+        fee_tier_min_amounts: [0; MAX_FEE_TIERS],
+        fee_tier_bps: [0; MAX_FEE_TIERS],
+        min_tip_amount: 0,
+        max_tip_amount: 0,
+        rate_limit_window_secs: 0,
+        rate_limit_max_tips: 0,
+        rate_limit_max_volume: 0,
+        pending_fee_bps: 0,
+        fee_activation_slot: 0,
+        multisig_threshold: 0,
+        multisig_signer_count: 0,
+        multisig_signers: [Pubkey::default(); MAX_MULTISIG_SIGNERS],
+        multisig_epoch: 0,
+        fee_manager: Pubkey::default(),
+        treasury_manager: Pubkey::default(),
+        pauser: Pubkey::default(),
+        receipt_nft_collection: Pubkey::default(),
+        receipt_nft_uri: ReceiptUri::default(),
+        receipt_tree: Pubkey::default(),
+        vesting_threshold: 0,
+        vesting_cliff_secs: 0,
+        vesting_duration_secs: 0,
+        dispute_window_slots: 0,
+        arbiter: Pubkey::default(),
+        verifier: Pubkey::default(),
+        require_verified: false,
+        require_top_level: false,
+        last_tip_slot: 0,
+        last_tip_unix_ts: 0,
+        created_at: Clock::get()?.unix_timestamp,
+        rebate_threshold: 0,
+        rebate_bps: 0,
+        governance_authority: Pubkey::default(),
+        expected_upgrade_authority: upgrade_authority.unwrap_or_default(),
+        audit_log_enabled: false,
+        platform_id,
+        self_tip_policy: SelfTipPolicy::Allow,
+        swap_program: Pubkey::default(),
+        max_creators: 0,
+        registered_creators: 0,
+        treasury_low_watermark: 0,
+        lenient_routing: false,
+    };
+
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    ConfigInitialized { admin: *admin.key, treasury: *treasury.key, fee_bps, platform_id }.emit(accounts)
+}
+
+/// What a tip sends: base units of the mint, or a USD amount converted at the mint's oracle price and
+/// capped at `max_amount` base units
+enum TipAmount {
+    Tokens(u64),
+    UsdCents { usd_cents: u64, max_amount: u64 },
+}
+
+/// Whose stats a tip counts toward: the signing tipper's, nobody's (TipAnonymous), or another wallet's
+/// that the tipper pays on behalf of (TipGift)
+#[derive(Clone, Copy, PartialEq)]
+enum TipCredit {
+    Tipper,
+    Anonymous,
+    Gift(Pubkey),
+}
+
+/// The subsystem a tip feeds on top of paying the creator, chosen by the instruction: a campaign
+/// (TipWithCampaign), a matching pool (TipWithMatch), a vesting schedule (TipWithVesting) or a swap
+/// (TipWithSwap). Each one's accounts come ahead of Tip's; a new subsystem gets a route and a variant
+/// of its own rather than another optional slot.
+enum TipRoute {
+    Plain,
+    Campaign,
+    Matched,
+    Vested,
+    Swap(TipSwap),
+}
+
+/// Tip: [amount: u64, memo: string, category: u8]
+/// Accounts: [config (w), tipper (s,w), tipper_token (w), creator, creator_token (w), treasury_token (w), mint, token_program,
+///            allowed_mint, creator_stats (w), tipper_stats (w), system_program, blocked_creator, blocked_tipper,
+///            mint_stats (w), creator_notify (optional), epoch_stats (optional, w), referrer_token (optional, w),
+///            fee_override (optional), receipt (optional, w), receipt_tree (optional, w), tree_authority,
+///            compression_program, noop_program, supporter_stats (optional, w), creator_profile (optional),
+///            leaderboard (optional, w), payer (optional, s,w), instructions_sysvar (optional),
+///            volume_window (optional, w), price_feed (optional), rebate (optional, w),
+///            collaborators (optional), associated_token_program (optional), promo_code (optional, w),
+///            fan_club (optional), nft_token, nft_metadata, charity (optional, w), charity_token (w),
+///            forward_rule (optional), forward_token (w), gifter_stats (optional, w),
+///            tipper_leaderboard (optional, w), then per treasury split entry: split_token (w),
+///            then per collaborator: collaborator_token (w), then transfer-hook accounts]
+/// An optional slot holding the program id is skipped. A `payer` funds every account the tip creates or
+/// grows instead of the tipper; `instructions_sysvar` is required while `require_top_level` is set.
+/// With `associated_token_program` passed, a `creator_token` that does not exist yet is created as the
+/// creator's ATA, so a creator's first tip in a mint does not fail. A `promo_code` takes its discount
+/// off the fee and uses up one redemption. A `fan_club` with the tipper's `nft_token` and its
+/// `nft_metadata` takes the club's discount off the fee when the NFT is in its verified collection.
+/// TipWithDonation shares these accounts and requires the mint's `charity`, which receives
+/// `donation_bps` of the creator's share ahead of the collaborators' cuts. The creator's `forward_rule`
+/// sends its share of what is left to `forward_token`, the destination's ATA; vesting tips are not
+/// forwarded. Self-tips are flagged or rejected as the config's `self_tip_policy` says. Under the config's
+/// `lenient_routing`, a referrer, charity or forward destination failing its checks is skipped with a
+/// RoutingSkipped event instead of failing the tip.
+/// The `route` variants take their own accounts ahead of these: TipWithCampaign its `campaign`,
+/// TipWithMatch the `matching_pool` and its `pool_vault`, TipWithVesting the `creator_vault` and the
+/// `vesting` schedule that tips of `vesting_threshold` or more must lock into, and TipWithSwap the config's
+/// `swap_program`, the creator's `payout_token` and `payout_mint` and the swap's `route_accounts`
+/// accounts, through which it converts what the creator would receive into their profile's `payout_mint`.
+/// TipAnonymous shares these accounts, but leaves the tipper out of events and TipperStats totals, so
+/// it rejects the slots that would record them. TipUsd shares them too and requires `price_feed`.
+/// TipGift shares them with `tipper_stats`, `receipt` and `supporter_stats` belonging to the attributed
+/// tipper, who is credited with the tip while the tipper pays for it; the tipper's own `gifter_stats`
+/// carries the rate-limit window. TipWithRef shares them and
+/// binds a non-zero `external_ref` to the tip in its receipt and a TipExternalRef event. TipWithNonce
+/// shares them too and goes through only while its `nonce` is the tipper's next `tip_nonce`.
+#[allow(clippy::too_many_arguments)]
+fn tip(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: TipAmount,
+    memo: String,
+    category: u8,
+    donation_bps: u16,
+    route: TipRoute,
+    external_ref: [u8; 32],
+    credit: TipCredit,
+    tip_nonce: Option<u64>,
+) -> Result<TipOutcome, ProgramError> {
+    let iter = &mut accounts.iter();
+    let campaign_acc = match route {
+        TipRoute::Campaign => Some(next_account_info(iter)?),
+        _ => None,
+    };
+    let pool_accs = match route {
+        TipRoute::Matched => Some((next_account_info(iter)?, next_account_info(iter)?)),
+        _ => None,
+    };
+    let vesting_accs = match route {
+        TipRoute::Vested => Some((next_account_info(iter)?, next_account_info(iter)?)),
+        _ => None,
+    };
+    let swap_leg = match route {
+        TipRoute::Swap(swap) => {
+            let (swap_program, payout_token, payout_mint) =
+                (next_account_info(iter)?, next_account_info(iter)?, next_account_info(iter)?);
+            let route_len = swap.route_accounts as usize;
+            let (route, rest) = iter.as_slice().split_at_checked(route_len).ok_or(ProgramError::NotEnoughAccountKeys)?;
+            *iter = rest.iter();
+            Some((swap, swap_program, payout_token, payout_mint, route))
+        }
+        _ => None,
+    };
+    let config_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let creator_token = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let creator_stats_acc = next_account_info(iter)?;
+    let tipper_stats_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+    let notify_acc = next_optional_account(iter, program_id);
+    let epoch_acc = next_optional_account(iter, program_id);
+    let referrer_token = next_optional_account(iter, program_id);
+    let fee_override_acc = next_optional_account(iter, program_id);
+    let receipt_acc = next_optional_account(iter, program_id);
+    let tree_accs = match next_optional_account(iter, program_id) {
+        Some(tree) => Some((tree, next_account_info(iter)?, next_account_info(iter)?, next_account_info(iter)?)),
+        None => None,
+    };
+    let supporter_acc = next_optional_account(iter, program_id);
+    let profile_acc = next_optional_account(iter, program_id);
+    let leaderboard_acc = next_optional_account(iter, program_id);
+    let payer_acc = next_optional_account(iter, program_id);
+    let ix_sysvar = next_optional_account(iter, program_id);
+    let volume_window_acc = next_optional_account(iter, program_id);
+    let price_feed_acc = next_optional_account(iter, program_id);
+    let rebate_acc = next_optional_account(iter, program_id);
+    let collaborators_acc = next_optional_account(iter, program_id);
+    let ata_program = next_optional_account(iter, program_id);
+    let promo_acc = next_optional_account(iter, program_id);
+    let fan_accs = match next_optional_account(iter, program_id) {
+        Some(fan_club_acc) => Some((fan_club_acc, next_account_info(iter)?, next_account_info(iter)?)),
+        None => None,
+    };
+    let charity_accs = match next_optional_account(iter, program_id) {
+        Some(charity_acc) => Some((charity_acc, next_account_info(iter)?)),
+        None => None,
+    };
+    let forward_accs = match next_optional_account(iter, program_id) {
+        Some(forward_rule_acc) => Some((forward_rule_acc, next_account_info(iter)?)),
+        None => None,
+    };
+    let gifter_stats_acc = next_optional_account(iter, program_id);
+    let tipper_leaderboard_acc = next_optional_account(iter, program_id);
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[
+        config_acc, tipper, tipper_token, creator_token, treasury_token, creator_stats_acc, tipper_stats_acc,
+    ])?;
+    let payer = rent_payer(payer_acc, tipper)?;
+    if let Some(ata_program) = ata_program.filter(|_| creator_token.data_is_empty()) {
+        create_creator_ata(payer, creator, creator_token, mint_acc, token_program, system_program, ata_program)?;
+    }
+    validation::token_accounts(&[tipper_token, creator_token, treasury_token])?;
+    let anonymous = credit == TipCredit::Anonymous;
+    // Stats, receipts and badges belong to the attributed tipper of a gift
+    let credited = match credit {
+        TipCredit::Gift(attributed_tipper) => attributed_tipper,
+        TipCredit::Tipper | TipCredit::Anonymous => *tipper.key,
+    };
+    let linked = [receipt_acc, tree_accs.map(|(tree, ..)| tree), supporter_acc, rebate_acc, tipper_leaderboard_acc];
+    if anonymous && linked.iter().any(Option::is_some) {
+        msg!("Anonymous tips cannot create receipts, supporter stats or rebates, or rank the tipper");
+        return Err(TipError::AnonymousTipLinked.into());
+    }
+    // Events name the default pubkey in place of an anonymous tipper
+    let public_tipper = if anonymous { Pubkey::default() } else { *tipper.key };
+
+    // Zero-copy: only the fee and counter fields are read or written
+    let mut config_data = config_acc.data.borrow_mut();
+    let config = TipConfigZc::load_mut(&mut config_data)?;
+    let platform_id = config.platform_id;
+    // Configs are only ever written at ["config", platform, bump] (by Initialize and MigrateConfig), so a
+    // program-owned one passing `load_mut` is at that address without hashing its seeds again on every tip
+    validation::program_owned(program_id, config_acc)?;
+    if config.is_initialized == 0 {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused != 0 {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    if config.require_top_level != 0 {
+        check_top_level(program_id, ix_sysvar)?;
+    }
+    check_tipper_not_blocked(program_id, platform_id, blocked_tipper_acc, tipper.key)?;
+
+    if memo.chars().count() > MAX_MEMO_CHARS {
+        return Err(TipError::MemoTooLong.into());
+    }
+    if category as usize >= TIP_CATEGORIES {
+        return Err(TipError::InvalidCategory.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let allowed = check_allowed_mint(
+        program_id,
+        config.platform_id,
+        allowed_mint_acc,
+        &[tipper_token, creator_token, treasury_token],
+    )?;
+    if allowed.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    let price = match price_feed_acc {
+        Some(price_feed_acc) => {
+            if *price_feed_acc.key != allowed.price_feed {
+                return Err(TipError::PriceFeedMismatch.into());
+            }
+            Some(oracle::load_price(price_feed_acc, Clock::get()?.slot)?)
+        }
+        None => None,
+    };
+    let amount = match amount {
+        TipAmount::Tokens(amount) => amount,
+        TipAmount::UsdCents { usd_cents, max_amount } => {
+            let price = price.ok_or(TipError::PriceFeedRequired)?;
+            let amount = price.tokens_for_usd_cents(usd_cents, mint.decimals)?;
+            if amount > max_amount {
+                msg!("{} USD cents is {} base units, above the maximum of {}", usd_cents, amount, max_amount);
+                return Err(TipError::SlippageExceeded.into());
+            }
+            amount
+        }
+    };
+    if amount == 0 {
+        msg!("Tip amount must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    check_tip_limits(allowed.min_tip(config.min_tip_amount), config.max_tip_amount, amount)?;
+    // A campaign capping its overflow takes only what its target still needs, and the rest of the tip
+    // never leaves the tipper
+    let requested = amount;
+    let amount = match campaign_acc {
+        Some(campaign_acc) => campaign_tip_amount(program_id, campaign_acc, creator.key, amount)?,
+        None => amount,
+    };
+    let tier = match profile_acc {
+        Some(profile_acc) => creator_tip_tier(program_id, profile_acc, creator, mint_acc.key, amount)?,
+        None => 0,
+    };
+
+    let mint_stats = load_mint_stats(program_id, platform_id, mint_acc.key, mint_stats_acc)?;
+    let mut schedule = config.fee_schedule().with_mint_stats(mint_stats.as_ref());
+    let creator_fee =
+        fee_override_acc.map(|acc| load_fee_override(program_id, platform_id, acc, creator.key)).transpose()?;
+    if let Some(fee_bps) = creator_fee.flatten() {
+        schedule = schedule.with_override(fee_bps);
+    }
+    // A campaign's own rate wins over the creator's override
+    let campaign_fee = campaign_acc.map(|acc| load_campaign_fee(program_id, acc, creator.key)).transpose()?;
+    if let Some(fee_bps) = campaign_fee.flatten() {
+        schedule = schedule.with_override(fee_bps);
+    }
+    let mut fee = compute_fee(schedule, amount)?;
+    if let Some(promo_acc) = promo_acc {
+        fee = redeem_promo_code(program_id, platform_id, accounts, promo_acc, &public_tipper, fee)?;
+    }
+    if let Some((fan_club_acc, nft_token, nft_metadata)) = fan_accs {
+        fee = apply_fan_discount(
+            program_id,
+            accounts,
+            fan_club_acc,
+            nft_token,
+            nft_metadata,
+            tipper.key,
+            &public_tipper,
+            creator.key,
+            fee,
+        )?;
+    }
+    check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+    check_not_blocked(program_id, platform_id, blocked_acc, creator.key)?;
+    check_self_tip(
+        config.self_tip_policy, accounts, tipper.key, &credited, &public_tipper, tipper_token, creator.key,
+        creator_token, profile_acc, mint_acc.key, amount,
+    )?;
+    if config.require_verified != 0 {
+        check_verified(program_id, profile_acc, creator)?;
+    }
+    let vesting = match (config.vesting_threshold, vesting_accs) {
+        (0, _) => None,
+        (threshold, _) if amount < threshold => None,
+        (_, _) if anonymous => {
+            msg!("Anonymous tips cannot vest; tip less than {}", { config.vesting_threshold });
+            return Err(TipError::AnonymousTipLinked.into());
+        }
+        (_, Some((vault_acc, vesting_acc))) => {
+            Some((load_vesting_vault(program_id, vault_acc, creator, creator_token, mint_acc)?, vault_acc, vesting_acc))
+        }
+        (threshold, None) => {
+            msg!("Tips of {} or more must vest in the creator's vault", threshold);
+            return Err(TipError::VestingRequired.into());
+        }
+    };
+    if let Some((_, swap_program, payout_token, payout_mint, _)) = swap_leg {
+        check_swap_route(
+            config.swap_program, swap_program, payout_token, payout_mint, creator.key, profile_acc, mint_acc.key,
+        )?;
+    }
+
+    // Surface frozen sources (e.g. frozen-by-default mints) before any CPI
+    let source = token::unpack_account(tipper_token)?;
+    if source.is_frozen() {
+        msg!("Source token account is frozen; ask the mint's freeze authority to thaw it");
+        return Err(TipError::SourceAccountFrozen.into());
+    }
+
+    let collaborators =
+        collaborators_acc.map(|acc| load_collaborators(program_id, acc, creator.key)).transpose()?.flatten();
+    let shares = collaborators.as_ref().map_or(&[][..], CollaboratorSplit::entries);
+
+    // Collaborator token accounts follow the treasury split accounts, then the tax account's ATA while
+    // the profile withholds tax, and whatever follows is forwarded to every transfer for transfer-hook mints
+    let split_count = config.treasury_split().entries().count();
+    let tail = iter.as_slice();
+    let tax_start = split_count + shares.len();
+    let collaborator_tokens = tail.get(split_count..tax_start).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let withholding = match profile_acc {
+        Some(profile_acc) => CreatorProfile::load(&profile_acc.data.borrow())?.tax_withholding(),
+        None => None,
+    };
+    let withholding = match withholding {
+        Some((tax_bps, tax_account)) => {
+            Some((tax_bps, tax_account, tail.get(tax_start).ok_or(ProgramError::NotEnoughAccountKeys)?))
+        }
+        None => None,
+    };
+    let hook_accounts = tail.get(tax_start + withholding.is_some() as usize..).unwrap_or(&[]);
+
+    // Optional destinations are checked before any money moves; under `lenient_routing` one that fails
+    // is dropped and its share stays with the treasury or creator
+    let lenient = config.lenient_routing != 0;
+    let referrer_token = match referrer_token {
+        Some(referrer_token) => {
+            let checked = check_referrer(referrer_token, tipper.key, mint_acc.key);
+            route_or_skip(lenient, accounts, creator.key, RoutingDestination::Referral, referrer_token.key, checked)?
+                .map(|()| referrer_token)
+        }
+        None => None,
+    };
+    let charity = match (donation_bps, charity_accs) {
+        (0, _) => None,
+        (_, Some((charity_acc, charity_token))) => {
+            let checked = load_charity(program_id, platform_id, charity_acc, charity_token, mint_acc.key, donation_bps);
+            route_or_skip(lenient, accounts, creator.key, RoutingDestination::Charity, charity_token.key, checked)?
+                .map(|charity| (charity, charity_acc, charity_token))
+        }
+        (_, None) => {
+            msg!("Donations need the mint's charity");
+            let missing = Err(TipError::InvalidDonation.into());
+            route_or_skip(lenient, accounts, creator.key, RoutingDestination::Charity, &Pubkey::default(), missing)?
+        }
+    };
+    // A vesting tip must reach the vault whole
+    let forward = match forward_accs.filter(|_| vesting.is_none()) {
+        Some((forward_rule_acc, forward_token)) => {
+            let checked = load_forward_rule(
+                program_id, forward_rule_acc, forward_token, creator.key, mint_acc.key, token_program.key,
+            )
+            .and_then(|rule| {
+                token::unpack_account(forward_token)?;
+                validation::writable(&[forward_token]).map(|()| rule)
+            });
+            route_or_skip(lenient, accounts, creator.key, RoutingDestination::Forward, forward_token.key, checked)?
+                .map(|rule| (rule, forward_token))
+        }
+        None => None,
+    };
+    let collaborator_bps: Vec<u16> = shares.iter().map(|share| share.share_bps).collect();
+    let parts = split_tip(
+        amount,
+        fee,
+        TipRates {
+            referral_bps: if referrer_token.is_some() { config.referral_bps } else { 0 },
+            donation_bps: if charity.is_some() { donation_bps } else { 0 },
+            collaborator_bps: &collaborator_bps,
+            tax_bps: withholding.map_or(0, |(tax_bps, _, _)| tax_bps),
+            forward_bps: forward.as_ref().map_or(0, |(rule, _)| rule.forward_bps),
+        },
+    )?;
+
+    if let Some((charity, charity_acc, charity_token)) = charity {
+        donate(
+            accounts, charity_acc, charity, charity_token, &public_tipper, creator.key, parts.donated, token_program,
+            tipper_token, mint_acc, tipper, mint.decimals, hook_accounts,
+        )?;
+    }
+    let collaborators_received = pay_collaborators(
+        accounts, shares, &parts.collaborators, collaborator_tokens, creator.key, token_program, tipper_token, mint_acc,
+        tipper, mint.decimals, hook_accounts,
+    )?;
+    if let Some((_, tax_account, tax_token)) = withholding {
+        withhold_tax(
+            accounts, &tax_account, tax_token, creator.key, parts.withheld, token_program, tipper_token, mint_acc,
+            tipper, mint.decimals, hook_accounts,
+        )?;
+    }
+    let forwarded_received = match forward {
+        Some((rule, forward_token)) => forward_tip(
+            accounts, &rule, forward_token, creator.key, parts.forwarded, token_program, tipper_token, mint_acc, tipper,
+            mint.decimals, hook_accounts,
+        )?,
+        None => 0,
+    };
+    let creator_amount = parts.creator;
+    let creator_received = match swap_leg {
+        // Stats count the share in the tip's mint, whatever it was swapped into
+        Some((swap, swap_program, payout_token, payout_mint, route)) => {
+            swap_to_payout(
+                accounts, swap, swap_program, payout_token, payout_mint, route, tipper_token, mint_acc.key, creator.key,
+                creator_amount,
+            )?;
+            creator_amount
+        }
+        None => transfer_tokens(
+            accounts,
+            token_program, tipper_token, mint_acc, creator_token, tipper, creator_amount, mint.decimals, &[],
+            hook_accounts,
+        )?,
+    };
+    // Forwarded tokens are still the creator's
+    let creator_received = add_checked(creator_received, forwarded_received)?;
+    let usd_received = price.map(|price| price.usd_micros(creator_received, mint.decimals)).transpose()?.unwrap_or(0);
+
+    let epoch_volume = bump_creator_stats(
+        program_id,
+        platform_id,
+        payer,
+        creator,
+        creator_stats_acc,
+        system_program,
+        amount,
+        creator_received,
+        category,
+        tier,
+        usd_received,
+    )?;
+    // A gift is charged to the paying tipper's rate-limit window rather than the attributed tipper's,
+    // so a sponsor can neither exhaust a fan's window nor slip past their own limit by gifting
+    let gift_limit = match credit {
+        TipCredit::Gift(attributed_tipper) if attributed_tipper != *tipper.key => Some(config.rate_limit()),
+        _ => None,
+    };
+    let rate_limit = if gift_limit.is_some() { RateLimit::default() } else { config.rate_limit() };
+    let nonce = bump_tipper_stats(
+        program_id, platform_id, payer, &credited, tipper_stats_acc, system_program, 1, amount, rate_limit, anonymous,
+        tip_nonce,
+    )?;
+    match (gift_limit.filter(|limit| limit.window_secs > 0), gifter_stats_acc) {
+        (Some(limit), Some(gifter_stats_acc)) => {
+            bump_tipper_stats(
+                program_id, platform_id, payer, tipper.key, gifter_stats_acc, system_program, 1, amount, limit, true,
+                None,
+            )?;
+        }
+        (Some(_), None) => {
+            msg!("Gifts need the tipper's own tipper_stats while a rate limit is set");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        (None, _) => {}
+    }
+    if let Some(tipper_leaderboard_acc) = tipper_leaderboard_acc {
+        let epoch_volume = TipperStats::load(&tipper_stats_acc.data.borrow())?.epoch_volume;
+        rank_on_leaderboard(
+            program_id, platform_id, tipper_leaderboard_acc, get_tipper_leaderboard_pda, &credited, epoch_volume,
+        )?;
+    }
+
+    msg!("Tip: {} to creator, {} fee", creator_amount, fee);
+
+    if let Some(receipt_acc) = receipt_acc {
+        let (pda, bump) = get_tip_receipt_pda(program_id, &credited, creator.key, nonce);
+        if *receipt_acc.key != pda {
+            msg!("Receipt nonce must be the tipper's tip count: {}", nonce);
+            return Err(ProgramError::InvalidSeeds);
+        }
+        create_pda_account(
+            payer,
+            receipt_acc,
+            system_program,
+            program_id,
+            TIP_RECEIPT_SIZE,
+            &[b"receipt", credited.as_ref(), creator.key.as_ref(), &nonce.to_le_bytes(), &[bump]],
+        )?;
+        let receipt = TipReceipt {
+            is_initialized: true,
+            tipper: credited,
+            creator: *creator.key,
+            nonce,
+            mint: *mint_acc.key,
+            amount,
+            fee,
+            slot: Clock::get()?.slot,
+            external_ref,
+        };
+        receipt.pack(&mut receipt_acc.data.borrow_mut())?;
+    }
+
+    if let Some((tree, tree_authority, _compression_program, _noop_program)) = tree_accs {
+        if *tree.key != { config.receipt_tree } {
+            return Err(TipError::ReceiptTreeMismatch.into());
+        }
+        let (authority_pda, bump) = get_tree_authority_pda(program_id, platform_id);
+        if *tree_authority.key != authority_pda {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let leaf = ReceiptLeaf {
+            tipper: credited,
+            creator: *creator.key,
+            nonce,
+            mint: *mint_acc.key,
+            amount,
+            fee,
+            slot: Clock::get()?.slot,
+        };
+        invoke_with_accounts(
+            &compression::append(tree.key, &authority_pda, leaf.hash()),
+            accounts,
+            &[&[b"tree_authority", &platform_seed(platform_id), &[bump]]],
+        )?;
+        ReceiptAppended { tree: *tree.key, leaf }.emit(accounts)?;
+    }
+
+    if let Some(supporter_acc) = supporter_acc {
+        bump_supporter_stats(program_id, payer, &credited, creator, supporter_acc, system_program, amount)?;
+    }
+
+    if let Some((mut vault, vault_acc, vesting_acc)) = vesting {
+        let index = vault.vesting_count;
+        let (pda, bump) = get_vesting_pda(program_id, vault_acc.key, index);
+        if *vesting_acc.key != pda {
+            msg!("Vesting index must be the vault's vesting count: {}", index);
+            return Err(ProgramError::InvalidSeeds);
+        }
+        create_pda_account(
+            payer,
+            vesting_acc,
+            system_program,
+            program_id,
+            VESTING_SCHEDULE_SIZE,
+            &[b"vesting", vault_acc.key.as_ref(), &index.to_le_bytes(), &[bump]],
+        )?;
+        let schedule = VestingSchedule {
+            is_initialized: true,
+            creator_vault: *vault_acc.key,
+            tipper: *tipper.key,
+            amount: creator_received,
+            released: 0,
+            start_ts: Clock::get()?.unix_timestamp,
+            cliff_secs: config.vesting_cliff_secs,
+            duration_secs: config.vesting_duration_secs,
+            dispute_until_slot: match config.dispute_window_slots {
+                0 => 0,
+                window => Clock::get()?.slot.saturating_add(window),
+            },
+            disputed: false,
+        };
+        schedule.pack(&mut vesting_acc.data.borrow_mut())?;
+
+        vault.locked = vault.locked.checked_add(creator_received).ok_or(TipError::MathOverflow)?;
+        vault.vesting_count = add_checked(vault.vesting_count, 1)?;
+        vault.pack(&mut vault_acc.data.borrow_mut())?;
+
+        msg!("Vesting: {} locked", creator_received);
+        TipVested { creator: *creator.key, tipper: *tipper.key, schedule: pda, amount: creator_received }
+            .emit(accounts)?;
+    }
+
+    if !memo.is_empty() {
+        TipMemo { tipper: public_tipper, creator: *creator.key, memo }.emit(accounts)?;
+    }
+    if external_ref != [0; 32] {
+        TipExternalRef { tipper: public_tipper, creator: *creator.key, external_ref }.emit(accounts)?;
+    }
+
+    if let Some(notify_acc) = notify_acc {
+        // A creator who never called SetNotify has no account and gets no event. An existing
+        // one is checked by its stored creator; only a missing one needs the PDA derived.
+        if notify_acc.owner == program_id && !notify_acc.data_is_empty() {
+            let notify = CreatorNotify::unpack(&notify_acc.data.borrow())?;
+            if notify.creator != *creator.key {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            if notify.notify {
+                CreatorNotified { creator: *creator.key, amount, tipper: public_tipper }.emit(accounts)?;
+            }
+        } else if *notify_acc.key != get_notify_pda(program_id, creator.key).0 {
+            return Err(ProgramError::InvalidSeeds);
+        }
+    }
+
+    if let Some(campaign_acc) = campaign_acc {
+        contribute_to_campaign(
+            program_id, accounts, campaign_acc, creator, payer, system_program, amount, requested,
+        )?;
+    }
+
+    if let Some((pool_acc, pool_vault)) = pool_accs {
+        match_tip(
+            program_id, accounts, pool_acc, pool_vault, creator, creator_token, mint_acc, token_program, amount,
+            hook_accounts,
+        )?;
+    }
+
+    if let Some(epoch_acc) = epoch_acc {
+        record_epoch_tip(program_id, platform_id, epoch_acc, amount)?;
+    }
+
+    if let Some(leaderboard_acc) = leaderboard_acc {
+        rank_on_leaderboard(program_id, platform_id, leaderboard_acc, get_leaderboard_pda, creator.key, epoch_volume)?;
+    }
+
+    if let Some(volume_window_acc) = volume_window_acc {
+        record_volume(program_id, platform_id, volume_window_acc, amount)?;
+    }
+
+    // Fee goes last so the referrer can take its share
+    let referral_fee = if referrer_token.is_some() { parts.referral } else { 0 };
+
+    // Rebates are paid out of the mint's treasury vault, so only fees that went into it earn one
+    let vault_before = match rebate_acc {
+        Some(_) if *treasury_token.key == get_treasury_vault_pda(program_id, platform_id, mint_acc.key).0 => {
+            Some(token::unpack_account(treasury_token)?.amount)
+        }
+        _ => None,
+    };
+    let treasury_received = pay_treasury_fee(
+        accounts,
+        config.treasury_split(), parts.treasury, iter, token_program, tipper_token, mint_acc, treasury_token, tipper,
+        mint.decimals, &[], hook_accounts,
+    )?;
+    let mut referral_received = 0;
+    if let (Some(referrer_token), true) = (referrer_token, referral_fee > 0) {
+        referral_received = transfer_tokens(
+            accounts,
+            token_program, tipper_token, mint_acc, referrer_token, tipper, referral_fee, mint.decimals, &[],
+            hook_accounts,
+        )?;
+        msg!("Referral: {} of the fee", referral_received);
+        ReferralPaid { referrer_token: *referrer_token.key, amount: referral_received }.emit(accounts)?;
+    }
+
+    // Totals count what arrived, net of any Token-2022 transfer fee the mint withheld
+    let fee_received = add_checked(treasury_received, referral_received)?;
+    let delivered = add_checked(add_checked(creator_received, collaborators_received)?, fee_received)?;
+    config.total_tips = add_checked(config.total_tips, 1)?;
+    let clock = Clock::get()?;
+    config.last_tip_slot = clock.slot;
+    config.last_tip_unix_ts = clock.unix_timestamp;
+    let mut stats =
+        open_mint_stats(program_id, platform_id, payer, mint_acc.key, mint_stats_acc, system_program, mint_stats)?;
+    let target_reached = add_mint_tip(&mut stats, delivered, treasury_received, referral_received)?;
+    save_mint_stats(accounts, &stats, mint_stats_acc, target_reached)?;
+    if let Some(rebate_acc) = rebate_acc {
+        let terms = RebateTerms { threshold: config.rebate_threshold, bps: config.rebate_bps };
+        let vault_fee = match vault_before {
+            Some(before) => token::unpack_account(treasury_token)?.amount.saturating_sub(before),
+            None => 0,
+        };
+        accrue_rebate(
+            program_id, platform_id, payer, tipper, mint_acc.key, rebate_acc, system_program, terms, amount, vault_fee,
+        )?;
+    }
+    if let Some(price) = price {
+        let usd_micros = price.usd_micros(delivered, mint.decimals)?;
+        TipValued { tipper: public_tipper, creator: *creator.key, mint: *mint_acc.key, amount: delivered, usd_micros }
+            .emit(accounts)?;
+    }
+    emit_tip(accounts, &public_tipper, creator.key, mint_acc.key, delivered, fee_received, category, tier)?;
+    if let TipCredit::Gift(attributed_tipper) = credit {
+        TipGifted { tipper: *tipper.key, attributed_tipper, creator: *creator.key, mint: *mint_acc.key, amount: delivered }
+            .emit(accounts)?;
+    }
+    Ok(TipOutcome {
+        creator_amount: add_checked(creator_received, collaborators_received)?,
+        fee: fee_received,
+        referral_amount: referral_received,
+    })
+}
+
+/// TipWrappedSol: [amount: u64, memo: String, category: u8]
+/// Accounts: Tip's, with the wSOL PDA ["wsol", tipper] as tipper_token, the native mint and SPL Token
+/// Wraps `amount` lamports of the tipper's SOL into a temporary wSOL account owned by the tipper,
+/// runs `Tip` from it and closes it again, so rent and any remainder return to the tipper
+fn tip_wrapped_sol(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    memo: String,
+    category: u8,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let _config_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let wsol = next_account_info(iter)?;
+    let _creator = next_account_info(iter)?;
+    let _creator_token = next_account_info(iter)?;
+    let _treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let system_program = accounts.get(11).ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    if *mint_acc.key != token::NATIVE_MINT || *token_program.key != spl_token::id() {
+        msg!("TipWrappedSol needs the SPL Token native mint");
+        return Err(TipError::MintMismatch.into());
+    }
+    let (pda, bump) = get_wsol_pda(program_id, tipper.key);
+    if *wsol.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if wsol.data_is_empty() {
+        create_vault(
+            tipper, wsol, mint_acc, token_program, system_program, tipper.key, &[b"wsol", tipper.key.as_ref(), &[bump]],
+        )?;
+    }
+    invoke(
+        &system_instruction::transfer(tipper.key, wsol.key, amount),
+        &[tipper.clone(), wsol.clone(), system_program.clone()],
+    )?;
+    invoke(&token::sync_native(token_program.key, wsol.key), std::slice::from_ref(wsol))?;
+
+    let amount = TipAmount::Tokens(amount);
+    let outcome =
+        tip(program_id, accounts, amount, memo, category, 0, TipRoute::Plain, [0; 32], TipCredit::Tipper, None)?;
+
+    invoke(
+        &token::close_account(token_program.key, wsol.key, tipper.key, tipper.key),
+        &[wsol.clone(), tipper.clone(), tipper.clone()],
+    )?;
+    return_tip_outcome(outcome)
+}
+
+/// TipWithReceiptNft: [amount: u64, memo: String, category: u8]
+/// Accounts: [nft_mint (s,w), nft_token (w), nft_metadata (w), nft_master_edition (w), receipt_authority,
+///            collection_mint, collection_metadata (w), collection_master_edition, token_metadata_program,
+///            spl_token_program, associated_token_program, then Tip's accounts]
+/// Runs `Tip`, then mints a one-of-one NFT from the fresh `nft_mint` keypair to the tipper's ATA
+/// and verifies it into the configured collection. The tipper pays all rent.
+fn tip_with_receipt_nft(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    memo: String,
+    category: u8,
+) -> ProgramResult {
+    let (nft_accounts, tip_accounts) = accounts.split_at_checked(11).ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let amount = TipAmount::Tokens(amount);
+    let outcome =
+        tip(program_id, tip_accounts, amount, memo, category, 0, TipRoute::Plain, [0; 32], TipCredit::Tipper, None)?;
+
+    let iter = &mut nft_accounts.iter();
+    let nft_mint = next_account_info(iter)?;
+    let nft_token = next_account_info(iter)?;
+    let nft_metadata = next_account_info(iter)?;
+    let nft_master_edition = next_account_info(iter)?;
+    let authority = next_account_info(iter)?;
+    let collection_mint = next_account_info(iter)?;
+    let collection_metadata = next_account_info(iter)?;
+    let collection_master_edition = next_account_info(iter)?;
+    let _metadata_program = next_account_info(iter)?;
+    let nft_token_program = next_account_info(iter)?;
+    let _associated_token_program = next_account_info(iter)?;
+    validation::writable(&[nft_mint, nft_token, nft_metadata, nft_master_edition, collection_metadata])?;
+
+    // Tip has already checked these
+    let tip_iter = &mut tip_accounts.iter();
+    let config_acc = next_account_info(tip_iter)?;
+    let tipper = next_account_info(tip_iter)?;
+    let system_program = tip_accounts.get(11).ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    if config.receipt_nft_collection == Pubkey::default() {
+        return Err(TipError::ReceiptNftDisabled.into());
+    }
+    if *collection_mint.key != config.receipt_nft_collection {
+        return Err(TipError::CollectionMismatch.into());
+    }
+    if *nft_token_program.key != spl_token::id() {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let (authority_pda, bump) = get_receipt_authority_pda(program_id, config.platform_id);
+    if *authority.key != authority_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let platform = platform_seed(config.platform_id);
+    let authority_seeds: &[&[u8]] = &[b"receipt_authority", &platform, &[bump]];
+
+    // Token Metadata derives and checks its own PDAs; the mint keypair signed the transaction
+    let rent = Rent::get()?;
+    invoke(
+        &system_instruction::create_account(
+            tipper.key, nft_mint.key, rent.minimum_balance(token::MINT_LEN), token::MINT_LEN as u64, &spl_token::id(),
+        ),
+        &[tipper.clone(), nft_mint.clone(), system_program.clone()],
+    )?;
+    invoke(
+        &token::initialize_mint2(&spl_token::id(), nft_mint.key, &authority_pda, Some(&authority_pda), 0),
+        std::slice::from_ref(nft_mint),
+    )?;
+    invoke(
+        &token::create_associated_token_account_idempotent(tipper.key, tipper.key, nft_mint.key, &spl_token::id()),
+        &[tipper.clone(), nft_token.clone(), nft_mint.clone(), system_program.clone(), nft_token_program.clone()],
+    )?;
+    invoke_signed(
+        &token::mint_to(&spl_token::id(), nft_mint.key, nft_token.key, &authority_pda, 1),
+        &[nft_mint.clone(), nft_token.clone(), authority.clone()],
+        &[authority_seeds],
+    )?;
+
+    let uri = config.receipt_nft_uri.as_str().replace("{mint}", &nft_mint.key.to_string());
+    invoke_signed(
+        &metadata::create_metadata_account_v3(
+            nft_metadata.key,
+            nft_mint.key,
+            &authority_pda,
+            tipper.key,
+            &authority_pda,
+            format!("Tip #{}", config.total_tips),
+            "TIP".to_string(),
+            uri,
+            collection_mint.key,
+        ),
+        &[nft_metadata.clone(), nft_mint.clone(), authority.clone(), tipper.clone(), system_program.clone()],
+        &[authority_seeds],
+    )?;
+    invoke_signed(
+        &metadata::create_master_edition_v3(
+            nft_master_edition.key,
+            nft_mint.key,
+            &authority_pda,
+            &authority_pda,
+            tipper.key,
+            nft_metadata.key,
+            &spl_token::id(),
+        ),
+        &[
+            nft_master_edition.clone(),
+            nft_mint.clone(),
+            authority.clone(),
+            tipper.clone(),
+            nft_metadata.clone(),
+            nft_token_program.clone(),
+            system_program.clone(),
+        ],
+        &[authority_seeds],
+    )?;
+    invoke_signed(
+        &metadata::verify_sized_collection_item(
+            nft_metadata.key,
+            &authority_pda,
+            tipper.key,
+            collection_mint.key,
+            collection_metadata.key,
+            collection_master_edition.key,
+        ),
+        &[
+            nft_metadata.clone(),
+            authority.clone(),
+            tipper.clone(),
+            collection_mint.clone(),
+            collection_metadata.clone(),
+            collection_master_edition.clone(),
+        ],
+        &[authority_seeds],
+    )?;
+
+    msg!("Receipt NFT: {}", nft_mint.key);
+    ReceiptNftMinted { tipper: *tipper.key, mint: *nft_mint.key, collection: *collection_mint.key }.emit(accounts)?;
+    return_tip_outcome(outcome)
+}
+
+/// The next account, or None if it is the program id placeholder (or missing)
+fn next_optional_account<'a, 'b: 'a>(
+    iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+    program_id: &Pubkey,
+) -> Option<&'a AccountInfo<'b>> {
+    next_account_info(iter).ok().filter(|acc| acc.key != program_id)
+}
+
+/// Apply an unexpired promo code with uses left to `fee`, using up one redemption; returns the
+/// discounted fee
+fn redeem_promo_code(
+    program_id: &Pubkey,
+    platform_id: u64,
+    accounts: &[AccountInfo],
+    promo_acc: &AccountInfo,
+    tipper: &Pubkey,
+    fee: u64,
+) -> Result<u64, ProgramError> {
+    validation::program_owned(program_id, promo_acc)?;
+    validation::writable(&[promo_acc])?;
+    let mut promo = PromoCode::unpack(&promo_acc.data.borrow())?;
+    if !promo.is_initialized || *promo_acc.key != get_promo_code_pda(program_id, platform_id, &promo.code_hash).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if Clock::get()?.unix_timestamp >= promo.expiry_ts {
+        return Err(TipError::PromoCodeExpired.into());
+    }
+    promo.remaining_uses = promo.remaining_uses.checked_sub(1).ok_or(TipError::PromoCodeExhausted)?;
+    promo.pack(&mut promo_acc.data.borrow_mut())?;
+
+    let (fee, fee_waived) = apply_discount(fee, promo.discount_bps)?;
+    PromoCodeRedeemed { promo_code: *promo_acc.key, tipper: *tipper, fee_waived, remaining_uses: promo.remaining_uses }
+        .emit(accounts)?;
+    Ok(fee)
+}
+
+/// Take the creator's fan club discount off `fee` if `nft_token` holds, for `tipper`, an NFT whose
+/// metadata is `nft_metadata` and names the club's collection as verified; returns the discounted fee
+#[allow(clippy::too_many_arguments)]
+fn apply_fan_discount(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fan_club_acc: &AccountInfo,
+    nft_token: &AccountInfo,
+    nft_metadata: &AccountInfo,
+    tipper: &Pubkey,
+    public_tipper: &Pubkey,
+    creator: &Pubkey,
+    fee: u64,
+) -> Result<u64, ProgramError> {
+    validation::program_owned(program_id, fan_club_acc)?;
+    if *fan_club_acc.key != get_fan_club_pda(program_id, creator).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let club = FanClub::unpack(&fan_club_acc.data.borrow())?;
+    if !club.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let holding = token::unpack_account(nft_token)?;
+    if holding.owner != *tipper || holding.amount == 0 {
+        return Err(TipError::NotFanClubMember.into());
+    }
+    if metadata::verified_collection(nft_metadata, &holding.mint)? != Some(club.collection) {
+        return Err(TipError::NotFanClubMember.into());
+    }
+
+    let (fee, fee_waived) = apply_discount(fee, club.discount_bps)?;
+    FanDiscountApplied { tipper: *public_tipper, creator: *creator, collection: club.collection, fee_waived }
+        .emit(accounts)?;
+    Ok(fee)
+}
+
+/// The creator's negotiated fee rate on the platform, if its admin set one
+fn load_fee_override(
+    program_id: &Pubkey,
+    platform_id: u64,
+    fee_override_acc: &AccountInfo,
+    creator: &Pubkey,
+) -> Result<Option<u16>, ProgramError> {
+    if *fee_override_acc.key != get_fee_override_pda(program_id, platform_id, creator).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if fee_override_acc.owner == program_id && !fee_override_acc.data_is_empty() {
+        return Ok(Some(FeeOverride::unpack(&fee_override_acc.data.borrow())?.fee_bps));
+    }
+    Ok(None)
+}
+
+/// Add a tip to the current epoch's stats; the account must have been opened by RolloverEpoch
+fn record_epoch_tip(program_id: &Pubkey, platform_id: u64, epoch_acc: &AccountInfo, amount: u64) -> ProgramResult {
+    if epoch_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut stats = EpochStats::unpack(&epoch_acc.data.borrow())?;
+    let current = epoch_index(Clock::get()?.unix_timestamp);
+    if !stats.is_initialized || stats.epoch_index != current {
+        msg!("Current epoch is {}", current);
+        return Err(TipError::WrongEpoch.into());
+    }
+    if *epoch_acc.key != get_epoch_stats_pda(program_id, platform_id, current).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    stats.tip_count = add_checked(stats.tip_count, 1)?;
+    stats.volume = add_checked(stats.volume, amount)?;
+    stats.pack(&mut epoch_acc.data.borrow_mut())?;
+    Ok(())
+}
+
+/// Re-rank `wallet` on the current epoch's leaderboard at `board_pda`: the creator leaderboard opened by
+/// OpenLeaderboard or the tipper leaderboard opened by OpenTipperLeaderboard
+fn rank_on_leaderboard(
+    program_id: &Pubkey,
+    platform_id: u64,
+    leaderboard_acc: &AccountInfo,
+    board_pda: fn(&Pubkey, u64, u64) -> (Pubkey, u8),
+    wallet: &Pubkey,
+    epoch_volume: u64,
+) -> ProgramResult {
+    if leaderboard_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut data = leaderboard_acc.data.borrow_mut();
+    let board = Leaderboard::load_mut(&mut data)?;
+    let current = epoch_index(Clock::get()?.unix_timestamp);
+    if board.epoch_index != current {
+        msg!("Current epoch is {}", current);
+        return Err(TipError::WrongEpoch.into());
+    }
+    if *leaderboard_acc.key != board_pda(program_id, platform_id, current).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    board.record(wallet, epoch_volume);
+    Ok(())
+}
+
+/// Add a tip to the hourly bucket of the current time, advancing the ring buffer past any hours
+/// without tips
+fn record_volume(program_id: &Pubkey, platform_id: u64, volume_window_acc: &AccountInfo, amount: u64) -> ProgramResult {
+    validation::program_owned(program_id, volume_window_acc)?;
+    validation::writable(&[volume_window_acc])?;
+    if *volume_window_acc.key != get_volume_window_pda(program_id, platform_id).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut data = volume_window_acc.data.borrow_mut();
+    VolumeWindow::load_mut(&mut data)?.record(Clock::get()?.unix_timestamp, amount)
+}
+
+/// TipMany: [amounts: Vec<u64>]
+/// Accounts: [config (w), tipper (s,w), tipper_token (w), treasury_token (w), mint, token_program, allowed_mint,
+///            tipper_stats (w), system_program, blocked_tipper, mint_stats (w),
+///            then per amount: creator, creator_token (w), creator_stats (w), blocked_creator,
+///            then per treasury split entry: split_token (w), then payer (optional, s,w)]
+/// Config, mint and tipper stats are written once for the whole batch; each tip is held to the self-tip
+/// policy
+fn tip_many(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amounts: Vec<u64>,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let tipper_stats_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, tipper, tipper_token, treasury_token, tipper_stats_acc])?;
+    validation::token_accounts(&[tipper_token, treasury_token])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+    check_tipper_not_blocked(program_id, config.platform_id, blocked_tipper_acc, tipper.key)?;
+    if amounts.is_empty() {
+        return Err(TipError::ZeroAmount.into());
+    }
+    let payer_index = 11 + 4 * amounts.len() + config.treasury_split().entries().count();
+    let payer = rent_payer(accounts.get(payer_index), tipper)?;
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let allowed = check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &[tipper_token, treasury_token])?;
+    if allowed.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    let min_tip_amount = allowed.min_tip(config.min_tip_amount);
+    if token::unpack_account(tipper_token)?.is_frozen() {
+        msg!("Source token account is frozen; ask the mint's freeze authority to thaw it");
+        return Err(TipError::SourceAccountFrozen.into());
+    }
+    let mut mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+
+    let mut target_reached = false;
+    let mut total_amount: u64 = 0;
+    let mut total_fee: u64 = 0;
+    for &amount in &amounts {
+        let creator = next_account_info(iter)?;
+        let creator_token = next_account_info(iter)?;
+        let creator_stats_acc = next_account_info(iter)?;
+        let blocked_acc = next_account_info(iter)?;
+
+        if amount == 0 {
+            msg!("Tip amount must be > 0");
+            return Err(TipError::ZeroAmount.into());
+        }
+        check_tip_limits(min_tip_amount, config.max_tip_amount, amount)?;
+        check_not_vesting(config.vesting_threshold, amount)?;
+        check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+        check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+        check_self_tip(
+            config.self_tip_policy as u8, accounts, tipper.key, tipper.key, tipper.key, tipper_token, creator.key,
+            creator_token, None, mint_acc.key, amount,
+        )?;
+
+        // Each tip counts toward the mint's fee target before the next one is priced
+        let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+        let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+        let creator_received = transfer_tokens(
+            accounts,
+            token_program, tipper_token, mint_acc, creator_token, tipper, creator_amount, mint.decimals, &[], &[],
+        )?;
+        bump_creator_stats(
+            program_id, config.platform_id, payer, creator, creator_stats_acc, system_program, amount,
+            creator_received, 0, 0, 0,
+        )?;
+
+        let delivered = add_checked(creator_received, fee)?;
+        if let Some(stats) = mint_stats.as_mut() {
+            target_reached |= add_mint_tip(stats, delivered, fee, 0)?;
+        }
+        emit_tip(accounts, tipper.key, creator.key, mint_acc.key, delivered, fee, 0, 0)?;
+        total_amount = add_checked(total_amount, amount)?;
+        total_fee = add_checked(total_fee, fee)?;
+    }
+
+    // One fee transfer for the whole batch; whatever a Token-2022 transfer fee withholds from it comes
+    // back off the totals
+    let fee_received = pay_treasury_fee(
+        accounts,
+        config.treasury_split(), total_fee, iter, token_program, tipper_token, mint_acc, treasury_token, tipper,
+        mint.decimals, &[], &[],
+    )?;
+    add_tips(&mut config, amounts.len() as u64)?;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    if let Some(stats) = mint_stats.as_mut() {
+        let withheld = total_fee.checked_sub(fee_received).ok_or(TipError::MathOverflow)?;
+        stats.total_volume = sub_checked(stats.total_volume, withheld)?;
+        stats.total_fees = sub_checked(stats.total_fees, withheld)?;
+        stats.total_treasury_fees = sub_checked(stats.total_treasury_fees, withheld)?;
+        save_mint_stats(accounts, stats, mint_stats_acc, target_reached)?;
+    }
+    bump_tipper_stats(
+        program_id, config.platform_id, payer, tipper.key, tipper_stats_acc, system_program, amounts.len() as u64,
+        total_amount, config.rate_limit(), false, None,
+    )?;
+
+    msg!("TipMany: {} tips, {} total, {} fee", amounts.len(), total_amount, total_fee);
+    Ok(())
+}
+
+/// SetSplit: [shares: Vec<SplitShare>]
+/// Accounts: [split (w), creator (s,w), system_program, payer (optional, s,w)]
+fn set_split(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    shares: Vec<SplitShare>,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let split_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[split_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let (pda, bump) = get_split_config_pda(program_id, creator.key);
+    if *split_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if shares.is_empty() || shares.len() > MAX_SPLIT_RECIPIENTS {
+        return Err(TipError::InvalidSplit.into());
+    }
+    let mut total_bps: u32 = 0;
+    for (i, share) in shares.iter().enumerate() {
+        if share.share_bps == 0 || shares[..i].iter().any(|s| s.recipient == share.recipient) {
+            return Err(TipError::InvalidSplit.into());
+        }
+        total_bps += share.share_bps as u32;
+    }
+    if total_bps != 10_000 {
+        msg!("Split shares sum to {} bps, expected 10000", total_bps);
+        return Err(TipError::InvalidSplit.into());
+    }
+
+    // Sized to the recipients; a later SetSplit grows or shrinks it
+    let size = split_config_size(shares.len());
+    if split_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            split_acc,
+            system_program,
+            program_id,
+            size,
+            &[b"split", creator.key.as_ref(), &[bump]],
+        )?;
+    } else {
+        validation::program_owned(program_id, split_acc)?;
+        resize_account(split_acc, payer, creator, system_program, size)?;
+    }
+
+    let mut split = SplitConfig {
+        is_initialized: true,
+        creator: *creator.key,
+        recipient_count: shares.len() as u8,
+        shares: [SplitShare::default(); MAX_SPLIT_RECIPIENTS],
+    };
+    split.shares[..shares.len()].copy_from_slice(&shares);
+    pack_prefix(&split, split_acc, SPLIT_CONFIG_SIZE)?;
+
+    msg!("Split set: {} recipients", shares.len());
+    SplitUpdated { creator: *creator.key, recipient_count: shares.len() as u8 }.emit(accounts)
+}
+
+/// SetCollaborators: [shares: Vec<SplitShare>]
+/// Accounts: [collaborators (w), creator (s,w), system_program, payer (optional, s,w)]
+/// Up to MAX_COLLABORATORS `(collaborator wallet, share_bps)` cuts of every `Tip` to the creator, after
+/// the protocol fee; the creator keeps what the shares leave. Empty shares clear the split.
+fn set_collaborators(program_id: &Pubkey, accounts: &[AccountInfo], shares: Vec<SplitShare>) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let collaborators_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[collaborators_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let (pda, bump) = get_collaborator_split_pda(program_id, creator.key);
+    if *collaborators_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if shares.len() > MAX_COLLABORATORS {
+        return Err(TipError::InvalidSplit.into());
+    }
+    let mut total_bps: u32 = 0;
+    for (i, share) in shares.iter().enumerate() {
+        if share.share_bps == 0
+            || share.recipient == *creator.key
+            || shares[..i].iter().any(|s| s.recipient == share.recipient)
+        {
+            return Err(TipError::InvalidSplit.into());
+        }
+        total_bps += share.share_bps as u32;
+    }
+    if total_bps > 10_000 {
+        msg!("Collaborator shares sum to {} bps, above 10000", total_bps);
+        return Err(TipError::InvalidSplit.into());
+    }
+
+    // Sized to the collaborators; a later SetCollaborators grows or shrinks it
+    let size = collaborator_split_size(shares.len());
+    if collaborators_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            collaborators_acc,
+            system_program,
+            program_id,
+            size,
+            &[b"collaborators", creator.key.as_ref(), &[bump]],
+        )?;
+    } else {
+        validation::program_owned(program_id, collaborators_acc)?;
+        resize_account(collaborators_acc, payer, creator, system_program, size)?;
+    }
+
+    let mut split = CollaboratorSplit {
+        is_initialized: true,
+        creator: *creator.key,
+        collaborator_count: shares.len() as u8,
+        collaborators: [SplitShare::default(); MAX_COLLABORATORS],
+    };
+    split.collaborators[..shares.len()].copy_from_slice(&shares);
+    pack_prefix(&split, collaborators_acc, COLLABORATOR_SPLIT_SIZE)?;
+
+    msg!("Collaborators set: {}", shares.len());
+    CollaboratorsUpdated { creator: *creator.key, shares }.emit(accounts)
+}
+
+/// TipSplit: [amount: u64]
+/// Accounts: [config (w), tipper (s,w), tipper_token (w), creator, split, treasury_token (w), mint, token_program,
+///            allowed_mint, creator_stats (w), tipper_stats (w), system_program, blocked_creator, blocked_tipper,
+///            mint_stats (w), then per share: recipient_token (w),
+///            then per treasury split entry: split_token (w), then payer (optional, s,w)]
+/// Each recipient gets floor(net * share_bps / 10000); the rounding dust goes to the largest share.
+/// A tip from the creator or any recipient is a self-tip
+fn tip_split(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let split_acc = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let creator_stats_acc = next_account_info(iter)?;
+    let tipper_stats_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, tipper, tipper_token, treasury_token, creator_stats_acc, tipper_stats_acc])?;
+    validation::token_accounts(&[tipper_token, treasury_token])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+    check_tipper_not_blocked(program_id, config.platform_id, blocked_tipper_acc, tipper.key)?;
+    if amount == 0 {
+        msg!("Tip amount must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+
+    let (split_pda, _) = get_split_config_pda(program_id, creator.key);
+    if *split_acc.key != split_pda || split_acc.owner != program_id {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let split = SplitConfig::load(&split_acc.data.borrow())?;
+    if !split.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let shares = &split.shares[..split.recipient_count as usize];
+    let self_tip = *tipper.key == *creator.key || shares.iter().any(|share| share.recipient == *tipper.key);
+    let policy = config.self_tip_policy as u8;
+    apply_self_tip_policy(policy, accounts, self_tip, tipper.key, creator.key, mint_acc.key, amount)?;
+
+    let mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+    let net = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let allowed = check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &[tipper_token, treasury_token])?;
+    if allowed.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    check_tip_limits(allowed.min_tip(config.min_tip_amount), config.max_tip_amount, amount)?;
+    check_not_vesting(config.vesting_threshold, amount)?;
+    if token::unpack_account(tipper_token)?.is_frozen() {
+        msg!("Source token account is frozen; ask the mint's freeze authority to thaw it");
+        return Err(TipError::SourceAccountFrozen.into());
+    }
+
+    let weights: Vec<u128> = shares.iter().map(|share| share.share_bps as u128).collect();
+    let payouts = split_by_weight(net, &weights)?;
+
+    let mut net_received = 0;
+    for (share, &payout) in shares.iter().zip(&payouts) {
+        let recipient_token = next_account_info(iter)?;
+        let expected = token::get_associated_token_address(&share.recipient, mint_acc.key, token_program.key);
+        if *recipient_token.key != expected {
+            msg!("Recipient token account must be the ATA of {}", share.recipient);
+            return Err(TipError::CreatorTokenMismatch.into());
+        }
+        if payout == 0 {
+            continue;
+        }
+        let delivered = transfer_tokens(
+            accounts,
+            token_program, tipper_token, mint_acc, recipient_token, tipper, payout, mint.decimals, &[], &[],
+        )?;
+        net_received = add_checked(net_received, delivered)?;
+    }
+
+    let fee_received = pay_treasury_fee(
+        accounts,
+        config.treasury_split(), fee, iter, token_program, tipper_token, mint_acc, treasury_token, tipper, mint.decimals,
+        &[], &[],
+    )?;
+
+    let payer = next_rent_payer(program_id, iter, tipper)?;
+    let delivered = add_checked(net_received, fee_received)?;
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, delivered, fee_received)?;
+    let platform_id = config.platform_id;
+    bump_creator_stats(
+        program_id, platform_id, payer, creator, creator_stats_acc, system_program, amount, net_received, 0, 0, 0,
+    )?;
+    bump_tipper_stats(
+        program_id, platform_id, payer, tipper.key, tipper_stats_acc, system_program, 1, amount, config.rate_limit(),
+        false, None,
+    )?;
+
+    msg!("TipSplit: {} to {} recipients, {} fee", net_received, shares.len(), fee_received);
+    emit_tip(accounts, tipper.key, creator.key, mint_acc.key, delivered, fee_received, 0, 0)
+}
+
+/// TipSol: [amount: u64]
+/// Accounts: [config (w), tipper (s,w), creator (w), treasury_token, treasury_wallet (w), system_program,
+///            tipper_stats (w), blocked_creator, blocked_tipper, mint_stats (w), sol_vault (optional, w)]
+/// The SOL fee goes to the wallet that owns the configured treasury token account; treasury splits
+/// only apply to token fees. Passing the creator's SolVault pays their share into it instead of their wallet.
+/// `mint_stats` is the MintStats of native SOL, under the default pubkey.
+/// SOL tips count toward the tipper's rate limit (tips, not volume) but not their stats. A tip from the
+/// creator's own wallet is a self-tip
+fn tip_sol(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let treasury_wallet = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let tipper_stats_acc = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+    let sol_vault_acc = next_optional_account(iter, program_id);
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, tipper, creator, treasury_wallet, tipper_stats_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+
+    if amount == 0 {
+        msg!("Tip amount must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    check_tip_limits(config.min_tip_amount, config.max_tip_amount, amount)?;
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+    check_tipper_not_blocked(program_id, config.platform_id, blocked_tipper_acc, tipper.key)?;
+    let (policy, self_tip) = (config.self_tip_policy as u8, *tipper.key == *creator.key);
+    apply_self_tip_policy(policy, accounts, self_tip, tipper.key, creator.key, &Pubkey::default(), amount)?;
+    // Lamports are not in the unit of the volume cap, so only the tip counts
+    let limit = config.rate_limit();
+    if limit.window_secs > 0 {
+        bump_tipper_stats(
+            program_id, config.platform_id, tipper, tipper.key, tipper_stats_acc, system_program, 1, 0, limit, true,
+            None,
+        )?;
+    }
+
+    let mint_stats = load_mint_stats(program_id, config.platform_id, &Pubkey::default(), mint_stats_acc)?;
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+
+    let recipient = match sol_vault_acc {
+        Some(vault_acc) => {
+            load_sol_vault(program_id, vault_acc, creator)?;
+            vault_acc
+        }
+        None => creator,
+    };
+    invoke(
+        &system_instruction::transfer(tipper.key, recipient.key, creator_amount),
+        &[tipper.clone(), recipient.clone(), system_program.clone()],
+    )?;
+
+    if fee > 0 {
+        if *treasury_token.key != config.treasury {
+            msg!("Treasury mismatch");
+            return Err(TipError::TreasuryMismatch.into());
+        }
+        let treasury_owner = token::unpack_account(treasury_token)?.owner;
+        if *treasury_wallet.key != treasury_owner {
+            msg!("Treasury wallet must own the treasury token account");
+            return Err(TipError::TreasuryWalletMismatch.into());
+        }
+        invoke(
+            &system_instruction::transfer(tipper.key, treasury_wallet.key, fee),
+            &[tipper.clone(), treasury_wallet.clone(), system_program.clone()],
+        )?;
+    }
+
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, amount, fee)?;
+    msg!("SOL tip: {} lamports to creator, {} fee", creator_amount, fee);
+    emit_tip(accounts, tipper.key, creator.key, &Pubkey::default(), amount, fee, 0, 0)
+}
+
+/// ScheduleFeeUpdate, UpdateFee: [new_fee_bps: u16]
+/// Accounts: [config (w), admin, fee_manager or governance_authority (s), instructions_sysvar (if admin_isolation)]
+/// Replaces any earlier schedule; the new rate applies via ApplyFeeUpdate after FEE_UPDATE_DELAY_SLOTS
+fn schedule_fee_update(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_fee: u16,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(admin.key, Role::FeeManager) && !config.has_role(admin.key, Role::Governance) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if new_fee > 1000 {
+        return Err(TipError::FeeTooHigh.into());
+    }
+
+    let activation_slot = Clock::get()?.slot.saturating_add(FEE_UPDATE_DELAY_SLOTS);
+    let old_value = audit_words(&[config.fee_bps as u64]);
+    config.pending_fee_bps = new_fee;
+    config.fee_activation_slot = activation_slot;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::ScheduleFeeUpdate,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[new_fee as u64, activation_slot]),
+    )?;
+
+    msg!("Fee update to {} bps scheduled for slot {}", new_fee, activation_slot);
+    FeeUpdateScheduled { new_fee_bps: new_fee, activation_slot }.emit(accounts)
+}
+
+/// ApplyFeeUpdate: no data
+/// Accounts: [config (w)]
+/// Permissionless once the scheduled slot is reached
+fn apply_fee_update(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.fee_activation_slot == 0 {
+        return Err(TipError::NoPendingFeeUpdate.into());
+    }
+    if Clock::get()?.slot < config.fee_activation_slot {
+        msg!("Fee update activates at slot {}", config.fee_activation_slot);
+        return Err(TipError::FeeUpdateTimelocked.into());
+    }
+
+    let old_fee_bps = config.fee_bps;
+    config.fee_bps = config.pending_fee_bps;
+    config.pending_fee_bps = 0;
+    config.fee_activation_slot = 0;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    FeeUpdatedEvent { old_fee_bps, new_fee_bps: config.fee_bps }.emit(accounts)
+}
+
+/// SetMinTipAmount: [min_tip_amount: u64]
+/// Accounts: [config (w), admin (s,w), allowed_mint (optional, w), system_program,
+///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+/// With an allowed_mint, sets that mint's own minimum in its base units; otherwise the config-wide one
+fn set_min_tip_amount(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_tip_amount: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let allowed_mint_acc = next_optional_account(iter, program_id);
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, admin])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    let (mint, old_value) = match allowed_mint_acc {
+        Some(allowed_mint_acc) => {
+            if allowed_mint_acc.owner != program_id {
+                return Err(ProgramError::IncorrectProgramId);
+            }
+            let mut allowed = AllowedMint::load(&allowed_mint_acc.data.borrow())?;
+            // Legacy entries grow to hold the minimum
+            if allowed_mint_acc.data_len() < ALLOWED_MINT_SIZE {
+                grow_account(allowed_mint_acc, payer, system_program, ALLOWED_MINT_SIZE)?;
+            }
+            let old_value = audit_words(&[allowed.min_tip_amount]);
+            allowed.min_tip_amount = min_tip_amount;
+            allowed.pack(&mut allowed_mint_acc.data.borrow_mut())?;
+            (Some(allowed.mint), old_value)
+        }
+        None => {
+            let old_value = audit_words(&[config.min_tip_amount]);
+            config.min_tip_amount = min_tip_amount;
+            config.pack(&mut config_acc.data.borrow_mut())?;
+            (None, old_value)
+        }
+    };
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetMinTipAmount,
+        admin.key,
+        &mint.unwrap_or_default(),
+        old_value,
+        audit_words(&[min_tip_amount]),
+    )?;
+
+    msg!("Min tip amount: {}", min_tip_amount);
+    MinTipAmountUpdated { mint, min_tip_amount }.emit(accounts)
+}
+
+/// SetPriceFeed: [price_feed: Pubkey]
+/// Accounts: [config, admin (s,w), allowed_mint (w), system_program,
+///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+fn set_price_feed(program_id: &Pubkey, accounts: &[AccountInfo], price_feed: Pubkey) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[admin, allowed_mint_acc])?;
+    validation::program_owned(program_id, allowed_mint_acc)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    let mut allowed = AllowedMint::load(&allowed_mint_acc.data.borrow())?;
+    // Legacy entries grow to hold the feed
+    if allowed_mint_acc.data_len() < ALLOWED_MINT_SIZE {
+        grow_account(allowed_mint_acc, payer, system_program, ALLOWED_MINT_SIZE)?;
+    }
+    let old_value = allowed.price_feed.to_bytes();
+    allowed.price_feed = price_feed;
+    allowed.pack(&mut allowed_mint_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetPriceFeed,
+        admin.key,
+        &allowed.mint,
+        old_value,
+        price_feed.to_bytes(),
+    )?;
+
+    msg!("Price feed for {}: {}", allowed.mint, price_feed);
+    PriceFeedUpdated { mint: allowed.mint, price_feed }.emit(accounts)
+}
+
+/// SetMaxTipAmount: [max_tip_amount: u64]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_max_tip_amount(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_tip_amount: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.max_tip_amount]);
+    config.max_tip_amount = max_tip_amount;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetMaxTipAmount,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[max_tip_amount]),
+    )?;
+
+    msg!("Max tip amount: {}", max_tip_amount);
+    MaxTipAmountUpdated { max_tip_amount }.emit(accounts)
+}
+
+/// SetRateLimit: [window_secs: u64, max_tips: u64, max_volume: u64]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_rate_limit(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    limit: RateLimit,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if limit.window_secs > i64::MAX as u64 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let old_value =
+        audit_words(&[config.rate_limit_window_secs, config.rate_limit_max_tips, config.rate_limit_max_volume]);
+    config.rate_limit_window_secs = limit.window_secs;
+    config.rate_limit_max_tips = limit.max_tips;
+    config.rate_limit_max_volume = limit.max_volume;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetRateLimit,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[limit.window_secs, limit.max_tips, limit.max_volume]),
+    )?;
+
+    msg!("Rate limit: {} tips / {} volume per {}s", limit.max_tips, limit.max_volume, limit.window_secs);
+    RateLimitUpdated { window_secs: limit.window_secs, max_tips: limit.max_tips, max_volume: limit.max_volume }
+        .emit(accounts)
+}
+
+/// SetFeeTiers: [tiers: Vec<FeeTier>]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+/// Tiers are sorted by strictly increasing `min_amount`; empty tiers restore the flat `fee_bps`
+fn set_fee_tiers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tiers: Vec<FeeTier>,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if tiers.len() > MAX_FEE_TIERS || tiers.windows(2).any(|pair| pair[0].min_amount >= pair[1].min_amount) {
+        msg!("Up to {} tiers with strictly increasing min_amount", MAX_FEE_TIERS);
+        return Err(TipError::InvalidFeeTiers.into());
+    }
+    if tiers.iter().any(|tier| tier.fee_bps > 1000) {
+        return Err(TipError::FeeTooHigh.into());
+    }
+
+    let old_value = audit_hash(&config.fee_tiers())?;
+    config.fee_tier_count = tiers.len() as u8;
+    config.fee_tier_min_amounts = [0; MAX_FEE_TIERS];
+    config.fee_tier_bps = [0; MAX_FEE_TIERS];
+    for (i, tier) in tiers.iter().enumerate() {
+        config.fee_tier_min_amounts[i] = tier.min_amount;
+        config.fee_tier_bps[i] = tier.fee_bps;
+    }
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetFeeTiers,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_hash(&tiers)?,
+    )?;
+
+    msg!("Fee tiers set: {}", tiers.len());
+    FeeTiersUpdated { tiers }.emit(accounts)
+}
+
+/// SetFeeOverride: [fee_bps: u16]
+/// Accounts: [fee_override (w), config, admin (s,w), creator, system_program,
+///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+/// Creates the override on first use; `Tip` then charges `fee_bps` for this creator instead of the global rate
+fn set_fee_override(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fee_bps: u16,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let fee_override_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[fee_override_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    if fee_bps > 1000 {
+        return Err(TipError::FeeTooHigh.into());
+    }
+
+    let (pda, bump) = get_fee_override_pda(program_id, config.platform_id, creator.key);
+    if *fee_override_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let old_value = if fee_override_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            fee_override_acc,
+            system_program,
+            program_id,
+            FEE_OVERRIDE_SIZE,
+            &[b"fee_override", &platform_seed(config.platform_id), creator.key.as_ref(), &[bump]],
+        )?;
+        [0; 32]
+    } else {
+        audit_words(&[FeeOverride::unpack(&fee_override_acc.data.borrow())?.fee_bps as u64])
+    };
+
+    let fee_override = FeeOverride { is_initialized: true, creator: *creator.key, fee_bps };
+    fee_override.pack(&mut fee_override_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetFeeOverride,
+        admin.key,
+        creator.key,
+        old_value,
+        audit_words(&[fee_bps as u64]),
+    )?;
+
+    msg!("Fee override for {}: {} bps", creator.key, fee_bps);
+    FeeOverrideUpdated { creator: *creator.key, fee_bps: Some(fee_bps) }.emit(accounts)
+}
+
+/// ClearFeeOverride: no data
+/// Accounts: [fee_override (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+/// Closes the override and returns its rent to the admin
+fn clear_fee_override(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let fee_override_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[fee_override_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if fee_override_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let fee_override = FeeOverride::unpack(&fee_override_acc.data.borrow())?;
+    if *fee_override_acc.key != get_fee_override_pda(program_id, config.platform_id, &fee_override.creator).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    close_pda(fee_override_acc, admin)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::ClearFeeOverride,
+        admin.key,
+        &fee_override.creator,
+        audit_words(&[fee_override.fee_bps as u64]),
+        [0; 32],
+    )?;
+
+    msg!("Fee override cleared for {}", fee_override.creator);
+    FeeOverrideUpdated { creator: fee_override.creator, fee_bps: None }.emit(accounts)
+}
+
+/// BlockCreator: no data
+/// Accounts: [blocked_creator (w), config, admin (s,w), creator, system_program,
+///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+/// Creates the block; `Tip` then refuses to pay the creator until UnblockCreator
+fn block_creator(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let blocked_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[blocked_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    let (pda, bump) = get_blocked_creator_pda(program_id, config.platform_id, creator.key);
+    if *blocked_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !blocked_acc.data_is_empty() {
+        msg!("{} is already blocked", creator.key);
+        return Ok(());
+    }
+    create_pda_account(
+        payer,
+        blocked_acc,
+        system_program,
+        program_id,
+        BLOCKED_CREATOR_SIZE,
+        &[b"blocked_creator", &platform_seed(config.platform_id), creator.key.as_ref(), &[bump]],
+    )?;
+    BlockedCreator { is_initialized: true, creator: *creator.key }.pack(&mut blocked_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::BlockCreator,
+        admin.key,
+        creator.key,
+        audit_words(&[0]),
+        audit_words(&[1]),
+    )?;
+
+    msg!("Blocked creator {}", creator.key);
+    CreatorBlockUpdated { creator: *creator.key, blocked: true }.emit(accounts)
+}
+
+/// UnblockCreator: no data
+/// Accounts: [blocked_creator (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+/// Closes the block and returns its rent to the admin
+fn unblock_creator(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let blocked_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[blocked_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if blocked_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let blocked = BlockedCreator::unpack(&blocked_acc.data.borrow())?;
+    if *blocked_acc.key != get_blocked_creator_pda(program_id, config.platform_id, &blocked.creator).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    close_pda(blocked_acc, admin)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::UnblockCreator,
+        admin.key,
+        &blocked.creator,
+        audit_words(&[1]),
+        audit_words(&[0]),
+    )?;
+
+    msg!("Unblocked creator {}", blocked.creator);
+    CreatorBlockUpdated { creator: blocked.creator, blocked: false }.emit(accounts)
+}
+
+/// BlockTipper: no data
+/// Accounts: [blocked_tipper (w), config, admin (s,w), tipper, system_program,
+///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+/// Creates the block; `Tip` then refuses the wallet as tipper until UnblockTipper
+fn block_tipper(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let blocked_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[blocked_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    let (pda, bump) = get_blocked_tipper_pda(program_id, config.platform_id, tipper.key);
+    if *blocked_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !blocked_acc.data_is_empty() {
+        msg!("{} is already blocked", tipper.key);
+        return Ok(());
+    }
+    create_pda_account(
+        payer,
+        blocked_acc,
+        system_program,
+        program_id,
+        BLOCKED_TIPPER_SIZE,
+        &[b"blocked_tipper", &platform_seed(config.platform_id), tipper.key.as_ref(), &[bump]],
+    )?;
+    BlockedTipper { is_initialized: true, tipper: *tipper.key }.pack(&mut blocked_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::BlockTipper,
+        admin.key,
+        tipper.key,
+        audit_words(&[0]),
+        audit_words(&[1]),
+    )?;
+
+    msg!("Blocked tipper {}", tipper.key);
+    TipperBlockUpdated { tipper: *tipper.key, blocked: true }.emit(accounts)
+}
+
+/// UnblockTipper: no data
+/// Accounts: [blocked_tipper (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+/// Closes the block and returns its rent to the admin
+fn unblock_tipper(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let blocked_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[blocked_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if blocked_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let blocked = BlockedTipper::unpack(&blocked_acc.data.borrow())?;
+    if *blocked_acc.key != get_blocked_tipper_pda(program_id, config.platform_id, &blocked.tipper).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    close_pda(blocked_acc, admin)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::UnblockTipper,
+        admin.key,
+        &blocked.tipper,
+        audit_words(&[1]),
+        audit_words(&[0]),
+    )?;
+
+    msg!("Unblocked tipper {}", blocked.tipper);
+    TipperBlockUpdated { tipper: blocked.tipper, blocked: false }.emit(accounts)
+}
+
+/// SetFeeTarget: [fee_target: u64]
+/// Accounts: [config, admin (s,w), mint_stats (w), mint, system_program, instructions_sysvar (if admin_isolation),
+///            payer (optional, s,w)]
+/// Sets the fee target of one mint (the default pubkey for SOL) in its MintStats, creating them or
+/// growing a legacy account; fees already counted there count toward it
+fn set_fee_target(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fee_target: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    let mint = mint_acc.key;
+    let stats = load_mint_stats(program_id, config.platform_id, mint, mint_stats_acc)?;
+    let mut stats = open_mint_stats(program_id, config.platform_id, payer, mint, mint_stats_acc, system_program, stats)?;
+    let old_value = audit_words(&[stats.fee_target]);
+    stats.fee_target = fee_target;
+    stats.pack(&mut mint_stats_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetFeeTarget,
+        admin.key,
+        mint,
+        old_value,
+        audit_words(&[fee_target]),
+    )?;
+    FeeTargetUpdated { fee_target, mint: *mint }.emit(accounts)
+}
+
+/// SetReferralBps: [referral_bps: u16]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_referral_bps(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    referral_bps: u16,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if referral_bps > 10_000 {
+        msg!("Referral share cannot exceed 100% of the fee");
+        return Err(TipError::FeeTooHigh.into());
+    }
+
+    let old_value = audit_words(&[config.referral_bps as u64]);
+    config.referral_bps = referral_bps;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetReferralBps,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[referral_bps as u64]),
+    )?;
+    msg!("Referral share updated to {} bps of the fee", referral_bps);
+    ReferralBpsUpdated { referral_bps }.emit(accounts)
+}
+
+/// AllowMint: no data
+/// Accounts: [allowed_mint (w), config, admin (s,w), mint, system_program, instructions_sysvar (if admin_isolation),
+///            payer (optional, s,w)]
+fn allow_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let allowed_mint_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let mint = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[allowed_mint_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    if token::unpack_mint(mint).is_err() {
+        msg!("Not an SPL Token or Token-2022 mint");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let (allowed_pda, bump) = get_allowed_mint_pda(program_id, config.platform_id, mint.key);
+    if *allowed_mint_acc.key != allowed_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !allowed_mint_acc.data_is_empty() {
+        return Ok(());
+    }
+
+    create_pda_account(
+        payer,
+        allowed_mint_acc,
+        system_program,
+        program_id,
+        ALLOWED_MINT_SIZE,
+        &[b"allowed_mint", &platform_seed(config.platform_id), mint.key.as_ref(), &[bump]],
+    )?;
+    let allowed = AllowedMint {
+        is_initialized: true,
+        mint: *mint.key,
+        min_tip_amount: 0,
+        price_feed: Pubkey::default(),
+    };
+    allowed.pack(&mut allowed_mint_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::AllowMint,
+        admin.key,
+        mint.key,
+        audit_words(&[0]),
+        audit_words(&[1]),
+    )?;
+
+    msg!("Mint allowed: {}", mint.key);
+    MintAllowlistUpdated { mint: *mint.key, allowed: true }.emit(accounts)
+}
+
+/// DisallowMint: no data
+/// Accounts: [allowed_mint (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+/// Closes the entry and returns its rent to the admin
+fn disallow_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let allowed_mint_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[allowed_mint_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if allowed_mint_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let allowed = AllowedMint::load(&allowed_mint_acc.data.borrow())?;
+    let (allowed_pda, _) = get_allowed_mint_pda(program_id, config.platform_id, &allowed.mint);
+    if *allowed_mint_acc.key != allowed_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    close_pda(allowed_mint_acc, admin)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::DisallowMint,
+        admin.key,
+        &allowed.mint,
+        audit_words(&[1]),
+        audit_words(&[0]),
+    )?;
+
+    msg!("Mint disallowed: {}", allowed.mint);
+    MintAllowlistUpdated { mint: allowed.mint, allowed: false }.emit(accounts)
+}
+
+/// Pause / Unpause: no data
+/// Accounts: [config (w), admin or pauser (s), instructions_sysvar (if admin_isolation)]
+fn set_paused(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    paused: bool,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(admin.key, Role::Pauser) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.paused as u64]);
+    config.paused = paused;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetPaused,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[paused as u64]),
+    )?;
+
+    msg!("Paused: {}", paused);
+    PausedUpdated { paused }.emit(accounts)
+}
+
+/// UpdateTreasury: no data
+/// Accounts: [config (w), admin, treasury_manager or governance_authority (s), new_treasury, allowed_mint,
+///            instructions_sysvar (if admin_isolation)]
+fn update_treasury(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let new_treasury = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(admin.key, Role::TreasuryManager) && !config.has_role(admin.key, Role::Governance) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    validate_treasury(new_treasury)?;
+    check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &[new_treasury])?;
+
+    let old_value = config.treasury.to_bytes();
+    config.treasury = *new_treasury.key;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::UpdateTreasury,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        new_treasury.key.to_bytes(),
+    )?;
+
+    msg!("Treasury updated: {}", new_treasury.key);
+    TreasuryUpdated { treasury: *new_treasury.key }.emit(accounts)
+}
+
+/// OpenTreasuryVault: no data
+/// Accounts: [config (w), admin or treasury_manager (s,w), treasury_vault (w), mint, token_program,
+///            allowed_mint, system_program, instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+/// Fees then accrue to a token account the config PDA owns, instead of an external one
+fn open_treasury_vault(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, admin, vault])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(admin.key, Role::TreasuryManager) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    check_token_program(mint_acc, token_program)?;
+    validation::program_owned(program_id, allowed_mint_acc)?;
+    let allowed = AllowedMint::load(&allowed_mint_acc.data.borrow())?;
+    if !allowed.is_initialized || allowed.mint != *mint_acc.key {
+        msg!("Mint {} is not allowed", mint_acc.key);
+        return Err(TipError::MintNotAllowed.into());
+    }
+    let (pda, bump) = get_treasury_vault_pda(program_id, config.platform_id, mint_acc.key);
+    if *vault.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !vault.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let (config_pda, _) = get_config_pda(program_id, config.platform_id);
+    create_vault(
+        payer,
+        vault,
+        mint_acc,
+        token_program,
+        system_program,
+        &config_pda,
+        &[b"treasury_vault", &platform_seed(config.platform_id), mint_acc.key.as_ref(), &[bump]],
+    )?;
+
+    let old_value = config.treasury.to_bytes();
+    config.treasury = pda;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::OpenTreasuryVault,
+        admin.key,
+        mint_acc.key,
+        old_value,
+        pda.to_bytes(),
+    )?;
+
+    msg!("Treasury vault opened: {}", pda);
+    TreasuryUpdated { treasury: pda }.emit(accounts)
+}
+
+/// WithdrawFees: [amount: u64]
+/// Accounts: [config, admin or treasury_manager (s), treasury_vault (w), destination (w), mint,
+///            token_program, instructions_sysvar (if admin_isolation)]
+fn withdraw_fees(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let destination = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[vault, destination])?;
+    validation::token_accounts(&[vault, destination])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(admin.key, Role::TreasuryManager) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if *vault.key != get_treasury_vault_pda(program_id, config.platform_id, mint_acc.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if amount == 0 {
+        msg!("Withdrawal must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    let mint = check_token_program(mint_acc, token_program)?;
+    let balance = token::unpack_account(vault)?.amount;
+    if amount > balance {
+        msg!("Treasury vault holds {}", balance);
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    transfer_tokens(
+        accounts,
+        token_program,
+        vault,
+        mint_acc,
+        destination,
+        config_acc,
+        amount,
+        mint.decimals,
+        &[&[b"config", &platform_seed(config.platform_id), &[config.bump]]],
+        &[],
+    )?;
+
+    let remaining = sub_checked(balance, amount)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::WithdrawFees,
+        admin.key,
+        destination.key,
+        audit_words(&[balance]),
+        audit_words(&[remaining]),
+    )?;
+    check_treasury_watermark(accounts, &config, vault.key, remaining)?;
+    msg!("Withdrew {} in fees, {} left", amount, remaining);
+    FeesWithdrawn { treasury_vault: *vault.key, destination: *destination.key, amount, remaining }.emit(accounts)
+}
+
+/// DistributeFees: [shares: Vec<SplitShare>]
+/// Accounts: [config, admin or treasury_manager (s), treasury_vault (w), mint, token_program,
+///            then per share: destination_token (w), then instructions_sysvar (if admin_isolation)]
+/// Rounding dust goes to the largest share
+fn distribute_fees(program_id: &Pubkey, accounts: &[AccountInfo], shares: Vec<SplitShare>) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let destinations =
+        shares.iter().map(|_| next_account_info(iter)).collect::<Result<Vec<_>, _>>()?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[vault])?;
+    validation::writable(&destinations)?;
+    validation::token_accounts(&[vault])?;
+    validation::token_accounts(&destinations)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(admin.key, Role::TreasuryManager) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if *vault.key != get_treasury_vault_pda(program_id, config.platform_id, mint_acc.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if shares.is_empty() || shares.len() > MAX_SPLIT_RECIPIENTS {
+        return Err(TipError::InvalidSplit.into());
+    }
+    let mut total_bps: u32 = 0;
+    for (i, (share, destination)) in shares.iter().zip(&destinations).enumerate() {
+        if share.share_bps == 0 || shares[..i].iter().any(|s| s.recipient == share.recipient) {
+            return Err(TipError::InvalidSplit.into());
+        }
+        if *destination.key != share.recipient {
+            msg!("Destination {} is not share {}'s recipient", destination.key, i);
+            return Err(TipError::InvalidSplit.into());
+        }
+        if token::unpack_account(destination)?.mint != *mint_acc.key {
+            return Err(TipError::MintMismatch.into());
+        }
+        total_bps += share.share_bps as u32;
+    }
+    if total_bps != 10_000 {
+        msg!("Shares sum to {} bps, expected 10000", total_bps);
+        return Err(TipError::InvalidSplit.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let balance = token::unpack_account(vault)?.amount;
+    if balance == 0 {
+        msg!("Treasury vault is empty");
+        return Err(TipError::ZeroAmount.into());
+    }
+    let weights: Vec<u128> = shares.iter().map(|share| share.share_bps as u128).collect();
+    let parts = split_by_weight(balance, &weights)?;
+    let signer_seeds: &[&[u8]] = &[b"config", &platform_seed(config.platform_id), &[config.bump]];
+    for (destination, &amount) in destinations.iter().zip(&parts) {
+        if amount == 0 {
+            continue;
+        }
+        transfer_tokens(
+            accounts,
+            token_program,
+            vault,
+            mint_acc,
+            destination,
+            config_acc,
+            amount,
+            mint.decimals,
+            &[signer_seeds],
+            &[],
+        )?;
+        FeesDistributed { treasury_vault: *vault.key, destination: *destination.key, amount }.emit(accounts)?;
+    }
+
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::DistributeFees,
+        admin.key,
+        vault.key,
+        audit_words(&[balance]),
+        audit_words(&[0]),
+    )?;
+    check_treasury_watermark(accounts, &config, vault.key, 0)?;
+    msg!("Distributed {} in fees to {} recipients", balance, shares.len());
+    Ok(())
+}
+
+/// SetTreasuryLowWatermark: [watermark: u64]
+/// Accounts: [config (w), admin or treasury_manager (s), instructions_sysvar (if admin_isolation)]
+fn set_treasury_low_watermark(program_id: &Pubkey, accounts: &[AccountInfo], watermark: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(admin.key, Role::TreasuryManager) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.treasury_low_watermark]);
+    config.treasury_low_watermark = watermark;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetTreasuryLowWatermark,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[watermark]),
+    )?;
+    TreasuryLowWatermarkUpdated { watermark }.emit(accounts)
+}
+
+/// Emit TreasuryLow when a payout has left the treasury vault with `balance`, below the watermark
+fn check_treasury_watermark(
+    accounts: &[AccountInfo],
+    config: &TipConfig,
+    treasury_vault: &Pubkey,
+    balance: u64,
+) -> ProgramResult {
+    if balance >= config.treasury_low_watermark {
+        return Ok(());
+    }
+    msg!("Treasury vault {} is down to {}", treasury_vault, balance);
+    TreasuryLow { treasury_vault: *treasury_vault, balance }.emit(accounts)
+}
+
+/// Check that `vault` is the `kind` vault whose token-account owner is `owner`, and return the seeds
+/// `owner` signs with
+fn emergency_vault_seeds(
+    program_id: &Pubkey,
+    kind: EmergencyVault,
+    config: &TipConfig,
+    owner: &AccountInfo,
+    vault: &AccountInfo,
+) -> Result<Vec<Vec<u8>>, ProgramError> {
+    let (expected_vault, seeds) = match kind {
+        EmergencyVault::Treasury => {
+            check_config_address(program_id, owner, config.platform_id, config.bump)?;
+            let mint = token::unpack_account(vault)?.mint;
+            let seeds = vec![b"config".to_vec(), platform_seed(config.platform_id), vec![config.bump]];
+            (get_treasury_vault_pda(program_id, config.platform_id, &mint).0, seeds)
+        }
+        EmergencyVault::Escrow => {
+            check_shared_authority(config)?;
+            validation::program_owned(program_id, owner)?;
+            let escrow = Escrow::unpack(&owner.data.borrow())?;
+            if !escrow.is_initialized {
+                return Err(TipError::Uninitialized.into());
+            }
+            let (pda, bump) = get_escrow_pda(program_id, config.platform_id, &escrow.tipper, &escrow.creator);
+            if *owner.key != pda {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let platform = platform_seed(config.platform_id);
+            let seeds = [b"escrow".as_slice(), &platform, escrow.tipper.as_ref(), escrow.creator.as_ref(), &[bump]];
+            (get_escrow_vault_pda(program_id, owner.key).0, seeds.map(<[u8]>::to_vec).to_vec())
+        }
+        EmergencyVault::MatchingPool => {
+            check_shared_authority(config)?;
+            validation::program_owned(program_id, owner)?;
+            let pool = MatchingPool::unpack(&owner.data.borrow())?;
+            if !pool.is_initialized {
+                return Err(TipError::Uninitialized.into());
+            }
+            let (pda, bump) = get_matching_pool_pda(program_id, &pool.sponsor, pool.pool_id);
+            if *owner.key != pda {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            let seeds = [b"pool".as_slice(), pool.sponsor.as_ref(), &pool.pool_id.to_le_bytes(), &[bump]];
+            (get_pool_vault_pda(program_id, owner.key).0, seeds.map(<[u8]>::to_vec).to_vec())
+        }
+    };
+    if *vault.key != expected_vault {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(seeds)
+}
+
+/// ScheduleEmergencyWithdraw: [kind: EmergencyVault, amount: u64]
+/// Accounts: [config, admin (s,w), withdrawal (w), vault_owner, vault, destination, system_program,
+///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+/// Admin-only; one pending withdrawal per vault, executable after EMERGENCY_WITHDRAW_DELAY_SLOTS
+fn schedule_emergency_withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    kind: EmergencyVault,
+    amount: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let withdrawal_acc = next_account_info(iter)?;
+    let owner = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let destination = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[admin, withdrawal_acc])?;
+    validation::token_accounts(&[vault, destination])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    if amount == 0 {
+        msg!("Withdrawal must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    emergency_vault_seeds(program_id, kind, &config, owner, vault)?;
+    if token::unpack_account(destination)?.mint != token::unpack_account(vault)?.mint {
+        return Err(TipError::MintMismatch.into());
+    }
+
+    let (pda, bump) = get_emergency_withdrawal_pda(program_id, config.platform_id, vault.key);
+    if *withdrawal_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !withdrawal_acc.data_is_empty() {
+        msg!("A withdrawal from this vault is already scheduled");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    create_pda_account(
+        payer,
+        withdrawal_acc,
+        system_program,
+        program_id,
+        EMERGENCY_WITHDRAWAL_SIZE,
+        &[b"emergency", &platform_seed(config.platform_id), vault.key.as_ref(), &[bump]],
+    )?;
+
+    let executable_slot = Clock::get()?.slot.saturating_add(EMERGENCY_WITHDRAW_DELAY_SLOTS);
+    let withdrawal = EmergencyWithdrawal {
+        is_initialized: true,
+        kind,
+        vault: *vault.key,
+        destination: *destination.key,
+        amount,
+        executable_slot,
+    };
+    withdrawal.pack(&mut withdrawal_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::ScheduleEmergencyWithdraw,
+        admin.key,
+        vault.key,
+        [0; 32],
+        audit_words(&[amount, executable_slot]),
+    )?;
+
+    msg!("Emergency withdrawal of {} from {} executable at slot {}", amount, vault.key, executable_slot);
+    EmergencyWithdrawScheduled { kind, vault: *vault.key, destination: *destination.key, amount, executable_slot }
+        .emit(accounts)
+}
+
+/// ExecuteEmergencyWithdraw: no data
+/// Accounts: [config, admin (s,w), withdrawal (w), vault_owner (w), vault (w), destination (w), mint, token_program,
+///            instructions_sysvar (if admin_isolation)]
+/// Admin-only, once the timelock has passed; an escrow's recorded amount shrinks by what was taken
+fn execute_emergency_withdraw(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let withdrawal_acc = next_account_info(iter)?;
+    let owner = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let destination = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[admin, withdrawal_acc, vault, destination])?;
+    validation::program_owned(program_id, withdrawal_acc)?;
+    validation::token_accounts(&[vault, destination])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let withdrawal = EmergencyWithdrawal::unpack(&withdrawal_acc.data.borrow())?;
+    if !withdrawal.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if *withdrawal_acc.key != get_emergency_withdrawal_pda(program_id, config.platform_id, &withdrawal.vault).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if withdrawal.vault != *vault.key || withdrawal.destination != *destination.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if Clock::get()?.slot < withdrawal.executable_slot {
+        msg!("Emergency withdrawal executable at slot {}", withdrawal.executable_slot);
+        return Err(TipError::EmergencyWithdrawTimelocked.into());
+    }
+
+    let seeds = emergency_vault_seeds(program_id, withdrawal.kind, &config, owner, vault)?;
+    let signer_seeds: Vec<&[u8]> = seeds.iter().map(Vec::as_slice).collect();
+    let mint = check_token_program(mint_acc, token_program)?;
+    let balance = token::unpack_account(vault)?.amount;
+    if withdrawal.amount > balance {
+        msg!("Vault holds {}", balance);
+        return Err(ProgramError::InsufficientFunds);
+    }
+    transfer_tokens(
+        accounts,
+        token_program, vault, mint_acc, destination, owner, withdrawal.amount, mint.decimals, &[&signer_seeds], &[],
+    )?;
+
+    if withdrawal.kind == EmergencyVault::Treasury {
+        check_treasury_watermark(accounts, &config, vault.key, sub_checked(balance, withdrawal.amount)?)?;
+    }
+    if withdrawal.kind == EmergencyVault::Escrow {
+        validation::writable(&[owner])?;
+        let mut escrow = Escrow::unpack(&owner.data.borrow())?;
+        escrow.amount = escrow.amount.saturating_sub(withdrawal.amount);
+        escrow.pack(&mut owner.data.borrow_mut())?;
+    }
+    close_pda(withdrawal_acc, admin)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::ExecuteEmergencyWithdraw,
+        admin.key,
+        vault.key,
+        audit_words(&[balance]),
+        audit_words(&[sub_checked(balance, withdrawal.amount)?]),
+    )?;
+
+    msg!("Emergency withdrawal of {} from {}", withdrawal.amount, vault.key);
+    EmergencyWithdrawExecuted { vault: *vault.key, destination: *destination.key, amount: withdrawal.amount }
+        .emit(accounts)
+}
+
+/// CancelEmergencyWithdraw: no data
+/// Accounts: [config, admin (s,w), withdrawal (w), instructions_sysvar (if admin_isolation)]
+fn cancel_emergency_withdraw(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let withdrawal_acc = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[admin, withdrawal_acc])?;
+    validation::program_owned(program_id, withdrawal_acc)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let withdrawal = EmergencyWithdrawal::unpack(&withdrawal_acc.data.borrow())?;
+    if !withdrawal.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if *withdrawal_acc.key != get_emergency_withdrawal_pda(program_id, config.platform_id, &withdrawal.vault).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    close_pda(withdrawal_acc, admin)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::CancelEmergencyWithdraw,
+        admin.key,
+        &withdrawal.vault,
+        audit_words(&[withdrawal.amount, withdrawal.executable_slot]),
+        [0; 32],
+    )?;
+
+    msg!("Emergency withdrawal from {} cancelled", withdrawal.vault);
+    EmergencyWithdrawCancelled { vault: withdrawal.vault }.emit(accounts)
+}
+
+/// SetRebate: [rebate_threshold: u64, rebate_bps: u16]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_rebate(program_id: &Pubkey, accounts: &[AccountInfo], rebate_threshold: u64, rebate_bps: u16) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if rebate_bps > 10_000 {
+        msg!("Rebate cannot exceed 100% of the fee");
+        return Err(TipError::FeeTooHigh.into());
+    }
+
+    let old_value = audit_words(&[config.rebate_threshold, config.rebate_bps as u64]);
+    config.rebate_threshold = rebate_threshold;
+    config.rebate_bps = rebate_bps;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetRebate,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[rebate_threshold, rebate_bps as u64]),
+    )?;
+    msg!("Rebate: {} bps of the fee above {} per period", rebate_bps, rebate_threshold);
+    RebateUpdated { rebate_threshold, rebate_bps }.emit(accounts)
+}
+
+/// ClaimRebate: no data
+/// Accounts: [config, tipper (s), rebate (w), treasury_vault (w), tipper_token (w), mint, token_program]
+/// Pays the tipper's whole accrued rebate out of the mint's treasury vault
+fn claim_rebate(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let rebate_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[rebate_acc, vault, tipper_token])?;
+    validation::token_accounts(&[vault, tipper_token])?;
+    validation::program_owned(program_id, rebate_acc)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+
+    let mut rebate = TipperRebate::unpack(&rebate_acc.data.borrow())?;
+    if !rebate.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if rebate.tipper != *tipper.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if rebate.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    if *rebate_acc.key != get_tipper_rebate_pda(program_id, config.platform_id, tipper.key, mint_acc.key).0
+        || *vault.key != get_treasury_vault_pda(program_id, config.platform_id, mint_acc.key).0
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if rebate.accrued == 0 {
+        msg!("No rebate to claim");
+        return Err(TipError::ZeroAmount.into());
+    }
+    let mint = check_token_program(mint_acc, token_program)?;
+    let amount = rebate.accrued;
+    transfer_tokens(
+        accounts,
+        token_program,
+        vault,
+        mint_acc,
+        tipper_token,
+        config_acc,
+        amount,
+        mint.decimals,
+        &[&[b"config", &platform_seed(config.platform_id), &[config.bump]]],
+        &[],
+    )?;
+
+    rebate.accrued = 0;
+    rebate.total_claimed = add_checked(rebate.total_claimed, amount)?;
+    rebate.pack(&mut rebate_acc.data.borrow_mut())?;
+    check_treasury_watermark(accounts, &config, vault.key, token::unpack_account(vault)?.amount)?;
+
+    msg!("Rebate of {} claimed", amount);
+    RebateClaimed { tipper: *tipper.key, mint: *mint_acc.key, amount }.emit(accounts)
+}
+
+/// Stake: [amount: u64]
+/// Accounts: [config, supporter (s,w), supporter_token (w), creator, stake (w), stake_vault (w), mint,
+///            token_program, allowed_mint, creator_stats (w), system_program, payer (optional, s,w)]
+/// Locks tokens behind a creator in their stake vault; the first stake in a mint opens the vault
+fn stake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let supporter = next_account_info(iter)?;
+    let supporter_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let stake_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let creator_stats_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !supporter.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[supporter, supporter_token, stake_acc, vault, creator_stats_acc])?;
+    validation::token_accounts(&[supporter_token])?;
+    let payer = next_rent_payer(program_id, iter, supporter)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    if amount == 0 {
+        msg!("Stake must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    let mint = check_token_program(mint_acc, token_program)?;
+    if check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &[supporter_token])?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+
+    let platform = platform_seed(config.platform_id);
+    let (pda, bump) = get_stake_position_pda(program_id, config.platform_id, supporter.key, creator.key, mint_acc.key);
+    if *stake_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let existing = if stake_acc.owner == program_id && !stake_acc.data_is_empty() {
+        Some(StakePosition::unpack(&stake_acc.data.borrow())?).filter(|position| position.is_initialized)
+    } else {
+        None
+    };
+    let mut position = match existing {
+        Some(position) => position,
+        None => {
+            create_pda_account(
+                payer,
+                stake_acc,
+                system_program,
+                program_id,
+                STAKE_POSITION_SIZE,
+                &[b"stake", &platform, supporter.key.as_ref(), creator.key.as_ref(), mint_acc.key.as_ref(), &[bump]],
+            )?;
+            StakePosition {
+                is_initialized: true,
+                supporter: *supporter.key,
+                creator: *creator.key,
+                mint: *mint_acc.key,
+                staked: 0,
+                unbonding: 0,
+                unbonding_ends_at: 0,
+            }
+        }
+    };
+
+    let (vault_pda, vault_bump) = get_stake_vault_pda(program_id, config.platform_id, creator.key, mint_acc.key);
+    if *vault.key != vault_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if vault.data_is_empty() {
+        let (config_pda, _) = get_config_pda(program_id, config.platform_id);
+        create_vault(
+            payer,
+            vault,
+            mint_acc,
+            token_program,
+            system_program,
+            &config_pda,
+            &[b"stake_vault", &platform, creator.key.as_ref(), mint_acc.key.as_ref(), &[vault_bump]],
+        )?;
+    }
+
+    let received =
+        transfer_tokens(
+            accounts, token_program, supporter_token, mint_acc, vault, supporter, amount, mint.decimals, &[], &[],
+        )?;
+    position.staked = add_checked(position.staked, received)?;
+    position.pack(&mut stake_acc.data.borrow_mut())?;
+
+    let mut stats =
+        load_or_create_creator_stats(program_id, config.platform_id, payer, creator, creator_stats_acc, system_program)?;
+    stats.total_staked = add_checked(stats.total_staked, received)?;
+    save_creator_stats(&stats, creator_stats_acc, payer, system_program)?;
+
+    msg!("Staked {} behind {}", received, creator.key);
+    Staked {
+        supporter: *supporter.key,
+        creator: *creator.key,
+        mint: *mint_acc.key,
+        amount: received,
+        staked: position.staked,
+    }
+    .emit(accounts)
+}
+
+/// Unstake: [amount: u64]
+/// Accounts: [supporter (s), stake (w), creator_stats (w), (config)]
+/// Starts `amount` unbonding; it stops counting toward the creator and badges right away and can
+/// be withdrawn STAKE_UNBONDING_SECS later. Allowed while paused so stakes are never trapped.
+fn unstake(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let supporter = next_account_info(iter)?;
+    let stake_acc = next_account_info(iter)?;
+    let creator_stats_acc = next_account_info(iter)?;
+
+    if !supporter.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[stake_acc, creator_stats_acc])?;
+    validation::program_owned(program_id, stake_acc)?;
+    let platform_id = optional_platform(program_id, iter)?;
+
+    let mut position = StakePosition::unpack(&stake_acc.data.borrow())?;
+    if !position.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if position.supporter != *supporter.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    let (position_pda, _) =
+        get_stake_position_pda(program_id, platform_id, supporter.key, &position.creator, &position.mint);
+    if *stake_acc.key != position_pda
+        || *creator_stats_acc.key != get_creator_stats_pda(program_id, platform_id, &position.creator).0
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if amount == 0 {
+        msg!("Unstake must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if amount > position.staked {
+        msg!("Only {} is staked", position.staked);
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    let unbonding_ends_at = Clock::get()?.unix_timestamp.saturating_add(STAKE_UNBONDING_SECS);
+    position.staked = sub_checked(position.staked, amount)?;
+    position.unbonding = add_checked(position.unbonding, amount)?;
+    position.unbonding_ends_at = unbonding_ends_at;
+    position.pack(&mut stake_acc.data.borrow_mut())?;
+
+    // Stake always saves the stats at the current layout, so a shorter account never counted any
+    if creator_stats_acc.owner == program_id && creator_stats_acc.data_len() >= CREATOR_STATS_SIZE {
+        let mut stats = CreatorStats::load(&creator_stats_acc.data.borrow())?;
+        stats.total_staked = stats.total_staked.saturating_sub(amount);
+        stats.pack(&mut creator_stats_acc.data.borrow_mut())?;
+    }
+
+    msg!("Unstaked {}, withdrawable at {}", amount, unbonding_ends_at);
+    Unstaked {
+        supporter: *supporter.key,
+        creator: position.creator,
+        mint: position.mint,
+        amount,
+        unbonding_ends_at,
+    }
+    .emit(accounts)
+}
+
+/// WithdrawStake: no data
+/// Accounts: [config, supporter (s,w), stake (w), stake_vault (w), supporter_token (w), mint, token_program]
+/// Pays out everything that has finished unbonding, closing the position once nothing is left in it
+fn withdraw_stake(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let supporter = next_account_info(iter)?;
+    let stake_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let supporter_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    if !supporter.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[supporter, stake_acc, vault, supporter_token])?;
+    validation::token_accounts(&[vault, supporter_token])?;
+    validation::program_owned(program_id, stake_acc)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+
+    let mut position = StakePosition::unpack(&stake_acc.data.borrow())?;
+    if !position.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if position.supporter != *supporter.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if position.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    let (position_pda, _) =
+        get_stake_position_pda(program_id, config.platform_id, supporter.key, &position.creator, &position.mint);
+    if *stake_acc.key != position_pda
+        || *vault.key != get_stake_vault_pda(program_id, config.platform_id, &position.creator, mint_acc.key).0
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if position.unbonding == 0 {
+        msg!("Nothing is unbonding");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if Clock::get()?.unix_timestamp < position.unbonding_ends_at {
+        msg!("Unbonding ends at {}", position.unbonding_ends_at);
+        return Err(TipError::StillUnbonding.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let amount = position.unbonding;
+    transfer_tokens(
+        accounts,
+        token_program,
+        vault,
+        mint_acc,
+        supporter_token,
+        config_acc,
+        amount,
+        mint.decimals,
+        &[&[b"config", &platform_seed(config.platform_id), &[config.bump]]],
+        &[],
+    )?;
+
+    position.unbonding = 0;
+    if position.staked == 0 {
+        close_pda(stake_acc, supporter)?;
+    } else {
+        position.pack(&mut stake_acc.data.borrow_mut())?;
+    }
+
+    msg!("Withdrew {} of stake", amount);
+    StakeWithdrawn { supporter: *supporter.key, creator: position.creator, mint: *mint_acc.key, amount }.emit(accounts)
+}
+
+/// SetTreasurySplit: [shares: Vec<SplitShare>]
+/// Accounts: [config (w), admin or treasury_manager (s), treasury, allowed_mint,
+///            then per share: recipient_token, instructions_sysvar (if admin_isolation)]
+/// Each share takes its bps of every treasury fee; the treasury keeps the rest. Empty shares clear the split.
+fn set_treasury_split(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    shares: Vec<SplitShare>,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let treasury = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(admin.key, Role::TreasuryManager) {
+        return Err(TipError::Unauthorized.into());
+    }
+
+    if shares.len() > MAX_TREASURY_SPLITS {
+        return Err(TipError::InvalidSplit.into());
+    }
+    let mut total_bps: u32 = 0;
+    for (i, share) in shares.iter().enumerate() {
+        if share.share_bps == 0
+            || share.recipient == config.treasury
+            || shares[..i].iter().any(|s| s.recipient == share.recipient)
+        {
+            return Err(TipError::InvalidSplit.into());
+        }
+        total_bps += share.share_bps as u32;
+    }
+    if total_bps > 10_000 {
+        msg!("Treasury split shares sum to {} bps, max 10000", total_bps);
+        return Err(TipError::InvalidSplit.into());
+    }
+
+    // Recipients must be live token accounts in the treasury's mint
+    if *treasury.key != config.treasury {
+        return Err(TipError::TreasuryMismatch.into());
+    }
+    let mut token_accounts = vec![treasury];
+    for share in &shares {
+        let recipient_token = next_account_info(iter)?;
+        if *recipient_token.key != share.recipient {
+            return Err(TipError::InvalidSplit.into());
+        }
+        validate_treasury(recipient_token)?;
+        token_accounts.push(recipient_token);
+    }
+    check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &token_accounts)?;
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_hash(&config.split_shares())?;
+    config.treasury_split_count = shares.len() as u8;
+    config.treasury_split_recipients = [Pubkey::default(); MAX_TREASURY_SPLITS];
+    config.treasury_split_bps = [0; MAX_TREASURY_SPLITS];
+    for (i, share) in shares.iter().enumerate() {
+        config.treasury_split_recipients[i] = share.recipient;
+        config.treasury_split_bps[i] = share.share_bps;
+    }
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetTreasurySplit,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_hash(&shares)?,
+    )?;
+
+    msg!("Treasury split set: {} recipients, {} bps", shares.len(), total_bps);
+    TreasurySplitUpdated { shares }.emit(accounts)
+}
+
+/// ProposeAdmin: no data
+/// Accounts: [config (w), admin (s), new_admin, instructions_sysvar (if admin_isolation)]
+fn propose_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let new_admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = config.pending_admin.to_bytes();
+    config.pending_admin = *new_admin.key;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::ProposeAdmin,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        new_admin.key.to_bytes(),
+    )?;
+
+    msg!("Admin proposed: {}", new_admin.key);
+    AdminProposed { pending_admin: *new_admin.key }.emit(accounts)
+}
+
+/// AcceptAdmin: no data
+/// Accounts: [config (w), new_admin (s)]
+fn accept_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let new_admin = next_account_info(iter)?;
+
+    if !new_admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.pending_admin == Pubkey::default() || config.pending_admin != *new_admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+
+    let old_admin = config.admin;
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::AcceptAdmin,
+        new_admin.key,
+        &Pubkey::default(),
+        old_admin.to_bytes(),
+        new_admin.key.to_bytes(),
+    )?;
+
+    msg!("Admin accepted: {}", new_admin.key);
+    AdminChanged { old_admin, new_admin: *new_admin.key }.emit(accounts)
+}
+
+/// HandoffOwnership: no data
+/// Accounts: [config (w), admin (s), new_admin, new_treasury, instructions_sysvar (if admin_isolation)]
+/// Swaps admin and treasury together so neither operator briefly controls the other's half
+fn handoff_ownership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let new_admin = next_account_info(iter)?;
+    let new_treasury = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    validate_treasury(new_treasury)?;
+
+    config.admin = *new_admin.key;
+    config.treasury = *new_treasury.key;
+    config.pending_admin = Pubkey::default();
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::HandoffOwnership,
+        admin.key,
+        new_treasury.key,
+        admin.key.to_bytes(),
+        new_admin.key.to_bytes(),
+    )?;
+
+    msg!("Ownership handed off to {}", new_admin.key);
+    AdminChanged { old_admin: *admin.key, new_admin: *new_admin.key }.emit(accounts)?;
+    TreasuryUpdated { treasury: *new_treasury.key }.emit(accounts)
+}
+
+/// SetMultisig: [signers: Vec<Pubkey>, threshold: u8]
+/// Accounts: [config (w), admin (s), new_admin (only when threshold is 0),
+///            instructions_sysvar (if admin_isolation)]
+/// A non-zero threshold hands `admin` to the multisig authority PDA, so later admin instructions
+/// (including changes to the signer set) go through ProposeAction/ExecuteAction. Threshold 0
+/// clears the set and hands `admin` to new_admin.
+fn set_multisig(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+
+    let new_admin = if threshold == 0 {
+        if !signers.is_empty() {
+            return Err(TipError::InvalidMultisig.into());
+        }
+        *next_account_info(iter)?.key
+    } else {
+        if signers.len() > MAX_MULTISIG_SIGNERS || threshold as usize > signers.len() {
+            return Err(TipError::InvalidMultisig.into());
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signers[..i].contains(signer) {
+                return Err(TipError::InvalidMultisig.into());
+            }
+        }
+        get_multisig_authority_pda(program_id, config.platform_id).0
+    };
+    check_admin_isolation(&config, iter)?;
+
+    let old_admin = config.admin;
+    config.admin = new_admin;
+    config.pending_admin = Pubkey::default();
+    config.multisig_threshold = threshold;
+    config.multisig_signer_count = signers.len() as u8;
+    config.multisig_signers = [Pubkey::default(); MAX_MULTISIG_SIGNERS];
+    config.multisig_signers[..signers.len()].copy_from_slice(&signers);
+    config.multisig_epoch = config.multisig_epoch.checked_add(1).ok_or(TipError::MathOverflow)?;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetMultisig,
+        admin.key,
+        &Pubkey::default(),
+        old_admin.to_bytes(),
+        audit_hash(&(new_admin, threshold, &signers))?,
+    )?;
+
+    msg!("Multisig: {} of {} signers", threshold, signers.len());
+    if old_admin != new_admin {
+        AdminChanged { old_admin, new_admin }.emit(accounts)?;
+    }
+    MultisigUpdated { signers, threshold }.emit(accounts)
+}
+
+/// Load a PendingAction owned by this program
+fn load_pending_action(program_id: &Pubkey, pending_acc: &AccountInfo) -> Result<PendingAction, ProgramError> {
+    if pending_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let pending = PendingAction::unpack(&pending_acc.data.borrow())?;
+    if !pending.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    Ok(pending)
+}
+
+/// ProposeAction: [nonce: u64, data: Vec<u8>]
+/// Accounts: [pending_action (w), config, proposer (s,w), system_program, payer (optional, s,w)]
+/// `data` is a Borsh-encoded TipInstruction; the proposer's approval is recorded immediately
+fn propose_action(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    nonce: u64,
+    data: Vec<u8>,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let pending_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let proposer = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !proposer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[pending_acc, proposer])?;
+    let payer = next_rent_payer(program_id, iter, proposer)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let index = config.multisig_signer_index(proposer.key).ok_or(TipError::NotMultisigSigner)?;
+
+    // Multisig instructions may not nest: the authority would otherwise approve for itself
+    if data.len() > MAX_ACTION_DATA {
+        return Err(TipError::InvalidAction.into());
+    }
+    match TipInstruction::unpack(&data) {
+        Ok(
+            TipInstruction::ProposeAction { .. }
+            | TipInstruction::ApproveAction
+            | TipInstruction::ExecuteAction
+            | TipInstruction::CancelAction,
+        )
+        | Err(_) => return Err(TipError::InvalidAction.into()),
+        Ok(_) => {}
+    }
+
+    let (pda, bump) = get_pending_action_pda(program_id, config.platform_id, nonce);
+    if *pending_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    create_pda_account(
+        payer,
+        pending_acc,
+        system_program,
+        program_id,
+        pending_action_size(data.len()),
+        &[b"pending_action", &platform_seed(config.platform_id), &nonce.to_le_bytes(), &[bump]],
+    )?;
+
+    let pending = PendingAction {
+        is_initialized: true,
+        nonce,
+        proposer: *proposer.key,
+        multisig_epoch: config.multisig_epoch,
+        approvals: 1 << index,
+        data,
+    };
+    pending.pack(&mut pending_acc.data.borrow_mut())?;
+
+    msg!("Action {} proposed by {}", nonce, proposer.key);
+    ActionProposed { action: pda, nonce, proposer: *proposer.key }.emit(accounts)
+}
+
+/// ApproveAction: no data
+/// Accounts: [pending_action (w), config, signer (s)]
+fn approve_action(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let pending_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let signer = next_account_info(iter)?;
+
+    if !signer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[pending_acc])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let index = config.multisig_signer_index(signer.key).ok_or(TipError::NotMultisigSigner)?;
+
+    let mut pending = load_pending_action(program_id, pending_acc)?;
+    if *pending_acc.key != get_pending_action_pda(program_id, config.platform_id, pending.nonce).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if pending.multisig_epoch != config.multisig_epoch {
+        return Err(TipError::StaleAction.into());
+    }
+    pending.approvals |= 1 << index;
+    pending.pack(&mut pending_acc.data.borrow_mut())?;
+
+    let approvals = pending.approvals.count_ones() as u8;
+    msg!("Action {} approved by {} ({}/{})", pending.nonce, signer.key, approvals, config.multisig_threshold);
+    ActionApproved { action: *pending_acc.key, signer: *signer.key, approvals }.emit(accounts)
+}
+
+/// ExecuteAction: no data
+/// Accounts: [pending_action (w), config, proposer (w), multisig_authority, tip_program,
+///            then the action's own accounts]
+/// Permissionless once approved: invokes the program with the stored instruction, signing for
+/// the multisig authority, and returns the PendingAction rent to the proposer
+fn execute_action(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let pending_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let proposer = next_account_info(iter)?;
+    let authority = next_account_info(iter)?;
+    let _tip_program = next_account_info(iter)?;
+
+    validation::writable(&[pending_acc, proposer])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+
+    let pending = load_pending_action(program_id, pending_acc)?;
+    if *pending_acc.key != get_pending_action_pda(program_id, config.platform_id, pending.nonce).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if pending.proposer != *proposer.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if pending.multisig_epoch != config.multisig_epoch {
+        return Err(TipError::StaleAction.into());
+    }
+    if config.multisig_threshold == 0 || pending.approvals.count_ones() < config.multisig_threshold as u32 {
+        return Err(TipError::ThresholdNotMet.into());
+    }
+
+    let (authority_pda, bump) = get_multisig_authority_pda(program_id, config.platform_id);
+    if *authority.key != authority_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Closed first so the action cannot run twice
+    close_pda(pending_acc, proposer)?;
+
+    let action_accounts: Vec<AccountInfo> = iter.cloned().collect();
+    let metas = action_accounts
+        .iter()
+        .map(|a| AccountMeta {
+            pubkey: *a.key,
+            is_signer: a.is_signer || *a.key == authority_pda,
+            is_writable: a.is_writable,
+        })
+        .collect();
+    invoke_signed(
+        &Instruction { program_id: *program_id, accounts: metas, data: pending.data },
+        &action_accounts,
+        &[&[b"multisig", &platform_seed(config.platform_id), &[bump]]],
+    )?;
+
+    msg!("Action {} executed", pending.nonce);
+    ActionExecuted { action: *pending_acc.key, nonce: pending.nonce }.emit(accounts)
+}
+
+/// CancelAction: no data
+/// Accounts: [pending_action (w), proposer (s,w)]
+/// The proposer withdraws an action (e.g. one made stale by SetMultisig) and reclaims its rent
+fn cancel_action(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let pending_acc = next_account_info(iter)?;
+    let proposer = next_account_info(iter)?;
+
+    if !proposer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[pending_acc, proposer])?;
+
+    let pending = load_pending_action(program_id, pending_acc)?;
+    if pending.proposer != *proposer.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    close_pda(pending_acc, proposer)?;
+
+    msg!("Action {} cancelled", pending.nonce);
+    ActionCancelled { action: *pending_acc.key, nonce: pending.nonce }.emit(accounts)
+}
+
+/// SetRole: [role: Role, authority: Pubkey]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+/// The default pubkey revokes the role
+fn set_role(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    role: Role,
+    authority: Pubkey,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = config.role_holder(role).to_bytes();
+    match role {
+        Role::FeeManager => config.fee_manager = authority,
+        Role::TreasuryManager => config.treasury_manager = authority,
+        Role::Pauser => config.pauser = authority,
+        Role::Arbiter => config.arbiter = authority,
+        Role::Verifier => config.verifier = authority,
+        Role::Governance => config.governance_authority = authority,
+    }
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetRole,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        authority.to_bytes(),
+    )?;
+
+    msg!("Role {:?} assigned to {}", role, authority);
+    RoleUpdated { role, authority }.emit(accounts)
+}
+
+/// InitReceiptTree: [max_depth: u32, max_buffer_size: u32]
+/// Accounts: [config (w), admin (s), merkle_tree (w), tree_authority, compression_program, noop_program,
+///            instructions_sysvar (if admin_isolation)]
+/// merkle_tree must already be allocated and owned by the compression program. Running it again
+/// with a fresh tree moves receipts there once the old one fills up.
+fn init_receipt_tree(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let tree = next_account_info(iter)?;
+    let tree_authority = next_account_info(iter)?;
+    let compression_program = next_account_info(iter)?;
+    let noop_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, tree])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if *compression_program.key != compression::ID || *tree.owner != compression::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let (authority_pda, bump) = get_tree_authority_pda(program_id, config.platform_id);
+    if *tree_authority.key != authority_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    invoke_signed(
+        &compression::init_empty_merkle_tree(tree.key, &authority_pda, max_depth, max_buffer_size),
+        &[tree.clone(), tree_authority.clone(), noop_program.clone()],
+        &[&[b"tree_authority", &platform_seed(config.platform_id), &[bump]]],
+    )?;
+
+    let old_value = config.receipt_tree.to_bytes();
+    config.receipt_tree = *tree.key;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::InitReceiptTree,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        tree.key.to_bytes(),
+    )?;
+
+    msg!("Receipt tree: {} (depth {})", tree.key, max_depth);
+    ReceiptTreeInitialized { tree: *tree.key, max_depth, max_buffer_size }.emit(accounts)
+}
+
+/// SetReceiptNft: [collection: Pubkey, uri_template: String]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+/// The collection must be sized, with the receipt authority PDA as its update authority.
+/// Pubkey::default() turns NFT receipts off.
+fn set_receipt_nft(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    collection: Pubkey,
+    uri_template: String,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = config.receipt_nft_collection.to_bytes();
+    config.receipt_nft_uri = ReceiptUri::new(&uri_template).ok_or(TipError::UriTooLong)?;
+    config.receipt_nft_collection = collection;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetReceiptNft,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        collection.to_bytes(),
+    )?;
+
+    msg!("Receipt NFT collection: {}", collection);
+    ReceiptNftUpdated { collection, uri_template }.emit(accounts)
+}
+
+/// SetAdminIsolation: [enabled: u8]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_admin_isolation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.admin_isolation as u64]);
+    config.admin_isolation = enabled;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetAdminIsolation,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[enabled as u64]),
+    )?;
+    AdminIsolationUpdated { enabled }.emit(accounts)
+}
+
+/// SweepExcessRent: no data
+/// Accounts: [config (w), admin (s,w), instructions_sysvar (if admin_isolation)]
+fn sweep_excess_rent(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let rent = Rent::get()?;
+    let required = rent.minimum_balance(config_acc.data_len());
+    let surplus = config_acc.lamports().saturating_sub(required);
+    if surplus == 0 {
+        return Ok(());
+    }
+
+    **config_acc.try_borrow_mut_lamports()? -= surplus;
+    **admin.try_borrow_mut_lamports()? += surplus;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SweepExcessRent,
+        admin.key,
+        &Pubkey::default(),
+        audit_words(&[surplus + required]),
+        audit_words(&[required]),
+    )?;
+
+    msg!("Swept {} excess lamports to admin", surplus);
+    RentSwept { lamports: surplus }.emit(accounts)
+}
+
+/// SetNotify: [notify: u8]
+/// Accounts: [creator_notify (w), creator (s,w), system_program, payer (optional, s,w)]
+fn set_notify(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    notify: bool,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let notify_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[notify_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let (pda, bump) = get_notify_pda(program_id, creator.key);
+    if *notify_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if notify_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            notify_acc,
+            system_program,
+            program_id,
+            NOTIFY_SIZE,
+            &[b"notify", creator.key.as_ref(), &[bump]],
+        )?;
+    }
+
+    let state = CreatorNotify {
+        is_initialized: true,
+        creator: *creator.key,
+        notify,
+    };
+    state.pack(&mut notify_acc.data.borrow_mut())?;
+    NotifyUpdated { creator: *creator.key, notify }.emit(accounts)
+}
+
+/// Build a profile, checking that `payout_token` is the creator's own token account or the token
+/// account of one of their vaults
+fn creator_profile(
+    program_id: &Pubkey,
+    creator: &AccountInfo,
+    payout_token: &AccountInfo,
+    display_name: &str,
+    metadata_uri: &str,
+    verified: bool,
+) -> Result<CreatorProfile, ProgramError> {
+    let token = token::unpack_account(payout_token)?;
+    if token.owner != *creator.key {
+        let (vault, _) = get_creator_vault_pda(program_id, creator.key, &token.mint);
+        if token.owner != vault || *payout_token.key != get_creator_vault_token_pda(program_id, &vault).0 {
+            msg!("Payout token account must belong to the creator or their vault");
+            return Err(TipError::CreatorTokenMismatch.into());
+        }
+    }
+    Ok(CreatorProfile {
+        is_initialized: true,
+        creator: *creator.key,
+        display_name: PaddedStr::new(display_name).ok_or(TipError::ProfileFieldTooLong)?,
+        metadata_uri: PaddedStr::new(metadata_uri).ok_or(TipError::ProfileFieldTooLong)?,
+        payout_token: *payout_token.key,
+        verified,
+        tip_mint: Pubkey::default(),
+        min_tip: 0,
+        tip_menu_len: 0,
+        tip_menu: [0; MAX_TIP_MENU],
+        tier_count: 0,
+        tier_thresholds: [0; MAX_TIP_TIERS],
+        payout_mint: Pubkey::default(),
+        tax_bps: 0,
+        tax_account: Pubkey::default(),
+    })
+}
+
+/// RegisterCreator: [display_name: String, metadata_uri: String]
+/// Accounts: [profile (w), creator (s,w), payout_token, system_program, config (w), payer (optional, s,w)]
+/// `config` is platform 0's, which counts profiles against `max_creators`
+fn register_creator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    display_name: String,
+    metadata_uri: String,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let profile_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let payout_token = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[profile_acc, creator])?;
+    validation::token_accounts(&[payout_token])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+    let mut config = creator_registry(program_id, config_acc)?;
+    if config.max_creators != 0 && config.registered_creators >= config.max_creators {
+        msg!("Creator cap of {} reached", config.max_creators);
+        return Err(TipError::CreatorCapReached.into());
+    }
+
+    let (pda, bump) = get_creator_profile_pda(program_id, creator.key);
+    if *profile_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !profile_acc.data_is_empty() {
+        msg!("Creator is already registered; use UpdateCreator");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    let profile = creator_profile(program_id, creator, payout_token, &display_name, &metadata_uri, false)?;
+
+    create_pda_account(
+        payer,
+        profile_acc,
+        system_program,
+        program_id,
+        CREATOR_PROFILE_SIZE,
+        &[b"creator_profile", creator.key.as_ref(), &[bump]],
+    )?;
+    profile.pack(&mut profile_acc.data.borrow_mut())?;
+    config.registered_creators = config.registered_creators.checked_add(1).ok_or(TipError::MathOverflow)?;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+
+    msg!("Registered creator {} as {}", creator.key, display_name);
+    CreatorProfileUpdated { creator: *creator.key, display_name, metadata_uri, payout_token: *payout_token.key }
+        .emit(accounts)
+}
+
+/// UpdateCreator: [display_name: String, metadata_uri: String]
+/// Accounts: [profile (w), creator (s), payout_token]
+fn update_creator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    display_name: String,
+    metadata_uri: String,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let profile_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let payout_token = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[profile_acc])?;
+    validation::token_accounts(&[payout_token])?;
+    if profile_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let current = CreatorProfile::load(&profile_acc.data.borrow())?;
+    if !current.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if current.creator != *creator.key {
+        return Err(TipError::Unauthorized.into());
+    }
+
+    // Verification is about the creator, not the payout account, so it survives updates, as do the tip
+    // menu, tiers, payout mint and tax withholding
+    let mut profile =
+        creator_profile(program_id, creator, payout_token, &display_name, &metadata_uri, current.verified)?;
+    profile.tip_mint = current.tip_mint;
+    profile.min_tip = current.min_tip;
+    profile.tip_menu_len = current.tip_menu_len;
+    profile.tip_menu = current.tip_menu;
+    profile.tier_count = current.tier_count;
+    profile.tier_thresholds = current.tier_thresholds;
+    profile.payout_mint = current.payout_mint;
+    profile.tax_bps = current.tax_bps;
+    profile.tax_account = current.tax_account;
+    // A legacy profile that never set a tip menu stays at its old size
+    pack_prefix(&profile, profile_acc, CREATOR_PROFILE_SIZE)?;
+
+    msg!("Updated creator profile for {}", creator.key);
+    CreatorProfileUpdated { creator: *creator.key, display_name, metadata_uri, payout_token: *payout_token.key }
+        .emit(accounts)
+}
+
+/// SetTipMenu: [min_tip: u64, tip_menu: Vec<u64>]
+/// Accounts: [profile (w), creator (s,w), mint, system_program, payer (optional, s,w)]
+/// Amounts are in `mint`'s base units; Tip enforces `min_tip` on tips in that mint that pass the
+/// profile. Legacy profiles grow to hold the menu. Switching to another mint clears the tiers.
+fn set_tip_menu(program_id: &Pubkey, accounts: &[AccountInfo], min_tip: u64, tip_menu: Vec<u64>) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let profile_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[profile_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+    token::unpack_mint(mint_acc)?;
+
+    if profile_acc.owner != program_id || *profile_acc.key != get_creator_profile_pda(program_id, creator.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut profile = CreatorProfile::load(&profile_acc.data.borrow())?;
+    if !profile.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if tip_menu.len() > MAX_TIP_MENU || tip_menu.iter().any(|amount| *amount == 0 || *amount < min_tip) {
+        msg!("Tip menu holds up to {} amounts, none zero or below the minimum of {}", MAX_TIP_MENU, min_tip);
+        return Err(TipError::InvalidTipMenu.into());
+    }
+
+    if profile_acc.data_len() < CREATOR_PROFILE_SIZE {
+        grow_account(profile_acc, payer, system_program, CREATOR_PROFILE_SIZE)?;
+    }
+    // Tier thresholds are amounts of the old mint
+    if profile.tip_mint != *mint_acc.key {
+        profile.tier_count = 0;
+        profile.tier_thresholds = [0; MAX_TIP_TIERS];
+    }
+    profile.tip_mint = *mint_acc.key;
+    profile.min_tip = min_tip;
+    profile.tip_menu_len = tip_menu.len() as u8;
+    profile.tip_menu = [0; MAX_TIP_MENU];
+    profile.tip_menu[..tip_menu.len()].copy_from_slice(&tip_menu);
+    profile.pack(&mut profile_acc.data.borrow_mut())?;
+
+    msg!("Creator {} minimum tip: {} of mint {}", creator.key, min_tip, mint_acc.key);
+    TipMenuUpdated { creator: *creator.key, mint: *mint_acc.key, min_tip, tip_menu }.emit(accounts)
+}
+
+/// SetForwardRule: [destination: Pubkey, forward_bps: u16]
+/// Accounts: [forward_rule (w), creator (s,w), system_program, payer (optional, s,w)]
+/// Tips passing the rule forward `forward_bps` of what the creator would receive, after the fee and
+/// collaborators' cuts, to `destination`'s ATA for the tip's mint; the rule is created on first use.
+fn set_forward_rule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    destination: Pubkey,
+    forward_bps: u16,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let forward_rule_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[forward_rule_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let (pda, bump) = get_forward_rule_pda(program_id, creator.key);
+    if *forward_rule_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if destination == *creator.key || forward_bps > 10_000 {
+        return Err(TipError::InvalidForwardRule.into());
+    }
+
+    if forward_rule_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            forward_rule_acc,
+            system_program,
+            program_id,
+            FORWARD_RULE_SIZE,
+            &[b"forward", creator.key.as_ref(), &[bump]],
+        )?;
+    } else {
+        validation::program_owned(program_id, forward_rule_acc)?;
+    }
+    ForwardRule { is_initialized: true, creator: *creator.key, destination, forward_bps }
+        .pack(&mut forward_rule_acc.data.borrow_mut())?;
+
+    msg!("Forward rule of {}: {} bps to {}", creator.key, forward_bps, destination);
+    ForwardRuleSet { creator: *creator.key, destination, forward_bps }.emit(accounts)
+}
+
+/// SetTipTiers: [thresholds: Vec<u64>]
+/// Accounts: [profile (w), creator (s,w), system_program, payer (optional, s,w)]
+/// Superchat tier thresholds in the profile's `tip_mint`, which SetTipMenu must have set; empty
+/// thresholds turn tiers off. Legacy profiles grow to hold them.
+fn set_tip_tiers(program_id: &Pubkey, accounts: &[AccountInfo], thresholds: Vec<u64>) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let profile_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[profile_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    if profile_acc.owner != program_id || *profile_acc.key != get_creator_profile_pda(program_id, creator.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut profile = CreatorProfile::load(&profile_acc.data.borrow())?;
+    if !profile.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if profile.tip_mint == Pubkey::default() {
+        msg!("Set the tip mint with SetTipMenu first");
+        return Err(TipError::InvalidTipTiers.into());
+    }
+    if thresholds.len() > MAX_TIP_TIERS
+        || thresholds.first() == Some(&0)
+        || thresholds.windows(2).any(|pair| pair[0] >= pair[1])
+    {
+        msg!("Up to {} non-zero thresholds, strictly increasing", MAX_TIP_TIERS);
+        return Err(TipError::InvalidTipTiers.into());
+    }
+
+    if profile_acc.data_len() < CREATOR_PROFILE_SIZE {
+        grow_account(profile_acc, payer, system_program, CREATOR_PROFILE_SIZE)?;
+    }
+    profile.tier_count = thresholds.len() as u8;
+    profile.tier_thresholds = [0; MAX_TIP_TIERS];
+    profile.tier_thresholds[..thresholds.len()].copy_from_slice(&thresholds);
+    profile.pack(&mut profile_acc.data.borrow_mut())?;
+
+    msg!("Creator {} tip tiers: {:?}", creator.key, thresholds);
+    TipTiersUpdated { creator: *creator.key, mint: profile.tip_mint, thresholds }.emit(accounts)
+}
+
+/// SetPayoutMint: [payout_mint: Pubkey]
+/// Accounts: [profile (w), creator (s,w), system_program, payer (optional, s,w)]
+/// Mint TipWithSwap converts the creator's share into (default = none). Legacy profiles grow to hold it.
+fn set_payout_mint(program_id: &Pubkey, accounts: &[AccountInfo], payout_mint: Pubkey) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let profile_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[profile_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    if profile_acc.owner != program_id || *profile_acc.key != get_creator_profile_pda(program_id, creator.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut profile = CreatorProfile::load(&profile_acc.data.borrow())?;
+    if !profile.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+
+    if profile_acc.data_len() < CREATOR_PROFILE_SIZE {
+        grow_account(profile_acc, payer, system_program, CREATOR_PROFILE_SIZE)?;
+    }
+    profile.payout_mint = payout_mint;
+    profile.pack(&mut profile_acc.data.borrow_mut())?;
+
+    msg!("Creator {} payout mint: {}", creator.key, payout_mint);
+    PayoutMintSet { creator: *creator.key, payout_mint }.emit(accounts)
+}
+
+/// SetFanClub: [collection: Pubkey, discount_bps: u16]
+/// Accounts: [fan_club (w), creator (s,w), system_program, payer (optional, s,w)]
+/// Holders of an NFT in the verified `collection` waive `discount_bps` of the fee on their tips to the
+/// creator; the club is created on first use.
+fn set_fan_club(program_id: &Pubkey, accounts: &[AccountInfo], collection: Pubkey, discount_bps: u16) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let fan_club_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[fan_club_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let (pda, bump) = get_fan_club_pda(program_id, creator.key);
+    if *fan_club_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if discount_bps > MAX_FAN_DISCOUNT_BPS {
+        return Err(TipError::InvalidFanDiscount.into());
+    }
+
+    if fan_club_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            fan_club_acc,
+            system_program,
+            program_id,
+            FAN_CLUB_SIZE,
+            &[b"fan_club", creator.key.as_ref(), &[bump]],
+        )?;
+    } else {
+        validation::program_owned(program_id, fan_club_acc)?;
+    }
+    FanClub { is_initialized: true, creator: *creator.key, collection, discount_bps }
+        .pack(&mut fan_club_acc.data.borrow_mut())?;
+
+    msg!("Fan club of {}: {} bps off for collection {}", creator.key, discount_bps, collection);
+    FanClubUpdated { creator: *creator.key, collection, discount_bps }.emit(accounts)
+}
+
+/// SetCreatorVerified: [verified: bool]
+/// Accounts: [profile (w), config, admin or verifier (s), instructions_sysvar (if admin_isolation)]
+fn set_creator_verified(program_id: &Pubkey, accounts: &[AccountInfo], verified: bool) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let profile_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let verifier = next_account_info(iter)?;
+
+    if !verifier.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[profile_acc])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(verifier.key, Role::Verifier) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_shared_authority(&config)?;
+    check_admin_isolation(&config, iter)?;
+
+    if profile_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut profile = CreatorProfile::load(&profile_acc.data.borrow())?;
+    if !profile.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let old_value = audit_words(&[profile.verified as u64]);
+    profile.verified = verified;
+    pack_prefix(&profile, profile_acc, CREATOR_PROFILE_SIZE)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetCreatorVerified,
+        verifier.key,
+        &profile.creator,
+        old_value,
+        audit_words(&[verified as u64]),
+    )?;
+
+    msg!("Creator {} verified: {}", profile.creator, verified);
+    CreatorVerified { creator: profile.creator, verified }.emit(accounts)
+}
+
+/// SetTaxWithholding: [tax_bps: u16, tax_account: Pubkey]
+/// Accounts: [profile (w), config, admin or verifier (s,w), system_program, instructions_sysvar (if admin_isolation)]
+/// The signer pays to grow a legacy profile.
+fn set_tax_withholding(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tax_bps: u16,
+    tax_account: Pubkey,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let profile_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let verifier = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !verifier.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[profile_acc])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(verifier.key, Role::Verifier) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_shared_authority(&config)?;
+    check_admin_isolation(&config, iter)?;
+
+    if tax_bps > 10_000 || (tax_bps > 0 && tax_account == Pubkey::default()) {
+        return Err(TipError::InvalidTaxWithholding.into());
+    }
+    if profile_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut profile = CreatorProfile::load(&profile_acc.data.borrow())?;
+    if !profile.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if profile_acc.data_len() < CREATOR_PROFILE_SIZE {
+        validation::writable(&[verifier])?;
+        grow_account(profile_acc, verifier, system_program, CREATOR_PROFILE_SIZE)?;
+    }
+    let old_value = audit_hash(&(profile.tax_bps, profile.tax_account))?;
+    profile.tax_bps = tax_bps;
+    profile.tax_account = tax_account;
+    profile.pack(&mut profile_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetTaxWithholding,
+        verifier.key,
+        &profile.creator,
+        old_value,
+        audit_hash(&(tax_bps, tax_account))?,
+    )?;
+
+    msg!("Creator {} tax withholding: {} bps to {}", profile.creator, tax_bps, tax_account);
+    TaxWithholdingSet { creator: profile.creator, tax_bps, tax_account }.emit(accounts)
+}
+
+/// SetRequireVerified: [required: bool]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_require_verified(program_id: &Pubkey, accounts: &[AccountInfo], required: bool) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.require_verified as u64]);
+    config.require_verified = required;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetRequireVerified,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[required as u64]),
+    )?;
+    RequireVerifiedUpdated { required }.emit(accounts)
+}
+
+/// SetMaxCreators: [max_creators: u32]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+/// Lowering the cap below `registered_creators` only stops new registrations
+fn set_max_creators(program_id: &Pubkey, accounts: &[AccountInfo], max_creators: u32) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let mut config = creator_registry(program_id, config_acc)?;
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.max_creators as u64]);
+    config.max_creators = max_creators;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetMaxCreators,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[max_creators as u64]),
+    )?;
+    MaxCreatorsUpdated { max_creators }.emit(accounts)
+}
+
+/// SetRequireTopLevel: [required: bool]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_require_top_level(program_id: &Pubkey, accounts: &[AccountInfo], required: bool) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.require_top_level as u64]);
+    config.require_top_level = required;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetRequireTopLevel,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[required as u64]),
+    )?;
+    RequireTopLevelUpdated { required }.emit(accounts)
+}
+
+/// SetLenientRouting: [enabled: bool]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_lenient_routing(program_id: &Pubkey, accounts: &[AccountInfo], enabled: bool) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.lenient_routing as u64]);
+    config.lenient_routing = enabled;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetLenientRouting,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[enabled as u64]),
+    )?;
+    LenientRoutingUpdated { enabled }.emit(accounts)
+}
+
+/// SetSelfTipPolicy: [policy: SelfTipPolicy]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+fn set_self_tip_policy(program_id: &Pubkey, accounts: &[AccountInfo], policy: SelfTipPolicy) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.self_tip_policy as u64]);
+    config.self_tip_policy = policy;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetSelfTipPolicy,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[policy as u64]),
+    )?;
+    msg!("Self-tip policy: {:?}", policy);
+    SelfTipPolicyUpdated { policy }.emit(accounts)
+}
+
+/// SetBadgeThresholds: [thresholds: [u64; 3]]
+/// Accounts: [badge_config (w), creator (s,w), system_program, payer (optional, s,w)]
+/// Bronze, silver and gold volume thresholds for the creator's supporters
+fn set_badge_thresholds(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    thresholds: [u64; 3],
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let badge_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[badge_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+    if thresholds[0] == 0 || thresholds[0] >= thresholds[1] || thresholds[1] >= thresholds[2] {
+        return Err(TipError::InvalidBadgeThresholds.into());
+    }
+
+    let (pda, bump) = get_badge_config_pda(program_id, creator.key);
+    if *badge_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if badge_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            badge_acc,
+            system_program,
+            program_id,
+            BADGE_CONFIG_SIZE,
+            &[b"badges", creator.key.as_ref(), &[bump]],
+        )?;
+    }
+
+    let badges = BadgeConfig { is_initialized: true, creator: *creator.key, thresholds };
+    badges.pack(&mut badge_acc.data.borrow_mut())?;
+    BadgeThresholdsUpdated { creator: *creator.key, thresholds }.emit(accounts)
+}
+
+/// ClaimBadge: no data
+/// Accounts: [supporter_stats (w), badge_config, tipper (s), stake (optional)]
+/// Moves the tipper up to the highest tier their volume, plus what they have staked behind the
+/// creator, reaches; tiers are never lowered
+fn claim_badge(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let supporter_acc = next_account_info(iter)?;
+    let badge_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[supporter_acc])?;
+    if supporter_acc.owner != program_id || badge_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Both are only ever created at their PDAs, so the stored keys stand in for re-deriving them
+    let mut stats = SupporterStats::unpack(&supporter_acc.data.borrow())?;
+    if !stats.is_initialized || stats.tipper != *tipper.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    let badges = BadgeConfig::unpack(&badge_acc.data.borrow())?;
+    if !badges.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if badges.creator != stats.creator {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let staked = match next_optional_account(iter, program_id) {
+        Some(stake_acc) => {
+            validation::program_owned(program_id, stake_acc)?;
+            let position = StakePosition::unpack(&stake_acc.data.borrow())?;
+            if !position.is_initialized || position.supporter != stats.tipper || position.creator != stats.creator {
+                return Err(ProgramError::InvalidSeeds);
+            }
+            position.staked
+        }
+        None => 0,
+    };
+
+    let tier = BadgeTier::for_volume(stats.total_volume.saturating_add(staked), &badges.thresholds);
+    if tier <= stats.badge {
+        return Err(TipError::NoBadgeUpgrade.into());
+    }
+    stats.badge = tier;
+    stats.pack(&mut supporter_acc.data.borrow_mut())?;
+
+    msg!("Badge {:?} for {}", tier, tipper.key);
+    BadgeClaimed { tipper: *tipper.key, creator: stats.creator, tier, total_volume: stats.total_volume }.emit(accounts)
+}
+
+/// OpenSession: [budget: u64, expiry_ts: i64]
+/// Accounts: [session (w), tipper (s,w), tipper_token (w), session_key, token_program, system_program,
+///            blocked_tipper, (config), payer (optional, s,w)]
+/// Re-opening replaces the previous budget, expiry and session key. The session only tips on the
+/// config's platform (platform 0 without one)
+fn open_session(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    budget: u64,
+    expiry_ts: i64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let session_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let session_key = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[session_acc, tipper, tipper_token])?;
+    validation::token_accounts(&[tipper_token])?;
+    let platform_id = optional_platform(program_id, iter)?;
+    let payer = next_rent_payer(program_id, iter, tipper)?;
+    check_tipper_not_blocked(program_id, platform_id, blocked_tipper_acc, tipper.key)?;
+
+    let (pda, bump) = get_session_pda(program_id, platform_id, tipper.key);
+    if *session_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if budget == 0 {
+        msg!("Session budget must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if expiry_ts <= Clock::get()?.unix_timestamp {
+        msg!("Session expiry must be in the future");
+        return Err(TipError::InvalidExpiry.into());
+    }
+
+    if session_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            session_acc,
+            system_program,
+            program_id,
+            SESSION_SIZE,
+            &[b"session", &platform_seed(platform_id), tipper.key.as_ref(), &[bump]],
+        )?;
+    }
+
+    if !token::is_token_program(token_program.key) || tipper_token.owner != token_program.key {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    invoke(
+        &token::approve(token_program.key, tipper_token.key, &pda, tipper.key, budget),
+        &[tipper_token.clone(), session_acc.clone(), tipper.clone()],
+    )?;
+
+    let session = Session {
+        is_initialized: true,
+        tipper: *tipper.key,
+        tipper_token: *tipper_token.key,
+        session_key: *session_key.key,
+        budget_remaining: budget,
+        expiry_ts,
+    };
+    session.pack(&mut session_acc.data.borrow_mut())?;
+    SessionOpened { tipper: *tipper.key, session_key: *session_key.key, budget, expiry_ts }.emit(accounts)
+}
+
+/// SessionTip: [amount: u64]
+/// Accounts: [config (w), session (w), session_key (s), tipper_token (w), creator, creator_token (w), treasury_token (w), mint,
+///            token_program, allowed_mint, tipper_stats (w), blocked_creator, blocked_tipper, mint_stats (w),
+///            then per treasury split entry: split_token (w)]
+/// Counts toward the tipper's rate limit but not their stats
+fn session_tip(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let session_acc = next_account_info(iter)?;
+    let session_key = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let creator_token = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let tipper_stats_acc = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    if !session_key.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, session_acc, tipper_token, creator_token, treasury_token, tipper_stats_acc])?;
+    validation::token_accounts(&[tipper_token, creator_token, treasury_token])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+
+    if session_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let mut session = Session::unpack(&session_acc.data.borrow())?;
+    if !session.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    // Sessions are opened for one platform, so another platform's config cannot charge them its fee
+    let (session_pda, session_bump) = get_session_pda(program_id, config.platform_id, &session.tipper);
+    if *session_acc.key != session_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if session.session_key != *session_key.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if session.tipper_token != *tipper_token.key {
+        msg!("Session token account mismatch");
+        return Err(TipError::SessionTokenMismatch.into());
+    }
+    if Clock::get()?.unix_timestamp >= session.expiry_ts {
+        msg!("Session expired");
+        return Err(TipError::SessionExpired.into());
+    }
+
+    if amount == 0 {
+        msg!("Tip amount must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if amount > session.budget_remaining {
+        msg!("Session budget exhausted: {} remaining", session.budget_remaining);
+        return Err(TipError::SessionBudgetExhausted.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let allowed = check_allowed_mint(
+        program_id,
+        config.platform_id,
+        allowed_mint_acc,
+        &[tipper_token, creator_token, treasury_token],
+    )?;
+    if allowed.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    check_tip_limits(allowed.min_tip(config.min_tip_amount), config.max_tip_amount, amount)?;
+    check_not_vesting(config.vesting_threshold, amount)?;
+    check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+    check_tipper_not_blocked(program_id, config.platform_id, blocked_tipper_acc, &session.tipper)?;
+    check_self_tip(
+        config.self_tip_policy as u8, accounts, &session.tipper, &session.tipper, &session.tipper, tipper_token,
+        creator.key, creator_token, None, mint_acc.key, amount,
+    )?;
+    charge_rate_limit(program_id, config.platform_id, &session.tipper, tipper_stats_acc, amount, config.rate_limit())?;
+
+    let mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+    let platform = platform_seed(config.platform_id);
+    let signer_seeds: &[&[u8]] = &[b"session", &platform, session.tipper.as_ref(), &[session_bump]];
+
+    let creator_received = transfer_tokens(
+        accounts,
+        token_program, tipper_token, mint_acc, creator_token, session_acc, creator_amount, mint.decimals,
+        &[signer_seeds], &[],
+    )?;
+
+    let fee_received = pay_treasury_fee(
+        accounts,
+        config.treasury_split(), fee, iter, token_program, tipper_token, mint_acc, treasury_token, session_acc, mint.decimals,
+        &[signer_seeds], &[],
+    )?;
+
+    session.budget_remaining -= amount;
+    session.pack(&mut session_acc.data.borrow_mut())?;
+
+    let delivered = add_checked(creator_received, fee_received)?;
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, delivered, fee_received)?;
+    msg!("Session tip: {} to creator, {} fee, {} budget left", creator_received, fee_received, session.budget_remaining);
+    emit_tip(accounts, &session.tipper, creator.key, mint_acc.key, delivered, fee_received, 0, 0)
+}
+
+/// Signer and message of the Ed25519 program instruction right before the current one, which the
+/// runtime has already verified; its offsets must all point into its own data
+fn ed25519_signed_message(ix_sysvar: &AccountInfo) -> Result<(Pubkey, Vec<u8>), ProgramError> {
+    if *ix_sysvar.key != sysvar::instructions::id() {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let invalid = || ProgramError::from(TipError::InvalidTipSignature);
+    let current = sysvar::instructions::load_current_index_checked(ix_sysvar)? as usize;
+    let index = current.checked_sub(1).ok_or_else(invalid)?;
+    let ix = sysvar::instructions::load_instruction_at_checked(index, ix_sysvar)?;
+    if ix.program_id != solana_program::ed25519_program::id() {
+        return Err(invalid());
+    }
+    // [count u8, padding u8, then per signature the u16s: signature offset, its instruction,
+    //  public key offset, its instruction, message offset, message size, its instruction]
+    let data = &ix.data;
+    let field = |i: usize| data.get(2 + 2 * i..4 + 2 * i).map(|b| u16::from_le_bytes([b[0], b[1]])).ok_or_else(invalid);
+    if data.first() != Some(&1) || [field(1)?, field(3)?, field(6)?] != [u16::MAX; 3] {
+        return Err(invalid());
+    }
+    let (key_offset, message_offset, message_size) = (field(2)? as usize, field(4)? as usize, field(5)? as usize);
+    let key = data.get(key_offset..key_offset + 32).ok_or_else(invalid)?;
+    let message = data.get(message_offset..message_offset + message_size).ok_or_else(invalid)?;
+    Ok((Pubkey::try_from(key).map_err(|_| invalid())?, message.to_vec()))
+}
+
+/// TipDelegated: [amount: u64, nonce: u64, expiry_ts: i64]
+/// Accounts: [config (w), relayer (s,w), tipper, delegation (w), tipper_token (w), creator, creator_token (w),
+///            treasury_token (w), mint, token_program, allowed_mint, system_program, instructions_sysvar,
+///            tipper_stats (w), blocked_creator, blocked_tipper, mint_stats (w),
+///            then per treasury split entry: split_token (w)]
+/// The relayer pays for the transaction; the tipper only signs a DelegatedTip off-chain and has approved
+/// the delegation PDA on `tipper_token` beforehand. Counts toward the tipper's rate limit but not their stats
+fn tip_delegated(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    nonce: u64,
+    expiry_ts: i64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let relayer = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let delegation_acc = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let creator_token = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let ix_sysvar = next_account_info(iter)?;
+    let tipper_stats_acc = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    if !relayer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, relayer, delegation_acc, tipper_token, creator_token, treasury_token])?;
+    validation::writable(&[tipper_stats_acc])?;
+    validation::token_accounts(&[tipper_token, creator_token, treasury_token])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+
+    let message = DelegatedTip {
+        program_id: *program_id,
+        platform_id: config.platform_id,
+        tipper_token: *tipper_token.key,
+        creator: *creator.key,
+        mint: *mint_acc.key,
+        amount,
+        nonce,
+        expiry_ts,
+    };
+    let (signer, signed) = ed25519_signed_message(ix_sysvar)?;
+    if signer != *tipper.key || signed != borsh::to_vec(&message)? {
+        msg!("Previous instruction must verify the tipper's signature over this tip");
+        return Err(TipError::InvalidTipSignature.into());
+    }
+    if Clock::get()?.unix_timestamp >= expiry_ts {
+        return Err(TipError::TipAuthorizationExpired.into());
+    }
+
+    let (pda, bump) = get_delegation_pda(program_id, config.platform_id, tipper.key);
+    if *delegation_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let platform = platform_seed(config.platform_id);
+    let seeds: &[&[u8]] = &[b"delegation", &platform, tipper.key.as_ref(), &[bump]];
+    if delegation_acc.data_is_empty() {
+        create_pda_account(relayer, delegation_acc, system_program, program_id, DELEGATION_SIZE, seeds)?;
+    }
+    let mut delegation = Delegation::unpack_or_zeroed(&delegation_acc.data.borrow())?;
+    // Each signed message is good for one tip
+    if delegation.nonce != nonce {
+        msg!("Delegated tip nonce must be {}", delegation.nonce);
+        return Err(TipError::InvalidTipSignature.into());
+    }
+    delegation.is_initialized = true;
+    delegation.tipper = *tipper.key;
+    delegation.nonce = add_checked(delegation.nonce, 1)?;
+
+    if amount == 0 {
+        msg!("Tip amount must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if token::unpack_account(tipper_token)?.owner != *tipper.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    let mint = check_token_program(mint_acc, token_program)?;
+    let allowed = check_allowed_mint(
+        program_id,
+        config.platform_id,
+        allowed_mint_acc,
+        &[tipper_token, creator_token, treasury_token],
+    )?;
+    if allowed.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    check_tip_limits(allowed.min_tip(config.min_tip_amount), config.max_tip_amount, amount)?;
+    check_not_vesting(config.vesting_threshold, amount)?;
+    check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+    check_tipper_not_blocked(program_id, config.platform_id, blocked_tipper_acc, tipper.key)?;
+    check_self_tip(
+        config.self_tip_policy as u8, accounts, tipper.key, tipper.key, tipper.key, tipper_token, creator.key,
+        creator_token, None, mint_acc.key, amount,
+    )?;
+    // The relayer pays for the tipper's stats if they have none yet
+    let (platform_id, limit) = (config.platform_id, config.rate_limit());
+    if limit.window_secs > 0 {
+        bump_tipper_stats(
+            program_id, platform_id, relayer, tipper.key, tipper_stats_acc, system_program, 1, amount, limit, true,
+            None,
+        )?;
+    }
+
+    let mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+
+    let creator_received = transfer_tokens(
+        accounts,
+        token_program, tipper_token, mint_acc, creator_token, delegation_acc, creator_amount, mint.decimals, &[seeds],
+        &[],
+    )?;
+    let fee_received = pay_treasury_fee(
+        accounts,
+        config.treasury_split(), fee, iter, token_program, tipper_token, mint_acc, treasury_token, delegation_acc,
+        mint.decimals, &[seeds], &[],
+    )?;
+
+    delegation.pack(&mut delegation_acc.data.borrow_mut())?;
+    let delivered = add_checked(creator_received, fee_received)?;
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, delivered, fee_received)?;
+    msg!("Delegated tip relayed by {}: {} to creator, {} fee", relayer.key, creator_received, fee_received);
+    emit_tip(accounts, tipper.key, creator.key, mint_acc.key, delivered, fee_received, 0, 0)
+}
+
+/// ScheduleTip: [schedule_id: u64, amount: u64, crank_reward: u64, execute_at: i64]
+/// Accounts: [scheduled_tip (w), vault (w), tipper (s,w), tipper_token (w), creator, mint, token_program,
+///            allowed_mint, system_program, blocked_tipper, (config), payer (optional, s,w)]
+/// Escrows `amount` plus `crank_reward` until `execute_at`, to be executed under the config's platform
+/// (platform 0 without one); the tipper picks any unused `schedule_id` and gets both rents back when
+/// the tip is executed or cancelled
+fn schedule_tip(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    schedule_id: u64,
+    amount: u64,
+    crank_reward: u64,
+    execute_at: i64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let scheduled_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[scheduled_acc, vault, tipper, tipper_token])?;
+    validation::token_accounts(&[tipper_token])?;
+    let platform_id = optional_platform(program_id, iter)?;
+    let payer = next_rent_payer(program_id, iter, tipper)?;
+    check_tipper_not_blocked(program_id, platform_id, blocked_tipper_acc, tipper.key)?;
+
+    let (scheduled_pda, scheduled_bump) =
+        get_scheduled_tip_pda(program_id, platform_id, tipper.key, creator.key, schedule_id);
+    if *scheduled_acc.key != scheduled_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (vault_pda, vault_bump) = get_scheduled_tip_vault_pda(program_id, &scheduled_pda);
+    if *vault.key != vault_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !scheduled_acc.data_is_empty() {
+        msg!("Scheduled tip {} already exists", schedule_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    if amount == 0 {
+        msg!("Tip amount must be > 0");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if execute_at <= Clock::get()?.unix_timestamp {
+        msg!("Scheduled tips must execute in the future");
+        return Err(ProgramError::InvalidArgument);
+    }
+    let total = add_checked(amount, crank_reward)?;
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    if check_allowed_mint(program_id, platform_id, allowed_mint_acc, &[tipper_token])?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+
+    let platform = platform_seed(platform_id);
+    let schedule_seed = schedule_id.to_le_bytes();
+    create_pda_account(
+        payer,
+        scheduled_acc,
+        system_program,
+        program_id,
+        SCHEDULED_TIP_SIZE,
+        &[b"scheduled", &platform, tipper.key.as_ref(), creator.key.as_ref(), &schedule_seed, &[scheduled_bump]],
+    )?;
+    create_vault(
+        payer,
+        vault,
+        mint_acc,
+        token_program,
+        system_program,
+        &scheduled_pda,
+        &[b"vault", scheduled_pda.as_ref(), &[vault_bump]],
+    )?;
+
+    // A Token-2022 transfer fee comes out of the tip, so the crank reward stays what the tipper offered
+    let received =
+        transfer_tokens(accounts, token_program, tipper_token, mint_acc, vault, tipper, total, mint.decimals, &[], &[])?;
+    let amount = received.checked_sub(crank_reward).filter(|&amount| amount > 0).ok_or(TipError::ZeroAmount)?;
+
+    let scheduled = ScheduledTip {
+        is_initialized: true,
+        tipper: *tipper.key,
+        creator: *creator.key,
+        mint: *mint_acc.key,
+        schedule_id,
+        amount,
+        crank_reward,
+        execute_at,
+    };
+    scheduled.pack(&mut scheduled_acc.data.borrow_mut())?;
+
+    msg!("Tip of {} scheduled for {}", amount, execute_at);
+    TipScheduled {
+        tipper: *tipper.key,
+        creator: *creator.key,
+        mint: *mint_acc.key,
+        schedule_id,
+        amount,
+        crank_reward,
+        execute_at,
+    }
+    .emit(accounts)
+}
+
+/// Load and check a scheduled tip of `platform_id` with its vault; returns it and its bump
+fn load_scheduled_tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    scheduled_acc: &AccountInfo,
+    vault: &AccountInfo,
+    mint_acc: &AccountInfo,
+    tipper: &AccountInfo,
+) -> Result<(ScheduledTip, u8), ProgramError> {
+    validation::program_owned(program_id, scheduled_acc)?;
+    let scheduled = ScheduledTip::unpack(&scheduled_acc.data.borrow())?;
+    if !scheduled.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let (pda, bump) =
+        get_scheduled_tip_pda(program_id, platform_id, &scheduled.tipper, &scheduled.creator, scheduled.schedule_id);
+    if *scheduled_acc.key != pda || *vault.key != get_scheduled_tip_vault_pda(program_id, &pda).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if scheduled.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    if scheduled.tipper != *tipper.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    Ok((scheduled, bump))
+}
+
+/// ExecuteScheduledTip: no data
+/// Accounts: [config (w), scheduled_tip (w), vault (w), creator, creator_token (w), treasury_token (w), mint,
+///            token_program, allowed_mint, tipper (w), cranker_token (w), tipper_stats (w), blocked_creator,
+///            blocked_tipper, mint_stats (w), then per treasury split entry: split_token (w)]
+/// Permissionless once due, with the config of the platform it was scheduled on. The fee and the
+/// tipper's rate limit apply as they are now, and the mint must still be allowed; the crank reward goes to
+/// `cranker_token` and both rents back to the tipper.
+fn execute_scheduled_tip(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let scheduled_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let creator_token = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let cranker_token = next_account_info(iter)?;
+    let tipper_stats_acc = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    validation::writable(&[config_acc, scheduled_acc, vault, creator_token, treasury_token, tipper, cranker_token])?;
+    validation::writable(&[tipper_stats_acc])?;
+    validation::token_accounts(&[vault, creator_token, treasury_token, cranker_token])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+
+    let (scheduled, bump) =
+        load_scheduled_tip(program_id, config.platform_id, scheduled_acc, vault, mint_acc, tipper)?;
+    if scheduled.creator != *creator.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if Clock::get()?.unix_timestamp < scheduled.execute_at {
+        msg!("Scheduled tip is due at {}", scheduled.execute_at);
+        return Err(TipError::NotYetDue.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let token_accounts = [vault, creator_token, treasury_token, cranker_token];
+    if check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &token_accounts)?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+    check_not_vesting(config.vesting_threshold, scheduled.amount)?;
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+    check_tipper_not_blocked(program_id, config.platform_id, blocked_tipper_acc, &scheduled.tipper)?;
+    let limit = config.rate_limit();
+    charge_rate_limit(program_id, config.platform_id, &scheduled.tipper, tipper_stats_acc, scheduled.amount, limit)?;
+
+    let amount = scheduled.amount;
+    let mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+    let platform = platform_seed(config.platform_id);
+    let schedule_seed = scheduled.schedule_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] =
+        &[b"scheduled", &platform, scheduled.tipper.as_ref(), scheduled.creator.as_ref(), &schedule_seed, &[bump]];
+
+    let crank_reward = match scheduled.crank_reward {
+        0 => 0,
+        reward => transfer_tokens(
+            accounts,
+            token_program, vault, mint_acc, cranker_token, scheduled_acc, reward, mint.decimals, &[signer_seeds], &[],
+        )?,
+    };
+    let creator_received = transfer_tokens(
+        accounts,
+        token_program, vault, mint_acc, creator_token, scheduled_acc, creator_amount, mint.decimals, &[signer_seeds],
+        &[],
+    )?;
+    let fee_received = pay_treasury_fee(
+        accounts, config.treasury_split(), fee, iter, token_program, vault, mint_acc, treasury_token, scheduled_acc,
+        mint.decimals, &[signer_seeds], &[],
+    )?;
+
+    close_escrow(scheduled_acc, vault, token_program, tipper, signer_seeds)?;
+
+    let delivered = add_checked(creator_received, fee_received)?;
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, delivered, fee_received)?;
+    msg!("Scheduled tip executed: {} to creator, {} fee, {} to the crank", creator_received, fee_received, crank_reward);
+    ScheduledTipExecuted {
+        tipper: scheduled.tipper,
+        creator: scheduled.creator,
+        schedule_id: scheduled.schedule_id,
+        amount,
+        cranker_token: *cranker_token.key,
+        crank_reward,
+    }
+    .emit(accounts)?;
+    emit_tip(accounts, &scheduled.tipper, &scheduled.creator, mint_acc.key, delivered, fee_received, 0, 0)
+}
+
+/// CancelScheduledTip: no data
+/// Accounts: [scheduled_tip (w), vault (w), tipper (s,w), tipper_token (w), mint, token_program,
+///            (config, for a tip scheduled on another platform than 0)]
+/// Refunds the tip and crank reward to any token account of the tipper's, at any time before it runs
+fn cancel_scheduled_tip(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let scheduled_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[scheduled_acc, vault, tipper, tipper_token])?;
+    validation::token_accounts(&[vault, tipper_token])?;
+    let platform_id = optional_platform(program_id, iter)?;
+
+    let (scheduled, bump) = load_scheduled_tip(program_id, platform_id, scheduled_acc, vault, mint_acc, tipper)?;
+    let mint = check_token_program(mint_acc, token_program)?;
+    let platform = platform_seed(platform_id);
+    let schedule_seed = scheduled.schedule_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] =
+        &[b"scheduled", &platform, scheduled.tipper.as_ref(), scheduled.creator.as_ref(), &schedule_seed, &[bump]];
+
+    let refund = token::unpack_account(vault)?.amount;
+    transfer_tokens(
+        accounts,
+        token_program, vault, mint_acc, tipper_token, scheduled_acc, refund, mint.decimals, &[signer_seeds], &[],
+    )?;
+    close_escrow(scheduled_acc, vault, token_program, tipper, signer_seeds)?;
+
+    msg!("Scheduled tip {} cancelled", scheduled.schedule_id);
+    ScheduledTipCancelled {
+        tipper: scheduled.tipper,
+        creator: scheduled.creator,
+        schedule_id: scheduled.schedule_id,
+        amount: refund,
+    }
+    .emit(accounts)
+}
+
+/// RolloverEpoch: no data
+/// Accounts: [epoch_stats (w), payer (s,w), system_program, (config)]
+/// Permissionless; the payer funds the new epoch's stats account
+fn rollover_epoch(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let epoch_acc = next_account_info(iter)?;
+    let payer = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[epoch_acc, payer])?;
+    let platform_id = optional_platform(program_id, iter)?;
+
+    let index = epoch_index(Clock::get()?.unix_timestamp);
+    let (pda, bump) = get_epoch_stats_pda(program_id, platform_id, index);
+    if *epoch_acc.key != pda {
+        msg!("Current epoch is {}", index);
+        return Err(TipError::WrongEpoch.into());
+    }
+    if !epoch_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        epoch_acc,
+        system_program,
+        program_id,
+        EPOCH_STATS_SIZE,
+        &[b"epoch", &platform_seed(platform_id), &index.to_le_bytes(), &[bump]],
+    )?;
+
+    let stats = EpochStats { is_initialized: true, epoch_index: index, tip_count: 0, volume: 0 };
+    stats.pack(&mut epoch_acc.data.borrow_mut())?;
+
+    msg!("Epoch {} opened", index);
+    EpochOpened { epoch_index: index }.emit(accounts)
+}
+
+/// SnapshotStats: no data
+/// Accounts: [snapshot (w), config, payer (s,w), system_program,
+///            then per creator: creator_stats, creator_snapshot (w)]
+/// Permissionless; the payer funds every snapshot it creates. Snapshots are never written again, so
+/// the first call of an epoch fixes its values and later calls only add missing creators.
+fn snapshot_stats(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let snapshot_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let payer = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[snapshot_acc, payer])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let platform_id = config.platform_id;
+    let now = Clock::get()?.unix_timestamp;
+    let index = epoch_index(now);
+    let epoch_seed = index.to_le_bytes();
+
+    let (pda, bump) = get_stats_snapshot_pda(program_id, platform_id, index);
+    if *snapshot_acc.key != pda {
+        msg!("Current epoch is {}", index);
+        return Err(TipError::WrongEpoch.into());
+    }
+    let platform_taken = snapshot_acc.data_is_empty();
+    if platform_taken {
+        create_pda_account(
+            payer,
+            snapshot_acc,
+            system_program,
+            program_id,
+            STATS_SNAPSHOT_SIZE,
+            &[b"snapshot", &platform_seed(platform_id), &epoch_seed, &[bump]],
+        )?;
+        StatsSnapshot {
+            is_initialized: true,
+            epoch_index: index,
+            taken_at: now,
+            total_tips: config.total_tips,
+            total_fees: config.total_fees,
+            total_treasury_fees: config.total_treasury_fees,
+            total_referral_fees: config.total_referral_fees,
+        }
+        .pack(&mut snapshot_acc.data.borrow_mut())?;
+    }
+
+    // A trailing Noop program only turns on event CPIs
+    let rest = match iter.as_slice() {
+        [rest @ .., last] if *last.key == compression::NOOP_ID => rest,
+        rest => rest,
+    };
+    if rest.len() % 2 != 0 {
+        msg!("Pass each creator as creator_stats, creator_snapshot");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let mut creators: u16 = 0;
+    for chunk in rest.chunks_exact(2) {
+        let [creator_stats_acc, creator_snapshot_acc] = chunk else { unreachable!() };
+        validation::writable(&[creator_snapshot_acc])?;
+        validation::program_owned(program_id, creator_stats_acc)?;
+        let stats = CreatorStats::load(&creator_stats_acc.data.borrow())?;
+        let (stats_pda, _) = get_creator_stats_pda(program_id, platform_id, &stats.creator);
+        if !stats.is_initialized || *creator_stats_acc.key != stats_pda {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        let (pda, bump) = get_creator_stats_snapshot_pda(program_id, platform_id, &stats.creator, index);
+        if *creator_snapshot_acc.key != pda {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if !creator_snapshot_acc.data_is_empty() {
+            continue;
+        }
+        create_pda_account(
+            payer,
+            creator_snapshot_acc,
+            system_program,
+            program_id,
+            CREATOR_STATS_SNAPSHOT_SIZE,
+            &[b"creator_snapshot", &platform_seed(platform_id), stats.creator.as_ref(), &epoch_seed, &[bump]],
+        )?;
+        CreatorStatsSnapshot {
+            is_initialized: true,
+            creator: stats.creator,
+            epoch_index: index,
+            taken_at: now,
+            tip_count: stats.tip_count,
+            total_received: stats.total_received,
+            largest_tip: stats.largest_tip,
+            total_usd_received: stats.total_usd_received,
+            total_staked: stats.total_staked,
+        }
+        .pack(&mut creator_snapshot_acc.data.borrow_mut())?;
+        creators = creators.saturating_add(1);
+    }
+
+    msg!("Epoch {} snapshot: platform {}, {} creators", index, platform_taken, creators);
+    StatsSnapshotTaken { epoch_index: index, platform: platform_taken, creators }.emit(accounts)
+}
+
+/// OpenLeaderboard / OpenTipperLeaderboard: no data
+/// Accounts: [leaderboard (w), payer (s,w), system_program, (config)]
+/// Permissionless; the payer funds the current epoch's creator leaderboard, or its tipper leaderboard
+/// with `tippers`
+fn open_leaderboard(program_id: &Pubkey, accounts: &[AccountInfo], tippers: bool) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let leaderboard_acc = next_account_info(iter)?;
+    let payer = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[leaderboard_acc, payer])?;
+    let platform_id = optional_platform(program_id, iter)?;
+
+    let index = epoch_index(Clock::get()?.unix_timestamp);
+    let (seed, (pda, bump)) = if tippers {
+        (&b"tipper_leaderboard"[..], get_tipper_leaderboard_pda(program_id, platform_id, index))
+    } else {
+        (&b"leaderboard"[..], get_leaderboard_pda(program_id, platform_id, index))
+    };
+    if *leaderboard_acc.key != pda {
+        msg!("Current epoch is {}", index);
+        return Err(TipError::WrongEpoch.into());
+    }
+    if !leaderboard_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        leaderboard_acc,
+        system_program,
+        program_id,
+        LEADERBOARD_SIZE,
+        &[seed, &platform_seed(platform_id), &index.to_le_bytes(), &[bump]],
+    )?;
+
+    let mut data = leaderboard_acc.data.borrow_mut();
+    let board: &mut Leaderboard = bytemuck::from_bytes_mut(&mut data);
+    board.discriminator = Leaderboard::DISCRIMINATOR;
+    board.epoch_index = index;
+
+    if tippers {
+        msg!("Tipper leaderboard for epoch {} opened", index);
+        return TipperLeaderboardOpened { epoch_index: index }.emit(accounts);
+    }
+    msg!("Leaderboard for epoch {} opened", index);
+    LeaderboardOpened { epoch_index: index }.emit(accounts)
+}
+
+/// FundRewardPool: [amount: u64, reward_per_epoch: u64, winners: u8]
+/// Accounts: [config, admin (s,w), admin_token (w), reward_pool (w), reward_vault (w), mint, token_program,
+///            allowed_mint, system_program, instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+/// Creates the reward pool on first use, fixing its mint; later calls top it up and replace the
+/// payout settings
+fn fund_reward_pool(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    reward_per_epoch: u64,
+    winners: u8,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let admin_token = next_account_info(iter)?;
+    let pool_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[admin, admin_token, pool_acc, vault])?;
+    validation::token_accounts(&[admin_token])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    if winners == 0 || winners > MAX_REWARD_WINNERS {
+        return Err(TipError::InvalidRewardWinners.into());
+    }
+    let mint = check_token_program(mint_acc, token_program)?;
+    if check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &[admin_token])?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+
+    let (vault_pda, vault_bump) = get_reward_vault_pda(program_id, config.platform_id);
+    if *vault.key != vault_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (pool_pda, pool_bump) = get_reward_pool_pda(program_id, config.platform_id);
+    if *pool_acc.key != pool_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut pool = if pool_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            pool_acc,
+            system_program,
+            program_id,
+            REWARD_POOL_SIZE,
+            &[b"reward_pool", &platform_seed(config.platform_id), &[pool_bump]],
+        )?;
+        let (config_pda, _) = get_config_pda(program_id, config.platform_id);
+        create_vault(
+            payer,
+            vault,
+            mint_acc,
+            token_program,
+            system_program,
+            &config_pda,
+            &[b"reward_vault", &platform_seed(config.platform_id), &[vault_bump]],
+        )?;
+        RewardPool {
+            is_initialized: true,
+            mint: *mint_acc.key,
+            reward_per_epoch: 0,
+            winners: 0,
+            total_funded: 0,
+            total_distributed: 0,
+        }
+    } else {
+        validation::program_owned(program_id, pool_acc)?;
+        RewardPool::unpack(&pool_acc.data.borrow())?
+    };
+    if pool.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+
+    let received = if amount > 0 {
+        transfer_tokens(accounts, token_program, admin_token, mint_acc, vault, admin, amount, mint.decimals, &[], &[])?
+    } else {
+        0
+    };
+    let old_value = audit_words(&[pool.reward_per_epoch, pool.winners as u64]);
+    pool.total_funded = add_checked(pool.total_funded, received)?;
+    pool.reward_per_epoch = reward_per_epoch;
+    pool.winners = winners;
+    pool.pack(&mut pool_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::FundRewardPool,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[reward_per_epoch, winners as u64, received]),
+    )?;
+
+    msg!("Reward pool funded with {}: {} per epoch to the top {}", received, reward_per_epoch, winners);
+    RewardPoolFunded { mint: pool.mint, amount: received, reward_per_epoch, winners }.emit(accounts)
+}
+
+/// DistributeEpochRewards: no data
+/// Accounts: [config, reward_pool (w), reward_vault (w), tipper_leaderboard (w), mint, token_program,
+///            cranker (s,w), then per paid leaderboard entry: tipper_token (w)]
+/// Pays the reward pool's `reward_per_epoch` (or what is left of it) to the top `winners` ranked
+/// tippers of an ended epoch, pro rata to the volume they sent, into their ATAs. Rounding dust stays
+/// in the pool. Closing the tipper leaderboard, with its rent going to the cranker, keeps the epoch
+/// from being paid twice, since OpenTipperLeaderboard only ever creates the current epoch's.
+fn distribute_epoch_rewards(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let pool_acc = next_account_info(iter)?;
+    let vault = next_account_info(iter)?;
+    let leaderboard_acc = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let cranker = next_account_info(iter)?;
+
+    if !cranker.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[pool_acc, vault, leaderboard_acc, cranker])?;
+    validation::program_owned(program_id, pool_acc)?;
+    validation::program_owned(program_id, leaderboard_acc)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+
+    let mut pool = RewardPool::unpack(&pool_acc.data.borrow())?;
+    if !pool.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if pool.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    if *pool_acc.key != get_reward_pool_pda(program_id, config.platform_id).0
+        || *vault.key != get_reward_vault_pda(program_id, config.platform_id).0
+    {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let (epoch, winners) = {
+        let data = leaderboard_acc.data.borrow();
+        let board = Leaderboard::load(&data)?;
+        let winners: Vec<LeaderboardEntry> =
+            board.entries.iter().take(pool.winners as usize).filter(|e| e.volume > 0).copied().collect();
+        (board.epoch_index, winners)
+    };
+    if *leaderboard_acc.key != get_tipper_leaderboard_pda(program_id, config.platform_id, epoch).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if epoch >= epoch_index(Clock::get()?.unix_timestamp) {
+        msg!("Epoch {} has not ended", epoch);
+        return Err(TipError::EpochNotOver.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let pot = pool.reward_per_epoch.min(token::unpack_account(vault)?.amount);
+    let weights: Vec<u128> = winners.iter().map(|e| e.volume as u128).collect();
+    let amounts = split_by_weight(pot, &weights)?;
+    let mut total = 0;
+    for (entry, &amount) in winners.iter().zip(&amounts) {
+        // Entries of a tipper leaderboard hold tippers
+        let tipper = entry.creator;
+        let tipper_token = next_account_info(iter)?;
+        if *tipper_token.key != token::get_associated_token_address(&tipper, mint_acc.key, token_program.key) {
+            msg!("Tipper token account must be the ATA of {}", tipper);
+            return Err(TipError::RewardTokenMismatch.into());
+        }
+        validation::writable(&[tipper_token])?;
+        if amount == 0 {
+            continue;
+        }
+        transfer_tokens(
+            accounts,
+            token_program,
+            vault,
+            mint_acc,
+            tipper_token,
+            config_acc,
+            amount,
+            mint.decimals,
+            &[&[b"config", &platform_seed(config.platform_id), &[config.bump]]],
+            &[],
+        )?;
+        total = add_checked(total, amount)?;
+        EpochRewardPaid { epoch_index: epoch, tipper, amount }.emit(accounts)?;
+    }
+
+    pool.total_distributed = add_checked(pool.total_distributed, total)?;
+    pool.pack(&mut pool_acc.data.borrow_mut())?;
+    close_pda(leaderboard_acc, cranker)?;
+
+    msg!("Epoch {} rewards: {} to {} tippers", epoch, total, winners.len());
+    EpochRewardsDistributed { epoch_index: epoch, mint: pool.mint, total, winners: winners.len() as u8 }.emit(accounts)
+}
+
+/// VerifyDeployment: no data
+/// Accounts: [config, program, program_data]
+/// Reads the upgradeable loader's accounts for this program and fails unless its upgrade authority is
+/// the one recorded at Initialize, so an integrator can put it ahead of its own instructions
+fn verify_deployment(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let program_acc = next_account_info(iter)?;
+    let program_data_acc = next_account_info(iter)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.expected_upgrade_authority == Pubkey::default() {
+        msg!("No upgrade authority was recorded at Initialize");
+        return Err(TipError::UpgradeAuthorityNotRecorded.into());
+    }
+
+    let (last_deploy_slot, upgrade_authority) = load_upgrade_authority(program_id, program_acc, program_data_acc)?;
+    if upgrade_authority != Some(config.expected_upgrade_authority) {
+        msg!("Upgrade authority is {:?}, expected {}", upgrade_authority, config.expected_upgrade_authority);
+        return Err(TipError::UpgradeAuthorityMismatch.into());
+    }
+
+    msg!("Deployment verified: upgrade authority {}", config.expected_upgrade_authority);
+    DeploymentVerified { upgrade_authority: config.expected_upgrade_authority, last_deploy_slot }.emit(accounts)
+}
+
+/// Slot of the last deploy and the upgrade authority (None once the program is immutable) from the
+/// upgradeable loader's bincode-encoded state: the program account is (tag 2 u32, programdata address),
+/// its programdata account (tag 3 u32, slot u64, Option<Pubkey>) followed by the program bytes
+fn load_upgrade_authority(
+    program_id: &Pubkey,
+    program_acc: &AccountInfo,
+    program_data_acc: &AccountInfo,
+) -> Result<(u64, Option<Pubkey>), ProgramError> {
+    if program_acc.key != program_id
+        || *program_acc.owner != bpf_loader_upgradeable::id()
+        || *program_data_acc.owner != bpf_loader_upgradeable::id()
+    {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let program = program_acc.data.borrow();
+    let program_tag = program.get(..4).ok_or(ProgramError::InvalidAccountData)?;
+    let program_data_key = program.get(4..36).ok_or(ProgramError::InvalidAccountData)?;
+    if program_tag != 2u32.to_le_bytes() || program_data_key != program_data_acc.key.as_ref() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let data = program_data_acc.data.borrow();
+    if data.get(..4).ok_or(ProgramError::InvalidAccountData)? != 3u32.to_le_bytes() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let slot = data
+        .get(4..12)
+        .ok_or(ProgramError::InvalidAccountData)?
+        .try_into()
+        .map(u64::from_le_bytes)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let authority = match data.get(12).ok_or(ProgramError::InvalidAccountData)? {
+        0 => None,
+        1 => Some(
+            data.get(13..45)
+                .ok_or(ProgramError::InvalidAccountData)?
+                .try_into()
+                .map(Pubkey::new_from_array)
+                .map_err(|_| ProgramError::InvalidAccountData)?,
+        ),
+        _ => return Err(ProgramError::InvalidAccountData),
+    };
+    Ok((slot, authority))
+}
+
+/// OpenAuditLog: no data
+/// Accounts: [config (w), admin (s,w), audit_log (w), system_program, instructions_sysvar (if admin_isolation),
+///            payer (optional, s,w)]
+/// Once opened the log cannot be turned off: every admin instruction must pass it after its own accounts
+fn open_audit_log(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let audit_log_acc = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, audit_log_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    let (pda, bump) = get_audit_log_pda(program_id, config.platform_id);
+    if *audit_log_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !audit_log_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        audit_log_acc,
+        system_program,
+        program_id,
+        AUDIT_LOG_SIZE,
+        &[b"audit_log", &platform_seed(config.platform_id), &[bump]],
+    )?;
+    bytemuck::from_bytes_mut::<AuditLog>(&mut audit_log_acc.data.borrow_mut()).discriminator = AuditLog::DISCRIMINATOR;
+
+    config.audit_log_enabled = true;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+
+    msg!("Audit log opened");
+    AuditLogOpened { audit_log: pda }.emit(accounts)
+}
+
+/// OpenVolumeWindow: no data
+/// Accounts: [volume_window (w), payer (s,w), system_program, (config)]
+/// Permissionless; the payer funds the platform-wide hourly volume ring buffer
+fn open_volume_window(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let volume_window_acc = next_account_info(iter)?;
+    let payer = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[volume_window_acc, payer])?;
+    let platform_id = optional_platform(program_id, iter)?;
+
+    let (pda, bump) = get_volume_window_pda(program_id, platform_id);
+    if *volume_window_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !volume_window_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        volume_window_acc,
+        system_program,
+        program_id,
+        VOLUME_WINDOW_SIZE,
+        &[b"volume_window", &platform_seed(platform_id), &[bump]],
+    )?;
+
+    let mut data = volume_window_acc.data.borrow_mut();
+    let window: &mut VolumeWindow = bytemuck::from_bytes_mut(&mut data);
+    window.discriminator = VolumeWindow::DISCRIMINATOR;
+    window.head_hour = Clock::get()?.unix_timestamp.div_euclid(VOLUME_BUCKET_SECS);
+
+    msg!("Volume window opened");
+    VolumeWindowOpened { volume_window: pda }.emit(accounts)
+}
+
+/// CloseConfig: no data
+/// Accounts: [config (w), admin (s), recipient (w), instructions_sysvar (if admin_isolation)]
+/// Tipping must be paused first; Initialize can recreate the config afterwards
+fn close_config(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let recipient = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, recipient])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if !config.paused {
+        msg!("Pause tipping before closing the config");
+        return Err(TipError::NotPaused.into());
+    }
+
+    audit(program_id, accounts, &config, AdminAction::CloseConfig, admin.key, recipient.key, [0; 32], [0; 32])?;
+    close_pda(config_acc, recipient)?;
+    msg!("Config closed");
+    ConfigClosed { recipient: *recipient.key }.emit(accounts)
+}
+
+/// CloseAccount: [kind: ClosableAccount]
+/// Accounts: [account (w), authority (s), recipient (w), (config, for stats accounts and subscriptions)]
+/// A creator profile needs platform 0's config, writable, to free its slot under `max_creators`
+fn close_account(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    kind: ClosableAccount,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let account = next_account_info(iter)?;
+    let authority = next_account_info(iter)?;
+    let recipient = next_account_info(iter)?;
+    let config_acc = iter.as_slice().first();
+
+    if !authority.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[account, recipient])?;
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let platform_id = optional_platform(program_id, iter)?;
+
+    let data = account.data.borrow();
+    let (pda, owner) = match kind {
+        ClosableAccount::CreatorStats => {
+            let stats = CreatorStats::load(&data)?;
+            (get_creator_stats_pda(program_id, platform_id, &stats.creator).0, stats.creator)
+        }
+        ClosableAccount::TipperStats => {
+            let stats = TipperStats::load(&data)?;
+            (get_tipper_stats_pda(program_id, platform_id, &stats.tipper).0, stats.tipper)
+        }
+        ClosableAccount::Subscription => {
+            let sub = Subscription::unpack(&data)?;
+            (get_subscription_pda(program_id, platform_id, &sub.tipper, &sub.creator).0, sub.tipper)
+        }
+        ClosableAccount::Campaign => {
+            let campaign = Campaign::load(&data)?;
+            if !campaign.finalized {
+                msg!("Finalize the campaign before closing it");
+                return Err(TipError::CampaignActive.into());
+            }
+            (get_campaign_pda(program_id, &campaign.creator, campaign.campaign_id).0, campaign.creator)
+        }
+        ClosableAccount::CreatorProfile => {
+            let profile = CreatorProfile::load(&data)?;
+            (get_creator_profile_pda(program_id, &profile.creator).0, profile.creator)
+        }
+    };
+    drop(data);
+
+    if *account.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if owner != *authority.key {
+        return Err(TipError::Unauthorized.into());
+    }
+
+    if kind == ClosableAccount::CreatorProfile {
+        let config_acc = config_acc.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let mut config = creator_registry(program_id, config_acc)?;
+        config.registered_creators = config.registered_creators.saturating_sub(1);
+        config.pack(&mut config_acc.data.borrow_mut())?;
+    }
+    close_pda(account, recipient)?;
+    msg!("{:?} account closed", kind);
+    AccountClosed { account: *account.key, kind, recipient: *recipient.key }.emit(accounts)
+}
+
+/// MigrateConfig: no data
+/// Accounts: [config (w), admin (s,w), system_program, payer (optional, s,w)]
+/// Reads any earlier layout, reallocs to CONFIG_SIZE and rewrites it at CONFIG_VERSION
+fn migrate_config(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, admin])?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    validation::program_owned(program_id, config_acc)?;
+    let mut config = TipConfig::load_any_version(&config_acc.data.borrow())?;
+    // Configs older than version 3 don't store their bump, so derive it here once; layouts from
+    // before platforms read as platform 0
+    let (pda, bump) = get_config_pda(program_id, config.platform_id);
+    if *config_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if config.version == CONFIG_VERSION && config_acc.data_len() == CONFIG_SIZE {
+        msg!("Config already at version {}", CONFIG_VERSION);
+        return Ok(());
+    }
+
+    grow_account(config_acc, payer, system_program, CONFIG_SIZE)?;
+
+    let old_version = config.version;
+    config.version = CONFIG_VERSION;
+    config.bump = bump;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+
+    msg!("Config migrated from version {} to {}", old_version, CONFIG_VERSION);
+    ConfigMigrated { from_version: old_version, to_version: CONFIG_VERSION }.emit(accounts)
+}
+
+/// DerivePdas: no data
+/// Accounts: [(config), (mint)]
+/// Returns the Borsh-encoded ProgramPdas of the config's platform, or platform 0, for the current epoch
+/// via return data; passing a mint adds its PDAs
+fn derive_pdas_ix(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let platform_id = optional_platform(program_id, iter)?;
+    let mint = iter.next().map(|mint_acc| mint_acc.key);
+    let pdas = derive_pdas(program_id, platform_id, epoch_index(Clock::get()?.unix_timestamp), mint);
+    set_return_data(&borsh::to_vec(&pdas)?);
+    Ok(())
+}
+
+/// CreatePromoCode: [code_hash: [u8; 32], discount_bps: u16, max_uses: u64, expiry_ts: i64]
+/// Accounts: [promo_code (w), config, admin (s,w), system_program, instructions_sysvar (if admin_isolation),
+///            payer (optional, s,w)]
+/// `code_hash` is the SHA-256 of the code (see `promo_code_hash`); a `Tip` passing the PromoCode before
+/// `expiry_ts` pays `discount_bps` less of its fee, up to `max_uses` times
+fn create_promo_code(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    code_hash: [u8; 32],
+    discount_bps: u16,
+    max_uses: u64,
+    expiry_ts: i64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let promo_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[promo_acc, admin])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    if discount_bps == 0 || discount_bps > 10_000 {
+        msg!("Promo discount must be 1-10000 bps");
+        return Err(TipError::FeeTooHigh.into());
+    }
+    if max_uses == 0 {
+        msg!("Promo code needs at least one use");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if expiry_ts <= Clock::get()?.unix_timestamp {
+        msg!("Promo code expiry must be in the future");
+        return Err(TipError::InvalidExpiry.into());
+    }
+
+    let (pda, bump) = get_promo_code_pda(program_id, config.platform_id, &code_hash);
+    if *promo_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !promo_acc.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    create_pda_account(
+        payer,
+        promo_acc,
+        system_program,
+        program_id,
+        PROMO_CODE_SIZE,
+        &[b"promo", &platform_seed(config.platform_id), &code_hash, &[bump]],
+    )?;
+    PromoCode { is_initialized: true, code_hash, discount_bps, remaining_uses: max_uses, expiry_ts }
+        .pack(&mut promo_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::CreatePromoCode,
+        admin.key,
+        &pda,
+        [0; 32],
+        audit_words(&[discount_bps as u64, max_uses, expiry_ts as u64]),
+    )?;
+
+    msg!("Promo code {}: {} bps off, {} uses", pda, discount_bps, max_uses);
+    PromoCodeCreated { promo_code: pda, discount_bps, max_uses, expiry_ts }.emit(accounts)
+}
+
+/// SetCharity: [max_donation_bps: u16]
+/// Accounts: [charity (w), config, admin (s,w), charity_token, system_program,
+///            instructions_sysvar (if admin_isolation), payer (optional, s,w)]
+/// Registers `charity_token` as the charity for tips in its mint, creating the Charity on first use;
+/// its donation totals carry over when the admin swaps in another charity
+fn set_charity(program_id: &Pubkey, accounts: &[AccountInfo], max_donation_bps: u16) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let charity_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+    let charity_token = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[charity_acc, admin])?;
+    validation::token_accounts(&[charity_token])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    let payer = next_rent_payer(program_id, iter, admin)?;
+
+    if max_donation_bps > 10_000 {
+        msg!("Donations cannot exceed 10000 bps");
+        return Err(TipError::InvalidDonation.into());
+    }
+    let mint = token::unpack_account(charity_token)?.mint;
+    let (pda, bump) = get_charity_pda(program_id, config.platform_id, &mint);
+    if *charity_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut charity = if charity_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            charity_acc,
+            system_program,
+            program_id,
+            CHARITY_SIZE,
+            &[b"charity", &platform_seed(config.platform_id), mint.as_ref(), &[bump]],
+        )?;
+        Charity {
+            is_initialized: true,
+            mint,
+            charity_token: Pubkey::default(),
+            max_donation_bps: 0,
+            total_donated: 0,
+            donation_count: 0,
+        }
+    } else {
+        validation::program_owned(program_id, charity_acc)?;
+        Charity::unpack(&charity_acc.data.borrow())?
+    };
+    let old_value = audit_words(&[charity.max_donation_bps as u64]);
+    charity.charity_token = *charity_token.key;
+    charity.max_donation_bps = max_donation_bps;
+    charity.pack(&mut charity_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetCharity,
+        admin.key,
+        charity_token.key,
+        old_value,
+        audit_words(&[max_donation_bps as u64]),
+    )?;
+
+    msg!("Charity for {}: {}, up to {} bps", mint, charity_token.key, max_donation_bps);
+    CharitySet { mint, charity_token: *charity_token.key, max_donation_bps }.emit(accounts)
+}
+
+/// ClosePromoCode: no data
+/// Accounts: [promo_code (w), config, admin (s,w), instructions_sysvar (if admin_isolation)]
+/// Ends a promo code early or reclaims the rent of a used-up one
+fn close_promo_code(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let promo_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[promo_acc, admin])?;
+    validation::program_owned(program_id, promo_acc)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let promo = PromoCode::unpack(&promo_acc.data.borrow())?;
+    if *promo_acc.key != get_promo_code_pda(program_id, config.platform_id, &promo.code_hash).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    close_pda(promo_acc, admin)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::ClosePromoCode,
+        admin.key,
+        promo_acc.key,
+        audit_words(&[promo.discount_bps as u64, promo.remaining_uses, promo.expiry_ts as u64]),
+        [0; 32],
+    )?;
+
+    msg!("Promo code {} closed with {} uses left", promo_acc.key, promo.remaining_uses);
+    PromoCodeClosed { promo_code: *promo_acc.key, remaining_uses: promo.remaining_uses }.emit(accounts)
+}
+
+/// GetConfig: no data
+/// Accounts: [config]
+/// Returns the Borsh-encoded TipConfig (without its discriminator) via return data
+fn get_config(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    set_return_data(&borsh::to_vec(&config)?);
+    Ok(())
+}
+
+/// IsPaused: no data
+/// Accounts: [config]
+/// Returns `[1]` via return data while the platform is paused, `[0]` otherwise
+fn is_paused(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    set_return_data(&[config.paused as u8]);
+    Ok(())
+}
+
+/// GetCreatorStats: no data
+/// Accounts: [creator_stats, creator, (config)]
+/// Returns the Borsh-encoded CreatorStats (without its discriminator) via return data, with fields
+/// missing from a legacy account at zero, and all zero but `creator` if they have never been tipped
+fn get_creator_stats(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let creator_stats_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let platform_id = optional_platform(program_id, iter)?;
+
+    if *creator_stats_acc.key != get_creator_stats_pda(program_id, platform_id, creator.key).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let stats = if creator_stats_acc.data_is_empty() {
+        CreatorStats { creator: *creator.key, ..CreatorStats::load(&[])? }
+    } else {
+        validation::program_owned(program_id, creator_stats_acc)?;
+        CreatorStats::load(&creator_stats_acc.data.borrow())?
+    };
+    set_return_data(&borsh::to_vec(&stats)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::collections::HashMap;
+    use std::sync::Once;
+
+    use solana_program::entrypoint::{MAX_PERMITTED_DATA_INCREASE, SUCCESS};
+    use solana_program::instruction::{AccountMeta, Instruction};
+    use solana_program::native_token::LAMPORTS_PER_SOL;
+    use solana_program::program_option::COption;
+    use solana_program::program_pack::Pack;
+    use solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+    use solana_program::program_utils::limited_deserialize;
+    use solana_program::system_instruction::SystemInstruction;
+    use solana_program::system_program;
+    use solana_program::sysvar::instructions::{BorrowedAccountMeta, BorrowedInstruction};
+    use spl_token::instruction::TokenInstruction;
+    use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+    use super::*;
+
+    fn overflow() -> ProgramError {
+        TipError::MathOverflow.into()
+    }
+
+    /// Unix time of every processor test
+    const NOW: i64 = 1_700_000_000;
+    const TIPPER_BALANCE: u64 = 1_000_000;
+
+    thread_local! {
+        /// (name, data) of each event the current test logged
+        static EVENTS: RefCell<Vec<(Vec<u8>, Vec<u8>)>> = const { RefCell::new(Vec::new()) };
+        static CPI_COUNT: Cell<usize> = const { Cell::new(0) };
+        static RETURN_DATA: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Syscalls for processor tests: a fixed clock, default rent, captured events, and SPL Token transfers
+    /// and approvals applied to the token accounts passed in
+    struct TestRuntime;
+
+    impl SyscallStubs for TestRuntime {
+        fn sol_log(&self, _message: &str) {}
+
+        fn sol_log_data(&self, fields: &[&[u8]]) {
+            EVENTS.with(|events| events.borrow_mut().push((fields[0].to_vec(), fields[1].to_vec())));
+        }
+
+        fn sol_set_return_data(&self, data: &[u8]) {
+            RETURN_DATA.with(|return_data| *return_data.borrow_mut() = data.to_vec());
+        }
+
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            let clock = Clock { slot: 100, unix_timestamp: NOW, ..Clock::default() };
+            unsafe { var_addr.cast::<Clock>().write(clock) };
+            SUCCESS
+        }
+
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { var_addr.cast::<Rent>().write(Rent::default()) };
+            SUCCESS
+        }
+
+        fn sol_invoke_signed(&self, ix: &Instruction, infos: &[AccountInfo], _seeds: &[&[&[u8]]]) -> ProgramResult {
+            CPI_COUNT.with(|count| count.set(count.get() + 1));
+            let info = |i: usize| infos.iter().find(|acc| *acc.key == ix.accounts[i].pubkey).unwrap();
+            if ix.program_id == system_program::id() {
+                return invoke_system(&ix.data, info);
+            }
+            if ix.program_id == compression::NOOP_ID {
+                return Ok(());
+            }
+            assert_eq!(ix.program_id, spl_token::id(), "only System, Noop and SPL Token CPIs are simulated");
+            match TokenInstruction::unpack(&ix.data)? {
+                TokenInstruction::TransferChecked { amount, decimals } => {
+                    let (source, mint, destination, authority) = (info(0), info(1), info(2), info(3));
+                    if Mint::unpack(&mint.data.borrow())?.decimals != decimals {
+                        return Err(spl_token::error::TokenError::MintDecimalsMismatch.into());
+                    }
+                    let mut from = TokenAccount::unpack(&source.data.borrow())?;
+                    if from.delegate == COption::Some(*authority.key) {
+                        from.delegated_amount =
+                            from.delegated_amount.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+                    } else {
+                        assert_eq!(from.owner, *authority.key);
+                    }
+                    from.amount = from.amount.checked_sub(amount).ok_or(ProgramError::InsufficientFunds)?;
+                    TokenAccount::pack(from, &mut source.data.borrow_mut())?;
+                    let mut to = TokenAccount::unpack(&destination.data.borrow())?;
+                    to.amount += amount;
+                    TokenAccount::pack(to, &mut destination.data.borrow_mut())
+                }
+                TokenInstruction::Approve { amount } => {
+                    let (source, delegate) = (info(0), info(1));
+                    let mut account = TokenAccount::unpack(&source.data.borrow())?;
+                    account.delegate = COption::Some(*delegate.key);
+                    account.delegated_amount = amount;
+                    TokenAccount::pack(account, &mut source.data.borrow_mut())
+                }
+                other => panic!("unexpected token instruction {:?}", other),
+            }
+        }
+    }
+
+    /// The System Program instructions `create_pda_account` and `grow_account` use, applied in place
+    /// (the ledger lays accounts out as the runtime does, so `realloc` works)
+    fn invoke_system<'a, 'b>(data: &[u8], info: impl Fn(usize) -> &'a AccountInfo<'b>) -> ProgramResult
+    where
+        'b: 'a,
+    {
+        let move_lamports = |from: &AccountInfo, to: &AccountInfo, lamports: u64| {
+            let mut from = from.lamports.borrow_mut();
+            **from = from.checked_sub(lamports).ok_or(ProgramError::InsufficientFunds)?;
+            **to.lamports.borrow_mut() += lamports;
+            Ok::<_, ProgramError>(())
+        };
+        match limited_deserialize(data, 1232).map_err(|_| ProgramError::InvalidInstructionData)? {
+            SystemInstruction::CreateAccount { lamports, space, owner } => {
+                let (funder, account) = (info(0), info(1));
+                assert_eq!(account.lamports(), 0, "create_account refuses funded addresses");
+                move_lamports(funder, account, lamports)?;
+                account.realloc(space as usize, true)?;
+                account.assign(&owner);
+                Ok(())
+            }
+            SystemInstruction::Transfer { lamports } => move_lamports(info(0), info(1), lamports),
+            SystemInstruction::Allocate { space } => info(0).realloc(space as usize, true),
+            SystemInstruction::Assign { owner } => {
+                info(0).assign(&owner);
+                Ok(())
+            }
+            other => panic!("unexpected system instruction {:?}", other),
+        }
+    }
+
+    /// Data of each `name` event the current test logged
+    fn events(name: &[u8]) -> Vec<Vec<u8>> {
+        EVENTS.with(|events| events.borrow().iter().filter(|(n, _)| n == name).map(|(_, data)| data.clone()).collect())
+    }
+
+    fn cpi_count() -> usize {
+        CPI_COUNT.with(Cell::get)
+    }
+
+    /// What the last instruction set as return data
+    fn return_data() -> Vec<u8> {
+        RETURN_DATA.with(|return_data| return_data.borrow().clone())
+    }
+
+    /// One ledger account. Its buffer is laid out as the runtime serializes accounts for a program, so
+    /// `AccountInfo::realloc` works: the original data length sits 4 bytes before the key and the
+    /// current one 8 bytes before the data, which is followed by the most a realloc may add.
+    struct TestAccount {
+        lamports: u64,
+        owner: Pubkey,
+        buf: Vec<u8>,
+    }
+
+    const KEY_OFFSET: usize = 4;
+    const DATA_OFFSET: usize = 48;
+
+    impl TestAccount {
+        fn new(key: &Pubkey, lamports: u64, data: &[u8], owner: Pubkey) -> Self {
+            let mut buf = vec![0; DATA_OFFSET + data.len() + MAX_PERMITTED_DATA_INCREASE];
+            buf[KEY_OFFSET..KEY_OFFSET + 32].copy_from_slice(key.as_ref());
+            buf[DATA_OFFSET..DATA_OFFSET + data.len()].copy_from_slice(data);
+            let mut account = Self { lamports, owner, buf };
+            account.set_len(data.len());
+            account
+        }
+
+        fn len(&self) -> usize {
+            u64::from_le_bytes(self.buf[DATA_OFFSET - 8..DATA_OFFSET].try_into().unwrap()) as usize
+        }
+
+        fn set_len(&mut self, len: usize) {
+            self.buf[DATA_OFFSET - 8..DATA_OFFSET].copy_from_slice(&(len as u64).to_le_bytes());
+            self.buf[..KEY_OFFSET].copy_from_slice(&(len as u32).to_le_bytes());
+        }
+
+        fn data(&self) -> &[u8] {
+            &self.buf[DATA_OFFSET..DATA_OFFSET + self.len()]
+        }
+
+        /// Lend the account to an instruction; the data may grow by up to MAX_PERMITTED_DATA_INCREASE
+        fn info(&mut self) -> AccountInfo<'_> {
+            let len = self.len();
+            // A later realloc may only grow past the length the instruction started with
+            if DATA_OFFSET + len + MAX_PERMITTED_DATA_INCREASE > self.buf.len() {
+                self.buf.resize(DATA_OFFSET + len + MAX_PERMITTED_DATA_INCREASE, 0);
+            }
+            self.set_len(len);
+            let (head, data) = self.buf.split_at_mut(DATA_OFFSET);
+            // SAFETY: the key lives in this account's own buffer, where `original_data_len` expects it
+            let key = unsafe { &*(head[KEY_OFFSET..].as_ptr() as *const Pubkey) };
+            AccountInfo::new(key, false, false, &mut self.lamports, &mut data[..len], &self.owner, false, 0)
+        }
+    }
+
+    /// Accounts for processor tests by address. `process` lends them to an instruction with its signer
+    /// and writable flags, one shared account however often it is listed, and creates any it lists
+    /// that are missing as empty system accounts.
+    #[derive(Default)]
+    struct TestLedger {
+        accounts: HashMap<Pubkey, TestAccount>,
+    }
+
+    impl TestLedger {
+        fn new() -> Self {
+            static RUNTIME: Once = Once::new();
+            RUNTIME.call_once(|| {
+                set_syscall_stubs(Box::new(TestRuntime));
+            });
+            EVENTS.with(|events| events.borrow_mut().clear());
+            CPI_COUNT.with(|count| count.set(0));
+            RETURN_DATA.with(|return_data| return_data.borrow_mut().clear());
+            Self::default()
+        }
+
+        /// Store a rent-exempt account
+        fn set(&mut self, key: Pubkey, owner: Pubkey, data: Vec<u8>) {
+            let lamports = Rent::default().minimum_balance(data.len());
+            self.accounts.insert(key, TestAccount::new(&key, lamports, &data, owner));
+        }
+
+        /// Give a wallet lamports to pay rent with
+        fn fund(&mut self, key: Pubkey, lamports: u64) {
+            self.accounts.entry(key).or_insert_with(|| TestAccount::new(&key, 0, &[], system_program::id())).lamports +=
+                lamports;
+        }
+
+        fn lamports(&self, key: &Pubkey) -> u64 {
+            self.accounts[key].lamports
+        }
+
+        fn owner(&self, key: &Pubkey) -> Pubkey {
+            self.accounts[key].owner
+        }
+
+        fn data(&self, key: &Pubkey) -> &[u8] {
+            self.accounts[key].data()
+        }
+
+        /// Fill the instructions sysvar as the runtime would while executing `transaction[current]`
+        fn set_transaction(&mut self, transaction: &[Instruction], current: u16) {
+            let borrowed: Vec<BorrowedInstruction> = transaction
+                .iter()
+                .map(|ix| BorrowedInstruction {
+                    program_id: &ix.program_id,
+                    accounts: ix
+                        .accounts
+                        .iter()
+                        .map(|meta| BorrowedAccountMeta {
+                            pubkey: &meta.pubkey,
+                            is_signer: meta.is_signer,
+                            is_writable: meta.is_writable,
+                        })
+                        .collect(),
+                    data: &ix.data,
+                })
+                .collect();
+            let mut data = sysvar::instructions::construct_instructions_data(&borrowed);
+            sysvar::instructions::store_current_index(&mut data, current);
+            self.set(sysvar::instructions::id(), sysvar::id(), data);
+        }
+
+        fn process(&mut self, ix: &Instruction) -> ProgramResult {
+            for meta in &ix.accounts {
+                let key = meta.pubkey;
+                self.accounts.entry(key).or_insert_with(|| TestAccount::new(&key, 0, &[], system_program::id()));
+            }
+            let infos: HashMap<Pubkey, AccountInfo> =
+                self.accounts.iter_mut().map(|(key, account)| (*key, account.info())).collect();
+            let accounts: Vec<AccountInfo> = ix
+                .accounts
+                .iter()
+                .map(|meta| AccountInfo {
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                    ..infos[&meta.pubkey].clone()
+                })
+                .collect();
+            let result = process_instruction(&ix.program_id, &accounts, &ix.data);
+            drop((accounts, infos));
+            // As at the end of a transaction, accounts left without lamports are deleted
+            for (key, account) in self.accounts.iter_mut().filter(|(_, account)| account.lamports == 0) {
+                *account = TestAccount::new(key, 0, &[], system_program::id());
+            }
+            result
+        }
+    }
+
+    fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = vec![0; TokenAccount::LEN];
+        let account =
+            TokenAccount { mint: *mint, owner: *owner, amount, state: AccountState::Initialized, ..Default::default() };
+        TokenAccount::pack(account, &mut data).unwrap();
+        data
+    }
+
+    fn packed<T: AccountType>(value: &T, size: usize) -> Vec<u8> {
+        let mut data = vec![0; size];
+        value.pack(&mut data).unwrap();
+        data
+    }
+
+    /// A config charging `fee_bps`, with a tipper holding TIPPER_BALANCE of one mint and token
+    /// accounts of it for the creator and the treasury
+    struct TipFixture {
+        program_id: Pubkey,
+        admin: Pubkey,
+        tipper: Pubkey,
+        tipper_token: Pubkey,
+        creator: Pubkey,
+        creator_token: Pubkey,
+        treasury_token: Pubkey,
+        mint: Pubkey,
+        ledger: TestLedger,
+    }
+
+    impl TipFixture {
+        fn new(fee_bps: u16) -> Self {
+            Self::with_decimals(fee_bps, 6)
+        }
+
+        fn with_decimals(fee_bps: u16, decimals: u8) -> Self {
+            let program_id = Pubkey::new_unique();
+            let (admin, tipper, creator, treasury) =
+                (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+            let mint = Pubkey::new_unique();
+            let tipper_token = Pubkey::new_unique();
+            let creator_token = token::get_associated_token_address(&creator, &mint, &spl_token::id());
+            let treasury_token = Pubkey::new_unique();
+            let mut ledger = TestLedger::new();
+
+            let (config_pda, bump) = get_config_pda(&program_id, 0);
+            let mut config = TipConfig::unpack_or_zeroed(&[0; CONFIG_SIZE]).unwrap();
+            config.is_initialized = true;
+            config.version = CONFIG_VERSION;
+            config.bump = bump;
+            config.admin = admin;
+            config.treasury = treasury_token;
+            config.fee_bps = fee_bps;
+            ledger.set(config_pda, program_id, packed(&config, CONFIG_SIZE));
+            let mut mint_data = vec![0; Mint::LEN];
+            Mint::pack(Mint { decimals, is_initialized: true, ..Default::default() }, &mut mint_data).unwrap();
+            ledger.set(mint, spl_token::id(), mint_data);
+            let allowed = AllowedMint { is_initialized: true, mint, min_tip_amount: 0, price_feed: Pubkey::default() };
+            ledger.set(get_allowed_mint_pda(&program_id, 0, &mint).0, program_id, packed(&allowed, ALLOWED_MINT_SIZE));
+            ledger.set(tipper_token, spl_token::id(), token_account(&mint, &tipper, TIPPER_BALANCE));
+            ledger.set(creator_token, spl_token::id(), token_account(&mint, &creator, 0));
+            ledger.set(treasury_token, spl_token::id(), token_account(&mint, &treasury, 0));
+            // Rent-exempt stats already exist, so tips never need a system program CPI
+            ledger.set(get_creator_stats_pda(&program_id, 0, &creator).0, program_id, vec![0; CREATOR_STATS_SIZE]);
+            ledger.set(get_tipper_stats_pda(&program_id, 0, &tipper).0, program_id, vec![0; TIPPER_STATS_SIZE]);
+            let mint_stats = MintStats {
+                is_initialized: true,
+                mint,
+                tip_count: 0,
+                total_volume: 0,
+                total_fees: 0,
+                total_treasury_fees: 0,
+                total_referral_fees: 0,
+                fee_target: 0,
+            };
+            ledger.set(get_mint_stats_pda(&program_id, 0, &mint).0, program_id, packed(&mint_stats, MINT_STATS_SIZE));
+
+            Self { program_id, admin, tipper, tipper_token, creator, creator_token, treasury_token, mint, ledger }
+        }
+
+        fn config_pda(&self) -> Pubkey {
+            get_config_pda(&self.program_id, 0).0
+        }
+
+        fn config(&self) -> TipConfig {
+            TipConfig::unpack(self.ledger.data(&self.config_pda())).unwrap()
+        }
+
+        fn balance(&self, token: &Pubkey) -> u64 {
+            TokenAccount::unpack(self.ledger.data(token)).unwrap().amount
+        }
+
+        fn tip(&mut self, amount: u64, extras: instruction::TipExtras) -> ProgramResult {
+            let ix = self.tip_ix(amount, extras);
+            self.ledger.process(&ix)
+        }
+
+        fn tip_ix(&self, amount: u64, extras: instruction::TipExtras) -> Instruction {
+            instruction::tip(
+                &self.program_id,
+                0,
+                &self.tipper,
+                &self.tipper_token,
+                &self.creator,
+                &self.creator_token,
+                &self.treasury_token,
+                &self.mint,
+                &spl_token::id(),
+                amount,
+                "",
+                extras,
+            )
+        }
+    }
+
+    #[test]
+    fn add_checked_at_u64_max() {
+        assert_eq!(add_checked(u64::MAX, 0), Ok(u64::MAX));
+        assert_eq!(add_checked(u64::MAX - 1, 1), Ok(u64::MAX));
+        assert_eq!(add_checked(u64::MAX, 1), Err(overflow()));
+        assert_eq!(add_checked(u64::MAX, u64::MAX), Err(overflow()));
+    }
+
+    #[test]
+    fn upgrade_authority_rejects_truncated_loader_accounts() {
+        let program_id = Pubkey::new_unique();
+        let program_data_key = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let loader = bpf_loader_upgradeable::id();
+        let mut program_data = [&3u32.to_le_bytes()[..], &42u64.to_le_bytes(), &[1], authority.as_ref()].concat();
+        let program_state = [&2u32.to_le_bytes()[..], program_data_key.as_ref()].concat();
+        let mut program = TestAccount::new(&program_id, 1, &program_state, loader);
+        for len in [0, 4, 12, 44] {
+            let mut truncated = TestAccount::new(&program_data_key, 1, &program_data[..len], loader);
+            assert_eq!(
+                load_upgrade_authority(&program_id, &program.info(), &truncated.info()),
+                Err(ProgramError::InvalidAccountData)
+            );
+        }
+        let mut full = TestAccount::new(&program_data_key, 1, &program_data, loader);
+        assert_eq!(load_upgrade_authority(&program_id, &program.info(), &full.info()), Ok((42, Some(authority))));
+        program_data[12] = 0;
+        let mut frozen = TestAccount::new(&program_data_key, 1, &program_data[..13], loader);
+        assert_eq!(load_upgrade_authority(&program_id, &program.info(), &frozen.info()), Ok((42, None)));
+    }
+
+    #[test]
+    fn rate_window_rejects_past_the_limit_and_resets_after_it() {
+        let limit = RateLimit { window_secs: 60, max_tips: 2, max_volume: 100 };
+        let mut stats = TipperStats::load(&[]).unwrap();
+        assert_eq!(charge_rate_window(&mut stats, 1_000, 1, 40, limit), Ok(()));
+        assert_eq!(charge_rate_window(&mut stats, 1_030, 1, 40, limit), Ok(()));
+        assert_eq!(charge_rate_window(&mut stats, 1_059, 1, 0, limit), Err(TipError::RateLimited.into()));
+
+        let mut stats = TipperStats::load(&[]).unwrap();
+        charge_rate_window(&mut stats, 1_000, 1, 90, limit).unwrap();
+        assert_eq!(charge_rate_window(&mut stats, 1_010, 1, 11, limit), Err(TipError::RateLimited.into()));
+        assert_eq!(charge_rate_window(&mut stats, 1_060, 1, 11, limit), Ok(()));
+        assert_eq!((stats.window_start_ts, stats.window_tip_count, stats.window_volume), (1_060, 1, 11));
+
+        assert_eq!(charge_rate_window(&mut stats, 1_061, 1_000, 1_000, RateLimit::default()), Ok(()));
+        assert_eq!(stats.window_tip_count, 1);
+    }
+
+    #[test]
+    fn derive_pdas_match_find_program_address() {
+        let program_id = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        for platform_id in [0, 7] {
+            let platform = platform_seed(platform_id);
+            let epoch = 42u64.to_le_bytes();
+            let find = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &program_id);
+            let pdas = derive_pdas(&program_id, platform_id, 42, Some(&mint));
+
+            assert_eq!((pdas.config, pdas.config_bump), find(&[b"config", &platform]));
+            assert_eq!((pdas.reward_pool, pdas.reward_pool_bump), find(&[b"reward_pool", &platform]));
+            assert_eq!((pdas.reward_vault, pdas.reward_vault_bump), find(&[b"reward_vault", &platform]));
+            assert_eq!((pdas.volume_window, pdas.volume_window_bump), find(&[b"volume_window", &platform]));
+            assert_eq!((pdas.audit_log, pdas.audit_log_bump), find(&[b"audit_log", &platform]));
+            assert_eq!((pdas.tree_authority, pdas.tree_authority_bump), find(&[b"tree_authority", &platform]));
+            assert_eq!(
+                (pdas.receipt_authority, pdas.receipt_authority_bump),
+                find(&[b"receipt_authority", &platform])
+            );
+            assert_eq!((pdas.multisig_authority, pdas.multisig_authority_bump), find(&[b"multisig", &platform]));
+            assert_eq!(pdas.epoch_index, 42);
+            assert_eq!((pdas.epoch_stats, pdas.epoch_stats_bump), find(&[b"epoch", &platform, &epoch]));
+            assert_eq!((pdas.leaderboard, pdas.leaderboard_bump), find(&[b"leaderboard", &platform, &epoch]));
+            assert_eq!(
+                (pdas.tipper_leaderboard, pdas.tipper_leaderboard_bump),
+                find(&[b"tipper_leaderboard", &platform, &epoch])
+            );
+            assert_eq!((pdas.stats_snapshot, pdas.stats_snapshot_bump), find(&[b"snapshot", &platform, &epoch]));
+
+            let by_mint = pdas.mint.unwrap();
+            assert_eq!(by_mint.mint, mint);
+            assert_eq!(
+                (by_mint.allowed_mint, by_mint.allowed_mint_bump),
+                find(&[b"allowed_mint", &platform, mint.as_ref()])
+            );
+            assert_eq!(
+                (by_mint.treasury_vault, by_mint.treasury_vault_bump),
+                find(&[b"treasury_vault", &platform, mint.as_ref()])
+            );
+            assert_eq!((by_mint.mint_stats, by_mint.mint_stats_bump), find(&[b"mint_stats", &platform, mint.as_ref()]));
+            assert_eq!((by_mint.charity, by_mint.charity_bump), find(&[b"charity", &platform, mint.as_ref()]));
+            assert_eq!(derive_pdas(&program_id, platform_id, 42, None).mint, None);
+        }
+    }
+
+    #[test]
+    fn mint_stats_split_fees_and_report_the_target_once() {
+        let legacy = MintStats {
+            is_initialized: true,
+            mint: Pubkey::new_unique(),
+            tip_count: 2,
+            total_volume: 500,
+            total_fees: 40,
+            total_treasury_fees: 40,
+            total_referral_fees: 0,
+            fee_target: 0,
+        };
+        let mut data = vec![0; MINT_STATS_SIZE];
+        legacy.pack(&mut data).unwrap();
+        // A legacy account ends after total_fees
+        let mut stats = MintStats::load(&data[..LEGACY_MINT_STATS_SIZE]).unwrap();
+        assert_eq!((stats.tip_count, stats.total_fees, stats.total_treasury_fees), (2, 40, 0));
+
+        stats.fee_target = 50;
+        assert_eq!(add_mint_tip(&mut stats, 100, 6, 2), Ok(false));
+        assert_eq!(add_mint_tip(&mut stats, 100, 2, 0), Ok(true));
+        assert_eq!(add_mint_tip(&mut stats, 100, 0, 0), Ok(false));
+        assert_eq!((stats.tip_count, stats.total_volume), (5, 800));
+        assert_eq!((stats.total_fees, stats.total_treasury_fees, stats.total_referral_fees), (50, 8, 2));
+    }
+
+    #[test]
+    fn self_tip_policy_flags_or_rejects_only_self_tips() {
+        let (tipper, creator, mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let apply = |policy: SelfTipPolicy, self_tip| {
+            apply_self_tip_policy(policy as u8, &[], self_tip, &tipper, &creator, &mint, 5)
+        };
+        assert_eq!(apply(SelfTipPolicy::Allow, true), Ok(()));
+        assert_eq!(apply(SelfTipPolicy::Flag, true), Ok(()));
+        assert_eq!(apply(SelfTipPolicy::Reject, false), Ok(()));
+        assert_eq!(apply(SelfTipPolicy::Reject, true), Err(TipError::SelfTipNotAllowed.into()));
+    }
+
+    #[test]
+    fn sub_checked_at_bounds() {
+        assert_eq!(sub_checked(u64::MAX, u64::MAX), Ok(0));
+        assert_eq!(sub_checked(u64::MAX, 0), Ok(u64::MAX));
+        assert_eq!(sub_checked(0, 1), Err(overflow()));
+        assert_eq!(sub_checked(u64::MAX - 1, u64::MAX), Err(overflow()));
+    }
+
+    #[test]
+    fn rent_payer_is_the_signer_right_after_the_accounts() {
+        let mut ledger = TestLedger::new();
+        let program_id = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let payer = Pubkey::new_unique();
+        ledger.fund(creator, LAMPORTS_PER_SOL);
+        ledger.fund(payer, LAMPORTS_PER_SOL);
+        let notify_pda = get_notify_pda(&program_id, &creator).0;
+        let rent = Rent::default().minimum_balance(NOTIFY_SIZE);
+
+        let mut unsigned = instruction::with_rent_payer(instruction::set_notify(&program_id, &creator, true), &payer);
+        unsigned.accounts.last_mut().unwrap().is_signer = false;
+        assert_eq!(ledger.process(&unsigned), Err(ProgramError::MissingRequiredSignature));
+
+        // Accounts after a Noop program in the slot are not taken for a payer
+        let mut after_noop = instruction::with_event_cpi(instruction::set_notify(&program_id, &creator, true));
+        after_noop.accounts.push(AccountMeta::new(payer, false));
+        ledger.process(&after_noop).unwrap();
+        assert_eq!(ledger.lamports(&creator), LAMPORTS_PER_SOL - rent);
+        assert_eq!(ledger.lamports(&payer), LAMPORTS_PER_SOL);
+
+        ledger.accounts.remove(&notify_pda);
+        let sponsored = instruction::with_rent_payer(instruction::set_notify(&program_id, &creator, true), &payer);
+        ledger.process(&instruction::with_event_cpi(sponsored)).unwrap();
+        assert_eq!(ledger.lamports(&creator), LAMPORTS_PER_SOL - rent);
+        assert_eq!(ledger.lamports(&payer), LAMPORTS_PER_SOL - rent);
+        assert_eq!(ledger.owner(&notify_pda), program_id);
+    }
+
+    #[test]
+    fn migrate_config_rewrites_the_baseline_layout_and_tops_up_rent() {
+        let mut ledger = TestLedger::new();
+        let program_id = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let treasury = Pubkey::new_unique();
+        let (config_pda, bump) = get_config_pda(&program_id, 0);
+        // The first release's config: is_initialized, admin, treasury, fee_bps, total_tips, total_volume
+        let totals = [7u64.to_le_bytes(), 9_000u64.to_le_bytes()].concat();
+        let legacy = [&[1][..], admin.as_ref(), treasury.as_ref(), &250u16.to_le_bytes(), &totals].concat();
+        assert_eq!(legacy.len(), 83);
+        ledger.set(config_pda, program_id, legacy);
+        ledger.fund(admin, LAMPORTS_PER_SOL);
+        let top_up = Rent::default().minimum_balance(CONFIG_SIZE) - Rent::default().minimum_balance(83);
+
+        ledger.process(&instruction::migrate_config(&program_id, 0, &admin)).unwrap();
+
+        assert_eq!(ledger.data(&config_pda).len(), CONFIG_SIZE);
+        assert_eq!(ledger.lamports(&config_pda), Rent::default().minimum_balance(CONFIG_SIZE));
+        assert_eq!(ledger.lamports(&admin), LAMPORTS_PER_SOL - top_up);
+        let config = TipConfig::load(ledger.data(&config_pda)).unwrap();
+        assert!(config.is_initialized);
+        assert_eq!((config.admin, config.treasury, config.fee_bps), (admin, treasury, 250));
+        assert_eq!((config.total_tips, config.total_volume), (7, 9_000));
+        assert_eq!((config.version, config.bump, config.platform_id), (CONFIG_VERSION, bump, 0));
+        let migrated = ConfigMigrated::try_from_slice(&events(b"ConfigMigrated")[0]).unwrap();
+        assert_eq!((migrated.from_version, migrated.to_version), (0, CONFIG_VERSION));
+
+        // A second run finds nothing to do and charges nothing
+        ledger.process(&instruction::migrate_config(&program_id, 0, &admin)).unwrap();
+        assert_eq!(ledger.lamports(&admin), LAMPORTS_PER_SOL - top_up);
+        assert_eq!(events(b"ConfigMigrated").len(), 1);
+    }
+
+    #[test]
+    fn tip_in_a_zero_decimal_mint_splits_whole_units() {
+        let mut fixture = TipFixture::with_decimals(250, 0);
+
+        fixture.tip(400, instruction::TipExtras::default()).unwrap();
+        assert_eq!(fixture.balance(&fixture.creator_token), 390);
+        assert_eq!(fixture.balance(&fixture.treasury_token), 10);
+        assert_eq!(fixture.balance(&fixture.tipper_token), TIPPER_BALANCE - 400);
+
+        // 2.5% of 39 whole units is under one unit, which the mint cannot express
+        assert_eq!(fixture.tip(39, instruction::TipExtras::default()), Err(TipError::FeeNotRepresentable.into()));
+        assert_eq!(fixture.balance(&fixture.tipper_token), TIPPER_BALANCE - 400);
+        fixture.tip(40, instruction::TipExtras::default()).unwrap();
+        assert_eq!(fixture.balance(&fixture.creator_token), 429);
+        assert_eq!(fixture.balance(&fixture.treasury_token), 11);
+        assert_eq!(fixture.config().total_tips, 2);
+    }
+
+    #[test]
+    fn rebates_accrue_only_on_fees_paid_into_the_treasury_vault() {
+        for into_vault in [false, true] {
+            let mut fixture = TipFixture::new(1_000);
+            let config_pda = fixture.config_pda();
+            let vault = get_treasury_vault_pda(&fixture.program_id, 0, &fixture.mint).0;
+            let mut config = fixture.config();
+            (config.rebate_threshold, config.rebate_bps) = (1, 5_000);
+            if into_vault {
+                config.treasury = vault;
+                fixture.treasury_token = vault;
+                fixture.ledger.set(vault, spl_token::id(), token_account(&fixture.mint, &config_pda, 0));
+            }
+            fixture.ledger.set(config_pda, fixture.program_id, packed(&config, CONFIG_SIZE));
+            fixture.ledger.fund(fixture.tipper, LAMPORTS_PER_SOL);
+
+            fixture.tip(1_000, instruction::TipExtras { rebate: true, ..Default::default() }).unwrap();
+
+            assert_eq!(fixture.balance(&fixture.treasury_token), 100);
+            let rebate_pda = get_tipper_rebate_pda(&fixture.program_id, 0, &fixture.tipper, &fixture.mint).0;
+            let rebate = TipperRebate::unpack(fixture.ledger.data(&rebate_pda)).unwrap();
+            assert_eq!(rebate.period_volume, 1_000);
+            if !into_vault {
+                // The fee went to an external treasury the vault never sees
+                assert_eq!(rebate.accrued, 0);
+                continue;
+            }
+            assert_eq!(rebate.accrued, 50);
+            let claim = instruction::claim_rebate(
+                &fixture.program_id, 0, &fixture.tipper, &fixture.tipper_token, &fixture.mint, &spl_token::id(),
+            );
+            fixture.ledger.process(&claim).unwrap();
+            assert_eq!(fixture.balance(&vault), 50);
+            assert_eq!(fixture.balance(&fixture.tipper_token), TIPPER_BALANCE - 1_000 + 50);
+        }
+    }
+
+    #[test]
+    fn tip_notifies_only_opted_in_creators() {
+        for opted_in in [true, false] {
+            let mut fixture = TipFixture::new(0);
+            let notify_pda = get_notify_pda(&fixture.program_id, &fixture.creator).0;
+            let notify = CreatorNotify { is_initialized: true, creator: fixture.creator, notify: opted_in };
+            fixture.ledger.set(notify_pda, fixture.program_id, packed(&notify, NOTIFY_SIZE));
+
+            fixture.tip(1_000, instruction::TipExtras::default()).unwrap();
+
+            assert_eq!(fixture.balance(&fixture.creator_token), 1_000);
+            let notified: Vec<CreatorNotified> =
+                events(b"CreatorNotified").iter().map(|data| CreatorNotified::try_from_slice(data).unwrap()).collect();
+            if opted_in {
+                assert_eq!(notified.len(), 1);
+                assert_eq!(notified[0].creator, fixture.creator);
+                assert_eq!(notified[0].tipper, fixture.tipper);
+                assert_eq!(notified[0].amount, 1_000);
+            } else {
+                assert!(notified.is_empty());
+            }
+        }
+
+        // A creator who never called SetNotify has no account at all
+        let mut fixture = TipFixture::new(0);
+        fixture.tip(1_000, instruction::TipExtras::default()).unwrap();
+        assert_eq!(fixture.balance(&fixture.creator_token), 1_000);
+        assert!(events(b"CreatorNotified").is_empty());
+        let stats_pda = get_creator_stats_pda(&fixture.program_id, 0, &fixture.creator).0;
+        let stats = CreatorStats::unpack(fixture.ledger.data(&stats_pda)).unwrap();
+        assert_eq!((stats.creator, stats.tip_count, stats.total_received), (fixture.creator, 1, 1_000));
+        assert_eq!(stats.last_tip_slot, 100);
+    }
+
+    #[test]
+    fn tip_with_campaign_takes_the_campaign_ahead_of_tips_accounts() {
+        let mut fixture = TipFixture::new(500);
+        let campaign_pda = get_campaign_pda(&fixture.program_id, &fixture.creator, 7).0;
+        let campaign = Campaign {
+            is_initialized: true,
+            creator: fixture.creator,
+            campaign_id: 7,
+            target_amount: 2_000,
+            raised: 0,
+            deadline_ts: NOW + 60,
+            finalized: false,
+            goal_met: false,
+            milestone_slots: [0; 4],
+            has_fee_override: true,
+            fee_override_bps: 0,
+            overflow: CampaignOverflow::Accept,
+        };
+        fixture.ledger.set(campaign_pda, fixture.program_id, packed(&campaign, CAMPAIGN_SIZE));
+
+        let ix = fixture.tip_ix(1_000, instruction::TipExtras { campaign: Some(&campaign_pda), ..Default::default() });
+        assert_eq!(ix.accounts[0].pubkey, campaign_pda);
+        assert_eq!(ix.accounts[1].pubkey, fixture.config_pda());
+        let data = TipInstruction::unpack(&ix.data).unwrap();
+        assert!(matches!(data, TipInstruction::TipWithCampaign { amount: 1_000, .. }));
+        fixture.ledger.process(&ix).unwrap();
+
+        // The campaign's own rate (here none) replaces the config's 5%
+        assert_eq!(fixture.balance(&fixture.creator_token), 1_000);
+        assert_eq!(Campaign::load(fixture.ledger.data(&campaign_pda)).unwrap().raised, 1_000);
+        assert_eq!(events(b"MilestoneReached").len(), 2);
+
+        // A plain Tip has no campaign slot, so the campaign's rate no longer applies
+        fixture.tip(1_000, instruction::TipExtras::default()).unwrap();
+        assert_eq!(fixture.balance(&fixture.creator_token), 1_950);
+        assert_eq!(Campaign::load(fixture.ledger.data(&campaign_pda)).unwrap().raised, 1_000);
+    }
+
+    #[test]
+    fn campaign_overflow_caps_or_accepts_tips_past_the_target() {
+        for overflow in [CampaignOverflow::Accept, CampaignOverflow::Cap] {
+            let mut fixture = TipFixture::new(0);
+            let campaign_pda = get_campaign_pda(&fixture.program_id, &fixture.creator, 7).0;
+            let campaign = Campaign {
+                is_initialized: true,
+                creator: fixture.creator,
+                campaign_id: 7,
+                target_amount: 2_000,
+                raised: 0,
+                deadline_ts: NOW + 60,
+                finalized: false,
+                goal_met: false,
+                milestone_slots: [0; 4],
+                has_fee_override: false,
+                fee_override_bps: 0,
+                overflow,
+            };
+            fixture.ledger.set(campaign_pda, fixture.program_id, packed(&campaign, CAMPAIGN_SIZE));
+            let to_campaign = || instruction::TipExtras { campaign: Some(&campaign_pda), ..Default::default() };
+            let raised = |fixture: &TipFixture| Campaign::load(fixture.ledger.data(&campaign_pda)).unwrap().raised;
+
+            // A tip landing exactly on the target is taken whole and exceeds nothing
+            fixture.tip(2_000, to_campaign()).unwrap();
+            assert_eq!(raised(&fixture), 2_000);
+            assert!(events(b"GoalExceeded").is_empty());
+
+            let mut under_target = Campaign::load(fixture.ledger.data(&campaign_pda)).unwrap();
+            under_target.raised = 1_500;
+            fixture.ledger.set(campaign_pda, fixture.program_id, packed(&under_target, CAMPAIGN_SIZE));
+            fixture.tip(1_000, to_campaign()).unwrap();
+            let exceeded = GoalExceeded::try_from_slice(&events(b"GoalExceeded")[0]).unwrap();
+            assert_eq!((exceeded.excess, exceeded.overflow), (500, overflow));
+            if overflow == CampaignOverflow::Accept {
+                assert_eq!(raised(&fixture), 2_500);
+                assert_eq!(fixture.balance(&fixture.creator_token), 3_000);
+                continue;
+            }
+            // Cap takes only the 500 the target still needed; the other 500 stays with the tipper
+            assert_eq!(raised(&fixture), 2_000);
+            assert_eq!(fixture.balance(&fixture.creator_token), 2_500);
+            assert_eq!(fixture.balance(&fixture.tipper_token), TIPPER_BALANCE - 2_500);
+            assert_eq!(fixture.tip(1, to_campaign()), Err(TipError::CampaignGoalReached.into()));
+        }
+    }
+
+    #[test]
+    fn tip_with_nonce_lands_only_in_order() {
+        let mut fixture = TipFixture::new(0);
+        let stats_pda = get_tipper_stats_pda(&fixture.program_id, 0, &fixture.tipper).0;
+        let nonced = |nonce| instruction::TipExtras { nonce: Some(nonce), ..Default::default() };
+        let tip_nonce = |fixture: &TipFixture| TipperStats::load(fixture.ledger.data(&stats_pda)).unwrap().tip_nonce;
+
+        fixture.tip(1_000, nonced(0)).unwrap();
+        assert_eq!(tip_nonce(&fixture), 1);
+        fixture.tip(1_000, nonced(1)).unwrap();
+        assert_eq!(tip_nonce(&fixture), 2);
+
+        // A replayed (stale) or reordered (future) nonce fails without advancing it
+        for nonce in [1, 3] {
+            assert_eq!(fixture.tip(1_000, nonced(nonce)), Err(TipError::TipNonceMismatch.into()));
+            assert_eq!(tip_nonce(&fixture), 2);
+        }
+
+        // Plain tips neither check nor advance it
+        fixture.tip(1_000, instruction::TipExtras::default()).unwrap();
+        assert_eq!(tip_nonce(&fixture), 2);
+        fixture.tip(1_000, nonced(2)).unwrap();
+        assert_eq!(tip_nonce(&fixture), 3);
+        let stats = TipperStats::load(fixture.ledger.data(&stats_pda)).unwrap();
+        assert_eq!(stats.tip_count, 4);
+    }
+
+    #[test]
+    fn lenient_routing_skips_a_missing_referrer_that_strict_routing_rejects() {
+        for lenient in [false, true] {
+            let mut fixture = TipFixture::new(500);
+            let mut config = fixture.config();
+            config.referral_bps = 2_000;
+            config.lenient_routing = lenient;
+            fixture.ledger.set(fixture.config_pda(), fixture.program_id, packed(&config, CONFIG_SIZE));
+            let missing = Pubkey::new_unique();
+
+            let extras = instruction::TipExtras { referrer_token: Some(&missing), ..Default::default() };
+            let result = fixture.tip(1_000, extras);
+            if !lenient {
+                assert_eq!(result, Err(ProgramError::IncorrectProgramId));
+                assert!(events(b"RoutingSkipped").is_empty());
+                continue;
+            }
+            result.unwrap();
+            // The referral share stays with the treasury, and the creator is paid as usual
+            assert_eq!(fixture.balance(&fixture.treasury_token), 50);
+            assert_eq!(fixture.balance(&fixture.creator_token), 950);
+            let skipped = RoutingSkipped::try_from_slice(&events(b"RoutingSkipped")[0]).unwrap();
+            let expected =
+                RoutingSkipped { creator: fixture.creator, destination: RoutingDestination::Referral, account: missing };
+            assert_eq!(skipped, expected);
+            assert!(events(b"ReferralPaid").is_empty());
+        }
+    }
+
+    #[test]
+    fn tip_withholds_the_profile_tax_share_only_while_withholding_is_on() {
+        for tax_bps in [0, 2_000] {
+            let mut fixture = TipFixture::new(500);
+            let tax_account = Pubkey::new_unique();
+            let tax_token = token::get_associated_token_address(&tax_account, &fixture.mint, &spl_token::id());
+            fixture.ledger.set(tax_token, spl_token::id(), token_account(&fixture.mint, &tax_account, 0));
+            let profile = CreatorProfile {
+                is_initialized: true,
+                creator: fixture.creator,
+                display_name: PaddedStr::new("creator").unwrap(),
+                metadata_uri: PaddedStr::new("").unwrap(),
+                payout_token: fixture.creator_token,
+                verified: false,
+                tip_mint: Pubkey::default(),
+                min_tip: 0,
+                tip_menu_len: 0,
+                tip_menu: [0; MAX_TIP_MENU],
+                tier_count: 0,
+                tier_thresholds: [0; MAX_TIP_TIERS],
+                payout_mint: Pubkey::default(),
+                tax_bps,
+                tax_account,
+            };
+            let profile_pda = get_creator_profile_pda(&fixture.program_id, &fixture.creator).0;
+            fixture.ledger.set(profile_pda, fixture.program_id, packed(&profile, CREATOR_PROFILE_SIZE));
+
+            let withheld_to = (tax_bps > 0).then_some(&tax_account);
+            let extras =
+                instruction::TipExtras { creator_profile: true, tax_account: withheld_to, ..Default::default() };
+            fixture.tip(1_000, extras).unwrap();
+
+            // 20% of the 950 left after the 5% fee is withheld, and every token is accounted for
+            let (withheld, creator) = if tax_bps > 0 { (190, 760) } else { (0, 950) };
+            assert_eq!(fixture.balance(&tax_token), withheld);
+            assert_eq!(fixture.balance(&fixture.creator_token), creator);
+            assert_eq!(fixture.balance(&fixture.treasury_token), 50);
+            assert_eq!(fixture.balance(&fixture.tipper_token) + 1_000, TIPPER_BALANCE);
+            let events = events(b"TaxWithheld");
+            if tax_bps == 0 {
+                assert!(events.is_empty());
+                continue;
+            }
+            let expected = TaxWithheld { creator: fixture.creator, tax_account, mint: fixture.mint, amount: 190 };
+            assert_eq!(TaxWithheld::try_from_slice(&events[0]).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn tip_rejects_frozen_source_before_any_cpi() {
+        let mut fixture = TipFixture::new(100);
+        let mut tipper_token = TokenAccount::unpack(fixture.ledger.data(&fixture.tipper_token)).unwrap();
+        tipper_token.state = AccountState::Frozen;
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount::pack(tipper_token, &mut data).unwrap();
+        fixture.ledger.set(fixture.tipper_token, spl_token::id(), data);
+
+        let result = fixture.tip(1_000, instruction::TipExtras::default());
+
+        assert_eq!(result, Err(TipError::SourceAccountFrozen.into()));
+        assert_eq!(cpi_count(), 0);
+        assert_eq!(fixture.balance(&fixture.tipper_token), TIPPER_BALANCE);
+        assert_eq!(fixture.balance(&fixture.treasury_token), 0);
+    }
+
+    #[test]
+    fn fee_target_starts_a_holiday_that_a_higher_target_ends() {
+        let mut fixture = TipFixture::new(100);
+        let mint_stats_pda = get_mint_stats_pda(&fixture.program_id, 0, &fixture.mint).0;
+        let stats = MintStats {
+            is_initialized: true,
+            mint: fixture.mint,
+            tip_count: 0,
+            total_volume: 0,
+            total_fees: 0,
+            total_treasury_fees: 0,
+            total_referral_fees: 0,
+            fee_target: 25,
+        };
+        fixture.ledger.set(mint_stats_pda, fixture.program_id, packed(&stats, MINT_STATS_SIZE));
+        let mut treasury = 0;
+        let mut tip_fee = |fixture: &mut TipFixture| {
+            fixture.tip(1_000, instruction::TipExtras::default()).unwrap();
+            let fee = fixture.balance(&fixture.treasury_token) - treasury;
+            treasury += fee;
+            fee
+        };
+
+        // 1% of each tip until 25 has been collected, the last fee cut short at the target
+        assert_eq!(tip_fee(&mut fixture), 10);
+        assert_eq!(tip_fee(&mut fixture), 10);
+        assert!(events(b"FeeTargetReached").is_empty());
+        assert_eq!(tip_fee(&mut fixture), 5);
+        assert_eq!(events(b"FeeTargetReached").len(), 1);
+        assert_eq!(tip_fee(&mut fixture), 0);
+        assert_eq!(fixture.balance(&fixture.creator_token), 1_000 + 1_000 + 1_000 + 1_000 - 25);
+        let stats = MintStats::load(fixture.ledger.data(&mint_stats_pda)).unwrap();
+        assert_eq!((stats.tip_count, stats.total_fees, stats.total_volume), (4, 25, 4_000));
+
+        let raise = instruction::set_fee_target(&fixture.program_id, 0, &fixture.admin, &fixture.mint, 40);
+        fixture.ledger.process(&raise).unwrap();
+        assert_eq!(tip_fee(&mut fixture), 10);
+        assert_eq!(tip_fee(&mut fixture), 5);
+        assert_eq!(tip_fee(&mut fixture), 0);
+        assert_eq!(events(b"FeeTargetReached").len(), 2);
+
+        // Leaving the stats out, or passing another mint's, cannot dodge the fee the target charges again
+        let raise = instruction::set_fee_target(&fixture.program_id, 0, &fixture.admin, &fixture.mint, 100);
+        fixture.ledger.process(&raise).unwrap();
+        let mut without_stats = fixture.tip_ix(1_000, instruction::TipExtras::default());
+        without_stats.accounts.retain(|meta| meta.pubkey != mint_stats_pda);
+        assert_eq!(fixture.ledger.process(&without_stats), Err(TipError::AccountNotWritable.into()));
+        let mut other_mint = fixture.tip_ix(1_000, instruction::TipExtras::default());
+        let other_stats = get_mint_stats_pda(&fixture.program_id, 0, &Pubkey::new_unique()).0;
+        for meta in other_mint.accounts.iter_mut().filter(|meta| meta.pubkey == mint_stats_pda) {
+            meta.pubkey = other_stats;
+        }
+        assert_eq!(fixture.ledger.process(&other_mint), Err(ProgramError::InvalidSeeds));
+        assert_eq!(tip_fee(&mut fixture), 10);
+    }
+
+    #[test]
+    fn sweep_excess_rent_returns_only_the_surplus() {
+        let mut fixture = TipFixture::new(0);
+        let config_pda = fixture.config_pda();
+        let rent_exempt = Rent::default().minimum_balance(CONFIG_SIZE);
+        assert_eq!(fixture.ledger.lamports(&config_pda), rent_exempt);
+        let accounts = vec![AccountMeta::new(config_pda, false), AccountMeta::new(fixture.admin, true)];
+        let sweep = Instruction::new_with_bytes(fixture.program_id, &[6], accounts);
+
+        // Nothing above rent exemption: no transfer
+        fixture.ledger.process(&sweep).unwrap();
+        assert_eq!(fixture.ledger.lamports(&config_pda), rent_exempt);
+        assert_eq!(fixture.ledger.lamports(&fixture.admin), 0);
+
+        fixture.ledger.accounts.get_mut(&config_pda).unwrap().lamports += 5_000;
+        fixture.ledger.process(&sweep).unwrap();
+        assert_eq!(fixture.ledger.lamports(&config_pda), rent_exempt);
+        assert_eq!(fixture.ledger.lamports(&fixture.admin), 5_000);
+    }
+
+    #[test]
+    fn admin_isolation_allows_only_standalone_admin_instructions() {
+        let mut fixture = TipFixture::new(0);
+        fixture.ledger.process(&instruction::set_admin_isolation(&fixture.program_id, 0, &fixture.admin, true)).unwrap();
+        assert!(fixture.config().admin_isolation);
+        let pause = instruction::pause(&fixture.program_id, 0, &fixture.admin);
+        let compute_budget = Instruction::new_with_bytes(COMPUTE_BUDGET_ID, &[2, 0, 0, 0, 0], vec![]);
+        let other = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]);
+
+        fixture.ledger.set_transaction(&[pause.clone(), other], 0);
+        assert_eq!(fixture.ledger.process(&pause), Err(TipError::AdminNotIsolated.into()));
+        assert!(!fixture.config().paused);
+
+        fixture.ledger.set_transaction(&[compute_budget, pause.clone()], 1);
+        fixture.ledger.process(&pause).unwrap();
+        assert!(fixture.config().paused);
+    }
+
+    #[test]
+    fn session_tips_draw_down_the_budget_until_exhausted() {
+        let mut fixture = TipFixture::new(100);
+        let session_key = Pubkey::new_unique();
+        let session_pda = get_session_pda(&fixture.program_id, 0, &fixture.tipper).0;
+        fixture.ledger.set(session_pda, fixture.program_id, vec![0; SESSION_SIZE]);
+        let open = instruction::open_session(
+            &fixture.program_id,
+            0,
+            &fixture.tipper,
+            &fixture.tipper_token,
+            &session_key,
+            &spl_token::id(),
+            2_500,
+            NOW + 3_600,
+        );
+        fixture.ledger.process(&open).unwrap();
+        let tipper_token = TokenAccount::unpack(fixture.ledger.data(&fixture.tipper_token)).unwrap();
+        assert_eq!((tipper_token.delegate, tipper_token.delegated_amount), (COption::Some(session_pda), 2_500));
+
+        let session_tip = |fixture: &mut TipFixture, amount: u64| {
+            let session_tip = instruction::session_tip(
+                &fixture.program_id,
+                0,
+                &fixture.tipper,
+                &session_key,
+                &fixture.tipper_token,
+                &fixture.creator,
+                &fixture.creator_token,
+                &fixture.treasury_token,
+                &fixture.mint,
+                &spl_token::id(),
+                amount,
+            );
+            fixture.ledger.process(&session_tip)
+        };
+        let budget_remaining = |fixture: &TipFixture| {
+            Session::unpack(fixture.ledger.data(&session_pda)).unwrap().budget_remaining
+        };
+
+        session_tip(&mut fixture, 1_000).unwrap();
+        session_tip(&mut fixture, 1_000).unwrap();
+        assert_eq!(budget_remaining(&fixture), 500);
+        assert_eq!(session_tip(&mut fixture, 1_000), Err(TipError::SessionBudgetExhausted.into()));
+        session_tip(&mut fixture, 500).unwrap();
+        assert_eq!(budget_remaining(&fixture), 0);
+        assert_eq!(session_tip(&mut fixture, 1), Err(TipError::SessionBudgetExhausted.into()));
+
+        assert_eq!(fixture.balance(&fixture.tipper_token), TIPPER_BALANCE - 2_500);
+        assert_eq!(fixture.balance(&fixture.treasury_token), 25);
+        assert_eq!(fixture.balance(&fixture.creator_token), 2_475);
+        assert_eq!(fixture.config().total_tips, 3);
+    }
+
+    #[test]
+    fn handoff_ownership_swaps_admin_and_treasury_together() {
+        let mut fixture = TipFixture::new(0);
+        let new_admin = Pubkey::new_unique();
+        let new_treasury = Pubkey::new_unique();
+        let accounts = vec![
+            AccountMeta::new(fixture.config_pda(), false),
+            AccountMeta::new_readonly(fixture.admin, true),
+            AccountMeta::new_readonly(new_admin, false),
+            AccountMeta::new_readonly(new_treasury, false),
+        ];
+        let handoff = Instruction::new_with_bytes(fixture.program_id, &[10], accounts);
+
+        // A wallet rather than a token account: neither half moves
+        assert_eq!(fixture.ledger.process(&handoff), Err(TipError::InvalidTreasury.into()));
+        let config = fixture.config();
+        assert_eq!((config.admin, config.treasury), (fixture.admin, fixture.treasury_token));
+
+        fixture.ledger.set(new_treasury, spl_token::id(), token_account(&Pubkey::new_unique(), &new_admin, 0));
+        fixture.ledger.process(&handoff).unwrap();
+        let config = fixture.config();
+        assert_eq!((config.admin, config.treasury), (new_admin, new_treasury));
+    }
+
+    #[test]
+    fn legacy_update_fee_tag_still_schedules_the_fee() {
+        let mut fixture = TipFixture::new(100);
+        let tag = |ix: &TipInstruction| borsh::to_vec(ix).unwrap()[0];
+        assert_eq!(tag(&TipInstruction::Tip { amount: 0, memo: String::new(), category: 0 }), 1);
+        assert_eq!(tag(&TipInstruction::UpdateFee { new_fee_bps: 0 }), 2);
+        assert_eq!(tag(&TipInstruction::ScheduleFeeUpdate { new_fee_bps: 0 }), 124);
+
+        let mut data = vec![2];
+        data.extend_from_slice(&250u16.to_le_bytes());
+        let ix = Instruction::new_with_bytes(
+            fixture.program_id,
+            &data,
+            vec![AccountMeta::new(fixture.config_pda(), false), AccountMeta::new_readonly(fixture.admin, true)],
+        );
+        fixture.ledger.process(&ix).unwrap();
+        let config = fixture.config();
+        assert_eq!((config.fee_bps, config.pending_fee_bps), (100, 250));
+        assert_eq!(config.fee_activation_slot, 100 + FEE_UPDATE_DELAY_SLOTS);
+    }
+
+    #[test]
+    fn legacy_tip_payload_tips_without_memo() {
+        let mut fixture = TipFixture::new(100);
+        let mut ix = instruction::tip(
+            &fixture.program_id,
+            0,
+            &fixture.tipper,
+            &fixture.tipper_token,
+            &fixture.creator,
+            &fixture.creator_token,
+            &fixture.treasury_token,
+            &fixture.mint,
+            &spl_token::id(),
+            0,
+            "",
+            instruction::TipExtras::default(),
+        );
+        ix.data = [&[1][..], &10_000u64.to_le_bytes()].concat();
+        assert_eq!(
+            TipInstruction::unpack(&ix.data),
+            Ok(TipInstruction::Tip { amount: 10_000, memo: String::new(), category: 0 })
+        );
+        fixture.ledger.process(&ix).unwrap();
+        assert_eq!(fixture.balance(&fixture.creator_token), 9_900);
+        assert_eq!(fixture.balance(&fixture.treasury_token), 100);
+        assert!(events(b"TipMemo").is_empty());
+        assert_eq!(TipInstruction::unpack(&[1, 0, 0]), Err(ProgramError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn is_paused_follows_pause_and_unpause() {
+        let mut fixture = TipFixture::new(100);
+        let (program_id, admin) = (fixture.program_id, fixture.admin);
+        let query = instruction::is_paused(&program_id, 0);
+        assert!(query.accounts.iter().all(|meta| !meta.is_signer));
+
+        fixture.ledger.process(&query).unwrap();
+        assert_eq!(return_data(), [0]);
+        fixture.ledger.process(&instruction::pause(&program_id, 0, &admin)).unwrap();
+        fixture.ledger.process(&query).unwrap();
+        assert_eq!(return_data(), [1]);
+        assert_eq!(fixture.tip(1_000, Default::default()), Err(TipError::Paused.into()));
+        fixture.ledger.process(&instruction::unpause(&program_id, 0, &admin)).unwrap();
+        fixture.ledger.process(&query).unwrap();
+        assert_eq!(return_data(), [0]);
+    }
+
+    #[test]
+    fn max_creators_caps_registrations_until_a_profile_closes() {
+        let mut fixture = TipFixture::new(100);
+        let (program_id, mint) = (fixture.program_id, fixture.mint);
+        let creators: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let register = |ledger: &mut TestLedger, creator: &Pubkey| {
+            let payout_token = Pubkey::new_unique();
+            ledger.set(payout_token, spl_token::id(), token_account(&mint, creator, 0));
+            ledger.fund(*creator, LAMPORTS_PER_SOL);
+            ledger.process(&instruction::register_creator(&program_id, creator, &payout_token, "name", "uri"))
+        };
+        let config_pda = fixture.config_pda();
+        let registered = |ledger: &TestLedger| TipConfig::unpack(ledger.data(&config_pda)).unwrap();
+
+        fixture.ledger.process(&instruction::set_max_creators(&program_id, &fixture.admin, 2)).unwrap();
+        register(&mut fixture.ledger, &creators[0]).unwrap();
+        register(&mut fixture.ledger, &creators[1]).unwrap();
+        let profile = get_creator_profile_pda(&program_id, &creators[0]).0;
+        assert_eq!(fixture.ledger.owner(&profile), program_id);
+        assert_eq!(registered(&fixture.ledger).registered_creators, 2);
+        assert_eq!(register(&mut fixture.ledger, &creators[2]), Err(TipError::CreatorCapReached.into()));
+
+        let close = instruction::close_account(
+            &program_id,
+            0,
+            &profile,
+            &creators[0],
+            &creators[0],
+            ClosableAccount::CreatorProfile,
+        );
+        fixture.ledger.process(&close).unwrap();
+        assert_eq!(fixture.ledger.lamports(&profile), 0);
+        assert_eq!(registered(&fixture.ledger).registered_creators, 1);
+        register(&mut fixture.ledger, &creators[2]).unwrap();
+        assert_eq!(registered(&fixture.ledger).registered_creators, 2);
+
+        // 0 lifts the cap
+        fixture.ledger.process(&instruction::set_max_creators(&program_id, &fixture.admin, 0)).unwrap();
+        register(&mut fixture.ledger, &creators[0]).unwrap();
+        assert_eq!(registered(&fixture.ledger).registered_creators, 3);
+    }
+
+    #[test]
+    fn treasury_low_fires_only_when_a_withdrawal_crosses_the_watermark() {
+        let mut fixture = TipFixture::new(100);
+        let (program_id, admin, mint) = (fixture.program_id, fixture.admin, fixture.mint);
+        let vault = get_treasury_vault_pda(&program_id, 0, &mint).0;
+        fixture.ledger.set(vault, spl_token::id(), token_account(&mint, &fixture.config_pda(), 1_000));
+        let destination = fixture.treasury_token;
+        let withdraw = instruction::withdraw_fees(&program_id, 0, &admin, &destination, &mint, &spl_token::id(), 300);
+
+        fixture.ledger.process(&instruction::set_treasury_low_watermark(&program_id, 0, &admin, 500)).unwrap();
+        fixture.ledger.process(&withdraw).unwrap();
+        assert_eq!(fixture.balance(&vault), 700);
+        assert!(events(b"TreasuryLow").is_empty());
+
+        fixture.ledger.process(&withdraw).unwrap();
+        assert_eq!(fixture.balance(&vault), 400);
+        let low: Vec<TreasuryLow> =
+            events(b"TreasuryLow").iter().map(|data| TreasuryLow::try_from_slice(data).unwrap()).collect();
+        assert_eq!(low, [TreasuryLow { treasury_vault: vault, balance: 400 }]);
+    }
+
+    #[test]
+    fn distribute_fees_splits_the_treasury_vault_by_weight() {
+        let mut fixture = TipFixture::new(100);
+        let (program_id, admin, mint) = (fixture.program_id, fixture.admin, fixture.mint);
+        let vault = get_treasury_vault_pda(&program_id, 0, &mint).0;
+        fixture.ledger.set(vault, spl_token::id(), token_account(&mint, &fixture.config_pda(), 1_001));
+        let destinations: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        for destination in &destinations {
+            fixture.ledger.set(*destination, spl_token::id(), token_account(&mint, &Pubkey::new_unique(), 0));
+        }
+        let shares = |bps: [u16; 3]| {
+            destinations.iter().zip(bps).map(|(&recipient, share_bps)| SplitShare { recipient, share_bps }).collect()
+        };
+        let distribute = |signer: &Pubkey, shares| {
+            instruction::distribute_fees(&program_id, 0, signer, &mint, &spl_token::id(), shares)
+        };
+
+        let outsider = Pubkey::new_unique();
+        let result = fixture.ledger.process(&distribute(&outsider, shares([5_000, 3_000, 2_000])));
+        assert_eq!(result, Err(TipError::Unauthorized.into()));
+        let result = fixture.ledger.process(&distribute(&admin, shares([5_000, 3_000, 1_000])));
+        assert_eq!(result, Err(TipError::InvalidSplit.into()));
+        let other_mint = token_account(&Pubkey::new_unique(), &Pubkey::new_unique(), 0);
+        fixture.ledger.set(destinations[2], spl_token::id(), other_mint);
+        let result = fixture.ledger.process(&distribute(&admin, shares([5_000, 3_000, 2_000])));
+        assert_eq!(result, Err(TipError::MintMismatch.into()));
+        assert_eq!(fixture.balance(&vault), 1_001);
+        fixture.ledger.set(destinations[2], spl_token::id(), token_account(&mint, &Pubkey::new_unique(), 0));
+
+        fixture.ledger.process(&distribute(&admin, shares([5_000, 3_000, 2_000]))).unwrap();
+        // The largest share takes the rounding dust
+        let received: Vec<u64> = destinations.iter().map(|destination| fixture.balance(destination)).collect();
+        assert_eq!(received, [501, 300, 200]);
+        assert_eq!(fixture.balance(&vault), 0);
+        assert_eq!(events(b"FeesDistributed").len(), 3);
+    }
 }
@@ -0,0 +1,192 @@
+//! Thin layer over Metaplex Token Metadata for receipt NFTs. Only the three instructions the
+//! program CPIs into are encoded here: a tag byte followed by the Borsh-encoded arguments. The
+//! only account read is a metadata account's verified collection, for fan club discounts.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_program,
+};
+
+pub const ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+const CREATE_MASTER_EDITION_V3: u8 = 17;
+const VERIFY_SIZED_COLLECTION_ITEM: u8 = 30;
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+
+/// Metadata account of `mint` (PDA of Token Metadata: ["metadata", program, mint])
+pub fn find_metadata_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", ID.as_ref(), mint.as_ref()], &ID).0
+}
+
+/// Master edition account of `mint` (PDA of Token Metadata: ["metadata", program, mint, "edition"])
+pub fn find_master_edition_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", ID.as_ref(), mint.as_ref(), b"edition"], &ID).0
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct Collection {
+    verified: bool,
+    key: Pubkey,
+}
+
+#[derive(BorshSerialize)]
+struct DataV2 {
+    name: String,
+    symbol: String,
+    uri: String,
+    seller_fee_basis_points: u16,
+    // `creators` and `uses` are always None, which Borsh encodes as one 0 byte whatever the type
+    creators: Option<u8>,
+    collection: Option<Collection>,
+    uses: Option<u8>,
+}
+
+#[derive(BorshSerialize)]
+struct CreateMetadataAccountArgsV3 {
+    data: DataV2,
+    is_mutable: bool,
+    /// Only set on collection NFTs themselves
+    collection_details: Option<u8>,
+}
+
+#[derive(BorshDeserialize)]
+struct Creator {
+    _address: Pubkey,
+    _verified: bool,
+    _share: u8,
+}
+
+/// Metadata account layout up to `collection`; the fields after it are never read
+#[derive(BorshDeserialize)]
+struct MetadataPrefix {
+    _key: u8,
+    _update_authority: Pubkey,
+    mint: Pubkey,
+    _name: String,
+    _symbol: String,
+    _uri: String,
+    _seller_fee_basis_points: u16,
+    _creators: Option<Vec<Creator>>,
+    _primary_sale_happened: bool,
+    _is_mutable: bool,
+    _edition_nonce: Option<u8>,
+    _token_standard: Option<u8>,
+    collection: Option<Collection>,
+}
+
+/// Collection `mint` is a verified member of, read from its metadata account; None if it names no
+/// collection or one that has not verified it
+pub fn verified_collection(metadata: &AccountInfo, mint: &Pubkey) -> Result<Option<Pubkey>, ProgramError> {
+    if *metadata.owner != ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if *metadata.key != find_metadata_pda(mint) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let prefix = MetadataPrefix::deserialize(&mut &metadata.data.borrow()[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if prefix.mint != *mint {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(prefix.collection.filter(|c| c.verified).map(|c| c.key))
+}
+
+fn encode(tag: u8, args: &impl BorshSerialize) -> Vec<u8> {
+    let mut data = vec![tag];
+    args.serialize(&mut data).expect("in-memory Borsh encoding cannot fail");
+    data
+}
+
+/// Mutable, royalty-free metadata that names `collection` as its (still unverified) collection
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_account_v3(
+    metadata: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    update_authority: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+    collection: &Pubkey,
+) -> Instruction {
+    let args = CreateMetadataAccountArgsV3 {
+        data: DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: Some(Collection { verified: false, key: *collection }),
+            uses: None,
+        },
+        is_mutable: true,
+        collection_details: None,
+    };
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*update_authority, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: encode(CREATE_METADATA_ACCOUNT_V3, &args),
+    }
+}
+
+/// One-of-one master edition (max_supply 0); takes over the mint and freeze authority
+pub fn create_master_edition_v3(
+    edition: &Pubkey,
+    mint: &Pubkey,
+    update_authority: &Pubkey,
+    mint_authority: &Pubkey,
+    payer: &Pubkey,
+    metadata: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*edition, false),
+            AccountMeta::new(*mint, false),
+            AccountMeta::new_readonly(*update_authority, true),
+            AccountMeta::new_readonly(*mint_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: encode(CREATE_MASTER_EDITION_V3, &Some(0u64)),
+    }
+}
+
+/// Verify `metadata` as a member of a sized collection whose update authority is `collection_authority`
+pub fn verify_sized_collection_item(
+    metadata: &Pubkey,
+    collection_authority: &Pubkey,
+    payer: &Pubkey,
+    collection_mint: &Pubkey,
+    collection_metadata: &Pubkey,
+    collection_master_edition: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: vec![
+            AccountMeta::new(*metadata, false),
+            AccountMeta::new_readonly(*collection_authority, true),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*collection_mint, false),
+            AccountMeta::new(*collection_metadata, false),
+            AccountMeta::new_readonly(*collection_master_edition, false),
+        ],
+        data: vec![VERIFY_SIZED_COLLECTION_ITEM],
+    }
+}
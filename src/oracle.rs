@@ -0,0 +1,87 @@
+//! Thin layer over Pyth push-oracle price accounts for USD valuation of tips. Only the fields the
+//! program reads are decoded, at their fixed offsets in the (v2) price account layout.
+
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey, pubkey::Pubkey};
+
+use crate::error::TipError;
+
+/// Pyth oracle program on mainnet
+pub const PYTH_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bnvwJ3");
+/// A price published more than this many slots ago (about a minute) is not used
+pub const MAX_PRICE_AGE_SLOTS: u64 = 150;
+/// USD values are recorded in millionths of a dollar
+pub const USD_DECIMALS: u32 = 6;
+
+const MAGIC: u32 = 0xa1b2c3d4;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const STATUS_TRADING: u32 = 1;
+// magic, version, account type, size, price type, then the exponent
+const EXPO_OFFSET: usize = 20;
+// Aggregate price: (price i64, conf u64, status u32, corporate action u32, publish slot u64)
+const AGG_OFFSET: usize = 208;
+const PRICE_ACCOUNT_MIN_LEN: usize = AGG_OFFSET + 32;
+
+/// Aggregate price of one feed: `price * 10^expo` USD per whole token
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Price {
+    pub price: i64,
+    pub expo: i32,
+    pub publish_slot: u64,
+}
+
+impl Price {
+    /// USD value of `amount` base units of a mint with `decimals`, in millionths of a dollar
+    pub fn usd_micros(&self, amount: u64, decimals: u8) -> Result<u64, ProgramError> {
+        let scaled = (amount as u128).checked_mul(self.price as u128).ok_or(TipError::MathOverflow)?;
+        let shift = self.expo as i64 + USD_DECIMALS as i64 - decimals as i64;
+        let value = if shift >= 0 {
+            10u128.checked_pow(shift as u32).and_then(|p| scaled.checked_mul(p))
+        } else {
+            Some(10u128.checked_pow(shift.unsigned_abs() as u32).map_or(0, |p| scaled / p))
+        };
+        value.and_then(|v| u64::try_from(v).ok()).ok_or_else(|| TipError::MathOverflow.into())
+    }
+
+    /// Base units of a mint with `decimals` worth `usd_cents`, rounded up so the tip is worth at
+    /// least that much
+    pub fn tokens_for_usd_cents(&self, usd_cents: u64, decimals: u8) -> Result<u64, ProgramError> {
+        let pow10 = |exp: u32| 10u128.checked_pow(exp).ok_or(TipError::MathOverflow);
+        let mut numerator = (usd_cents as u128).checked_mul(pow10(decimals as u32)?).ok_or(TipError::MathOverflow)?;
+        let mut denominator = (self.price as u128) * 100;
+        if self.expo < 0 {
+            numerator = numerator.checked_mul(pow10(self.expo.unsigned_abs())?).ok_or(TipError::MathOverflow)?;
+        } else {
+            denominator = denominator.checked_mul(pow10(self.expo as u32)?).ok_or(TipError::MathOverflow)?;
+        }
+        u64::try_from(numerator.div_ceil(denominator)).map_err(|_| TipError::MathOverflow.into())
+    }
+}
+
+/// Current aggregate price of a Pyth price account, rejecting feeds that are not trading, have no
+/// positive price or were last published more than MAX_PRICE_AGE_SLOTS before `current_slot`
+pub fn load_price(acc: &AccountInfo, current_slot: u64) -> Result<Price, ProgramError> {
+    if *acc.owner != PYTH_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = acc.data.borrow();
+    if data.len() < PRICE_ACCOUNT_MIN_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let u32_at = |i: usize| u32::from_le_bytes(data[i..i + 4].try_into().unwrap());
+    let u64_at = |i: usize| u64::from_le_bytes(data[i..i + 8].try_into().unwrap());
+    if u32_at(0) != MAGIC || u32_at(8) != ACCOUNT_TYPE_PRICE {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let price = Price {
+        price: u64_at(AGG_OFFSET) as i64,
+        expo: u32_at(EXPO_OFFSET) as i32,
+        publish_slot: u64_at(AGG_OFFSET + 24),
+    };
+    if u32_at(AGG_OFFSET + 16) != STATUS_TRADING
+        || price.price <= 0
+        || current_slot.saturating_sub(price.publish_slot) > MAX_PRICE_AGE_SLOTS
+    {
+        return Err(TipError::StalePrice.into());
+    }
+    Ok(price)
+}
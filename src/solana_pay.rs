@@ -0,0 +1,162 @@
+//! Solana Pay transaction requests for tips (feature `solana-pay`).
+//!
+//! A wallet scans a `solana:` link whose target is the app's HTTPS endpoint, GETs it for a label
+//! and icon, then POSTs `{"account": "<wallet>"}` and signs the transaction it gets back. This module
+//! builds the link, both response bodies and the unsigned transaction, using `instruction::tip_to_creator`
+//! so the account order always matches the program. Amounts are in the mint's base units.
+//!
+//! Spec: <https://docs.solanapay.com/spec#specification-transaction-request>
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_program::{hash::Hash, message::Message, pubkey::Pubkey};
+
+use crate::{
+    instruction::{tip_to_creator, TipExtras},
+    token,
+};
+
+/// What a tip QR code asks for; these become the query parameters of the request link
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TipRequest {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+    pub amount: u64,
+    pub memo: String,
+    pub platform_id: u64,
+}
+
+impl TipRequest {
+    /// Query string carrying every field, for `link` and for the endpoint to parse back with `from_query`
+    pub fn query(&self) -> String {
+        format!(
+            "creator={}&mint={}&token_program={}&amount={}&memo={}&platform_id={}",
+            self.creator,
+            self.mint,
+            self.token_program,
+            self.amount,
+            percent_encode(&self.memo),
+            self.platform_id,
+        )
+    }
+
+    /// Parse the query string of a request link (without the leading `?`); None if a field is
+    /// missing or malformed
+    pub fn from_query(query: &str) -> Option<Self> {
+        let param = |name: &str| {
+            query.split('&').find_map(|pair| pair.strip_prefix(name)?.strip_prefix('=')).map(percent_decode)
+        };
+        Some(TipRequest {
+            creator: param("creator")??.parse().ok()?,
+            mint: param("mint")??.parse().ok()?,
+            token_program: param("token_program")??.parse().ok()?,
+            amount: param("amount")??.parse().ok()?,
+            memo: param("memo").unwrap_or(Some(String::new()))?,
+            platform_id: param("platform_id").unwrap_or(Some("0".into()))?.parse().ok()?,
+        })
+    }
+
+    /// `solana:` link for a QR code that points the wallet at `endpoint` (an https URL without a query)
+    pub fn link(&self, endpoint: &str) -> String {
+        format!("solana:{}", percent_encode(&format!("{}?{}", endpoint, self.query())))
+    }
+}
+
+/// Body of the endpoint's GET response: what the wallet shows before asking for the account
+pub fn get_response(label: &str, icon: &str) -> String {
+    format!(r#"{{"label":{},"icon":{}}}"#, json_string(label), json_string(icon))
+}
+
+/// Unsigned transaction tipping `request` from `tipper`'s ATA, in wire format with the tipper as
+/// fee payer. `extras` carries the tip's optional accounts, e.g. the config's `treasury_split`.
+pub fn tip_transaction(
+    program_id: &Pubkey,
+    request: &TipRequest,
+    tipper: &Pubkey,
+    treasury_token: &Pubkey,
+    extras: TipExtras,
+    recent_blockhash: &Hash,
+) -> Vec<u8> {
+    let tipper_token = token::get_associated_token_address(tipper, &request.mint, &request.token_program);
+    let ix = tip_to_creator(
+        program_id,
+        request.platform_id,
+        tipper,
+        &tipper_token,
+        &request.creator,
+        treasury_token,
+        &request.mint,
+        &request.token_program,
+        request.amount,
+        &request.memo,
+        extras,
+    );
+    let message = Message::new_with_blockhash(&[ix], Some(tipper), recent_blockhash);
+    // Transaction wire format: compact-u16 signature count, that many empty signatures, the message
+    let signatures = message.header.num_required_signatures;
+    let mut tx = vec![signatures];
+    tx.resize(1 + signatures as usize * 64, 0);
+    tx.extend_from_slice(&message.serialize());
+    tx
+}
+
+/// Body of the endpoint's POST response for `tipper`, the `account` the wallet sent
+pub fn post_response(
+    program_id: &Pubkey,
+    request: &TipRequest,
+    tipper: &Pubkey,
+    treasury_token: &Pubkey,
+    extras: TipExtras,
+    recent_blockhash: &Hash,
+    message: Option<&str>,
+) -> String {
+    let tx = tip_transaction(program_id, request, tipper, treasury_token, extras, recent_blockhash);
+    let transaction = json_string(&STANDARD.encode(tx));
+    match message {
+        Some(message) => format!(r#"{{"transaction":{},"message":{}}}"#, transaction, json_string(message)),
+        None => format!(r#"{{"transaction":{}}}"#, transaction),
+    }
+}
+
+/// RFC 3986 percent-encoding of everything but unreserved characters
+fn percent_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// None for a bad escape or a result that is not UTF-8; `+` stays a plus sign
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
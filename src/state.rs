@@ -0,0 +1,2545 @@
+use std::{io, mem::size_of};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
+use shank::{ShankAccount, ShankType};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::TipError;
+
+/// Anchor-compatible account layout: an 8-byte discriminator, sha256("account:<Name>")[..8],
+/// followed by the Borsh-encoded struct.
+/// Account structs also derive ShankAccount for `shank idl`, which only reads literal array lengths, so
+/// their array fields spell out MAX_FEE_TIERS and friends; the arrays built from the constants keep
+/// them in step
+pub trait AccountType: BorshSerialize + BorshDeserialize {
+    const DISCRIMINATOR: [u8; 8];
+
+    /// Decode an account of this type; any other discriminator is rejected
+    fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        match data.strip_prefix(&Self::DISCRIMINATOR) {
+            Some(rest) => Ok(Self::try_from_slice(rest)?),
+            None => Err(TipError::WrongAccountType.into()),
+        }
+    }
+
+    /// Like `unpack`, but a freshly created all-zero account decodes to its zero value
+    fn unpack_or_zeroed(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.iter().all(|b| *b == 0) {
+            return Ok(Self::try_from_slice(data.get(8..).ok_or(ProgramError::AccountDataTooSmall)?)?);
+        }
+        Self::unpack(data)
+    }
+
+    fn pack(&self, data: &mut [u8]) -> Result<(), ProgramError> {
+        let (discriminator, rest) = data.split_at_mut_checked(8).ok_or(ProgramError::AccountDataTooSmall)?;
+        discriminator.copy_from_slice(&Self::DISCRIMINATOR);
+        self.serialize(&mut &mut rest[..])?;
+        Ok(())
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct TipConfig {
+    pub is_initialized: bool,
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub total_tips: u64,
+    /// Frozen: sums mixed mints, so tips now count their volume in the mint's MintStats instead
+    pub total_volume: u64,
+    /// Frozen, like `total_volume`: tips now count their fees in the mint's MintStats
+    pub total_fees: u64,
+    /// Frozen: the fee target is now set per mint, in MintStats, and this one no longer applies
+    pub fee_target: u64,
+    /// Admin instructions must be alone in their transaction (checked via the instructions sysvar)
+    pub admin_isolation: bool,
+    /// Nominated by ProposeAdmin, takes over once it signs AcceptAdmin (default = none)
+    pub pending_admin: Pubkey,
+    /// Emergency brake: every tip path rejects while set
+    pub paused: bool,
+    /// Share of each fee paid to the tip's referrer, in bps of the fee
+    pub referral_bps: u16,
+    /// Frozen, like `total_fees`; MintStats splits each mint's fees the same way
+    pub total_treasury_fees: u64,
+    pub total_referral_fees: u64,
+    /// Layout version; anything below CONFIG_VERSION must go through MigrateConfig
+    pub version: u8,
+    /// Canonical bump of the config PDA, so later instructions can use create_program_address
+    pub bump: u8,
+    /// Number of used treasury split entries (0 = the whole fee goes to `treasury`)
+    pub treasury_split_count: u8,
+    /// Extra fee recipients (token accounts), each taking `treasury_split_bps` of the treasury's fee
+    pub treasury_split_recipients: [Pubkey; 3],
+    pub treasury_split_bps: [u16; 3],
+    /// Number of used fee tiers (0 = flat `fee_bps`)
+    pub fee_tier_count: u8,
+    /// Ascending tip sizes from which each tier's bps applies; tips below the first use `fee_bps`
+    pub fee_tier_min_amounts: [u64; 4],
+    pub fee_tier_bps: [u16; 4],
+    /// Smallest accepted tip in base units, for SOL and for mints without their own minimum (0 = none)
+    pub min_tip_amount: u64,
+    /// Largest accepted single tip in base units (0 = no cap)
+    pub max_tip_amount: u64,
+    /// Rate-limit window per tipper, in seconds (0 = no rate limit)
+    pub rate_limit_window_secs: u64,
+    /// Most tips a tipper may send per window (0 = unlimited)
+    pub rate_limit_max_tips: u64,
+    /// Most volume, in base units, a tipper may send per window (0 = unlimited)
+    pub rate_limit_max_volume: u64,
+    /// Fee rate scheduled by ScheduleFeeUpdate, applied by ApplyFeeUpdate
+    pub pending_fee_bps: u16,
+    /// Slot from which the pending fee may be applied (0 = nothing scheduled)
+    pub fee_activation_slot: u64,
+    /// Approvals needed to execute a PendingAction (0 = multisig off, `admin` signs directly)
+    pub multisig_threshold: u8,
+    pub multisig_signer_count: u8,
+    /// Only the first `multisig_signer_count` entries are used
+    pub multisig_signers: [Pubkey; 5],
+    /// Bumped by every SetMultisig so approvals from an older signer set cannot execute
+    pub multisig_epoch: u64,
+    /// May call ScheduleFeeUpdate alongside the admin (default = none)
+    pub fee_manager: Pubkey,
+    /// May call UpdateTreasury and SetTreasurySplit alongside the admin (default = none)
+    pub treasury_manager: Pubkey,
+    /// May call Pause and Unpause alongside the admin (default = none)
+    pub pauser: Pubkey,
+    /// Sized collection that TipWithReceiptNft mints into (default = NFT receipts off)
+    pub receipt_nft_collection: Pubkey,
+    /// Metadata URI for receipt NFTs; `{mint}` is replaced with the NFT's mint address
+    #[idl_type("[u8; 128]")]
+    pub receipt_nft_uri: ReceiptUri,
+    /// Concurrent Merkle tree that `Tip` appends compressed receipts to (default = none)
+    pub receipt_tree: Pubkey,
+    /// Tips of at least this many base units vest in the creator's vault (0 = no vesting)
+    pub vesting_threshold: u64,
+    /// Nothing of a vesting tip is released before this many seconds
+    pub vesting_cliff_secs: u64,
+    /// Seconds over which a vesting tip is released linearly (0 = all at the cliff)
+    pub vesting_duration_secs: u64,
+    /// Slots after a vesting tip during which its tipper may dispute it (0 = no disputes)
+    pub dispute_window_slots: u64,
+    /// May call ResolveDispute alongside the admin (default = none)
+    pub arbiter: Pubkey,
+    /// May call SetCreatorVerified alongside the admin (default = none)
+    pub verifier: Pubkey,
+    /// `Tip` only pays creators whose profile is verified; tip paths that take no profile are closed
+    pub require_verified: bool,
+    /// `Tip` must be a top-level instruction, so other programs cannot wrap it via CPI
+    pub require_top_level: bool,
+    /// Slot and unix timestamp of the most recent tip of any kind (0 = none yet)
+    pub last_tip_slot: u64,
+    pub last_tip_unix_ts: i64,
+    /// Unix timestamp of Initialize (0 for configs migrated from before this field)
+    pub created_at: i64,
+    /// Tippers whose volume in one mint this rebate period reaches this many base units earn rebates
+    /// (0 = rebates off)
+    pub rebate_threshold: u64,
+    /// Share of the fee of every tip at or above the threshold that accrues back to the tipper
+    pub rebate_bps: u16,
+    /// Realms/SPL Governance account that may call ScheduleFeeUpdate and UpdateTreasury, signing via
+    /// CPI from an executed proposal (default = none)
+    pub governance_authority: Pubkey,
+    /// Upgrade authority the program is expected to have, recorded at Initialize and checked by
+    /// VerifyDeployment (default = none recorded)
+    pub expected_upgrade_authority: Pubkey,
+    /// Set for good by OpenAuditLog; every admin instruction must then append to the audit log
+    pub audit_log_enabled: bool,
+    /// Platform this config runs (see `platform_seed`); every platform PDA is derived with it
+    pub platform_id: u64,
+    /// What `Tip` does when the tipper is the creator or controls the account being paid
+    pub self_tip_policy: SelfTipPolicy,
+    /// Swap program TipWithSwap may CPI into to convert a creator's share (default = swaps off)
+    pub swap_program: Pubkey,
+    /// Most creator profiles RegisterCreator may open, counted on platform 0's config (0 = unlimited)
+    pub max_creators: u32,
+    /// Creator profiles open, counted on platform 0's config: RegisterCreator adds one and closing a profile
+    /// removes one (profiles registered before this field are not counted)
+    pub registered_creators: u32,
+    /// Treasury vault balance, in base units of the vault's mint, below which a payout from it emits
+    /// TreasuryLow (0 = off)
+    pub treasury_low_watermark: u64,
+    /// Skip a tip's referral, charity or forward destination that fails its checks, emitting
+    /// RoutingSkipped, instead of failing the tip
+    pub lenient_routing: bool,
+}
+
+/// Zero-copy view of a current-version TipConfig account for the tip hot path. Borsh encodes
+/// fixed-size fields packed and little-endian, so this `repr(C, packed)` struct covers exactly
+/// the same bytes; keep the two in sync.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct TipConfigZc {
+    pub discriminator: [u8; 8],
+    pub is_initialized: u8,
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_bps: u16,
+    pub total_tips: u64,
+    pub total_volume: u64,
+    pub total_fees: u64,
+    pub fee_target: u64,
+    pub admin_isolation: u8,
+    pub pending_admin: Pubkey,
+    pub paused: u8,
+    pub referral_bps: u16,
+    pub total_treasury_fees: u64,
+    pub total_referral_fees: u64,
+    pub version: u8,
+    pub bump: u8,
+    pub treasury_split_count: u8,
+    pub treasury_split_recipients: [Pubkey; MAX_TREASURY_SPLITS],
+    pub treasury_split_bps: [u16; MAX_TREASURY_SPLITS],
+    pub fee_tier_count: u8,
+    pub fee_tier_min_amounts: [u64; MAX_FEE_TIERS],
+    pub fee_tier_bps: [u16; MAX_FEE_TIERS],
+    pub min_tip_amount: u64,
+    pub max_tip_amount: u64,
+    pub rate_limit_window_secs: u64,
+    pub rate_limit_max_tips: u64,
+    pub rate_limit_max_volume: u64,
+    pub pending_fee_bps: u16,
+    pub fee_activation_slot: u64,
+    pub multisig_threshold: u8,
+    pub multisig_signer_count: u8,
+    pub multisig_signers: [Pubkey; MAX_MULTISIG_SIGNERS],
+    pub multisig_epoch: u64,
+    pub fee_manager: Pubkey,
+    pub treasury_manager: Pubkey,
+    pub pauser: Pubkey,
+    pub receipt_nft_collection: Pubkey,
+    pub receipt_nft_uri: ReceiptUri,
+    pub receipt_tree: Pubkey,
+    pub vesting_threshold: u64,
+    pub vesting_cliff_secs: u64,
+    pub vesting_duration_secs: u64,
+    pub dispute_window_slots: u64,
+    pub arbiter: Pubkey,
+    pub verifier: Pubkey,
+    pub require_verified: u8,
+    pub require_top_level: u8,
+    pub last_tip_slot: u64,
+    pub last_tip_unix_ts: i64,
+    pub created_at: i64,
+    pub rebate_threshold: u64,
+    pub rebate_bps: u16,
+    pub governance_authority: Pubkey,
+    pub expected_upgrade_authority: Pubkey,
+    pub audit_log_enabled: u8,
+    pub platform_id: u64,
+    /// `SelfTipPolicy` index
+    pub self_tip_policy: u8,
+    pub swap_program: Pubkey,
+    pub max_creators: u32,
+    pub registered_creators: u32,
+    pub treasury_low_watermark: u64,
+    pub lenient_routing: u8,
+}
+
+const _: () = assert!(size_of::<TipConfigZc>() == CONFIG_SIZE);
+
+impl TipConfigZc {
+    /// Borrow a current-version config in place, with the same checks as `TipConfig::load`
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if data.len() != CONFIG_SIZE {
+            return Err(TipError::ConfigOutdated.into());
+        }
+        let config: &mut Self = bytemuck::from_bytes_mut(data);
+        if config.discriminator != TipConfig::DISCRIMINATOR {
+            return Err(TipError::WrongAccountType.into());
+        }
+        if config.version != CONFIG_VERSION {
+            return Err(TipError::ConfigOutdated.into());
+        }
+        Ok(config)
+    }
+
+    /// The fee rates, without a fee target; see `FeeSchedule::with_mint_stats`
+    pub fn fee_schedule(&self) -> FeeSchedule {
+        FeeSchedule {
+            fee_bps: self.fee_bps,
+            fee_target: 0,
+            total_fees: 0,
+            tier_count: self.fee_tier_count,
+            tier_min_amounts: self.fee_tier_min_amounts,
+            tier_bps: self.fee_tier_bps,
+        }
+    }
+
+    pub fn rate_limit(&self) -> RateLimit {
+        RateLimit {
+            window_secs: self.rate_limit_window_secs,
+            max_tips: self.rate_limit_max_tips,
+            max_volume: self.rate_limit_max_volume,
+        }
+    }
+
+    pub fn treasury_split(&self) -> TreasurySplit {
+        TreasurySplit {
+            treasury: self.treasury,
+            count: self.treasury_split_count,
+            recipients: self.treasury_split_recipients,
+            bps: self.treasury_split_bps,
+        }
+    }
+}
+
+/// The config and mint fields that decide a tip's fee
+#[derive(Clone, Copy, Debug)]
+pub struct FeeSchedule {
+    pub fee_bps: u16,
+    pub fee_target: u64,
+    pub total_fees: u64,
+    pub tier_count: u8,
+    pub tier_min_amounts: [u64; MAX_FEE_TIERS],
+    pub tier_bps: [u16; MAX_FEE_TIERS],
+}
+
+impl FeeSchedule {
+    /// The same schedule under the fee target of the tip's mint, if its MintStats exists
+    pub fn with_mint_stats(self, stats: Option<&MintStats>) -> Self {
+        match stats {
+            Some(stats) => FeeSchedule { fee_target: stats.fee_target, total_fees: stats.total_fees, ..self },
+            None => self,
+        }
+    }
+
+    /// The same schedule at a flat `fee_bps`, for a creator with a FeeOverride
+    pub fn with_override(self, fee_bps: u16) -> Self {
+        FeeSchedule { fee_bps, tier_count: 0, ..self }
+    }
+
+    /// Rate for a tip of `amount`: the highest tier it reaches, else the flat `fee_bps`
+    pub fn bps_for(&self, amount: u64) -> u16 {
+        self.tier_min_amounts
+            .iter()
+            .zip(self.tier_bps)
+            .take(self.tier_count as usize)
+            .rev()
+            .find(|(min_amount, _)| amount >= **min_amount)
+            .map_or(self.fee_bps, |(_, bps)| bps)
+    }
+}
+
+/// One row of the fee table: tips of at least `min_amount` base units pay `fee_bps`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_amount: u64,
+    pub fee_bps: u16,
+}
+
+pub const MAX_FEE_TIERS: usize = 4;
+
+/// Where the treasury's part of a fee goes: each split entry takes its bps, `treasury` keeps the
+/// rest (including rounding dust)
+#[derive(Clone, Copy, Debug)]
+pub struct TreasurySplit {
+    pub treasury: Pubkey,
+    pub count: u8,
+    pub recipients: [Pubkey; MAX_TREASURY_SPLITS],
+    pub bps: [u16; MAX_TREASURY_SPLITS],
+}
+
+impl TreasurySplit {
+    /// (recipient, share_bps) for each used entry
+    pub fn entries(&self) -> impl Iterator<Item = (Pubkey, u16)> + '_ {
+        self.recipients.iter().copied().zip(self.bps.iter().copied()).take(self.count as usize)
+    }
+}
+
+/// Per-tipper limits, enforced through TipperStats over fixed windows of `window_secs`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RateLimit {
+    pub window_secs: u64,
+    pub max_tips: u64,
+    pub max_volume: u64,
+}
+
+/// Wash-tipping guard: how `Tip` treats a tip whose tipper (or attributed tipper) is the creator,
+/// owns `creator_token`, or pays from the `payout_token` of the creator's passed profile
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SelfTipPolicy {
+    #[default]
+    Allow,
+    /// Let it through but emit `SelfTip`, so indexers and reward distributors can discount it
+    Flag,
+    /// Fail it with `SelfTipNotAllowed`
+    Reject,
+}
+
+/// An optional destination of a tip's money that the config's `lenient_routing` may skip
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoutingDestination {
+    /// `referrer_token`, paid `referral_bps` of the fee
+    Referral,
+    /// The mint's charity, paid `donation_bps` of the creator's share
+    Charity,
+    /// The creator's forward rule and its `forward_token`
+    Forward,
+}
+
+/// Fee recipients besides `treasury`
+pub const MAX_TREASURY_SPLITS: usize = 3;
+
+/// Delay between ScheduleFeeUpdate and ApplyFeeUpdate, ~1 day at 400 ms slots
+pub const FEE_UPDATE_DELAY_SLOTS: u64 = 216_000;
+
+pub const MAX_MULTISIG_SIGNERS: usize = 5;
+
+pub const MAX_RECEIPT_URI: usize = 128;
+
+/// Zero-padded UTF-8, so the config stays fixed-size. Borsh-encoded as the bare byte array, written
+/// out by hand because `shank idl` cannot read tuple structs that derive it
+#[repr(transparent)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ReceiptUri(pub [u8; MAX_RECEIPT_URI]);
+
+impl BorshSerialize for ReceiptUri {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl BorshDeserialize for ReceiptUri {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Self(BorshDeserialize::deserialize_reader(reader)?))
+    }
+}
+
+impl Default for ReceiptUri {
+    fn default() -> Self {
+        Self([0; MAX_RECEIPT_URI])
+    }
+}
+
+impl ReceiptUri {
+    /// None if `uri` does not fit or contains a NUL byte
+    pub fn new(uri: &str) -> Option<Self> {
+        if uri.len() > MAX_RECEIPT_URI || uri.contains('\0') {
+            return None;
+        }
+        let mut bytes = [0; MAX_RECEIPT_URI];
+        bytes[..uri.len()].copy_from_slice(uri.as_bytes());
+        Some(Self(bytes))
+    }
+
+    pub fn as_str(&self) -> &str {
+        let len = self.0.iter().position(|b| *b == 0).unwrap_or(MAX_RECEIPT_URI);
+        // Only ever written from a &str by `new`
+        std::str::from_utf8(&self.0[..len]).unwrap_or_default()
+    }
+}
+
+pub const MAX_DISPLAY_NAME: usize = 32;
+pub const MAX_METADATA_URI: usize = 200;
+
+/// Zero-padded UTF-8 of at most `N` bytes, so profile accounts stay fixed-size. Borsh-encoded by hand
+/// like ReceiptUri
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PaddedStr<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> BorshSerialize for PaddedStr<N> {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.serialize(writer)
+    }
+}
+
+impl<const N: usize> BorshDeserialize for PaddedStr<N> {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Self(BorshDeserialize::deserialize_reader(reader)?))
+    }
+}
+
+impl<const N: usize> PaddedStr<N> {
+    /// None if `s` does not fit or contains a NUL byte
+    pub fn new(s: &str) -> Option<Self> {
+        if s.len() > N || s.contains('\0') {
+            return None;
+        }
+        let mut bytes = [0; N];
+        bytes[..s.len()].copy_from_slice(s.as_bytes());
+        Some(Self(bytes))
+    }
+
+    pub fn as_str(&self) -> &str {
+        let len = self.0.iter().position(|b| *b == 0).unwrap_or(N);
+        // Only ever written from a &str by `new`
+        std::str::from_utf8(&self.0[..len]).unwrap_or_default()
+    }
+}
+
+/// Bump together with any new TipConfig field (always appended at the end)
+pub const CONFIG_VERSION: u8 = 28;
+
+impl TipConfig {
+    /// The fee rates, without a fee target; see `FeeSchedule::with_mint_stats`
+    pub fn fee_schedule(&self) -> FeeSchedule {
+        FeeSchedule {
+            fee_bps: self.fee_bps,
+            fee_target: 0,
+            total_fees: 0,
+            tier_count: self.fee_tier_count,
+            tier_min_amounts: self.fee_tier_min_amounts,
+            tier_bps: self.fee_tier_bps,
+        }
+    }
+
+    pub fn rate_limit(&self) -> RateLimit {
+        RateLimit {
+            window_secs: self.rate_limit_window_secs,
+            max_tips: self.rate_limit_max_tips,
+            max_volume: self.rate_limit_max_volume,
+        }
+    }
+
+    /// The fee table as SetFeeTiers takes it
+    pub fn fee_tiers(&self) -> Vec<FeeTier> {
+        (0..self.fee_tier_count as usize)
+            .map(|i| FeeTier { min_amount: self.fee_tier_min_amounts[i], fee_bps: self.fee_tier_bps[i] })
+            .collect()
+    }
+
+    /// The treasury split as SetTreasurySplit takes it
+    pub fn split_shares(&self) -> Vec<SplitShare> {
+        (0..self.treasury_split_count as usize)
+            .map(|i| SplitShare { recipient: self.treasury_split_recipients[i], share_bps: self.treasury_split_bps[i] })
+            .collect()
+    }
+
+    /// Holder of `role` (default = unassigned)
+    pub fn role_holder(&self, role: Role) -> Pubkey {
+        match role {
+            Role::FeeManager => self.fee_manager,
+            Role::TreasuryManager => self.treasury_manager,
+            Role::Pauser => self.pauser,
+            Role::Arbiter => self.arbiter,
+            Role::Verifier => self.verifier,
+            Role::Governance => self.governance_authority,
+        }
+    }
+
+    /// The admin always qualifies; a role holder only for their own role, and while the multisig is on
+    /// only for a role that cannot move fees or change the fee rate
+    pub fn has_role(&self, key: &Pubkey, role: Role) -> bool {
+        let holder = self.role_holder(role);
+        let suspended = self.multisig_threshold > 0 && role.needs_multisig();
+        *key == self.admin || (!suspended && holder != Pubkey::default() && *key == holder)
+    }
+
+    /// Index of `key` in the multisig signer set
+    pub fn multisig_signer_index(&self, key: &Pubkey) -> Option<usize> {
+        self.multisig_signers[..self.multisig_signer_count as usize].iter().position(|s| s == key)
+    }
+
+    pub fn treasury_split(&self) -> TreasurySplit {
+        TreasurySplit {
+            treasury: self.treasury,
+            count: self.treasury_split_count,
+            recipients: self.treasury_split_recipients,
+            bps: self.treasury_split_bps,
+        }
+    }
+
+    /// Decode a config written by the current program version
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() != CONFIG_SIZE {
+            return Err(TipError::ConfigOutdated.into());
+        }
+        let config = Self::unpack(data)?;
+        if config.version != CONFIG_VERSION {
+            return Err(TipError::ConfigOutdated.into());
+        }
+        Ok(config)
+    }
+
+    /// Decode any earlier layout. Fields have only ever been appended, so fields past the end
+    /// of an old account take their default value. Version 0 and 1 configs have no discriminator.
+    pub fn load_any_version(data: &[u8]) -> Result<Self, ProgramError> {
+        fn field<T: BorshDeserialize + Default>(buf: &mut &[u8]) -> io::Result<T> {
+            if buf.is_empty() {
+                Ok(T::default())
+            } else {
+                T::deserialize(buf)
+            }
+        }
+        let buf = &mut match data.strip_prefix(&Self::DISCRIMINATOR) {
+            Some(rest) => rest,
+            None => data,
+        };
+        Ok(Self {
+            is_initialized: field(buf)?,
+            admin: field(buf)?,
+            treasury: field(buf)?,
+            fee_bps: field(buf)?,
+            total_tips: field(buf)?,
+            total_volume: field(buf)?,
+            total_fees: field(buf)?,
+            fee_target: field(buf)?,
+            admin_isolation: field(buf)?,
+            pending_admin: field(buf)?,
+            paused: field(buf)?,
+            referral_bps: field(buf)?,
+            total_treasury_fees: field(buf)?,
+            total_referral_fees: field(buf)?,
+            version: field(buf)?,
+            bump: field(buf)?,
+            treasury_split_count: field(buf)?,
+            treasury_split_recipients: field(buf)?,
+            treasury_split_bps: field(buf)?,
+            fee_tier_count: field(buf)?,
+            fee_tier_min_amounts: field(buf)?,
+            fee_tier_bps: field(buf)?,
+            min_tip_amount: field(buf)?,
+            max_tip_amount: field(buf)?,
+            rate_limit_window_secs: field(buf)?,
+            rate_limit_max_tips: field(buf)?,
+            rate_limit_max_volume: field(buf)?,
+            pending_fee_bps: field(buf)?,
+            fee_activation_slot: field(buf)?,
+            multisig_threshold: field(buf)?,
+            multisig_signer_count: field(buf)?,
+            multisig_signers: field(buf)?,
+            multisig_epoch: field(buf)?,
+            fee_manager: field(buf)?,
+            treasury_manager: field(buf)?,
+            pauser: field(buf)?,
+            receipt_nft_collection: field(buf)?,
+            receipt_nft_uri: field(buf)?,
+            receipt_tree: field(buf)?,
+            vesting_threshold: field(buf)?,
+            vesting_cliff_secs: field(buf)?,
+            vesting_duration_secs: field(buf)?,
+            dispute_window_slots: field(buf)?,
+            arbiter: field(buf)?,
+            verifier: field(buf)?,
+            require_verified: field(buf)?,
+            require_top_level: field(buf)?,
+            last_tip_slot: field(buf)?,
+            last_tip_unix_ts: field(buf)?,
+            created_at: field(buf)?,
+            rebate_threshold: field(buf)?,
+            rebate_bps: field(buf)?,
+            governance_authority: field(buf)?,
+            expected_upgrade_authority: field(buf)?,
+            audit_log_enabled: field(buf)?,
+            platform_id: field(buf)?,
+            self_tip_policy: field(buf)?,
+            swap_program: field(buf)?,
+            max_creators: field(buf)?,
+            registered_creators: field(buf)?,
+            treasury_low_watermark: field(buf)?,
+            lenient_routing: field(buf)?,
+        })
+    }
+}
+
+/// Borsh size of TipConfig, one term per field in declaration order
+pub const fn config_size() -> usize {
+    size_of::<[u8; 8]>() // discriminator
+        + size_of::<bool>() // is_initialized
+        + size_of::<Pubkey>() // admin
+        + size_of::<Pubkey>() // treasury
+        + size_of::<u16>() // fee_bps
+        + size_of::<u64>() // total_tips
+        + size_of::<u64>() // total_volume
+        + size_of::<u64>() // total_fees
+        + size_of::<u64>() // fee_target
+        + size_of::<bool>() // admin_isolation
+        + size_of::<Pubkey>() // pending_admin
+        + size_of::<bool>() // paused
+        + size_of::<u16>() // referral_bps
+        + size_of::<u64>() // total_treasury_fees
+        + size_of::<u64>() // total_referral_fees
+        + size_of::<u8>() // version
+        + size_of::<u8>() // bump
+        + size_of::<u8>() // treasury_split_count
+        + size_of::<[Pubkey; MAX_TREASURY_SPLITS]>() // treasury_split_recipients
+        + size_of::<[u16; MAX_TREASURY_SPLITS]>() // treasury_split_bps
+        + size_of::<u8>() // fee_tier_count
+        + size_of::<[u64; MAX_FEE_TIERS]>() // fee_tier_min_amounts
+        + size_of::<[u16; MAX_FEE_TIERS]>() // fee_tier_bps
+        + size_of::<u64>() // min_tip_amount
+        + size_of::<u64>() // max_tip_amount
+        + size_of::<u64>() // rate_limit_window_secs
+        + size_of::<u64>() // rate_limit_max_tips
+        + size_of::<u64>() // rate_limit_max_volume
+        + size_of::<u16>() // pending_fee_bps
+        + size_of::<u64>() // fee_activation_slot
+        + size_of::<u8>() // multisig_threshold
+        + size_of::<u8>() // multisig_signer_count
+        + size_of::<[Pubkey; MAX_MULTISIG_SIGNERS]>() // multisig_signers
+        + size_of::<u64>() // multisig_epoch
+        + size_of::<Pubkey>() // fee_manager
+        + size_of::<Pubkey>() // treasury_manager
+        + size_of::<Pubkey>() // pauser
+        + size_of::<Pubkey>() // receipt_nft_collection
+        + size_of::<ReceiptUri>() // receipt_nft_uri
+        + size_of::<Pubkey>() // receipt_tree
+        + size_of::<u64>() // vesting_threshold
+        + size_of::<u64>() // vesting_cliff_secs
+        + size_of::<u64>() // vesting_duration_secs
+        + size_of::<u64>() // dispute_window_slots
+        + size_of::<Pubkey>() // arbiter
+        + size_of::<Pubkey>() // verifier
+        + size_of::<bool>() // require_verified
+        + size_of::<bool>() // require_top_level
+        + size_of::<u64>() // last_tip_slot
+        + size_of::<i64>() // last_tip_unix_ts
+        + size_of::<i64>() // created_at
+        + size_of::<u64>() // rebate_threshold
+        + size_of::<u16>() // rebate_bps
+        + size_of::<Pubkey>() // governance_authority
+        + size_of::<Pubkey>() // expected_upgrade_authority
+        + size_of::<u8>() // audit_log_enabled
+        + size_of::<u64>() // platform_id
+        + size_of::<u8>() // self_tip_policy
+        + size_of::<Pubkey>() // swap_program
+        + size_of::<u32>() // max_creators
+        + size_of::<u32>() // registered_creators
+        + size_of::<u64>() // treasury_low_watermark
+        + size_of::<bool>() // lenient_routing
+}
+
+pub const CONFIG_SIZE: usize = config_size(); // 1068 bytes
+
+impl AccountType for TipConfig {
+    const DISCRIMINATOR: [u8; 8] = [36, 69, 242, 222, 10, 60, 155, 218];
+}
+
+/// Seed that scopes a PDA to one platform, right after the PDA's prefix. Platform 0 is the
+/// deployment's original config: its seed is empty, so its accounts keep their original addresses.
+pub fn platform_seed(platform_id: u64) -> Vec<u8> {
+    match platform_id {
+        0 => vec![],
+        id => id.to_le_bytes().to_vec(),
+    }
+}
+
+/// One config per platform (PDA: ["config", platform])
+pub fn get_config_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config", &platform_seed(platform_id)], program_id)
+}
+
+/// Per-creator opt-in for CreatorNotified events (PDA: ["notify", creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct CreatorNotify {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub notify: bool,
+}
+
+pub const NOTIFY_SIZE: usize = 8 + 1 + 32 + 1; // 42 bytes
+
+impl AccountType for CreatorNotify {
+    const DISCRIMINATOR: [u8; 8] = [74, 208, 182, 238, 142, 190, 86, 10];
+}
+
+pub fn get_notify_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"notify", creator.as_ref()], program_id)
+}
+
+/// Pre-authorized tipping budget (PDA: ["session", platform, tipper]); the PDA is the SPL delegate
+/// on tipper_token and session_key signs each SessionTip in place of the tipper
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct Session {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    pub tipper_token: Pubkey,
+    pub session_key: Pubkey,
+    pub budget_remaining: u64,
+    pub expiry_ts: i64,
+}
+
+pub const SESSION_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 8 + 8; // 121 bytes
+
+impl AccountType for Session {
+    const DISCRIMINATOR: [u8; 8] = [243, 81, 72, 115, 214, 188, 72, 144];
+}
+
+pub fn get_session_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"session", &platform_seed(platform_id), tipper.as_ref()], program_id)
+}
+
+/// Relayed tipping for one wallet (PDA: ["delegation", platform, tipper]); the tipper approves the PDA as SPL
+/// delegate on their token account and signs a DelegatedTip message per TipDelegated
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct Delegation {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    /// Nonce the next DelegatedTip message must carry
+    pub nonce: u64,
+}
+
+pub const DELEGATION_SIZE: usize = 8 + 1 + 32 + 8; // 49 bytes
+
+impl AccountType for Delegation {
+    const DISCRIMINATOR: [u8; 8] = [237, 90, 140, 159, 124, 255, 243, 80];
+}
+
+pub fn get_delegation_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"delegation", &platform_seed(platform_id), tipper.as_ref()], program_id)
+}
+
+/// Off-chain authorization for one TipDelegated: the tipper signs its Borsh encoding with Ed25519
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DelegatedTip {
+    /// Binds the signature to this program
+    pub program_id: Pubkey,
+    /// Binds the signature to this platform's config, and so to its fee
+    pub platform_id: u64,
+    pub tipper_token: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    /// The tipper's Delegation::nonce
+    pub nonce: u64,
+    /// Unix timestamp after which the message is rejected
+    pub expiry_ts: i64,
+}
+
+/// Lifetime tip stats for a creator wallet (PDA: ["creator", platform, creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct CreatorStats {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub tip_count: u64,
+    /// Net of fees, i.e. what actually reached the creator
+    pub total_received: u64,
+    /// Largest single tip amount, before fees
+    pub largest_tip: u64,
+    pub last_tip_slot: u64,
+    /// Leaderboard epoch that `epoch_volume` belongs to
+    pub epoch_index: u64,
+    /// Sum of tip amounts in `epoch_index`, before fees
+    pub epoch_volume: u64,
+    /// Tips per category; tips from instructions without a category count as 0
+    pub category_counts: [u64; 8],
+    /// Unix timestamp of the most recent tip
+    pub last_tip_unix_ts: i64,
+    /// Unix timestamp of the creator's first tip (0 for accounts created before this field)
+    pub created_at: i64,
+    /// Creator's share of tips passed with a price feed, valued when received, in millionths of a USD
+    pub total_usd_received: u64,
+    /// Tokens supporters currently have staked behind the creator, across mints
+    pub total_staked: u64,
+    /// `Tip`s per superchat tier, tier 1 first; tips below every threshold are not counted
+    pub tier_counts: [u64; 4],
+}
+
+/// Categories a `Tip` can carry (e.g. applause, superchat, question); their meaning is up to clients
+pub const TIP_CATEGORIES: usize = 8;
+
+/// Superchat tier thresholds a creator may set
+pub const MAX_TIP_TIERS: usize = 4;
+
+pub const CREATOR_STATS_SIZE: usize =
+    8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 * TIP_CATEGORIES + 8 + 8 + 8 + 8 + 8 * MAX_TIP_TIERS; // 217 bytes
+
+impl CreatorStats {
+    /// Decode any layout; fields missing from a legacy account start at zero
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < CREATOR_STATS_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(CREATOR_STATS_SIZE, 0);
+            return Self::unpack_or_zeroed(&padded);
+        }
+        Self::unpack_or_zeroed(data)
+    }
+}
+
+impl AccountType for CreatorStats {
+    const DISCRIMINATOR: [u8; 8] = [239, 158, 112, 237, 227, 82, 97, 129];
+}
+
+pub fn get_creator_stats_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"creator", &platform_seed(platform_id), creator.as_ref()], program_id)
+}
+
+/// Lifetime tipping activity for a wallet (PDA: ["tipper", platform, tipper])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct TipperStats {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    pub tip_count: u64,
+    /// Sum of tip amounts sent, before fees
+    pub total_volume: u64,
+    /// Start of the current rate-limit window (unix seconds)
+    pub window_start_ts: i64,
+    pub window_tip_count: u64,
+    pub window_volume: u64,
+    /// UTC day (unix seconds / 86400) of the latest tip
+    pub last_tip_day: i64,
+    /// Consecutive UTC days with at least one tip, ending at `last_tip_day`
+    pub current_streak_days: u64,
+    pub longest_streak: u64,
+    /// Leaderboard epoch that `epoch_volume` belongs to
+    pub epoch_index: u64,
+    /// Sum of tip amounts sent in `epoch_index`, before fees
+    pub epoch_volume: u64,
+    /// Nonce the tipper's next TipWithNonce must carry; counts only those tips
+    pub tip_nonce: u64,
+}
+
+pub const TIPPER_STATS_SIZE: usize = 8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8; // 129 bytes
+
+/// Accounts created before rate limiting existed; those from before streaks are 81 bytes
+pub const LEGACY_TIPPER_STATS_SIZE: usize = 8 + 1 + 32 + 8 + 8; // 57 bytes
+
+/// Accounts from before epoch volume, the first layout with the rate-limit window and streak
+pub const RATE_LIMIT_TIPPER_STATS_SIZE: usize = 8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8; // 105 bytes
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+impl TipperStats {
+    /// Decode any layout; fields missing from a legacy account start at zero
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < TIPPER_STATS_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(TIPPER_STATS_SIZE, 0);
+            return Self::unpack_or_zeroed(&padded);
+        }
+        Self::unpack_or_zeroed(data)
+    }
+}
+
+impl AccountType for TipperStats {
+    const DISCRIMINATOR: [u8; 8] = [122, 219, 214, 77, 138, 151, 107, 134];
+}
+
+pub fn get_tipper_stats_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tipper", &platform_seed(platform_id), tipper.as_ref()], program_id)
+}
+
+/// Marks a mint as accepted by the tip instructions (PDA: ["allowed_mint", platform, mint])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct AllowedMint {
+    pub is_initialized: bool,
+    pub mint: Pubkey,
+    /// Smallest accepted tip in this mint's base units (0 = TipConfig.min_tip_amount)
+    pub min_tip_amount: u64,
+    /// Pyth price account `Tip` values this mint with (default = no USD valuation)
+    pub price_feed: Pubkey,
+}
+
+pub const ALLOWED_MINT_SIZE: usize = 8 + 1 + 32 + 8 + 32; // 81 bytes
+
+/// Entries created before `min_tip_amount` existed; those from before `price_feed` are 49 bytes
+pub const LEGACY_ALLOWED_MINT_SIZE: usize = 8 + 1 + 32; // 41 bytes
+
+impl AllowedMint {
+    /// Decode any layout; a legacy entry has no minimum or price feed of its own
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < ALLOWED_MINT_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(ALLOWED_MINT_SIZE, 0);
+            return Self::unpack(&padded);
+        }
+        Self::unpack(data)
+    }
+
+    /// Effective minimum tip for this mint
+    pub fn min_tip(&self, config_min: u64) -> u64 {
+        if self.min_tip_amount > 0 {
+            self.min_tip_amount
+        } else {
+            config_min
+        }
+    }
+}
+
+impl AccountType for AllowedMint {
+    const DISCRIMINATOR: [u8; 8] = [173, 229, 179, 46, 121, 164, 247, 6];
+}
+
+pub fn get_allowed_mint_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"allowed_mint", &platform_seed(platform_id), mint.as_ref()], program_id)
+}
+
+/// Negotiated fee rate for one creator's `Tip`s, replacing the tiers and flat `fee_bps`
+/// (PDA: ["fee_override", platform, creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct FeeOverride {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub fee_bps: u16,
+}
+
+pub const FEE_OVERRIDE_SIZE: usize = 8 + 1 + 32 + 2; // 43 bytes
+
+impl AccountType for FeeOverride {
+    const DISCRIMINATOR: [u8; 8] = [45, 33, 41, 248, 253, 236, 239, 85];
+}
+
+pub fn get_fee_override_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fee_override", &platform_seed(platform_id), creator.as_ref()], program_id)
+}
+
+/// Compliance block on one creator: `Tip` refuses to pay them while it exists
+/// (PDA: ["blocked_creator", platform, creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct BlockedCreator {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+}
+
+pub const BLOCKED_CREATOR_SIZE: usize = 8 + 1 + 32; // 41 bytes
+
+impl AccountType for BlockedCreator {
+    const DISCRIMINATOR: [u8; 8] = [232, 65, 17, 232, 37, 199, 70, 251];
+}
+
+pub fn get_blocked_creator_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"blocked_creator", &platform_seed(platform_id), creator.as_ref()], program_id)
+}
+
+/// Compliance block on one wallet: `Tip` refuses to let it tip while this exists
+/// (PDA: ["blocked_tipper", platform, tipper])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct BlockedTipper {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+}
+
+pub const BLOCKED_TIPPER_SIZE: usize = 8 + 1 + 32; // 41 bytes
+
+impl AccountType for BlockedTipper {
+    const DISCRIMINATOR: [u8; 8] = [45, 228, 49, 136, 29, 228, 36, 49];
+}
+
+pub fn get_blocked_tipper_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"blocked_tipper", &platform_seed(platform_id), tipper.as_ref()], program_id)
+}
+
+/// Protocol fee discount a `Tip` redeems by passing this account (PDA: ["promo", platform,
+/// code_hash]). Only the SHA-256 of the code is stored, so the code itself cannot be read from the
+/// chain.
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct PromoCode {
+    pub is_initialized: bool,
+    pub code_hash: [u8; 32],
+    /// Share of the fee waived, in bps (10000 = fee-free)
+    pub discount_bps: u16,
+    /// Redemptions left; each tip that passes the code uses one
+    pub remaining_uses: u64,
+    /// The code no longer applies from this unix timestamp
+    pub expiry_ts: i64,
+}
+
+pub const PROMO_CODE_SIZE: usize = 8 + 1 + 32 + 2 + 8 + 8; // 59 bytes
+
+impl AccountType for PromoCode {
+    const DISCRIMINATOR: [u8; 8] = [253, 157, 67, 176, 230, 182, 64, 61];
+}
+
+/// What a PromoCode stores in place of `code`
+pub fn promo_code_hash(code: &str) -> [u8; 32] {
+    solana_program::hash::hash(code.as_bytes()).to_bytes()
+}
+
+pub fn get_promo_code_pda(program_id: &Pubkey, platform_id: u64, code_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"promo", &platform_seed(platform_id), code_hash], program_id)
+}
+
+/// On-chain directory entry for a creator (PDA: ["creator_profile", creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct CreatorProfile {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    #[idl_type("[u8; 32]")]
+    pub display_name: PaddedStr<MAX_DISPLAY_NAME>,
+    /// Off-chain JSON with the avatar, links and so on
+    #[idl_type("[u8; 200]")]
+    pub metadata_uri: PaddedStr<MAX_METADATA_URI>,
+    /// Token account frontends should pass as `creator_token`
+    pub payout_token: Pubkey,
+    /// Set by the admin or verifier once the creator passes KYC
+    pub verified: bool,
+    /// Mint `min_tip` and the tip menu are denominated in (default = none set)
+    pub tip_mint: Pubkey,
+    /// Smallest tip `Tip` accepts in `tip_mint` when passed this profile, in base units
+    pub min_tip: u64,
+    pub tip_menu_len: u8,
+    /// Suggested amounts for frontends; only the first `tip_menu_len` entries are used
+    pub tip_menu: [u64; 6],
+    pub tier_count: u8,
+    /// Superchat tier thresholds in `tip_mint`, strictly increasing: a tip reaching the first
+    /// `tier_count` of them is tier `n`
+    pub tier_thresholds: [u64; 4],
+    /// Mint TipWithSwap converts the creator's share into, paid to their ATA (default = none set)
+    pub payout_mint: Pubkey,
+    /// Share of the creator's own part of a tip that Tip withholds for `tax_account` (0 = off)
+    pub tax_bps: u16,
+    /// Wallet whose ATA in the tip's mint receives the withheld tax
+    pub tax_account: Pubkey,
+}
+
+pub const MAX_TIP_MENU: usize = 6;
+
+pub const CREATOR_PROFILE_SIZE: usize = 8 + 1 + 32 + MAX_DISPLAY_NAME + MAX_METADATA_URI + 32 + 1 + 32 + 8 + 1
+    + MAX_TIP_MENU * 8
+    + 1
+    + MAX_TIP_TIERS * 8
+    + 32
+    + 2
+    + 32; // 494 bytes
+
+/// Profiles registered before the tip menu existed
+pub const LEGACY_CREATOR_PROFILE_SIZE: usize = 8 + 1 + 32 + MAX_DISPLAY_NAME + MAX_METADATA_URI + 32 + 1; // 306 bytes
+
+impl AccountType for CreatorProfile {
+    const DISCRIMINATOR: [u8; 8] = [251, 250, 184, 111, 214, 178, 32, 221];
+}
+
+impl CreatorProfile {
+    /// Decode any layout; a legacy profile has no minimum, menu, tiers, payout mint or tax withholding
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < CREATOR_PROFILE_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(CREATOR_PROFILE_SIZE, 0);
+            return Self::unpack(&padded);
+        }
+        Self::unpack(data)
+    }
+
+    /// Suggested tip amounts in `tip_mint`, in the creator's order
+    pub fn tip_menu(&self) -> &[u64] {
+        &self.tip_menu[..(self.tip_menu_len as usize).min(MAX_TIP_MENU)]
+    }
+
+    /// Superchat tier thresholds in `tip_mint`, lowest first
+    pub fn tier_thresholds(&self) -> &[u64] {
+        &self.tier_thresholds[..(self.tier_count as usize).min(MAX_TIP_TIERS)]
+    }
+
+    /// Superchat tier of a tip of `amount` in `mint`: how many thresholds it reaches (0 in other mints)
+    pub fn tip_tier(&self, mint: &Pubkey, amount: u64) -> u8 {
+        if *mint != self.tip_mint {
+            return 0;
+        }
+        self.tier_thresholds().iter().take_while(|threshold| amount >= **threshold).count() as u8
+    }
+
+    /// `tax_bps` and `tax_account`, while tax withholding is on
+    pub fn tax_withholding(&self) -> Option<(u16, Pubkey)> {
+        (self.tax_bps > 0).then_some((self.tax_bps, self.tax_account))
+    }
+}
+
+pub fn get_creator_profile_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"creator_profile", creator.as_ref()], program_id)
+}
+
+pub const MAX_SPLIT_RECIPIENTS: usize = 5;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct SplitShare {
+    pub recipient: Pubkey,
+    pub share_bps: u16,
+}
+
+/// How a creator's net tip is divided between co-hosts (PDA: ["split", creator]). Stored at
+/// `split_config_size(recipient_count)`: only the used entries are on-chain.
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct SplitConfig {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub recipient_count: u8,
+    /// Only the first `recipient_count` entries are used; shares sum to 10000 bps
+    pub shares: [SplitShare; 5],
+}
+
+/// Size of a split with every entry; accounts created before splits were resized are this big
+pub const SPLIT_CONFIG_SIZE: usize = split_config_size(MAX_SPLIT_RECIPIENTS); // 212 bytes
+
+pub const fn split_config_size(recipients: usize) -> usize {
+    8 + 1 + 32 + 1 + recipients * (32 + 2)
+}
+
+impl AccountType for SplitConfig {
+    const DISCRIMINATOR: [u8; 8] = [49, 201, 50, 228, 22, 142, 12, 222];
+}
+
+impl SplitConfig {
+    /// Decode a split of any size; the entries it does not store read as unused
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < SPLIT_CONFIG_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(SPLIT_CONFIG_SIZE, 0);
+            return Self::unpack(&padded);
+        }
+        Self::unpack(data)
+    }
+}
+
+pub fn get_split_config_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"split", creator.as_ref()], program_id)
+}
+
+pub const MAX_COLLABORATORS: usize = 4;
+
+/// Fixed cuts of every `Tip` to a creator that go straight to their collaborators, taken from the
+/// creator's share after the protocol fee (PDA: ["collaborators", creator]). Stored at
+/// `collaborator_split_size(collaborator_count)` like SplitConfig.
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct CollaboratorSplit {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub collaborator_count: u8,
+    /// Only the first `collaborator_count` entries are used; shares sum to at most 10000 bps and the
+    /// creator keeps the rest
+    pub collaborators: [SplitShare; 4],
+}
+
+/// Size with every entry; accounts created before splits were resized are this big
+pub const COLLABORATOR_SPLIT_SIZE: usize = collaborator_split_size(MAX_COLLABORATORS); // 178 bytes
+
+pub const fn collaborator_split_size(collaborators: usize) -> usize {
+    8 + 1 + 32 + 1 + collaborators * (32 + 2)
+}
+
+impl AccountType for CollaboratorSplit {
+    const DISCRIMINATOR: [u8; 8] = [171, 62, 214, 5, 139, 90, 47, 168];
+}
+
+impl CollaboratorSplit {
+    /// Decode a split of any size; the entries it does not store read as unused
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < COLLABORATOR_SPLIT_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(COLLABORATOR_SPLIT_SIZE, 0);
+            return Self::unpack(&padded);
+        }
+        Self::unpack(data)
+    }
+
+    pub fn entries(&self) -> &[SplitShare] {
+        &self.collaborators[..self.collaborator_count as usize]
+    }
+}
+
+pub fn get_collaborator_split_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"collaborators", creator.as_ref()], program_id)
+}
+
+/// Largest share of the fee a fan club may waive
+pub const MAX_FAN_DISCOUNT_BPS: u16 = 5_000;
+
+/// Holders of an NFT from a verified Metaplex `collection` get `discount_bps` off the fee on their
+/// tips to the creator (PDA: ["fan_club", creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct FanClub {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    /// Collection mint
+    pub collection: Pubkey,
+    pub discount_bps: u16,
+}
+
+pub const FAN_CLUB_SIZE: usize = 8 + 1 + 32 + 32 + 2; // 75 bytes
+
+impl AccountType for FanClub {
+    const DISCRIMINATOR: [u8; 8] = [172, 57, 47, 93, 198, 54, 143, 217];
+}
+
+pub fn get_fan_club_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fan_club", creator.as_ref()], program_id)
+}
+
+/// Share of every `Tip` to the creator, after the protocol fee and collaborators' cuts, that goes
+/// straight to `destination`'s ATA for the tip's mint (PDA: ["forward", creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct ForwardRule {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    /// Wallet whose ATA receives the forwarded share, e.g. the creator's savings wallet
+    pub destination: Pubkey,
+    /// Share of what the creator would receive that is forwarded (0 = rule off)
+    pub forward_bps: u16,
+}
+
+pub const FORWARD_RULE_SIZE: usize = 8 + 1 + 32 + 32 + 2; // 75 bytes
+
+impl AccountType for ForwardRule {
+    const DISCRIMINATOR: [u8; 8] = [43, 182, 71, 161, 234, 252, 24, 193];
+}
+
+pub fn get_forward_rule_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"forward", creator.as_ref()], program_id)
+}
+
+/// Deposit address for wallets and exchanges that can only make plain transfers (PDA: ["tip_link",
+/// platform, creator, link_id]): lamports sent to it and tokens sent to its ATAs are swept to the
+/// creator, less the fee, by ClaimTipLinkSol and ClaimTipLink
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct TipLink {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub link_id: u64,
+    pub bump: u8,
+    /// Sweeps so far, SOL and token alike
+    pub claim_count: u64,
+    pub created_at: i64,
+}
+
+pub const TIP_LINK_SIZE: usize = 8 + 1 + 32 + 8 + 1 + 8 + 8; // 66 bytes
+
+impl AccountType for TipLink {
+    const DISCRIMINATOR: [u8; 8] = [75, 99, 87, 253, 206, 99, 75, 96];
+}
+
+pub fn get_tip_link_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey, link_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"tip_link", &platform_seed(platform_id), creator.as_ref(), &link_id.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Charity registered by the admin for tips in `mint` (PDA: ["charity", platform, mint]);
+/// TipWithDonation routes up to `max_donation_bps` of the creator's share to `charity_token`
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct Charity {
+    pub is_initialized: bool,
+    pub mint: Pubkey,
+    pub charity_token: Pubkey,
+    /// Largest share of the creator's share a tip may donate (0 = donations off)
+    pub max_donation_bps: u16,
+    /// What `charity_token` received, in base units
+    pub total_donated: u64,
+    pub donation_count: u64,
+}
+
+pub const CHARITY_SIZE: usize = 8 + 1 + 32 + 32 + 2 + 8 + 8; // 91 bytes
+
+impl AccountType for Charity {
+    const DISCRIMINATOR: [u8; 8] = [216, 215, 135, 92, 4, 204, 74, 102];
+}
+
+pub fn get_charity_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"charity", &platform_seed(platform_id), mint.as_ref()], program_id)
+}
+
+/// Recurring tip (PDA: ["sub", platform, tipper, creator]); the PDA is the SPL delegate on tipper_token
+/// and anyone may crank ProcessSubscription with its platform's config once next_payment_slot is reached
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct Subscription {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub tipper_token: Pubkey,
+    pub amount: u64,
+    pub interval_slots: u64,
+    pub next_payment_slot: u64,
+}
+
+pub const SUBSCRIPTION_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 8 + 8 + 8; // 129 bytes
+
+impl AccountType for Subscription {
+    const DISCRIMINATOR: [u8; 8] = [64, 7, 26, 135, 102, 132, 98, 33];
+}
+
+pub fn get_subscription_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sub", &platform_seed(platform_id), tipper.as_ref(), creator.as_ref()], program_id)
+}
+
+/// Seconds after which the tipper may also reclaim an unresolved escrowed tip
+pub const ESCROW_TIMEOUT_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Tip held for creator review (PDA: ["escrow", platform, tipper, creator]); the tokens sit in a vault
+/// token account (PDA: ["vault", escrow]) whose owner is the escrow PDA
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct Escrow {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    /// Refund destination on decline
+    pub tipper_token: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub created_ts: i64,
+}
+
+pub const ESCROW_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 32 + 8 + 8; // 153 bytes
+
+impl AccountType for Escrow {
+    const DISCRIMINATOR: [u8; 8] = [31, 213, 123, 187, 186, 22, 218, 155];
+}
+
+pub fn get_escrow_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"escrow", &platform_seed(platform_id), tipper.as_ref(), creator.as_ref()],
+        program_id,
+    )
+}
+
+pub fn get_escrow_vault_pda(program_id: &Pubkey, escrow: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", escrow.as_ref()], program_id)
+}
+
+/// One-off tip queued for `execute_at` (PDA: ["scheduled", platform, tipper, creator, schedule_id (u64 LE)]);
+/// the tokens sit in a vault token account (PDA: ["vault", scheduled_tip]) whose owner is the
+/// scheduled tip PDA, until anyone cranks ExecuteScheduledTip
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct ScheduledTip {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub schedule_id: u64,
+    /// Tipped to the creator, before fees
+    pub amount: u64,
+    /// Paid to whoever executes the tip, on top of `amount`
+    pub crank_reward: u64,
+    /// Unix timestamp from which the tip can be executed
+    pub execute_at: i64,
+}
+
+pub const SCHEDULED_TIP_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 8; // 137 bytes
+
+impl AccountType for ScheduledTip {
+    const DISCRIMINATOR: [u8; 8] = [96, 40, 183, 222, 11, 151, 60, 74];
+}
+
+pub fn get_scheduled_tip_pda(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    schedule_id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"scheduled", &platform_seed(platform_id), tipper.as_ref(), creator.as_ref(), &schedule_id.to_le_bytes()],
+        program_id,
+    )
+}
+
+pub fn get_scheduled_tip_vault_pda(program_id: &Pubkey, scheduled_tip: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", scheduled_tip.as_ref()], program_id)
+}
+
+/// What TipWithCampaign does with the part of a tip beyond a campaign's target
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CampaignOverflow {
+    /// Take the whole tip, raising more than the target
+    #[default]
+    Accept,
+    /// Take only what the target still needs, leaving the rest with the tipper; a tip to a campaign
+    /// that has met its target fails
+    Cap,
+}
+
+/// Crowdfunding goal (PDA: ["campaign", creator, campaign_id (u64 LE)]); TipWithCampaign counts toward
+/// it
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct Campaign {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub campaign_id: u64,
+    pub target_amount: u64,
+    /// Sum of contributions, before fees
+    pub raised: u64,
+    pub deadline_ts: i64,
+    pub finalized: bool,
+    pub goal_met: bool,
+    /// Slot of the tip that took `raised` to 25/50/75/100% of the target (0 = not yet)
+    pub milestone_slots: [u64; 4],
+    /// Fee charged on tips that reference this campaign, instead of the global rate
+    pub has_fee_override: bool,
+    pub fee_override_bps: u16,
+    /// Set with SetCampaignOverflow (default = accept)
+    pub overflow: CampaignOverflow,
+}
+
+pub const CAMPAIGN_SIZE: usize = 8 + 1 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 8 * 4 + 1 + 2 + 1; // 111 bytes
+
+impl Campaign {
+    /// Decode any layout; fields missing from a legacy account start at zero
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < CAMPAIGN_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(CAMPAIGN_SIZE, 0);
+            return Self::unpack(&padded);
+        }
+        Self::unpack(data)
+    }
+}
+
+impl AccountType for Campaign {
+    const DISCRIMINATOR: [u8; 8] = [50, 40, 49, 11, 157, 220, 229, 192];
+}
+
+pub fn get_campaign_pda(program_id: &Pubkey, creator: &Pubkey, campaign_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"campaign", creator.as_ref(), &campaign_id.to_le_bytes()], program_id)
+}
+
+/// Sponsor-funded 1:1 tip matching (PDA: ["pool", sponsor, pool_id (u64 LE)]); the funds sit in
+/// a vault token account (PDA: ["pool_vault", pool]) whose owner is the pool PDA
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct MatchingPool {
+    pub is_initialized: bool,
+    pub sponsor: Pubkey,
+    pub pool_id: u64,
+    pub mint: Pubkey,
+    /// Only tips to this creator are matched (default = any creator)
+    pub creator: Pubkey,
+    pub total_matched: u64,
+}
+
+pub const MATCHING_POOL_SIZE: usize = 8 + 1 + 32 + 8 + 32 + 32 + 8; // 121 bytes
+
+impl AccountType for MatchingPool {
+    const DISCRIMINATOR: [u8; 8] = [193, 46, 246, 112, 41, 79, 78, 195];
+}
+
+pub fn get_matching_pool_pda(program_id: &Pubkey, sponsor: &Pubkey, pool_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool", sponsor.as_ref(), &pool_id.to_le_bytes()], program_id)
+}
+
+pub fn get_pool_vault_pda(program_id: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool_vault", pool.as_ref()], program_id)
+}
+
+/// Program-held treasury token account for one mint (PDA: ["treasury_vault", platform, mint]),
+/// owned by the config PDA so only WithdrawFees can move fees out of it
+pub fn get_treasury_vault_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"treasury_vault", &platform_seed(platform_id), mint.as_ref()], program_id)
+}
+
+/// Delay between ScheduleEmergencyWithdraw and ExecuteEmergencyWithdraw, ~7 days at 400 ms slots:
+/// as long as an escrowed tip takes to become reclaimable by its tipper
+pub const EMERGENCY_WITHDRAW_DELAY_SLOTS: u64 = 7 * FEE_UPDATE_DELAY_SLOTS;
+
+/// Program-owned vaults the admin can drain with an emergency withdrawal
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmergencyVault {
+    /// Vault of an Escrow; the vault owner is the escrow
+    Escrow,
+    /// Vault of a MatchingPool; the vault owner is the pool
+    MatchingPool,
+    /// Treasury vault of one mint; the vault owner is the config
+    Treasury,
+}
+
+/// Admin withdrawal from one program-owned vault, waiting out EMERGENCY_WITHDRAW_DELAY_SLOTS
+/// (PDA: ["emergency", platform, vault])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct EmergencyWithdrawal {
+    pub is_initialized: bool,
+    pub kind: EmergencyVault,
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    /// First slot ExecuteEmergencyWithdraw may run at
+    pub executable_slot: u64,
+}
+
+pub const EMERGENCY_WITHDRAWAL_SIZE: usize = 8 + 1 + 1 + 32 + 32 + 8 + 8; // 90 bytes
+
+impl AccountType for EmergencyWithdrawal {
+    const DISCRIMINATOR: [u8; 8] = [162, 7, 219, 84, 133, 40, 251, 118];
+}
+
+pub fn get_emergency_withdrawal_pda(program_id: &Pubkey, platform_id: u64, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"emergency", &platform_seed(platform_id), vault.as_ref()], program_id)
+}
+
+/// Length of a leaderboard epoch; the epoch index is unix_timestamp / EPOCH_LENGTH_SECS
+pub const EPOCH_LENGTH_SECS: i64 = 7 * 24 * 60 * 60;
+
+pub fn epoch_index(unix_timestamp: i64) -> u64 {
+    unix_timestamp.max(0) as u64 / EPOCH_LENGTH_SECS as u64
+}
+
+/// Tip totals for one epoch (PDA: ["epoch", platform, epoch_index (u64 LE)]), created by RolloverEpoch
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct EpochStats {
+    pub is_initialized: bool,
+    pub epoch_index: u64,
+    pub tip_count: u64,
+    pub volume: u64,
+}
+
+pub const EPOCH_STATS_SIZE: usize = 8 + 1 + 8 + 8 + 8; // 33 bytes
+
+impl AccountType for EpochStats {
+    const DISCRIMINATOR: [u8; 8] = [118, 2, 81, 207, 154, 225, 238, 179];
+}
+
+pub fn get_epoch_stats_pda(program_id: &Pubkey, platform_id: u64, epoch_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"epoch", &platform_seed(platform_id), &epoch_index.to_le_bytes()], program_id)
+}
+
+/// The platform's running totals as of `taken_at`, frozen by the first SnapshotStats of an epoch
+/// (PDA: ["snapshot", platform, epoch_index (u64 LE)])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct StatsSnapshot {
+    pub is_initialized: bool,
+    pub epoch_index: u64,
+    pub taken_at: i64,
+    pub total_tips: u64,
+    /// The config's frozen fee totals; fees are counted per mint in MintStats
+    pub total_fees: u64,
+    pub total_treasury_fees: u64,
+    pub total_referral_fees: u64,
+}
+
+pub const STATS_SNAPSHOT_SIZE: usize = 8 + 1 + 8 + 8 + 8 + 8 + 8 + 8; // 57 bytes
+
+impl AccountType for StatsSnapshot {
+    const DISCRIMINATOR: [u8; 8] = [78, 55, 165, 174, 165, 93, 218, 143];
+}
+
+pub fn get_stats_snapshot_pda(program_id: &Pubkey, platform_id: u64, epoch_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"snapshot", &platform_seed(platform_id), &epoch_index.to_le_bytes()], program_id)
+}
+
+/// A creator's CreatorStats totals as of `taken_at`, frozen by the first SnapshotStats of an epoch
+/// that passes them (PDA: ["creator_snapshot", platform, creator, epoch_index (u64 LE)])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct CreatorStatsSnapshot {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub epoch_index: u64,
+    pub taken_at: i64,
+    pub tip_count: u64,
+    pub total_received: u64,
+    pub largest_tip: u64,
+    pub total_usd_received: u64,
+    pub total_staked: u64,
+}
+
+pub const CREATOR_STATS_SNAPSHOT_SIZE: usize = 8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8; // 97 bytes
+
+impl AccountType for CreatorStatsSnapshot {
+    const DISCRIMINATOR: [u8; 8] = [227, 246, 77, 33, 185, 205, 30, 93];
+}
+
+pub fn get_creator_stats_snapshot_pda(
+    program_id: &Pubkey,
+    platform_id: u64,
+    creator: &Pubkey,
+    epoch_index: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"creator_snapshot", &platform_seed(platform_id), creator.as_ref(), &epoch_index.to_le_bytes()],
+        program_id,
+    )
+}
+
+pub const MAX_LEADERBOARD_ENTRIES: usize = 32;
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable, ShankType)]
+pub struct LeaderboardEntry {
+    /// The ranked creator, or the ranked tipper on a tipper leaderboard
+    pub creator: Pubkey,
+    /// Their `CreatorStats::epoch_volume` (or `TipperStats::epoch_volume`) as of their latest ranked tip
+    pub volume: u64,
+}
+
+/// Top creators of one epoch by tip volume, highest first (PDA: ["leaderboard", platform, epoch_index]).
+/// The same layout ranks the epoch's top tippers by volume sent (PDA: ["tipper_leaderboard", platform,
+/// epoch_index]).
+/// Zero-copy so other programs can read it in place: discriminator, epoch_index (u64 LE), then
+/// MAX_LEADERBOARD_ENTRIES × (creator, volume u64 LE); unused entries are all zero.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable, ShankAccount)]
+pub struct Leaderboard {
+    pub discriminator: [u8; 8],
+    pub epoch_index: u64,
+    pub entries: [LeaderboardEntry; 32],
+}
+
+pub const LEADERBOARD_SIZE: usize = size_of::<Leaderboard>(); // 1296 bytes
+
+impl Leaderboard {
+    pub const DISCRIMINATOR: [u8; 8] = [247, 186, 238, 243, 194, 30, 9, 36];
+
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.len() != LEADERBOARD_SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let board: &Self = bytemuck::from_bytes(data);
+        if board.discriminator != Self::DISCRIMINATOR {
+            return Err(TipError::WrongAccountType.into());
+        }
+        Ok(board)
+    }
+
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::load(data)?;
+        Ok(bytemuck::from_bytes_mut(data))
+    }
+
+    /// Set `creator`'s volume and move them up past everyone they now beat. A creator not yet
+    /// ranked takes the last entry if they beat it; volumes only grow within an epoch, so a
+    /// ranked creator never has to move down.
+    pub fn record(&mut self, creator: &Pubkey, volume: u64) {
+        let last = MAX_LEADERBOARD_ENTRIES - 1;
+        let Some(mut i) = self.entries.iter().position(|e| e.creator == *creator).or_else(|| {
+            (volume > { self.entries[last].volume }).then_some(last)
+        }) else {
+            return;
+        };
+        self.entries[i] = LeaderboardEntry { creator: *creator, volume };
+        while i > 0 && { self.entries[i - 1].volume } < volume {
+            self.entries.swap(i - 1, i);
+            i -= 1;
+        }
+    }
+}
+
+pub fn get_leaderboard_pda(program_id: &Pubkey, platform_id: u64, epoch_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"leaderboard", &platform_seed(platform_id), &epoch_index.to_le_bytes()], program_id)
+}
+
+pub fn get_tipper_leaderboard_pda(program_id: &Pubkey, platform_id: u64, epoch_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"tipper_leaderboard", &platform_seed(platform_id), &epoch_index.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Most tipper leaderboard entries one DistributeEpochRewards pays
+pub const MAX_REWARD_WINNERS: u8 = 10;
+
+/// Admin-funded rewards for the top of each epoch's tipper leaderboard (PDA: ["reward_pool", platform]).
+/// The tokens sit in a vault token account (PDA: ["reward_vault", platform]) owned by the config
+/// PDA.
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct RewardPool {
+    pub is_initialized: bool,
+    /// Fixed by the first FundRewardPool
+    pub mint: Pubkey,
+    /// Paid out per epoch, or the vault balance if that is less
+    pub reward_per_epoch: u64,
+    /// How many of the tipper leaderboard's top entries share each epoch's reward, pro rata to their volume
+    pub winners: u8,
+    pub total_funded: u64,
+    pub total_distributed: u64,
+}
+
+pub const REWARD_POOL_SIZE: usize = 8 + 1 + 32 + 8 + 1 + 8 + 8; // 66 bytes
+
+impl AccountType for RewardPool {
+    const DISCRIMINATOR: [u8; 8] = [201, 58, 147, 12, 240, 93, 176, 35];
+}
+
+pub fn get_reward_pool_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_pool", &platform_seed(platform_id)], program_id)
+}
+
+pub fn get_reward_vault_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reward_vault", &platform_seed(platform_id)], program_id)
+}
+
+pub const VOLUME_BUCKET_SECS: i64 = 60 * 60;
+/// Seven days of hourly buckets
+pub const VOLUME_WINDOW_BUCKETS: usize = 7 * 24;
+
+/// Tip volume per hour over the last seven days, as a ring buffer (PDA: ["volume_window", platform]).
+/// Zero-copy like Leaderboard: discriminator, head_hour (i64 LE), then VOLUME_WINDOW_BUCKETS
+/// volumes (u64 LE), where hour `h` lives at index `h % VOLUME_WINDOW_BUCKETS`.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable, ShankAccount)]
+pub struct VolumeWindow {
+    pub discriminator: [u8; 8],
+    /// Hour (unix timestamp / VOLUME_BUCKET_SECS) of the newest bucket; older hours are behind it
+    pub head_hour: i64,
+    pub volumes: [u64; 168],
+}
+
+pub const VOLUME_WINDOW_SIZE: usize = size_of::<VolumeWindow>(); // 1360 bytes
+
+impl VolumeWindow {
+    pub const DISCRIMINATOR: [u8; 8] = [98, 110, 118, 43, 4, 2, 7, 209];
+
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.len() != VOLUME_WINDOW_SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let window: &Self = bytemuck::from_bytes(data);
+        if window.discriminator != Self::DISCRIMINATOR {
+            return Err(TipError::WrongAccountType.into());
+        }
+        Ok(window)
+    }
+
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::load(data)?;
+        Ok(bytemuck::from_bytes_mut(data))
+    }
+
+    /// Add `amount` to the bucket of `unix_timestamp`, first clearing the buckets of every hour
+    /// since the last tip so they can be reused
+    pub fn record(&mut self, unix_timestamp: i64, amount: u64) -> Result<(), ProgramError> {
+        let hour = unix_timestamp.div_euclid(VOLUME_BUCKET_SECS);
+        let head = self.head_hour;
+        if hour > head {
+            let stale = (hour - head).min(VOLUME_WINDOW_BUCKETS as i64);
+            for h in hour - stale + 1..=hour {
+                self.volumes[h.rem_euclid(VOLUME_WINDOW_BUCKETS as i64) as usize] = 0;
+            }
+            self.head_hour = hour;
+        }
+        // A timestamp behind the head (clock drift between slots) still lands in its own hour, unless
+        // that hour has already left the window
+        if self.head_hour - hour >= VOLUME_WINDOW_BUCKETS as i64 {
+            return Ok(());
+        }
+        let bucket = hour.rem_euclid(VOLUME_WINDOW_BUCKETS as i64) as usize;
+        self.volumes[bucket] = { self.volumes[bucket] }.checked_add(amount).ok_or(TipError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Volume in the `hours` hourly buckets up to and including the one of `unix_timestamp`
+    /// (24 = today, VOLUME_WINDOW_BUCKETS = the week), counting hours without tips as empty
+    pub fn volume(&self, unix_timestamp: i64, hours: usize) -> u64 {
+        let hour = unix_timestamp.div_euclid(VOLUME_BUCKET_SECS);
+        let head = self.head_hour;
+        (hour - hours.min(VOLUME_WINDOW_BUCKETS) as i64 + 1..=hour.min(head))
+            .filter(|h| head - h < VOLUME_WINDOW_BUCKETS as i64)
+            .map(|h| self.volumes[h.rem_euclid(VOLUME_WINDOW_BUCKETS as i64) as usize])
+            .fold(0u64, u64::saturating_add)
+    }
+}
+
+pub fn get_volume_window_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"volume_window", &platform_seed(platform_id)], program_id)
+}
+
+/// Admin instructions as recorded in the audit log, named after the instruction (SetPaused covers
+/// Pause and Unpause)
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdminAction {
+    ScheduleFeeUpdate,
+    SetMinTipAmount,
+    SetPriceFeed,
+    SetMaxTipAmount,
+    SetRateLimit,
+    SetFeeTiers,
+    SetFeeOverride,
+    ClearFeeOverride,
+    BlockCreator,
+    UnblockCreator,
+    BlockTipper,
+    UnblockTipper,
+    SetFeeTarget,
+    SetReferralBps,
+    AllowMint,
+    DisallowMint,
+    SetPaused,
+    UpdateTreasury,
+    OpenTreasuryVault,
+    WithdrawFees,
+    ScheduleEmergencyWithdraw,
+    ExecuteEmergencyWithdraw,
+    CancelEmergencyWithdraw,
+    SetRebate,
+    SetTreasurySplit,
+    ProposeAdmin,
+    HandoffOwnership,
+    SetMultisig,
+    SetRole,
+    InitReceiptTree,
+    SetReceiptNft,
+    SetAdminIsolation,
+    SweepExcessRent,
+    SetCreatorVerified,
+    SetRequireVerified,
+    SetRequireTopLevel,
+    SetVesting,
+    SetDisputeWindow,
+    ResolveDispute,
+    FundRewardPool,
+    CloseConfig,
+    AcceptAdmin,
+    CreatePromoCode,
+    ClosePromoCode,
+    SetCampaignFee,
+    SetCharity,
+    SetSelfTipPolicy,
+    SetSwapProgram,
+    SetMaxCreators,
+    SetTreasuryLowWatermark,
+    DistributeFees,
+    SetLenientRouting,
+    SetTaxWithholding,
+}
+
+pub const AUDIT_LOG_ENTRIES: usize = 64;
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable, ShankType)]
+pub struct AuditEntry {
+    /// `AdminAction` index
+    pub action: u8,
+    /// Signer of the instruction: the admin, a role holder or the multisig authority
+    pub actor: Pubkey,
+    /// Account the action applies to, such as a creator, mint or vault (default = the config)
+    pub target: Pubkey,
+    pub slot: u64,
+    /// Setting before and after: an integer or several as consecutive u64 LE words, a pubkey, or
+    /// the SHA-256 of the Borsh encoding of a list
+    pub old_value: [u8; 32],
+    pub new_value: [u8; 32],
+}
+
+/// The last AUDIT_LOG_ENTRIES admin actions as a ring buffer (PDA: ["audit_log", platform]).
+/// Zero-copy like Leaderboard: discriminator, count (u64 LE), then the entries, where the n-th
+/// action ever recorded (from 0) lives at index `n % AUDIT_LOG_ENTRIES`.
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable, ShankAccount)]
+pub struct AuditLog {
+    pub discriminator: [u8; 8],
+    /// Actions recorded since OpenAuditLog
+    pub count: u64,
+    pub entries: [AuditEntry; 64],
+}
+
+pub const AUDIT_LOG_SIZE: usize = size_of::<AuditLog>(); // 8784 bytes
+
+impl AuditLog {
+    pub const DISCRIMINATOR: [u8; 8] = [230, 207, 176, 233, 170, 130, 101, 244];
+
+    pub fn load(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.len() != AUDIT_LOG_SIZE {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let log: &Self = bytemuck::from_bytes(data);
+        if log.discriminator != Self::DISCRIMINATOR {
+            return Err(TipError::WrongAccountType.into());
+        }
+        Ok(log)
+    }
+
+    pub fn load_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        Self::load(data)?;
+        Ok(bytemuck::from_bytes_mut(data))
+    }
+
+    /// Overwrite the oldest entry once the buffer is full
+    pub fn append(&mut self, entry: AuditEntry) {
+        let count = self.count;
+        self.entries[(count % AUDIT_LOG_ENTRIES as u64) as usize] = entry;
+        self.count = count + 1;
+    }
+}
+
+pub fn get_audit_log_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"audit_log", &platform_seed(platform_id)], program_id)
+}
+
+/// Proof of one `Tip`, created on request and paid for by the tipper
+/// (PDA: ["receipt", tipper, creator, nonce]); `nonce` is the tipper's tip_count before the tip
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct TipReceipt {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    pub nonce: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub slot: u64,
+    /// Caller's reference from TipWithRef, e.g. a hash of the chat message or order (zero = none)
+    pub external_ref: [u8; 32],
+}
+
+pub const TIP_RECEIPT_SIZE: usize = 8 + 1 + 32 + 32 + 8 + 32 + 8 + 8 + 8 + 32; // 169 bytes
+
+impl TipReceipt {
+    /// Decode either layout; receipts written before `external_ref` existed read it as zero
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < TIP_RECEIPT_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(TIP_RECEIPT_SIZE, 0);
+            return Self::unpack(&padded);
+        }
+        Self::unpack(data)
+    }
+}
+
+impl AccountType for TipReceipt {
+    const DISCRIMINATOR: [u8; 8] = [209, 195, 63, 183, 0, 211, 175, 174];
+}
+
+pub fn get_tip_receipt_pda(program_id: &Pubkey, tipper: &Pubkey, creator: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"receipt", tipper.as_ref(), creator.as_ref(), &nonce.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Supporter badge levels, lowest first
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BadgeTier {
+    #[default]
+    None,
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl BadgeTier {
+    /// Highest tier whose threshold `volume` reaches; thresholds are bronze, silver, gold
+    pub fn for_volume(volume: u64, thresholds: &[u64; 3]) -> Self {
+        match thresholds.iter().rposition(|threshold| volume >= *threshold) {
+            Some(2) => BadgeTier::Gold,
+            Some(1) => BadgeTier::Silver,
+            Some(_) => BadgeTier::Bronze,
+            None => BadgeTier::None,
+        }
+    }
+}
+
+/// A creator's badge thresholds in base units of tip volume (PDA: ["badges", creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct BadgeConfig {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    /// Bronze, silver, gold; strictly increasing
+    pub thresholds: [u64; 3],
+}
+
+pub const BADGE_CONFIG_SIZE: usize = 8 + 1 + 32 + 3 * 8; // 65 bytes
+
+impl AccountType for BadgeConfig {
+    const DISCRIMINATOR: [u8; 8] = [118, 207, 243, 185, 139, 238, 159, 60];
+}
+
+pub fn get_badge_config_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"badges", creator.as_ref()], program_id)
+}
+
+/// Cumulative `Tip` volume from one tipper to one creator (PDA: ["supporter", tipper, creator])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct SupporterStats {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    /// Sum of tip amounts, before fees
+    pub total_volume: u64,
+    /// Last tier claimed with ClaimBadge
+    pub badge: BadgeTier,
+}
+
+pub const SUPPORTER_STATS_SIZE: usize = 8 + 1 + 32 + 32 + 8 + 1; // 82 bytes
+
+impl AccountType for SupporterStats {
+    const DISCRIMINATOR: [u8; 8] = [195, 201, 78, 124, 250, 12, 83, 18];
+}
+
+pub fn get_supporter_stats_pda(program_id: &Pubkey, tipper: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"supporter", tipper.as_ref(), creator.as_ref()], program_id)
+}
+
+/// A creator's pull-based payout vault for one mint (PDA: ["creator_vault", creator, mint]). Tips
+/// passing the vault token account (PDA: ["vault", creator_vault]) as `creator_token` accrue there
+/// until the creator calls Claim.
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct CreatorVault {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    /// Sum of all claims, in base units
+    pub total_claimed: u64,
+    /// Part of the balance still held by vesting schedules; Claim cannot touch it
+    pub locked: u64,
+    /// Vesting schedules created so far; the next one is numbered `vesting_count`
+    pub vesting_count: u64,
+}
+
+pub const CREATOR_VAULT_SIZE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8; // 97 bytes
+
+impl AccountType for CreatorVault {
+    const DISCRIMINATOR: [u8; 8] = [200, 135, 38, 98, 35, 236, 238, 12];
+}
+
+/// Share of each vault PayoutBatch sweeps that goes to the caller
+pub const PAYOUT_INCENTIVE_BPS: u64 = 10;
+
+pub fn get_creator_vault_pda(program_id: &Pubkey, creator: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"creator_vault", creator.as_ref(), mint.as_ref()], program_id)
+}
+
+pub fn get_creator_vault_token_pda(program_id: &Pubkey, creator_vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", creator_vault.as_ref()], program_id)
+}
+
+/// Creator's share of one large tip, locked in their vault and released by ReleaseVested
+/// (PDA: ["vesting", creator_vault, index (u64 LE)])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct VestingSchedule {
+    pub is_initialized: bool,
+    pub creator_vault: Pubkey,
+    /// Paid the rent; gets it back once everything is released
+    pub tipper: Pubkey,
+    /// Creator's share of the tip, after fees
+    pub amount: u64,
+    pub released: u64,
+    pub start_ts: i64,
+    /// Config values at tip time
+    pub cliff_secs: u64,
+    pub duration_secs: u64,
+    /// Last slot in which the tipper may call DisputeTip (0 = not disputable)
+    pub dispute_until_slot: u64,
+    /// Set by DisputeTip; freezes releases until ResolveDispute
+    pub disputed: bool,
+}
+
+pub const VESTING_SCHEDULE_SIZE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1; // 122 bytes
+
+impl AccountType for VestingSchedule {
+    const DISCRIMINATOR: [u8; 8] = [130, 200, 173, 148, 39, 75, 243, 147];
+}
+
+impl VestingSchedule {
+    /// Amount vested by `now`: nothing before the cliff, then linear over `duration_secs` from the start
+    pub fn vested_at(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start_ts).max(0) as u64;
+        if elapsed < self.cliff_secs {
+            0
+        } else if elapsed >= self.duration_secs {
+            self.amount
+        } else {
+            (self.amount as u128 * elapsed as u128 / self.duration_secs as u128) as u64
+        }
+    }
+}
+
+pub fn get_vesting_pda(program_id: &Pubkey, creator_vault: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vesting", creator_vault.as_ref(), &index.to_le_bytes()], program_id)
+}
+
+/// A creator's program-owned lamport vault (PDA: ["sol_vault", creator]). TipSol pays the creator's
+/// share into it when passed, and the creator takes it out with WithdrawSol; the balance above rent
+/// is the withdrawable amount.
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct SolVault {
+    pub is_initialized: bool,
+    pub creator: Pubkey,
+    /// Sum of all withdrawals, in lamports
+    pub total_withdrawn: u64,
+}
+
+pub const SOL_VAULT_SIZE: usize = 8 + 1 + 32 + 8; // 49 bytes
+
+impl AccountType for SolVault {
+    const DISCRIMINATOR: [u8; 8] = [21, 132, 230, 103, 19, 209, 129, 248];
+}
+
+pub fn get_sol_vault_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"sol_vault", creator.as_ref()], program_id)
+}
+
+/// Tip totals for one mint, in its base units; native SOL is counted under the default pubkey
+/// (PDA: ["mint_stats", platform, mint])
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct MintStats {
+    pub is_initialized: bool,
+    pub mint: Pubkey,
+    pub tip_count: u64,
+    /// Sum of what arrived, creator share plus fee
+    pub total_volume: u64,
+    /// Fees that arrived (total_fees = total_treasury_fees + total_referral_fees)
+    pub total_fees: u64,
+    pub total_treasury_fees: u64,
+    pub total_referral_fees: u64,
+    /// Fee holiday in this mint once total_fees reaches this (0 = no target)
+    pub fee_target: u64,
+}
+
+pub const MINT_STATS_SIZE: usize = 8 + 1 + 32 + 8 + 8 + 8 + 8 + 8 + 8; // 89 bytes
+
+/// Accounts created before the fee split and fee target moved here from the config
+pub const LEGACY_MINT_STATS_SIZE: usize = 8 + 1 + 32 + 8 + 8 + 8; // 65 bytes
+
+impl MintStats {
+    /// Decode any layout; fields missing from a legacy account start at zero
+    pub fn load(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < MINT_STATS_SIZE {
+            let mut padded = data.to_vec();
+            padded.resize(MINT_STATS_SIZE, 0);
+            return Self::unpack(&padded);
+        }
+        Self::unpack(data)
+    }
+}
+
+impl AccountType for MintStats {
+    const DISCRIMINATOR: [u8; 8] = [137, 217, 172, 217, 89, 249, 85, 214];
+}
+
+pub fn get_mint_stats_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"mint_stats", &platform_seed(platform_id), mint.as_ref()], program_id)
+}
+
+/// Rebate periods are fixed 30-day windows since the Unix epoch
+pub const REBATE_PERIOD_SECS: i64 = 30 * SECONDS_PER_DAY;
+
+pub fn rebate_period(unix_timestamp: i64) -> i64 {
+    unix_timestamp.div_euclid(REBATE_PERIOD_SECS)
+}
+
+/// A tipper's fee rebates in one mint (PDA: ["rebate", platform, tipper, mint]), paid out of that mint's
+/// treasury vault by ClaimRebate
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct TipperRebate {
+    pub is_initialized: bool,
+    pub tipper: Pubkey,
+    pub mint: Pubkey,
+    /// Rebate period that `period_volume` belongs to
+    pub period: i64,
+    /// Sum of tip amounts in `period`, before fees
+    pub period_volume: u64,
+    /// Earned and not yet claimed, in base units
+    pub accrued: u64,
+    pub total_claimed: u64,
+}
+
+pub const TIPPER_REBATE_SIZE: usize = 8 + 1 + 32 + 32 + 8 + 8 + 8 + 8; // 105 bytes
+
+impl AccountType for TipperRebate {
+    const DISCRIMINATOR: [u8; 8] = [135, 212, 124, 144, 202, 170, 78, 196];
+}
+
+pub fn get_tipper_rebate_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"rebate", &platform_seed(platform_id), tipper.as_ref(), mint.as_ref()], program_id)
+}
+
+/// Seconds between Unstake and the tokens becoming withdrawable
+pub const STAKE_UNBONDING_SECS: i64 = 7 * SECONDS_PER_DAY;
+
+/// One supporter's stake behind one creator in one mint (PDA: ["stake", platform, supporter,
+/// creator, mint]). The tokens sit in the creator's stake vault (PDA: ["stake_vault", platform,
+/// creator, mint]), a token account owned by the config PDA that pools every supporter's stake for
+/// that creator and mint.
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct StakePosition {
+    pub is_initialized: bool,
+    pub supporter: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    /// Counted in the creator's `total_staked` and the supporter's badge volume
+    pub staked: u64,
+    /// Unstaked and waiting out the unbonding period; no longer counted anywhere
+    pub unbonding: u64,
+    /// When `unbonding` can be withdrawn; every Unstake restarts it
+    pub unbonding_ends_at: i64,
+}
+
+pub const STAKE_POSITION_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 8 + 8 + 8; // 129 bytes
+
+impl AccountType for StakePosition {
+    const DISCRIMINATOR: [u8; 8] = [78, 165, 30, 111, 171, 125, 11, 220];
+}
+
+pub fn get_stake_position_pda(
+    program_id: &Pubkey,
+    platform_id: u64,
+    supporter: &Pubkey,
+    creator: &Pubkey,
+    mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"stake", &platform_seed(platform_id), supporter.as_ref(), creator.as_ref(), mint.as_ref()],
+        program_id,
+    )
+}
+
+pub fn get_stake_vault_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"stake_vault", &platform_seed(platform_id), creator.as_ref(), mint.as_ref()],
+        program_id,
+    )
+}
+
+/// Leaf of the compressed receipt tree: keccak256 of its Borsh encoding. The full record is logged
+/// as `ReceiptAppended` so indexers can serve it with a proof.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReceiptLeaf {
+    pub tipper: Pubkey,
+    pub creator: Pubkey,
+    /// The tipper's tip_count before the tip, as for TipReceipt
+    pub nonce: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub slot: u64,
+}
+
+impl ReceiptLeaf {
+    pub fn hash(&self) -> [u8; 32] {
+        solana_program::keccak::hash(&borsh::to_vec(self).expect("in-memory Borsh encoding cannot fail")).to_bytes()
+    }
+}
+
+/// Temporary wSOL account of TipWrappedSol, owned by the tipper and closed in the same instruction
+/// (PDA: ["wsol", tipper])
+pub fn get_wsol_pda(program_id: &Pubkey, tipper: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"wsol", tipper.as_ref()], program_id)
+}
+
+/// Sole authority of the receipt tree (PDA: ["tree_authority", platform])
+pub fn get_tree_authority_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"tree_authority", &platform_seed(platform_id)], program_id)
+}
+
+/// Mint authority and collection update authority for receipt NFTs (PDA: ["receipt_authority", platform])
+pub fn get_receipt_authority_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"receipt_authority", &platform_seed(platform_id)], program_id)
+}
+
+/// Signs as `admin` while the multisig is on (PDA: ["multisig", platform])
+pub fn get_multisig_authority_pda(program_id: &Pubkey, platform_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"multisig", &platform_seed(platform_id)], program_id)
+}
+
+/// Largest Borsh-encoded TipInstruction a PendingAction may carry
+pub const MAX_ACTION_DATA: usize = 512;
+
+/// Admin instruction awaiting multisig approval (PDA: ["pending_action", platform, nonce]); ExecuteAction
+/// replays `data` as the multisig authority once `approvals` reaches the threshold
+#[derive(BorshSerialize, BorshDeserialize, ShankAccount)]
+pub struct PendingAction {
+    pub is_initialized: bool,
+    pub nonce: u64,
+    /// Paid the rent; gets it back on execute or cancel
+    pub proposer: Pubkey,
+    /// `multisig_epoch` at proposal time
+    pub multisig_epoch: u64,
+    /// Bit i set = multisig signer i approved
+    pub approvals: u8,
+    pub data: Vec<u8>,
+}
+
+pub const fn pending_action_size(data_len: usize) -> usize {
+    8 + 1 + 8 + 32 + 8 + 1 + 4 + data_len
+}
+
+impl AccountType for PendingAction {
+    const DISCRIMINATOR: [u8; 8] = [10, 76, 29, 155, 104, 63, 34, 51];
+}
+
+pub fn get_pending_action_pda(program_id: &Pubkey, platform_id: u64, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pending_action", &platform_seed(platform_id), &nonce.to_le_bytes()], program_id)
+}
+
+/// Limited admin powers assigned with SetRole
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// ScheduleFeeUpdate
+    FeeManager,
+    /// UpdateTreasury, SetTreasurySplit
+    TreasuryManager,
+    /// Pause, Unpause
+    Pauser,
+    /// ResolveDispute
+    Arbiter,
+    /// SetCreatorVerified, SetTaxWithholding
+    Verifier,
+    /// ScheduleFeeUpdate, UpdateTreasury; meant for a DAO's governance account
+    Governance,
+}
+
+impl Role {
+    /// Roles over the fee rate or where fees go, which only the multisig exercises while it is on
+    pub fn needs_multisig(self) -> bool {
+        matches!(self, Role::FeeManager | Role::TreasuryManager | Role::Governance)
+    }
+}
+
+/// PDA kinds that `CloseAccount` can reclaim
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClosableAccount {
+    /// Authority: the creator
+    CreatorStats,
+    /// Authority: the tipper
+    TipperStats,
+    /// Authority: the tipper
+    Subscription,
+    /// Authority: the creator, once finalized
+    Campaign,
+    /// Authority: the creator; frees a slot under `max_creators`
+    CreatorProfile,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(n: u8) -> Pubkey {
+        Pubkey::new_from_array([n; 32])
+    }
+
+    /// A config with every field set to a distinct non-default value and every array full
+    fn populated_config() -> TipConfig {
+        TipConfig {
+            is_initialized: true,
+            admin: key(1),
+            treasury: key(2),
+            fee_bps: 0x0303,
+            total_tips: 0x0404_0404_0404_0404,
+            total_volume: 0x0505_0505_0505_0505,
+            total_fees: 0x0606_0606_0606_0606,
+            fee_target: 0x0707_0707_0707_0707,
+            admin_isolation: true,
+            pending_admin: key(8),
+            paused: true,
+            referral_bps: 0x0909,
+            total_treasury_fees: 0x0a0a_0a0a_0a0a_0a0a,
+            total_referral_fees: 0x0b0b_0b0b_0b0b_0b0b,
+            version: CONFIG_VERSION,
+            bump: 0x0c,
+            treasury_split_count: MAX_TREASURY_SPLITS as u8,
+            treasury_split_recipients: [key(13), key(14), key(15)],
+            treasury_split_bps: [0x1010, 0x1111, 0x1212],
+            fee_tier_count: MAX_FEE_TIERS as u8,
+            fee_tier_min_amounts: [0x1313, 0x1414, 0x1515, 0x1616],
+            fee_tier_bps: [0x1717, 0x1818, 0x1919, 0x1a1a],
+            min_tip_amount: 0x1b1b_1b1b_1b1b_1b1b,
+            max_tip_amount: 0x1c1c_1c1c_1c1c_1c1c,
+            rate_limit_window_secs: 0x1d1d_1d1d_1d1d_1d1d,
+            rate_limit_max_tips: 0x1e1e_1e1e_1e1e_1e1e,
+            rate_limit_max_volume: 0x1f1f_1f1f_1f1f_1f1f,
+            pending_fee_bps: 0x2020,
+            fee_activation_slot: 0x2121_2121_2121_2121,
+            multisig_threshold: 0x22,
+            multisig_signer_count: MAX_MULTISIG_SIGNERS as u8,
+            multisig_signers: [key(35), key(36), key(37), key(38), key(39)],
+            multisig_epoch: 0x2828_2828_2828_2828,
+            fee_manager: key(41),
+            treasury_manager: key(42),
+            pauser: key(43),
+            receipt_nft_collection: key(44),
+            receipt_nft_uri: ReceiptUri([0x2d; MAX_RECEIPT_URI]),
+            receipt_tree: key(46),
+            vesting_threshold: 0x2f2f_2f2f_2f2f_2f2f,
+            vesting_cliff_secs: 0x3030_3030_3030_3030,
+            vesting_duration_secs: 0x3131_3131_3131_3131,
+            dispute_window_slots: 0x3232_3232_3232_3232,
+            arbiter: key(51),
+            verifier: key(52),
+            require_verified: true,
+            require_top_level: true,
+            last_tip_slot: 0x3535_3535_3535_3535,
+            last_tip_unix_ts: 0x3636_3636_3636_3636,
+            created_at: 0x3737_3737_3737_3737,
+            rebate_threshold: 0x3838_3838_3838_3838,
+            rebate_bps: 0x3939,
+            governance_authority: key(58),
+            expected_upgrade_authority: key(59),
+            audit_log_enabled: true,
+            platform_id: 0x3d3d_3d3d_3d3d_3d3d,
+            self_tip_policy: SelfTipPolicy::Reject,
+            swap_program: key(63),
+            max_creators: 0x4040_4040,
+            registered_creators: 0x4141_4141,
+            treasury_low_watermark: 0x4242_4242_4242_4242,
+            lenient_routing: true,
+        }
+    }
+
+    #[test]
+    fn config_size_matches_borsh_encoding() {
+        // TipConfig has no Option or Vec fields, so a populated config is as large as any
+        let encoded = borsh::to_vec(&populated_config()).unwrap();
+        assert_eq!(TipConfig::DISCRIMINATOR.len() + encoded.len(), CONFIG_SIZE);
+
+        let mut data = vec![0; CONFIG_SIZE];
+        populated_config().pack(&mut data).unwrap();
+        assert!(TipConfig::unpack(&data).is_ok());
+        assert!(populated_config().pack(&mut [0; CONFIG_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn zero_copy_view_matches_borsh_layout() {
+        let config = populated_config();
+        let mut data = vec![0; CONFIG_SIZE];
+        config.pack(&mut data).unwrap();
+        let zc = *TipConfigZc::load_mut(&mut data).unwrap();
+
+        assert_eq!({ zc.discriminator }, TipConfig::DISCRIMINATOR);
+        assert_eq!(zc.is_initialized, config.is_initialized as u8);
+        assert_eq!({ zc.admin }, config.admin);
+        assert_eq!({ zc.treasury }, config.treasury);
+        assert_eq!({ zc.fee_bps }, config.fee_bps);
+        assert_eq!({ zc.total_tips }, config.total_tips);
+        assert_eq!({ zc.total_volume }, config.total_volume);
+        assert_eq!({ zc.total_fees }, config.total_fees);
+        assert_eq!({ zc.fee_target }, config.fee_target);
+        assert_eq!(zc.admin_isolation, config.admin_isolation as u8);
+        assert_eq!({ zc.pending_admin }, config.pending_admin);
+        assert_eq!(zc.paused, config.paused as u8);
+        assert_eq!({ zc.referral_bps }, config.referral_bps);
+        assert_eq!({ zc.total_treasury_fees }, config.total_treasury_fees);
+        assert_eq!({ zc.total_referral_fees }, config.total_referral_fees);
+        assert_eq!(zc.version, config.version);
+        assert_eq!(zc.bump, config.bump);
+        assert_eq!(zc.treasury_split_count, config.treasury_split_count);
+        assert_eq!({ zc.treasury_split_recipients }, config.treasury_split_recipients);
+        assert_eq!({ zc.treasury_split_bps }, config.treasury_split_bps);
+        assert_eq!(zc.fee_tier_count, config.fee_tier_count);
+        assert_eq!({ zc.fee_tier_min_amounts }, config.fee_tier_min_amounts);
+        assert_eq!({ zc.fee_tier_bps }, config.fee_tier_bps);
+        assert_eq!({ zc.min_tip_amount }, config.min_tip_amount);
+        assert_eq!({ zc.max_tip_amount }, config.max_tip_amount);
+        assert_eq!({ zc.rate_limit_window_secs }, config.rate_limit_window_secs);
+        assert_eq!({ zc.rate_limit_max_tips }, config.rate_limit_max_tips);
+        assert_eq!({ zc.rate_limit_max_volume }, config.rate_limit_max_volume);
+        assert_eq!({ zc.pending_fee_bps }, config.pending_fee_bps);
+        assert_eq!({ zc.fee_activation_slot }, config.fee_activation_slot);
+        assert_eq!(zc.multisig_threshold, config.multisig_threshold);
+        assert_eq!(zc.multisig_signer_count, config.multisig_signer_count);
+        assert_eq!({ zc.multisig_signers }, config.multisig_signers);
+        assert_eq!({ zc.multisig_epoch }, config.multisig_epoch);
+        assert_eq!({ zc.fee_manager }, config.fee_manager);
+        assert_eq!({ zc.treasury_manager }, config.treasury_manager);
+        assert_eq!({ zc.pauser }, config.pauser);
+        assert_eq!({ zc.receipt_nft_collection }, config.receipt_nft_collection);
+        assert_eq!({ zc.receipt_nft_uri }.0, config.receipt_nft_uri.0);
+        assert_eq!({ zc.receipt_tree }, config.receipt_tree);
+        assert_eq!({ zc.vesting_threshold }, config.vesting_threshold);
+        assert_eq!({ zc.vesting_cliff_secs }, config.vesting_cliff_secs);
+        assert_eq!({ zc.vesting_duration_secs }, config.vesting_duration_secs);
+        assert_eq!({ zc.dispute_window_slots }, config.dispute_window_slots);
+        assert_eq!({ zc.arbiter }, config.arbiter);
+        assert_eq!({ zc.verifier }, config.verifier);
+        assert_eq!(zc.require_verified, config.require_verified as u8);
+        assert_eq!(zc.require_top_level, config.require_top_level as u8);
+        assert_eq!({ zc.last_tip_slot }, config.last_tip_slot);
+        assert_eq!({ zc.last_tip_unix_ts }, config.last_tip_unix_ts);
+        assert_eq!({ zc.created_at }, config.created_at);
+        assert_eq!({ zc.rebate_threshold }, config.rebate_threshold);
+        assert_eq!({ zc.rebate_bps }, config.rebate_bps);
+        assert_eq!({ zc.governance_authority }, config.governance_authority);
+        assert_eq!({ zc.expected_upgrade_authority }, config.expected_upgrade_authority);
+        assert_eq!(zc.audit_log_enabled, config.audit_log_enabled as u8);
+        assert_eq!({ zc.platform_id }, config.platform_id);
+        assert_eq!(zc.self_tip_policy, config.self_tip_policy as u8);
+        assert_eq!({ zc.swap_program }, config.swap_program);
+        assert_eq!({ zc.max_creators }, config.max_creators);
+        assert_eq!({ zc.registered_creators }, config.registered_creators);
+        assert_eq!({ zc.treasury_low_watermark }, config.treasury_low_watermark);
+        assert_eq!(zc.lenient_routing, config.lenient_routing as u8);
+    }
+
+    #[test]
+    fn multisig_suspends_fee_and_treasury_roles() {
+        let mut config = populated_config();
+        config.multisig_threshold = 0;
+        assert!(config.has_role(&key(41), Role::FeeManager));
+        assert!(config.has_role(&key(42), Role::TreasuryManager));
+        assert!(config.has_role(&key(58), Role::Governance));
+        assert!(!config.has_role(&key(42), Role::FeeManager));
+
+        config.multisig_threshold = 2;
+        assert!(!config.has_role(&key(41), Role::FeeManager));
+        assert!(!config.has_role(&key(42), Role::TreasuryManager));
+        assert!(!config.has_role(&key(58), Role::Governance));
+        assert!(config.has_role(&key(43), Role::Pauser));
+        assert!(config.has_role(&key(51), Role::Arbiter));
+        assert!(config.has_role(&key(52), Role::Verifier));
+        assert!(config.has_role(&config.admin, Role::TreasuryManager));
+    }
+}
@@ -0,0 +1,374 @@
+//! Recurring tips charged against a token delegation the tipper grants the subscription PDA, by any
+//! crank once each period is due.
+
+use super::*;
+
+/// CreateSubscription: [amount: u64, interval_slots: u64, allowance: u64]
+/// Accounts: [sub (w), tipper (s,w), tipper_token (w), creator, token_program, system_program, blocked_tipper,
+///            (config), payer (optional, s,w)]
+/// Re-creating replaces the amount, interval and allowance and makes a payment due now. Only the
+/// config's platform (platform 0 without one) can charge it
+pub fn create_subscription(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    interval_slots: u64,
+    allowance: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let sub_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[sub_acc, tipper, tipper_token])?;
+    validation::token_accounts(&[tipper_token])?;
+    let platform_id = optional_platform(program_id, iter)?;
+    let payer = next_rent_payer(program_id, iter, tipper)?;
+    check_tipper_not_blocked(program_id, platform_id, blocked_tipper_acc, tipper.key)?;
+
+    let (pda, bump) = get_subscription_pda(program_id, platform_id, tipper.key, creator.key);
+    if *sub_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if amount == 0 || allowance < amount {
+        msg!("Subscription amount must be > 0 and allowance must cover at least one payment");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if interval_slots == 0 {
+        return Err(TipError::InvalidInterval.into());
+    }
+
+    if sub_acc.data_is_empty() {
+        create_pda_account(
+            payer,
+            sub_acc,
+            system_program,
+            program_id,
+            SUBSCRIPTION_SIZE,
+            &[b"sub", &platform_seed(platform_id), tipper.key.as_ref(), creator.key.as_ref(), &[bump]],
+        )?;
+    }
+
+    if !token::is_token_program(token_program.key) || tipper_token.owner != token_program.key {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    invoke(
+        &token::approve(token_program.key, tipper_token.key, &pda, tipper.key, allowance),
+        &[tipper_token.clone(), sub_acc.clone(), tipper.clone()],
+    )?;
+
+    let sub = Subscription {
+        is_initialized: true,
+        tipper: *tipper.key,
+        creator: *creator.key,
+        tipper_token: *tipper_token.key,
+        amount,
+        interval_slots,
+        next_payment_slot: Clock::get()?.slot,
+    };
+    sub.pack(&mut sub_acc.data.borrow_mut())?;
+
+    msg!("Subscription created: {} every {} slots", amount, interval_slots);
+    SubscriptionCreated { tipper: *tipper.key, creator: *creator.key, amount, interval_slots }.emit(accounts)
+}
+
+/// CancelSubscription: no data
+/// Accounts: [sub (w), tipper (s,w), tipper_token (w), token_program]
+/// Revokes the delegation if the subscription still holds it and refunds the PDA rent to the tipper
+pub fn cancel_subscription(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let sub_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[sub_acc, tipper, tipper_token])?;
+    validation::token_accounts(&[tipper_token])?;
+
+    if sub_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let sub = Subscription::unpack(&sub_acc.data.borrow())?;
+    if !sub.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if sub.tipper != *tipper.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if sub.tipper_token != *tipper_token.key {
+        return Err(TipError::SessionTokenMismatch.into());
+    }
+
+    if token::unpack_account(tipper_token)?.delegate == Some(*sub_acc.key).into() {
+        if tipper_token.owner != token_program.key {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        invoke(
+            &token::revoke(token_program.key, tipper_token.key, tipper.key),
+            &[tipper_token.clone(), tipper.clone()],
+        )?;
+    }
+
+    close_pda(sub_acc, tipper)?;
+
+    msg!("Subscription to {} cancelled", sub.creator);
+    SubscriptionCancelled { tipper: sub.tipper, creator: sub.creator }.emit(accounts)
+}
+
+/// ProcessSubscription: no data, permissionless crank
+/// Accounts: [config (w), sub (w), tipper_token (w), creator, creator_token (w), treasury_token (w), mint, token_program,
+///            allowed_mint, tipper_stats (w), blocked_creator, blocked_tipper, mint_stats (w),
+///            then per treasury split entry: split_token (w)]
+/// Pays one period; missed periods are not charged retroactively. Each payment counts toward the
+/// tipper's rate limit
+pub fn process_subscription(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let sub_acc = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let creator_token = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let tipper_stats_acc = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let blocked_tipper_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    validation::writable(&[config_acc, sub_acc, tipper_token, creator_token, treasury_token, tipper_stats_acc])?;
+    validation::token_accounts(&[tipper_token, creator_token, treasury_token])?;
+
+    let mut config = load_subscription_config(program_id, config_acc)?;
+    let (mut sub, sub_bump) = load_subscription(program_id, config.platform_id, sub_acc, creator, tipper_token)?;
+    let slot = Clock::get()?.slot;
+    if slot < sub.next_payment_slot {
+        msg!("Next payment due at slot {}", sub.next_payment_slot);
+        return Err(TipError::PaymentNotDue.into());
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let token_accounts = [tipper_token, creator_token, treasury_token];
+    if check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &token_accounts)?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+    check_not_vesting(config.vesting_threshold, sub.amount)?;
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+    check_tipper_not_blocked(program_id, config.platform_id, blocked_tipper_acc, &sub.tipper)?;
+    charge_rate_limit(program_id, config.platform_id, &sub.tipper, tipper_stats_acc, sub.amount, config.rate_limit())?;
+
+    let mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+    let (creator_received, fee_received) = charge_subscription(
+        accounts,
+        &config, mint_stats.as_ref(), &mut sub, sub_bump, sub_acc, tipper_token, creator_token, treasury_token, mint_acc,
+        token_program, mint.decimals, iter, slot,
+    )?;
+
+    let delivered = add_checked(creator_received, fee_received)?;
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, delivered, fee_received)?;
+    msg!(
+        "Subscription payment: {} to creator, {} fee, next at slot {}",
+        creator_received, fee_received, sub.next_payment_slot
+    );
+    emit_tip(accounts, &sub.tipper, &sub.creator, mint_acc.key, delivered, fee_received, 0, 0)
+}
+
+/// ProcessSubscriptionsBatch: no data, permissionless crank
+/// Accounts: [config (w), treasury_token (w), mint, token_program, allowed_mint, mint_stats (w),
+///            then per subscription: sub (w), tipper_token (w), creator, creator_token (w), tipper_stats (w),
+///            blocked_creator, blocked_tipper, then per treasury split entry: split_token (w)]
+/// ProcessSubscription for every subscription passed, all in one mint. Subscriptions that are not due,
+/// whose token account is frozen or no longer funds and delegates a full period, that a vesting
+/// threshold now covers, whose creator or tipper is blocked or whose tipper is at their rate limit
+/// are skipped rather than failing the batch.
+pub fn process_subscriptions_batch(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    validation::writable(&[config_acc, treasury_token])?;
+    validation::token_accounts(&[treasury_token])?;
+
+    let mut config = load_subscription_config(program_id, config_acc)?;
+    let mint = check_token_program(mint_acc, token_program)?;
+    if check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &[treasury_token])?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    let mut mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+
+    // A trailing Noop program only turns on event CPIs
+    let rest = match iter.as_slice() {
+        [rest @ .., last] if *last.key == compression::NOOP_ID => rest,
+        rest => rest,
+    };
+    let (sub_accounts, split_accounts) = rest
+        .split_at_checked(rest.len().saturating_sub(config.treasury_split().entries().count()))
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if sub_accounts.is_empty() || sub_accounts.len() % 7 != 0 {
+        msg!("Pass each subscription as sub, tipper_token, creator, creator_token, tipper_stats and both blocks");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let slot = Clock::get()?.slot;
+    let limit = config.rate_limit();
+    let mut target_reached = false;
+    let mut paid: u16 = 0;
+    let mut skipped: u16 = 0;
+    for chunk in sub_accounts.chunks_exact(7) {
+        let [sub_acc, tipper_token, creator, creator_token, tipper_stats_acc, blocked_acc, blocked_tipper_acc] = chunk
+        else {
+            unreachable!()
+        };
+        validation::writable(&[sub_acc, tipper_token, creator_token, tipper_stats_acc])?;
+        validation::token_accounts(&[tipper_token, creator_token])?;
+
+        let (mut sub, sub_bump) = load_subscription(program_id, config.platform_id, sub_acc, creator, tipper_token)?;
+        check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+        let source = token::unpack_account(tipper_token)?;
+        if source.mint != *mint_acc.key {
+            return Err(TipError::MintMismatch.into());
+        }
+        let funded = !source.is_frozen()
+            && source.amount >= sub.amount
+            && source.delegate == Some(*sub_acc.key).into()
+            && source.delegated_amount >= sub.amount;
+        let vesting = config.vesting_threshold > 0 && sub.amount >= config.vesting_threshold;
+        let blocked = creator_blocked(program_id, config.platform_id, blocked_acc, creator.key)?
+            || tipper_blocked(program_id, config.platform_id, blocked_tipper_acc, &sub.tipper)?;
+        if slot < sub.next_payment_slot || !funded || vesting || blocked {
+            msg!("Skipping subscription {}", sub_acc.key);
+            skipped += 1;
+            continue;
+        }
+        match charge_rate_limit(program_id, config.platform_id, &sub.tipper, tipper_stats_acc, sub.amount, limit) {
+            Err(err) if err == TipError::RateLimited.into() => {
+                msg!("Skipping rate-limited subscription {}", sub_acc.key);
+                skipped += 1;
+                continue;
+            }
+            result => result?,
+        }
+
+        let (creator_received, fee_received) = charge_subscription(
+            accounts,
+            &config, mint_stats.as_ref(), &mut sub, sub_bump, sub_acc, tipper_token, creator_token, treasury_token,
+            mint_acc, token_program, mint.decimals, &mut split_accounts.iter(), slot,
+        )?;
+        let delivered = add_checked(creator_received, fee_received)?;
+        if let Some(stats) = mint_stats.as_mut() {
+            target_reached |= add_mint_tip(stats, delivered, fee_received, 0)?;
+        }
+        paid += 1;
+        emit_tip(accounts, &sub.tipper, &sub.creator, mint_acc.key, delivered, fee_received, 0, 0)?;
+    }
+    if paid > 0 {
+        add_tips(&mut config, paid as u64)?;
+        config.pack(&mut config_acc.data.borrow_mut())?;
+        if let Some(stats) = mint_stats {
+            save_mint_stats(accounts, &stats, mint_stats_acc, target_reached)?;
+        }
+    }
+
+    msg!("Subscription batch: {} paid, {} skipped", paid, skipped);
+    SubscriptionsBatchProcessed { paid, skipped }.emit(accounts)
+}
+
+/// The config as both subscription cranks need it: initialized, not paused and not requiring
+/// verified creators
+pub fn load_subscription_config(program_id: &Pubkey, config_acc: &AccountInfo) -> Result<TipConfig, ProgramError> {
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+    Ok(config)
+}
+
+/// Load and check a subscription of `platform_id` against the creator and token account passed for
+/// it; returns it and its bump
+pub fn load_subscription(
+    program_id: &Pubkey,
+    platform_id: u64,
+    sub_acc: &AccountInfo,
+    creator: &AccountInfo,
+    tipper_token: &AccountInfo,
+) -> Result<(Subscription, u8), ProgramError> {
+    if sub_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let sub = Subscription::unpack(&sub_acc.data.borrow())?;
+    if !sub.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    let (sub_pda, sub_bump) = get_subscription_pda(program_id, platform_id, &sub.tipper, &sub.creator);
+    if *sub_acc.key != sub_pda || sub.creator != *creator.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if sub.tipper_token != *tipper_token.key {
+        msg!("Subscription token account mismatch");
+        return Err(TipError::SessionTokenMismatch.into());
+    }
+    Ok((sub, sub_bump))
+}
+
+/// Pay one period of a due subscription through its delegation, under the fee target in the mint's
+/// stats, and schedule the next; returns what reached the creator and the treasury
+#[allow(clippy::too_many_arguments)]
+pub fn charge_subscription<'a, 'b: 'a>(
+    accounts: &[AccountInfo<'b>],
+    config: &TipConfig,
+    mint_stats: Option<&MintStats>,
+    sub: &mut Subscription,
+    sub_bump: u8,
+    sub_acc: &AccountInfo<'b>,
+    tipper_token: &AccountInfo<'b>,
+    creator_token: &AccountInfo<'b>,
+    treasury_token: &AccountInfo<'b>,
+    mint_acc: &AccountInfo<'b>,
+    token_program: &AccountInfo<'b>,
+    decimals: u8,
+    split_iter: &mut impl Iterator<Item = &'a AccountInfo<'b>>,
+    slot: u64,
+) -> Result<(u64, u64), ProgramError> {
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats), sub.amount)?;
+    let creator_amount = sub.amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+    let platform = platform_seed(config.platform_id);
+    let signer_seeds: &[&[u8]] = &[b"sub", &platform, sub.tipper.as_ref(), sub.creator.as_ref(), &[sub_bump]];
+
+    let creator_received = transfer_tokens(
+        accounts,
+        token_program, tipper_token, mint_acc, creator_token, sub_acc, creator_amount, decimals, &[signer_seeds], &[],
+    )?;
+
+    let fee_received = pay_treasury_fee(
+        accounts,
+        config.treasury_split(), fee, split_iter, token_program, tipper_token, mint_acc, treasury_token, sub_acc,
+        decimals, &[signer_seeds], &[],
+    )?;
+
+    sub.next_payment_slot = sub.next_payment_slot.saturating_add(sub.interval_slots).max(slot + 1);
+    sub.pack(&mut sub_acc.data.borrow_mut())?;
+    Ok((creator_received, fee_received))
+}
@@ -0,0 +1,127 @@
+//! TipWithSwap: converts the creator's share of a tip into their profile's `payout_mint` through the
+//! config's `swap_program`.
+
+use super::*;
+
+/// TipWithSwap's conversion of the creator's share: the swap instruction's data, how many of the
+/// accounts after `payout_mint` it takes, and the least the creator's payout ATA must receive
+pub struct TipSwap {
+    pub min_out: u64,
+    pub route_accounts: u8,
+    pub data: Vec<u8>,
+}
+
+/// Check TipWithSwap's accounts: the config's swap program, the payout mint of the creator's profile
+/// (`profile_acc`, already checked to be theirs) and the creator's ATA for that mint
+pub fn check_swap_route(
+    config_swap_program: Pubkey,
+    swap_program: &AccountInfo,
+    payout_token: &AccountInfo,
+    payout_mint: &AccountInfo,
+    creator: &Pubkey,
+    profile_acc: Option<&AccountInfo>,
+    mint: &Pubkey,
+) -> ProgramResult {
+    if config_swap_program == Pubkey::default() || *swap_program.key != config_swap_program {
+        msg!("Swaps must go through the config's swap program");
+        return Err(TipError::InvalidSwapRoute.into());
+    }
+    let Some(profile_acc) = profile_acc else {
+        msg!("TipWithSwap needs the creator's profile");
+        return Err(TipError::InvalidSwapRoute.into());
+    };
+    let payout = CreatorProfile::load(&profile_acc.data.borrow())?.payout_mint;
+    if payout == Pubkey::default() || payout == *mint || *payout_mint.key != payout {
+        msg!("Payout mint must be the one in the creator's profile, other than the tip's mint");
+        return Err(TipError::InvalidSwapRoute.into());
+    }
+    if !token::is_token_program(payout_mint.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if *payout_token.key != token::get_associated_token_address(creator, payout_mint.key, payout_mint.owner) {
+        msg!("Payout token account must be the ATA of {} for {}", creator, payout_mint.key);
+        return Err(TipError::CreatorTokenMismatch.into());
+    }
+    validation::writable(&[payout_token])
+}
+
+/// Swap `amount` out of `tipper_token` into the creator's payout ATA by CPI to the swap program, which
+/// the tipper's signature authorizes. The route itself is up to the client; the balances around the
+/// call must show exactly `amount` spent and at least `min_out` received. Returns what was received.
+#[allow(clippy::too_many_arguments)]
+pub fn swap_to_payout<'a>(
+    accounts: &[AccountInfo<'a>],
+    swap: TipSwap,
+    swap_program: &AccountInfo<'a>,
+    payout_token: &AccountInfo<'a>,
+    payout_mint: &AccountInfo<'a>,
+    route: &[AccountInfo<'a>],
+    tipper_token: &AccountInfo<'a>,
+    mint: &Pubkey,
+    creator: &Pubkey,
+    amount: u64,
+) -> Result<u64, ProgramError> {
+    let balance_in = token::unpack_account(tipper_token)?.amount;
+    let balance_out = token::unpack_account(payout_token)?.amount;
+    let metas = route
+        .iter()
+        .map(|acc| AccountMeta { pubkey: *acc.key, is_signer: acc.is_signer, is_writable: acc.is_writable })
+        .collect();
+    let ix = Instruction { program_id: *swap_program.key, accounts: metas, data: swap.data };
+    invoke_with_accounts(&ix, accounts, &[])?;
+
+    let spent = balance_in.saturating_sub(token::unpack_account(tipper_token)?.amount);
+    let received = token::unpack_account(payout_token)?.amount.saturating_sub(balance_out);
+    if spent != amount || received < swap.min_out {
+        msg!("Swap spent {} of {} and returned {}, minimum {}", spent, amount, received, swap.min_out);
+        return Err(TipError::SwapBelowMinimum.into());
+    }
+    msg!("Swapped {} for {} of {}", amount, received, payout_mint.key);
+    let (input_mint, output_mint) = (*mint, *payout_mint.key);
+    TipSwapped { creator: *creator, input_mint, output_mint, amount_in: amount, amount_out: received }.emit(accounts)?;
+    Ok(received)
+}
+
+/// SetSwapProgram: [swap_program: Pubkey]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+pub fn set_swap_program(program_id: &Pubkey, accounts: &[AccountInfo], swap_program: Pubkey) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+    // Neither converts anything, and a self-CPI would re-enter the tip
+    if swap_program == *program_id || token::is_token_program(&swap_program) {
+        msg!("Swap program cannot be this program or a token program");
+        return Err(TipError::InvalidSwapRoute.into());
+    }
+
+    let old_value = config.swap_program.to_bytes();
+    config.swap_program = swap_program;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetSwapProgram,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        swap_program.to_bytes(),
+    )?;
+    msg!("Swap program: {}", swap_program);
+    SwapProgramSet { swap_program }.emit(accounts)
+}
@@ -0,0 +1,51 @@
+//! Pure fee and split arithmetic plus builders for the states it acts on, for property tests and
+//! auditors (feature `test-utils`). Nothing here touches accounts or sysvars.
+
+use bytemuck::Zeroable;
+
+pub use crate::fees::{
+    apply_discount, bps_share, collaborator_cuts, compute_fee, split_by_weight, split_tip, split_treasury_fee, TipParts,
+    TipRates,
+};
+use crate::state::{FeeSchedule, FeeTier, Leaderboard, VolumeWindow, MAX_FEE_TIERS};
+
+/// A schedule charging `fee_bps` on every tip, with no tiers or fee target
+pub fn flat_schedule(fee_bps: u16) -> FeeSchedule {
+    FeeSchedule {
+        fee_bps,
+        fee_target: 0,
+        total_fees: 0,
+        tier_count: 0,
+        tier_min_amounts: [0; MAX_FEE_TIERS],
+        tier_bps: [0; MAX_FEE_TIERS],
+    }
+}
+
+/// A schedule as SetFeeTiers leaves it: `tiers` in increasing `min_amount` order (at most
+/// MAX_FEE_TIERS), over `fee_bps` for tips below the first
+pub fn tiered_schedule(fee_bps: u16, tiers: &[FeeTier]) -> FeeSchedule {
+    let mut schedule = flat_schedule(fee_bps);
+    schedule.tier_count = tiers.len() as u8;
+    for (i, tier) in tiers.iter().enumerate() {
+        schedule.tier_min_amounts[i] = tier.min_amount;
+        schedule.tier_bps[i] = tier.fee_bps;
+    }
+    schedule
+}
+
+/// An empty leaderboard for `epoch_index`, as OpenLeaderboard leaves it
+pub fn leaderboard(epoch_index: u64) -> Leaderboard {
+    let mut board = Leaderboard::zeroed();
+    board.discriminator = Leaderboard::DISCRIMINATOR;
+    board.epoch_index = epoch_index;
+    board
+}
+
+/// An empty volume window whose newest bucket is the hour of `unix_timestamp`, as
+/// OpenVolumeWindow leaves it
+pub fn volume_window(unix_timestamp: i64) -> VolumeWindow {
+    let mut window = VolumeWindow::zeroed();
+    window.discriminator = VolumeWindow::DISCRIMINATOR;
+    window.head_hour = unix_timestamp.div_euclid(crate::state::VOLUME_BUCKET_SECS);
+    window
+}
@@ -0,0 +1,156 @@
+//! `solana-program-test` harness (feature `testing`): runs the processor natively in a `ProgramTest`
+//! bank next to the SPL Token and Associated Token programs, with helpers to create mints and token
+//! accounts, initialize a platform config and check the `TipConfig` it holds.
+//!
+//! Every transaction is paid and signed by the context's payer, which is also the admin `initialize_config`
+//! sets, so most tests need no other keypair than their tippers and mint authorities.
+
+use solana_program::{instruction::Instruction, program_pack::Pack, pubkey::Pubkey, system_instruction};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+
+use crate::{
+    instruction,
+    state::{get_config_pda, AccountType, TipConfig},
+    token, PROGRAM_ID,
+};
+
+/// The program at PROGRAM_ID, run natively so tests need no SBF build
+pub fn program_test() -> ProgramTest {
+    let mut test = ProgramTest::new("tip_program", PROGRAM_ID, processor!(crate::process_instruction));
+    test.prefer_bpf(false);
+    test
+}
+
+/// A started `program_test()` bank
+pub async fn start() -> ProgramTestContext {
+    program_test().start_with_context().await
+}
+
+/// Send `ixs` in one transaction paid by the context's payer and signed by `signers` as well
+pub async fn process(
+    ctx: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = ctx.banks_client.get_latest_blockhash().await?;
+    let mut all_signers = vec![&ctx.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&ctx.payer.pubkey()), &all_signers, blockhash);
+    ctx.banks_client.process_transaction(tx).await
+}
+
+/// Fund `to` with `lamports` from the payer, e.g. for tippers, who pay the rent of the stats accounts
+/// their first tip creates
+pub async fn fund(ctx: &mut ProgramTestContext, to: &Pubkey, lamports: u64) -> Result<(), BanksClientError> {
+    let ix = system_instruction::transfer(&ctx.payer.pubkey(), to, lamports);
+    process(ctx, &[ix], &[]).await
+}
+
+/// A new SPL Token mint with `decimals`, minted by `authority`
+pub async fn create_mint(
+    ctx: &mut ProgramTestContext,
+    authority: &Pubkey,
+    decimals: u8,
+) -> Result<Pubkey, BanksClientError> {
+    let mint = Keypair::new();
+    let rent = ctx.banks_client.get_rent().await?;
+    let ixs = [
+        system_instruction::create_account(
+            &ctx.payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(token::MINT_LEN),
+            token::MINT_LEN as u64,
+            &spl_token::id(),
+        ),
+        token::initialize_mint2(&spl_token::id(), &mint.pubkey(), authority, None, decimals),
+    ];
+    process(ctx, &ixs, &[&mint]).await?;
+    Ok(mint.pubkey())
+}
+
+/// The associated token account of `owner` for `mint`, created if missing
+pub async fn create_ata(ctx: &mut ProgramTestContext, owner: &Pubkey, mint: &Pubkey) -> Result<Pubkey, BanksClientError> {
+    let ix = token::create_associated_token_account_idempotent(&ctx.payer.pubkey(), owner, mint, &spl_token::id());
+    process(ctx, &[ix], &[]).await?;
+    Ok(token::get_associated_token_address(owner, mint, &spl_token::id()))
+}
+
+/// Mint `amount` base units of `mint` into `account`
+pub async fn mint_to(
+    ctx: &mut ProgramTestContext,
+    mint: &Pubkey,
+    account: &Pubkey,
+    authority: &Keypair,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let ix = token::mint_to(&spl_token::id(), mint, account, &authority.pubkey(), amount);
+    process(ctx, &[ix], &[authority]).await
+}
+
+/// Create the config of `platform_id` with the payer as admin and `treasury` (a token account) as
+/// treasury. Returns the config address
+pub async fn initialize_config(
+    ctx: &mut ProgramTestContext,
+    platform_id: u64,
+    treasury: &Pubkey,
+    fee_bps: u16,
+) -> Result<Pubkey, BanksClientError> {
+    let ix = instruction::initialize(&PROGRAM_ID, platform_id, treasury, &ctx.payer.pubkey(), fee_bps, None);
+    process(ctx, &[ix], &[]).await?;
+    Ok(get_config_pda(&PROGRAM_ID, platform_id).0)
+}
+
+/// Allowlist `mint` on `platform_id`, signed by the payer as admin
+pub async fn allow_mint(ctx: &mut ProgramTestContext, platform_id: u64, mint: &Pubkey) -> Result<(), BanksClientError> {
+    let ix = instruction::allow_mint(&PROGRAM_ID, platform_id, &ctx.payer.pubkey(), mint);
+    process(ctx, &[ix], &[]).await
+}
+
+/// The account at `address` decoded as `T`, or None if it does not exist or is not a `T`
+pub async fn account<T: AccountType>(ctx: &mut ProgramTestContext, address: &Pubkey) -> Option<T> {
+    let account = ctx.banks_client.get_account(*address).await.ok()??;
+    T::unpack(&account.data).ok()
+}
+
+/// The config of `platform_id`; panics if it has not been initialized
+pub async fn config(ctx: &mut ProgramTestContext, platform_id: u64) -> TipConfig {
+    let address = get_config_pda(&PROGRAM_ID, platform_id).0;
+    let account = ctx.banks_client.get_account(address).await.unwrap().expect("config not initialized");
+    TipConfig::load(&account.data).expect("not a TipConfig")
+}
+
+/// Balance of the token account at `address` (0 if it does not exist)
+pub async fn token_balance(ctx: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    match ctx.banks_client.get_account(*address).await.unwrap() {
+        Some(account) => spl_token::state::Account::unpack(&account.data).expect("not a token account").amount,
+        None => 0,
+    }
+}
+
+/// The config as `initialize_config` leaves it: `admin` and `treasury` set, charging `fee_bps`,
+/// unpaused and without tips
+#[track_caller]
+pub fn assert_initialized(config: &TipConfig, admin: &Pubkey, treasury: &Pubkey, fee_bps: u16) {
+    assert!(config.is_initialized, "config is not initialized");
+    assert_eq!(config.admin, *admin, "admin");
+    assert_eq!(config.treasury, *treasury, "treasury");
+    assert_fee_bps(config, fee_bps);
+    assert_paused(config, false);
+    assert_total_tips(config, 0);
+}
+
+#[track_caller]
+pub fn assert_fee_bps(config: &TipConfig, fee_bps: u16) {
+    assert_eq!(config.fee_bps, fee_bps, "fee_bps");
+}
+
+#[track_caller]
+pub fn assert_paused(config: &TipConfig, paused: bool) {
+    assert_eq!(config.paused, paused, "paused");
+}
+
+#[track_caller]
+pub fn assert_total_tips(config: &TipConfig, total_tips: u64) {
+    assert_eq!(config.total_tips, total_tips, "total_tips");
+}
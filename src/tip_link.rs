@@ -0,0 +1,233 @@
+//! Tip links: a PDA address per creator that anyone can send tokens or SOL to without the program,
+//! swept to the creator when they claim.
+
+use super::*;
+
+/// The creator's SolVault; like CreatorVault it only exists at its PDA, so the stored creator suffices
+/// Check `tip_link_acc` is `creator`'s tip link on `platform_id`, returning it with its bump
+pub fn load_tip_link(
+    program_id: &Pubkey,
+    tip_link_acc: &AccountInfo,
+    platform_id: u64,
+    creator: &AccountInfo,
+) -> Result<TipLink, ProgramError> {
+    if tip_link_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let tip_link = TipLink::unpack(&tip_link_acc.data.borrow())?;
+    if !tip_link.is_initialized || tip_link.creator != *creator.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if *tip_link_acc.key != get_tip_link_pda(program_id, platform_id, creator.key, tip_link.link_id).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(tip_link)
+}
+
+/// CreateTipLink: [link_id: u64]
+/// Accounts: [tip_link (w), config, creator (s,w), system_program, payer (optional, s,w)]
+/// Lamports already sent to the address stay in it and are swept by the first ClaimTipLinkSol
+pub fn create_tip_link(program_id: &Pubkey, accounts: &[AccountInfo], link_id: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let tip_link_acc = next_account_info(iter)?;
+    let config_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[tip_link_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+
+    let (pda, bump) = get_tip_link_pda(program_id, config.platform_id, creator.key, link_id);
+    if *tip_link_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !tip_link_acc.data_is_empty() {
+        msg!("Tip link {} already exists", link_id);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        tip_link_acc,
+        system_program,
+        program_id,
+        TIP_LINK_SIZE,
+        &[b"tip_link", &platform_seed(config.platform_id), creator.key.as_ref(), &link_id.to_le_bytes(), &[bump]],
+    )?;
+
+    let tip_link = TipLink {
+        is_initialized: true,
+        creator: *creator.key,
+        link_id,
+        bump,
+        claim_count: 0,
+        created_at: Clock::get()?.unix_timestamp,
+    };
+    tip_link.pack(&mut tip_link_acc.data.borrow_mut())?;
+
+    msg!("Tip link {} opened at {}", link_id, pda);
+    TipLinkCreated { creator: *creator.key, tip_link: pda, link_id }.emit(accounts)
+}
+
+/// ClaimTipLink: no data
+/// Accounts: [config (w), tip_link (w), link_token (w), creator (s), creator_token (w), treasury_token (w), mint,
+///            token_program, allowed_mint, blocked_creator, mint_stats (w),
+///            then per treasury split entry: split_token (w)]
+/// `link_token` may be any token account the tip link owns, usually its ATA; the fee is charged now.
+/// A blocked creator cannot claim until unblocked
+pub fn claim_tip_link(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let tip_link_acc = next_account_info(iter)?;
+    let link_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let creator_token = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let allowed_mint_acc = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, tip_link_acc, link_token, creator_token, treasury_token])?;
+    validation::token_accounts(&[link_token, creator_token, treasury_token])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+    let mut tip_link = load_tip_link(program_id, tip_link_acc, config.platform_id, creator)?;
+    let token_accounts = [link_token, creator_token, treasury_token];
+    if check_allowed_mint(program_id, config.platform_id, allowed_mint_acc, &token_accounts)?.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    let mint = check_token_program(mint_acc, token_program)?;
+    check_creator_ata(program_id, creator, creator_token, mint_acc, token_program)?;
+
+    let link_balance = token::unpack_account(link_token)?;
+    if link_balance.owner != *tip_link_acc.key {
+        msg!("Link token account must be owned by the tip link");
+        return Err(ProgramError::IllegalOwner);
+    }
+    let amount = link_balance.amount;
+    if amount == 0 {
+        msg!("Nothing to claim");
+        return Err(TipError::ZeroAmount.into());
+    }
+
+    let mint_stats = load_mint_stats(program_id, config.platform_id, mint_acc.key, mint_stats_acc)?;
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+    let platform = platform_seed(config.platform_id);
+    let link_seed = tip_link.link_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[b"tip_link", &platform, creator.key.as_ref(), &link_seed, &[tip_link.bump]];
+
+    let creator_received = transfer_tokens(
+        accounts,
+        token_program, link_token, mint_acc, creator_token, tip_link_acc, creator_amount, mint.decimals,
+        &[signer_seeds], &[],
+    )?;
+    let fee_received = pay_treasury_fee(
+        accounts, config.treasury_split(), fee, iter, token_program, link_token, mint_acc, treasury_token, tip_link_acc,
+        mint.decimals, &[signer_seeds], &[],
+    )?;
+
+    tip_link.claim_count = tip_link.claim_count.checked_add(1).ok_or(TipError::MathOverflow)?;
+    tip_link.pack(&mut tip_link_acc.data.borrow_mut())?;
+
+    let delivered = add_checked(creator_received, fee_received)?;
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, delivered, fee_received)?;
+    msg!("Tip link claimed: {} to creator, {} fee", creator_received, fee_received);
+    TipLinkClaimed { creator: *creator.key, tip_link: *tip_link_acc.key, mint: *mint_acc.key, amount, fee }
+        .emit(accounts)?;
+    emit_tip(accounts, &Pubkey::default(), creator.key, mint_acc.key, delivered, fee_received, 0, 0)
+}
+
+/// ClaimTipLinkSol: no data
+/// Accounts: [config (w), tip_link (w), creator (s,w), treasury_token, treasury_wallet (w), blocked_creator,
+///            mint_stats (w)]
+/// Sweeps everything above the tip link's rent-exempt minimum, unless the creator is blocked; `mint_stats`
+/// is the MintStats of native SOL
+pub fn claim_tip_link_sol(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let tip_link_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let treasury_token = next_account_info(iter)?;
+    let treasury_wallet = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+    let mint_stats_acc = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc, tip_link_acc, creator, treasury_wallet])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.paused {
+        msg!("Tipping is paused");
+        return Err(TipError::Paused.into());
+    }
+    check_verification_not_required(&config)?;
+    check_not_blocked(program_id, config.platform_id, blocked_acc, creator.key)?;
+
+    let mut tip_link = load_tip_link(program_id, tip_link_acc, config.platform_id, creator)?;
+    let rent = Rent::get()?.minimum_balance(tip_link_acc.data_len());
+    let amount = tip_link_acc.lamports().saturating_sub(rent);
+    if amount == 0 {
+        msg!("Nothing to claim");
+        return Err(TipError::ZeroAmount.into());
+    }
+
+    let mint_stats = load_mint_stats(program_id, config.platform_id, &Pubkey::default(), mint_stats_acc)?;
+    let fee = compute_fee(config.fee_schedule().with_mint_stats(mint_stats.as_ref()), amount)?;
+    let creator_amount = amount.checked_sub(fee).ok_or(TipError::MathOverflow)?;
+    if fee > 0 {
+        if *treasury_token.key != config.treasury {
+            msg!("Treasury mismatch");
+            return Err(TipError::TreasuryMismatch.into());
+        }
+        let treasury_owner = token::unpack_account(treasury_token)?.owner;
+        if *treasury_wallet.key != treasury_owner {
+            msg!("Treasury wallet must own the treasury token account");
+            return Err(TipError::TreasuryWalletMismatch.into());
+        }
+    }
+
+    **tip_link_acc.try_borrow_mut_lamports()? -= amount;
+    **creator.try_borrow_mut_lamports()? += creator_amount;
+    **treasury_wallet.try_borrow_mut_lamports()? += fee;
+
+    tip_link.claim_count = tip_link.claim_count.checked_add(1).ok_or(TipError::MathOverflow)?;
+    tip_link.pack(&mut tip_link_acc.data.borrow_mut())?;
+
+    record_tip(accounts, &mut config, config_acc, mint_stats_acc, mint_stats, amount, fee)?;
+    msg!("Tip link claimed: {} lamports to creator, {} fee", creator_amount, fee);
+    TipLinkClaimed { creator: *creator.key, tip_link: *tip_link_acc.key, mint: Pubkey::default(), amount, fee }
+        .emit(accounts)?;
+    emit_tip(accounts, &Pubkey::default(), creator.key, &Pubkey::default(), amount, fee, 0, 0)
+}
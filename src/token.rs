@@ -0,0 +1,269 @@
+//! Thin layer over SPL Token and Token-2022. Both programs share the base account/mint
+//! layouts and instruction encoding; Token-2022 only appends extension data after them.
+
+use solana_program::{
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction},
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack},
+    pubkey,
+    pubkey::Pubkey,
+};
+use spl_token::{
+    instruction::TokenInstruction,
+    state::{Account, Mint},
+};
+
+pub const TOKEN_2022_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+/// Base token account size, without Token-2022 extensions
+pub const ACCOUNT_LEN: usize = Account::LEN;
+/// Base mint size, without Token-2022 extensions
+pub const MINT_LEN: usize = Mint::LEN;
+/// Wrapped SOL under SPL Token
+pub const NATIVE_MINT: Pubkey = spl_token::native_mint::ID;
+
+// Token-2022 TransferFeeExtension / TransferCheckedWithFee
+const TRANSFER_FEE_EXTENSION: u8 = 23;
+const TRANSFER_CHECKED_WITH_FEE: u8 = 1;
+// ExtensionType::TransferFeeConfig and its (fixed) value length
+const TRANSFER_FEE_CONFIG: u16 = 1;
+const TRANSFER_FEE_CONFIG_LEN: usize = 108;
+
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::id() || *program_id == TOKEN_2022_ID
+}
+
+/// Associated token account address for `wallet` under either token program
+pub fn get_associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Base token account state, for accounts owned by either token program
+pub fn unpack_account(acc: &AccountInfo) -> Result<Account, ProgramError> {
+    if !is_token_program(acc.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = acc.data.borrow();
+    let account = Account::unpack_from_slice(data.get(..Account::LEN).ok_or(ProgramError::InvalidAccountData)?)?;
+    if !account.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(account)
+}
+
+/// Base mint state, for mints owned by either token program
+pub fn unpack_mint(acc: &AccountInfo) -> Result<Mint, ProgramError> {
+    if !is_token_program(acc.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = acc.data.borrow();
+    let mint = Mint::unpack_from_slice(data.get(..Mint::LEN).ok_or(ProgramError::InvalidAccountData)?)?;
+    if !mint.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    Ok(mint)
+}
+
+/// Fee a Token-2022 mint with the transfer-fee extension withholds from an `amount` transfer during
+/// `epoch`; 0 for SPL Token mints and mints without the extension. Extensions follow the base mint
+/// padded to the account size and an account-type byte, as (u16 type, u16 length, value) entries.
+pub fn transfer_fee(mint: &AccountInfo, epoch: u64, amount: u64) -> Result<u64, ProgramError> {
+    if *mint.owner != TOKEN_2022_ID || amount == 0 {
+        return Ok(0);
+    }
+    let data = mint.data.borrow();
+    let mut offset = Account::LEN + 1;
+    while let Some(header) = data.get(offset..offset + 4) {
+        let ty = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let value = data.get(offset + 4..offset + 4 + len).ok_or(ProgramError::InvalidAccountData)?;
+        if ty == 0 {
+            break;
+        }
+        if ty == TRANSFER_FEE_CONFIG {
+            if len != TRANSFER_FEE_CONFIG_LEN {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            // Two authorities and the withheld amount, then the older and newer fees as
+            // (epoch u64, maximum_fee u64, basis_points u16)
+            let u64_at = |i: usize| u64::from_le_bytes(value[i..i + 8].try_into().unwrap());
+            let newer = 72 + 18;
+            let fee = if epoch >= u64_at(newer) { newer } else { 72 };
+            let maximum_fee = u64_at(fee + 8);
+            let bps = u16::from_le_bytes([value[fee + 16], value[fee + 17]]) as u128;
+            let raw = (amount as u128 * bps).div_ceil(10_000);
+            return Ok(raw.min(maximum_fee as u128) as u64);
+        }
+        offset += 4 + len;
+    }
+    Ok(0)
+}
+
+pub fn transfer_checked(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::TransferChecked { amount, decimals }.pack(),
+    }
+}
+
+/// Token-2022 transfer that fails unless the mint withholds exactly `fee`, so the amount the program
+/// records as delivered is the amount that arrives
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_fee(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    authority: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    fee: u64,
+) -> Instruction {
+    let mut data = vec![TRANSFER_FEE_EXTENSION, TRANSFER_CHECKED_WITH_FEE];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    data.extend_from_slice(&fee.to_le_bytes());
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
+
+pub fn initialize_account3(token_program: &Pubkey, account: &Pubkey, mint: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(*mint, false),
+        ],
+        data: TokenInstruction::InitializeAccount3 { owner: *owner }.pack(),
+    }
+}
+
+pub fn close_account(token_program: &Pubkey, account: &Pubkey, destination: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: TokenInstruction::CloseAccount.pack(),
+    }
+}
+
+/// Bring a native (wSOL) account's token balance in line with its lamports
+pub fn sync_native(token_program: &Pubkey, account: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![AccountMeta::new(*account, false)],
+        data: TokenInstruction::SyncNative.pack(),
+    }
+}
+
+pub fn revoke(token_program: &Pubkey, source: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: TokenInstruction::Revoke.pack(),
+    }
+}
+
+pub fn approve(
+    token_program: &Pubkey,
+    source: &Pubkey,
+    delegate: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*delegate, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: TokenInstruction::Approve { amount }.pack(),
+    }
+}
+
+pub fn initialize_mint2(
+    token_program: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+    decimals: u8,
+) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![AccountMeta::new(*mint, false)],
+        data: TokenInstruction::InitializeMint2 {
+            decimals,
+            mint_authority: *mint_authority,
+            freeze_authority: freeze_authority.copied().into(),
+        }
+        .pack(),
+    }
+}
+
+pub fn mint_to(token_program: &Pubkey, mint: &Pubkey, account: &Pubkey, authority: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: *token_program,
+        accounts: vec![
+            AccountMeta::new(*mint, false),
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data: TokenInstruction::MintTo { amount }.pack(),
+    }
+}
+
+/// Create `wallet`'s associated token account for `mint`, or do nothing if it already exists
+pub fn create_associated_token_account_idempotent(
+    payer: &Pubkey,
+    wallet: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(get_associated_token_address(wallet, mint, token_program), false),
+            AccountMeta::new_readonly(*wallet, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+        // AssociatedTokenAccountInstruction::CreateIdempotent
+        data: vec![1],
+    }
+}
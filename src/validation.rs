@@ -0,0 +1,39 @@
+//! Account checks every handler runs on its fixed accounts before any business logic. Decoding an
+//! account proves nothing about who controls it: anyone can create an account elsewhere whose bytes
+//! decode as one of ours, and a read-only account would otherwise only fail at the runtime's
+//! post-instruction check, after every CPI has already run.
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{error::TipError, token};
+
+/// Every account the handler writes to, or moves tokens or lamports out of or into
+pub fn writable(accounts: &[&AccountInfo]) -> ProgramResult {
+    for acc in accounts {
+        if !acc.is_writable {
+            msg!("Account {} must be writable", acc.key);
+            return Err(TipError::AccountNotWritable.into());
+        }
+    }
+    Ok(())
+}
+
+/// Program state (config and every PDA) must be owned by this program
+pub fn program_owned(program_id: &Pubkey, acc: &AccountInfo) -> ProgramResult {
+    if acc.owner != program_id {
+        msg!("Account {} is not owned by this program", acc.key);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Token accounts must belong to SPL Token or Token-2022
+pub fn token_accounts(accounts: &[&AccountInfo]) -> ProgramResult {
+    for acc in accounts {
+        if !token::is_token_program(acc.owner) {
+            msg!("Account {} is not a token account", acc.key);
+            return Err(ProgramError::IncorrectProgramId);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,642 @@
+//! Creator vaults: the token and SOL vaults tips can be paid into, and the vesting schedules and
+//! disputes on tips of `vesting_threshold` or more, which only TipWithVesting can make.
+
+use super::*;
+
+/// Large tips must go through `TipWithVesting`, the only path that can set up vesting (threshold 0 = no vesting)
+pub fn check_not_vesting(vesting_threshold: u64, amount: u64) -> ProgramResult {
+    if vesting_threshold > 0 && amount >= vesting_threshold {
+        msg!("Tips of {} or more must vest through Tip", vesting_threshold);
+        return Err(TipError::VestingRequired.into());
+    }
+    Ok(())
+}
+
+/// The creator's vault for the tip's mint, whose token account must be the tip's `creator_token`
+pub fn load_vesting_vault(
+    program_id: &Pubkey,
+    vault_acc: &AccountInfo,
+    creator: &AccountInfo,
+    creator_token: &AccountInfo,
+    mint_acc: &AccountInfo,
+) -> Result<CreatorVault, ProgramError> {
+    if vault_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    // Vaults are only ever created at their PDA, so the stored keys stand in for re-deriving it
+    let vault = CreatorVault::unpack(&vault_acc.data.borrow())?;
+    if !vault.is_initialized || vault.creator != *creator.key || vault.mint != *mint_acc.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if *creator_token.key != get_creator_vault_token_pda(program_id, vault_acc.key).0 {
+        msg!("Vesting tips must pay into the creator's vault");
+        return Err(TipError::CreatorTokenMismatch.into());
+    }
+    Ok(vault)
+}
+
+pub fn load_sol_vault(
+    program_id: &Pubkey,
+    vault_acc: &AccountInfo,
+    creator: &AccountInfo,
+) -> Result<SolVault, ProgramError> {
+    if vault_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let vault = SolVault::unpack(&vault_acc.data.borrow())?;
+    if !vault.is_initialized || vault.creator != *creator.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    Ok(vault)
+}
+
+/// OpenVault: no data
+/// Accounts: [creator_vault (w), vault_token (w), creator (s,w), mint, token_program, system_program,
+///            payer (optional, s,w)]
+/// The creator pays rent for both PDAs; tips reach the vault once clients pass `vault_token` as `creator_token`
+pub fn open_vault(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault_acc = next_account_info(iter)?;
+    let vault_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[vault_acc, vault_token, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let (vault_pda, vault_bump) = get_creator_vault_pda(program_id, creator.key, mint_acc.key);
+    if *vault_acc.key != vault_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let (token_pda, token_bump) = get_creator_vault_token_pda(program_id, &vault_pda);
+    if *vault_token.key != token_pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !vault_acc.data_is_empty() {
+        msg!("Vault for this mint is already open");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    check_token_program(mint_acc, token_program)?;
+
+    create_pda_account(
+        payer,
+        vault_acc,
+        system_program,
+        program_id,
+        CREATOR_VAULT_SIZE,
+        &[b"creator_vault", creator.key.as_ref(), mint_acc.key.as_ref(), &[vault_bump]],
+    )?;
+    create_vault(
+        payer,
+        vault_token,
+        mint_acc,
+        token_program,
+        system_program,
+        &vault_pda,
+        &[b"vault", vault_pda.as_ref(), &[token_bump]],
+    )?;
+
+    let vault = CreatorVault {
+        is_initialized: true,
+        creator: *creator.key,
+        mint: *mint_acc.key,
+        total_claimed: 0,
+        locked: 0,
+        vesting_count: 0,
+    };
+    vault.pack(&mut vault_acc.data.borrow_mut())?;
+
+    msg!("Vault opened for {}", creator.key);
+    VaultOpened { creator: *creator.key, mint: *mint_acc.key, vault_token: token_pda }.emit(accounts)
+}
+
+/// Claim: [amount: u64]
+/// Accounts: [creator_vault (w), vault_token (w), creator (s), destination (w), mint, token_program, blocked_creator]
+/// `destination` can be any token account for the mint, so the creator can rotate payout wallets;
+/// `amount` 0 claims everything not locked by vesting. Vaults are shared by every platform, so
+/// `blocked_creator` is the creator's block on platform 0, which freezes claims while it exists
+pub fn claim(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault_acc = next_account_info(iter)?;
+    let vault_token = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let destination = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let blocked_acc = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[vault_acc, vault_token, destination])?;
+    check_not_blocked(program_id, 0, blocked_acc, creator.key)?;
+    validation::token_accounts(&[vault_token, destination])?;
+    if vault_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut vault = CreatorVault::unpack(&vault_acc.data.borrow())?;
+    if !vault.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if vault.creator != *creator.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if vault.mint != *mint_acc.key {
+        return Err(TipError::MintMismatch.into());
+    }
+    let (vault_pda, vault_bump) = get_creator_vault_pda(program_id, creator.key, mint_acc.key);
+    if *vault_acc.key != vault_pda || *vault_token.key != get_creator_vault_token_pda(program_id, &vault_pda).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let mint = check_token_program(mint_acc, token_program)?;
+    let claimable = token::unpack_account(vault_token)?.amount.saturating_sub(vault.locked);
+    let amount = if amount == 0 { claimable } else { amount };
+    if amount == 0 {
+        msg!("Nothing to claim");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if amount > claimable {
+        msg!("Only {} is claimable; {} is still vesting", claimable, vault.locked);
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    invoke_signed(
+        &token::transfer_checked(
+            token_program.key, vault_token.key, mint_acc.key, destination.key, vault_acc.key, amount, mint.decimals,
+        ),
+        &[vault_token.clone(), mint_acc.clone(), destination.clone(), vault_acc.clone()],
+        &[&[b"creator_vault", creator.key.as_ref(), mint_acc.key.as_ref(), &[vault_bump]]],
+    )?;
+
+    vault.total_claimed = vault.total_claimed.checked_add(amount).ok_or(TipError::MathOverflow)?;
+    vault.pack(&mut vault_acc.data.borrow_mut())?;
+
+    msg!("Claimed {} to {}", amount, destination.key);
+    TipsClaimed { creator: *creator.key, mint: *mint_acc.key, destination: *destination.key, amount }.emit(accounts)
+}
+
+/// PayoutBatch: [min_amount: u64], permissionless crank
+/// Accounts: [caller (s), caller_token (w), mint, token_program,
+///            then per vault: creator_vault (w), vault_token (w), creator_token (w), blocked_creator]
+/// Sweeps everything not locked by vesting from each vault, all in one mint, to the creator's ATA,
+/// less PAYOUT_INCENTIVE_BPS for `caller_token`. Vaults with less than `min_amount` claimable, whose
+/// creator has no ATA or is blocked on platform 0 (as for `Claim`) are skipped rather than failing
+/// the batch.
+pub fn payout_batch(program_id: &Pubkey, accounts: &[AccountInfo], min_amount: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let caller = next_account_info(iter)?;
+    let caller_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+
+    if !caller.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[caller_token])?;
+    validation::token_accounts(&[caller_token])?;
+    let mint = check_token_program(mint_acc, token_program)?;
+
+    // A trailing Noop program only turns on event CPIs
+    let rest = match iter.as_slice() {
+        [rest @ .., last] if *last.key == compression::NOOP_ID => rest,
+        rest => rest,
+    };
+    if rest.is_empty() || rest.len() % 4 != 0 {
+        msg!("Pass each vault as creator_vault, vault_token, creator_token, blocked_creator");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let mut paid: u16 = 0;
+    let mut skipped: u16 = 0;
+    let mut total_incentive: u64 = 0;
+    for chunk in rest.chunks_exact(4) {
+        let [vault_acc, vault_token, creator_token, blocked_acc] = chunk else { unreachable!() };
+        validation::writable(&[vault_acc, vault_token, creator_token])?;
+        if vault_acc.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let mut vault = CreatorVault::unpack(&vault_acc.data.borrow())?;
+        if !vault.is_initialized {
+            return Err(TipError::Uninitialized.into());
+        }
+        if vault.mint != *mint_acc.key {
+            return Err(TipError::MintMismatch.into());
+        }
+        let (vault_pda, vault_bump) = get_creator_vault_pda(program_id, &vault.creator, mint_acc.key);
+        if *vault_acc.key != vault_pda || *vault_token.key != get_creator_vault_token_pda(program_id, &vault_pda).0 {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        if *creator_token.key != token::get_associated_token_address(&vault.creator, mint_acc.key, token_program.key) {
+            msg!("Payouts go to the creator's associated token account");
+            return Err(TipError::CreatorTokenMismatch.into());
+        }
+
+        let blocked = creator_blocked(program_id, 0, blocked_acc, &vault.creator)?;
+        let claimable = token::unpack_account(vault_token)?.amount.saturating_sub(vault.locked);
+        if claimable == 0 || claimable < min_amount || creator_token.data_is_empty() || blocked {
+            msg!("Skipping vault {}", vault_acc.key);
+            skipped += 1;
+            continue;
+        }
+        let incentive = bps_share(claimable, PAYOUT_INCENTIVE_BPS);
+        let amount = sub_checked(claimable, incentive)?;
+
+        let seeds: &[&[u8]] = &[b"creator_vault", vault.creator.as_ref(), mint_acc.key.as_ref(), &[vault_bump]];
+        invoke_signed(
+            &token::transfer_checked(
+                token_program.key,
+                vault_token.key,
+                mint_acc.key,
+                creator_token.key,
+                vault_acc.key,
+                amount,
+                mint.decimals,
+            ),
+            &[vault_token.clone(), mint_acc.clone(), creator_token.clone(), vault_acc.clone()],
+            &[seeds],
+        )?;
+        if incentive > 0 {
+            invoke_signed(
+                &token::transfer_checked(
+                    token_program.key,
+                    vault_token.key,
+                    mint_acc.key,
+                    caller_token.key,
+                    vault_acc.key,
+                    incentive,
+                    mint.decimals,
+                ),
+                &[vault_token.clone(), mint_acc.clone(), caller_token.clone(), vault_acc.clone()],
+                &[seeds],
+            )?;
+        }
+
+        vault.total_claimed = add_checked(vault.total_claimed, claimable)?;
+        vault.pack(&mut vault_acc.data.borrow_mut())?;
+        total_incentive = add_checked(total_incentive, incentive)?;
+        paid += 1;
+        TipsClaimed { creator: vault.creator, mint: *mint_acc.key, destination: *creator_token.key, amount }
+            .emit(accounts)?;
+    }
+
+    msg!("Payout batch: {} paid, {} skipped, {} to the caller", paid, skipped, total_incentive);
+    PayoutBatchProcessed { caller: *caller.key, mint: *mint_acc.key, paid, skipped, incentive: total_incentive }
+        .emit(accounts)
+}
+
+/// OpenSolVault: no data
+/// Accounts: [sol_vault (w), creator (s,w), system_program, payer (optional, s,w)]
+/// SOL counterpart of OpenVault; tips reach the vault once clients pass it to TipSol
+pub fn open_sol_vault(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let system_program = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[vault_acc, creator])?;
+    let payer = next_rent_payer(program_id, iter, creator)?;
+
+    let (pda, bump) = get_sol_vault_pda(program_id, creator.key);
+    if *vault_acc.key != pda {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !vault_acc.data_is_empty() {
+        msg!("SOL vault is already open");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        vault_acc,
+        system_program,
+        program_id,
+        SOL_VAULT_SIZE,
+        &[b"sol_vault", creator.key.as_ref(), &[bump]],
+    )?;
+    let vault = SolVault { is_initialized: true, creator: *creator.key, total_withdrawn: 0 };
+    vault.pack(&mut vault_acc.data.borrow_mut())?;
+
+    msg!("SOL vault opened for {}", creator.key);
+    SolVaultOpened { creator: *creator.key, sol_vault: pda }.emit(accounts)
+}
+
+/// WithdrawSol: [amount: u64]
+/// Accounts: [sol_vault (w), creator (s), destination (w)]
+/// `amount` 0 withdraws everything above the vault's rent-exempt minimum
+pub fn withdraw_sol(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vault_acc = next_account_info(iter)?;
+    let creator = next_account_info(iter)?;
+    let destination = next_account_info(iter)?;
+
+    if !creator.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[vault_acc, destination])?;
+    let mut vault = load_sol_vault(program_id, vault_acc, creator)?;
+
+    let rent = Rent::get()?.minimum_balance(vault_acc.data_len());
+    let available = vault_acc.lamports().saturating_sub(rent);
+    let amount = if amount == 0 { available } else { amount };
+    if amount == 0 {
+        msg!("Nothing to withdraw");
+        return Err(TipError::ZeroAmount.into());
+    }
+    if amount > available {
+        msg!("Only {} lamports are withdrawable", available);
+        return Err(ProgramError::InsufficientFunds);
+    }
+
+    **vault_acc.try_borrow_mut_lamports()? -= amount;
+    **destination.try_borrow_mut_lamports()? += amount;
+
+    vault.total_withdrawn = vault.total_withdrawn.checked_add(amount).ok_or(TipError::MathOverflow)?;
+    vault.pack(&mut vault_acc.data.borrow_mut())?;
+
+    msg!("Withdrew {} lamports to {}", amount, destination.key);
+    SolWithdrawn { creator: *creator.key, destination: *destination.key, amount }.emit(accounts)
+}
+
+/// SetVesting: [threshold: u64, cliff_secs: u64, duration_secs: u64]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+/// Applies to tips made from now on; existing schedules keep their terms
+pub fn set_vesting(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u64,
+    cliff_secs: u64,
+    duration_secs: u64,
+) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    if cliff_secs > i64::MAX as u64 || duration_secs > i64::MAX as u64 {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let old_value = audit_words(&[config.vesting_threshold, config.vesting_cliff_secs, config.vesting_duration_secs]);
+    config.vesting_threshold = threshold;
+    config.vesting_cliff_secs = cliff_secs;
+    config.vesting_duration_secs = duration_secs;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetVesting,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[threshold, cliff_secs, duration_secs]),
+    )?;
+
+    msg!("Vesting: tips from {} vest over {}s after a {}s cliff", threshold, duration_secs, cliff_secs);
+    VestingUpdated { threshold, cliff_secs, duration_secs }.emit(accounts)
+}
+
+/// ReleaseVested: no data
+/// Accounts: [vesting (w), creator_vault (w), tipper (w)]
+/// Permissionless: unlocks whatever has vested so the creator can claim it, and closes the
+/// schedule (rent to the tipper) once it is fully released
+pub fn release_vested(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vesting_acc = next_account_info(iter)?;
+    let vault_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+
+    validation::writable(&[vesting_acc, vault_acc, tipper])?;
+
+    if vesting_acc.owner != program_id || vault_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Both are only ever created at their PDAs, so the stored keys stand in for re-deriving them
+    let mut schedule = VestingSchedule::unpack(&vesting_acc.data.borrow())?;
+    if !schedule.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if schedule.creator_vault != *vault_acc.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if schedule.tipper != *tipper.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if schedule.disputed {
+        return Err(TipError::TipDisputed.into());
+    }
+    let mut vault = CreatorVault::unpack(&vault_acc.data.borrow())?;
+
+    let vested = schedule.vested_at(Clock::get()?.unix_timestamp);
+    let released = vested.checked_sub(schedule.released).ok_or(TipError::MathOverflow)?;
+    if released == 0 {
+        return Err(TipError::NothingVested.into());
+    }
+    schedule.released = vested;
+    vault.locked = vault.locked.saturating_sub(released);
+    vault.pack(&mut vault_acc.data.borrow_mut())?;
+
+    let remaining = sub_checked(schedule.amount, schedule.released)?;
+    if remaining == 0 {
+        close_pda(vesting_acc, tipper)?;
+    } else {
+        schedule.pack(&mut vesting_acc.data.borrow_mut())?;
+    }
+
+    msg!("Released {} to the creator, {} still vesting", released, remaining);
+    VestedReleased { schedule: *vesting_acc.key, creator: vault.creator, amount: released, remaining }.emit(accounts)
+}
+
+/// SetDisputeWindow: [window_slots: u64]
+/// Accounts: [config (w), admin (s), instructions_sysvar (if admin_isolation)]
+/// Applies to vesting tips made from now on
+pub fn set_dispute_window(program_id: &Pubkey, accounts: &[AccountInfo], window_slots: u64) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let admin = next_account_info(iter)?;
+
+    if !admin.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[config_acc])?;
+
+    let mut config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if config.admin != *admin.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_admin_isolation(&config, iter)?;
+
+    let old_value = audit_words(&[config.dispute_window_slots]);
+    config.dispute_window_slots = window_slots;
+    config.pack(&mut config_acc.data.borrow_mut())?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::SetDisputeWindow,
+        admin.key,
+        &Pubkey::default(),
+        old_value,
+        audit_words(&[window_slots]),
+    )?;
+
+    msg!("Dispute window: {} slots", window_slots);
+    DisputeWindowUpdated { window_slots }.emit(accounts)
+}
+
+/// DisputeTip: no data
+/// Accounts: [vesting (w), tipper (s)]
+/// Freezes the unreleased part of a vesting tip until the admin or arbiter calls ResolveDispute
+pub fn dispute_tip(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let vesting_acc = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+
+    if !tipper.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[vesting_acc])?;
+    if vesting_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut schedule = VestingSchedule::unpack(&vesting_acc.data.borrow())?;
+    if !schedule.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if schedule.tipper != *tipper.key {
+        return Err(TipError::Unauthorized.into());
+    }
+    if schedule.disputed {
+        return Err(TipError::TipDisputed.into());
+    }
+    if Clock::get()?.slot > schedule.dispute_until_slot {
+        msg!("Dispute window closed at slot {}", schedule.dispute_until_slot);
+        return Err(TipError::DisputeWindowClosed.into());
+    }
+
+    schedule.disputed = true;
+    schedule.pack(&mut vesting_acc.data.borrow_mut())?;
+
+    let amount = sub_checked(schedule.amount, schedule.released)?;
+    msg!("Tip disputed: {} frozen", amount);
+    TipDisputed { schedule: *vesting_acc.key, tipper: *tipper.key, amount }.emit(accounts)
+}
+
+/// ResolveDispute: [refund: bool]
+/// Accounts: [config, arbiter (s), vesting (w), creator_vault (w), vault_token (w), tipper_token (w), mint,
+///            token_program, tipper (w), instructions_sysvar (if admin_isolation)]
+/// Admin or arbiter: the unreleased amount goes back to the tipper (`refund`) or is unlocked for the
+/// creator at once. Either way the schedule closes and its rent returns to the tipper; the fee is
+/// not refunded.
+pub fn resolve_dispute(program_id: &Pubkey, accounts: &[AccountInfo], refund: bool) -> ProgramResult {
+    let iter = &mut accounts.iter();
+    let config_acc = next_account_info(iter)?;
+    let arbiter = next_account_info(iter)?;
+    let vesting_acc = next_account_info(iter)?;
+    let vault_acc = next_account_info(iter)?;
+    let vault_token = next_account_info(iter)?;
+    let tipper_token = next_account_info(iter)?;
+    let mint_acc = next_account_info(iter)?;
+    let token_program = next_account_info(iter)?;
+    let tipper = next_account_info(iter)?;
+
+    if !arbiter.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    validation::writable(&[vesting_acc, vault_acc, vault_token, tipper_token, tipper])?;
+    validation::token_accounts(&[vault_token, tipper_token])?;
+
+    let config = TipConfig::load(&config_acc.data.borrow())?;
+    check_config_address(program_id, config_acc, config.platform_id, config.bump)?;
+    if !config.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !config.has_role(arbiter.key, Role::Arbiter) {
+        return Err(TipError::Unauthorized.into());
+    }
+    check_shared_authority(&config)?;
+    check_admin_isolation(&config, iter)?;
+
+    if vesting_acc.owner != program_id || vault_acc.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let schedule = VestingSchedule::unpack(&vesting_acc.data.borrow())?;
+    if !schedule.is_initialized {
+        return Err(TipError::Uninitialized.into());
+    }
+    if !schedule.disputed {
+        return Err(TipError::NotDisputed.into());
+    }
+    if schedule.creator_vault != *vault_acc.key || schedule.tipper != *tipper.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let mut vault = CreatorVault::unpack(&vault_acc.data.borrow())?;
+    let (vault_pda, vault_bump) = get_creator_vault_pda(program_id, &vault.creator, &vault.mint);
+    if *vault_acc.key != vault_pda || *vault_token.key != get_creator_vault_token_pda(program_id, &vault_pda).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    let amount = sub_checked(schedule.amount, schedule.released)?;
+    if refund {
+        if vault.mint != *mint_acc.key {
+            return Err(TipError::MintMismatch.into());
+        }
+        if token::unpack_account(tipper_token)?.owner != schedule.tipper {
+            return Err(TipError::Unauthorized.into());
+        }
+        let mint = check_token_program(mint_acc, token_program)?;
+        invoke_signed(
+            &token::transfer_checked(
+                token_program.key, vault_token.key, mint_acc.key, tipper_token.key, vault_acc.key, amount, mint.decimals,
+            ),
+            &[vault_token.clone(), mint_acc.clone(), tipper_token.clone(), vault_acc.clone()],
+            &[&[b"creator_vault", vault.creator.as_ref(), vault.mint.as_ref(), &[vault_bump]]],
+        )?;
+    }
+    vault.locked = vault.locked.saturating_sub(amount);
+    vault.pack(&mut vault_acc.data.borrow_mut())?;
+    close_pda(vesting_acc, tipper)?;
+    audit(
+        program_id,
+        accounts,
+        &config,
+        AdminAction::ResolveDispute,
+        arbiter.key,
+        vesting_acc.key,
+        audit_words(&[amount]),
+        audit_words(&[refund as u64]),
+    )?;
+
+    msg!("Dispute resolved: {} to the {}", amount, if refund { "tipper" } else { "creator" });
+    DisputeResolved { schedule: *vesting_acc.key, refunded: refund, amount }.emit(accounts)
+}
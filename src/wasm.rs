@@ -0,0 +1,266 @@
+//! wasm-bindgen exports of the client helpers for browser frontends (feature `wasm`, wasm32 only).
+//!
+//! `Pubkey` is solana-program's own wasm class, so addresses move in and out as `Pubkey`s and
+//! convert to `@solana/web3.js` keys through `toBytes()`. Instructions come back as
+//! `ProgramInstruction`s exposing their program id, account metas and data. Amounts and platform
+//! ids are `bigint`s; decoders take raw account data and throw on malformed input.
+
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+use wasm_bindgen::prelude::*;
+
+use crate::client;
+use crate::state::{CreatorStats, TipConfig, TipperStats};
+
+#[wasm_bindgen(js_name = AccountMeta)]
+pub struct WasmAccountMeta {
+    pubkey: Pubkey,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+#[wasm_bindgen(js_class = AccountMeta)]
+impl WasmAccountMeta {
+    #[wasm_bindgen(getter)]
+    pub fn pubkey(&self) -> Pubkey {
+        self.pubkey
+    }
+
+    #[wasm_bindgen(getter, js_name = isSigner)]
+    pub fn is_signer(&self) -> bool {
+        self.is_signer
+    }
+
+    #[wasm_bindgen(getter, js_name = isWritable)]
+    pub fn is_writable(&self) -> bool {
+        self.is_writable
+    }
+}
+
+#[wasm_bindgen]
+pub struct ProgramInstruction(Instruction);
+
+#[wasm_bindgen]
+impl ProgramInstruction {
+    #[wasm_bindgen(getter, js_name = programId)]
+    pub fn program_id(&self) -> Pubkey {
+        self.0.program_id
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn accounts(&self) -> Vec<WasmAccountMeta> {
+        self.0
+            .accounts
+            .iter()
+            .map(|meta| WasmAccountMeta { pubkey: meta.pubkey, is_signer: meta.is_signer, is_writable: meta.is_writable })
+            .collect()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn data(&self) -> Vec<u8> {
+        self.0.data.clone()
+    }
+
+    /// Append the optional rent `payer`, as `instruction::with_rent_payer`
+    #[wasm_bindgen(js_name = withRentPayer)]
+    pub fn with_rent_payer(self, payer: &Pubkey) -> ProgramInstruction {
+        ProgramInstruction(client::with_rent_payer(self.0, payer))
+    }
+}
+
+#[wasm_bindgen(js_name = findConfigPda)]
+pub fn find_config_pda(program_id: &Pubkey, platform_id: u64) -> Pubkey {
+    client::find_config_pda(program_id, platform_id).0
+}
+
+#[wasm_bindgen(js_name = findCreatorStatsPda)]
+pub fn find_creator_stats_pda(program_id: &Pubkey, platform_id: u64, creator: &Pubkey) -> Pubkey {
+    client::find_creator_stats_pda(program_id, platform_id, creator).0
+}
+
+#[wasm_bindgen(js_name = findTipperStatsPda)]
+pub fn find_tipper_stats_pda(program_id: &Pubkey, platform_id: u64, tipper: &Pubkey) -> Pubkey {
+    client::find_tipper_stats_pda(program_id, platform_id, tipper).0
+}
+
+#[wasm_bindgen(js_name = findCreatorProfilePda)]
+pub fn find_creator_profile_pda(program_id: &Pubkey, creator: &Pubkey) -> Pubkey {
+    client::find_creator_profile_pda(program_id, creator).0
+}
+
+#[wasm_bindgen(js_name = findSupporterStatsPda)]
+pub fn find_supporter_stats_pda(program_id: &Pubkey, tipper: &Pubkey, creator: &Pubkey) -> Pubkey {
+    client::find_supporter_stats_pda(program_id, tipper, creator).0
+}
+
+#[wasm_bindgen(js_name = findAllowedMintPda)]
+pub fn find_allowed_mint_pda(program_id: &Pubkey, platform_id: u64, mint: &Pubkey) -> Pubkey {
+    client::find_allowed_mint_pda(program_id, platform_id, mint).0
+}
+
+/// `Tip` without any of the optional slots; see `instruction::tip`
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen]
+pub fn tip(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    tipper_token: &Pubkey,
+    creator: &Pubkey,
+    creator_token: &Pubkey,
+    treasury_token: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    memo: &str,
+) -> ProgramInstruction {
+    ProgramInstruction(client::tip(
+        program_id, platform_id, tipper, tipper_token, creator, creator_token, treasury_token, mint,
+        token_program, amount, memo, client::TipExtras::default(),
+    ))
+}
+
+#[wasm_bindgen(js_name = tipSol)]
+pub fn tip_sol(
+    program_id: &Pubkey,
+    platform_id: u64,
+    tipper: &Pubkey,
+    creator: &Pubkey,
+    treasury_token: &Pubkey,
+    treasury_wallet: &Pubkey,
+    amount: u64,
+) -> ProgramInstruction {
+    ProgramInstruction(client::tip_sol(program_id, platform_id, tipper, creator, treasury_token, treasury_wallet, amount))
+}
+
+#[wasm_bindgen(js_name = TipConfig)]
+pub struct WasmTipConfig(TipConfig);
+
+#[wasm_bindgen(js_class = TipConfig)]
+impl WasmTipConfig {
+    #[wasm_bindgen(getter)]
+    pub fn admin(&self) -> Pubkey {
+        self.0.admin
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn treasury(&self) -> Pubkey {
+        self.0.treasury
+    }
+
+    #[wasm_bindgen(getter, js_name = feeBps)]
+    pub fn fee_bps(&self) -> u16 {
+        self.0.fee_bps
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn paused(&self) -> bool {
+        self.0.paused
+    }
+
+    #[wasm_bindgen(getter, js_name = totalTips)]
+    pub fn total_tips(&self) -> u64 {
+        self.0.total_tips
+    }
+
+    #[wasm_bindgen(getter, js_name = totalVolume)]
+    pub fn total_volume(&self) -> u64 {
+        self.0.total_volume
+    }
+
+    #[wasm_bindgen(getter, js_name = minTipAmount)]
+    pub fn min_tip_amount(&self) -> u64 {
+        self.0.min_tip_amount
+    }
+
+    #[wasm_bindgen(getter, js_name = maxTipAmount)]
+    pub fn max_tip_amount(&self) -> u64 {
+        self.0.max_tip_amount
+    }
+}
+
+#[wasm_bindgen(js_name = CreatorStats)]
+pub struct WasmCreatorStats(CreatorStats);
+
+#[wasm_bindgen(js_class = CreatorStats)]
+impl WasmCreatorStats {
+    #[wasm_bindgen(getter)]
+    pub fn creator(&self) -> Pubkey {
+        self.0.creator
+    }
+
+    #[wasm_bindgen(getter, js_name = tipCount)]
+    pub fn tip_count(&self) -> u64 {
+        self.0.tip_count
+    }
+
+    #[wasm_bindgen(getter, js_name = totalReceived)]
+    pub fn total_received(&self) -> u64 {
+        self.0.total_received
+    }
+
+    #[wasm_bindgen(getter, js_name = largestTip)]
+    pub fn largest_tip(&self) -> u64 {
+        self.0.largest_tip
+    }
+
+    #[wasm_bindgen(getter, js_name = lastTipUnixTs)]
+    pub fn last_tip_unix_ts(&self) -> i64 {
+        self.0.last_tip_unix_ts
+    }
+
+    #[wasm_bindgen(getter, js_name = categoryCounts)]
+    pub fn category_counts(&self) -> Vec<u64> {
+        self.0.category_counts.to_vec()
+    }
+
+    #[wasm_bindgen(getter, js_name = tierCounts)]
+    pub fn tier_counts(&self) -> Vec<u64> {
+        self.0.tier_counts.to_vec()
+    }
+}
+
+#[wasm_bindgen(js_name = TipperStats)]
+pub struct WasmTipperStats(TipperStats);
+
+#[wasm_bindgen(js_class = TipperStats)]
+impl WasmTipperStats {
+    #[wasm_bindgen(getter)]
+    pub fn tipper(&self) -> Pubkey {
+        self.0.tipper
+    }
+
+    #[wasm_bindgen(getter, js_name = tipCount)]
+    pub fn tip_count(&self) -> u64 {
+        self.0.tip_count
+    }
+
+    #[wasm_bindgen(getter, js_name = totalVolume)]
+    pub fn total_volume(&self) -> u64 {
+        self.0.total_volume
+    }
+
+    #[wasm_bindgen(getter, js_name = currentStreakDays)]
+    pub fn current_streak_days(&self) -> u64 {
+        self.0.current_streak_days
+    }
+
+    #[wasm_bindgen(getter, js_name = longestStreak)]
+    pub fn longest_streak(&self) -> u64 {
+        self.0.longest_streak
+    }
+}
+
+#[wasm_bindgen(js_name = decodeConfig)]
+pub fn decode_config(data: &[u8]) -> Result<WasmTipConfig, JsError> {
+    client::decode_config(data).map(WasmTipConfig).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = decodeCreatorStats)]
+pub fn decode_creator_stats(data: &[u8]) -> Result<WasmCreatorStats, JsError> {
+    client::decode_account(data).map(WasmCreatorStats).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = decodeTipperStats)]
+pub fn decode_tipper_stats(data: &[u8]) -> Result<WasmTipperStats, JsError> {
+    client::decode_account(data).map(WasmTipperStats).map_err(|e| JsError::new(&e.to_string()))
+}
@@ -0,0 +1,172 @@
+//! Property tests for the fee and split arithmetic every tip path shares, run against the functions
+//! Tip itself calls, and for the volume counters tips feed.
+
+use proptest::prelude::*;
+use solana_program::pubkey::Pubkey;
+use tip_program::{
+    state::{FeeTier, MAX_COLLABORATORS, MAX_FEE_TIERS, VOLUME_BUCKET_SECS, VOLUME_WINDOW_BUCKETS},
+    test_utils::*,
+};
+
+/// Fee tiers as SetFeeTiers accepts them: increasing minimums, at most 1000 bps each
+fn fee_tiers() -> impl Strategy<Value = Vec<FeeTier>> {
+    prop::collection::btree_set(1..u64::MAX, 0..=MAX_FEE_TIERS).prop_flat_map(|minimums| {
+        let count = minimums.len();
+        prop::collection::vec(0..=1000u16, count).prop_map(move |bps| {
+            minimums.iter().zip(bps).map(|(&min_amount, fee_bps)| FeeTier { min_amount, fee_bps }).collect()
+        })
+    })
+}
+
+/// Collaborator shares as SetCollaborators accepts them: at most 10000 bps in all
+fn collaborator_bps() -> impl Strategy<Value = Vec<u16>> {
+    prop::collection::vec(0..=2_500u16, 0..=MAX_COLLABORATORS)
+}
+
+proptest! {
+    #[test]
+    fn tip_parts_sum_to_amount(
+        amount in 1..=u64::MAX,
+        fee_bps in 0..=1000u16,
+        tiers in fee_tiers(),
+        discount_bps in 0..=10_000u16,
+        referral_bps in 0..=10_000u16,
+        donation_bps in 0..=10_000u16,
+        collaborator_bps in collaborator_bps(),
+        tax_bps in 0..=10_000u16,
+        forward_bps in 0..=10_000u16,
+    ) {
+        let schedule = tiered_schedule(fee_bps, &tiers);
+        let Ok(fee) = compute_fee(schedule, amount) else {
+            // Only a tip too small to carry its fee is refused
+            prop_assert!((amount as u128 * schedule.bps_for(amount) as u128) < 10_000);
+            return Ok(());
+        };
+        prop_assert!(fee <= amount);
+        let (fee, waived) = apply_discount(fee, discount_bps).unwrap();
+        prop_assert_eq!(waived, bps_share(fee + waived, discount_bps as u64));
+        let rates = TipRates { referral_bps, donation_bps, collaborator_bps: &collaborator_bps, tax_bps, forward_bps };
+        let parts = split_tip(amount, fee, rates).unwrap();
+        prop_assert_eq!(parts.collaborators.len(), collaborator_bps.len());
+        prop_assert_eq!(parts.referral as u128 + parts.treasury as u128, fee as u128);
+        let paid = [parts.referral, parts.treasury, parts.donated, parts.withheld, parts.forwarded, parts.creator];
+        let total = paid.iter().chain(&parts.collaborators).map(|&part| part as u128).sum::<u128>();
+        prop_assert_eq!(total, amount as u128);
+    }
+
+    #[test]
+    fn fee_is_refused_only_when_it_rounds_to_zero(amount in 1..=u64::MAX, fee_bps in 1..=1000u16) {
+        let representable = amount as u128 * fee_bps as u128 >= 10_000;
+        prop_assert_eq!(compute_fee(flat_schedule(fee_bps), amount).is_ok(), representable);
+    }
+
+    #[test]
+    fn fee_stops_at_target(
+        amount in 10_000..=u64::MAX,
+        fee_bps in 1..=1000u16,
+        target in 1..u64::MAX,
+        paid in 0..u64::MAX,
+    ) {
+        let mut schedule = flat_schedule(fee_bps);
+        schedule.fee_target = target;
+        schedule.total_fees = paid;
+        let fee = compute_fee(schedule, amount).unwrap();
+        prop_assert!(fee <= target.saturating_sub(paid));
+        prop_assert!(fee <= bps_share(amount, fee_bps as u64));
+    }
+
+    #[test]
+    fn bps_share_never_exceeds_amount(amount: u64, bps in 0..=10_000u64) {
+        let share = bps_share(amount, bps);
+        prop_assert!(share <= amount);
+        prop_assert_eq!(share as u128, amount as u128 * bps as u128 / 10_000);
+    }
+
+    #[test]
+    fn treasury_split_pays_exactly_the_fee(fee: u64, entries in prop::collection::vec(0..=2_500u16, 0..=4)) {
+        // Entries are capped so they sum to at most 10000 bps, as SetTreasurySplit requires
+        let (shares, treasury) = split_treasury_fee(fee, &entries).unwrap();
+        prop_assert_eq!(shares.len(), entries.len());
+        for (&share, &bps) in shares.iter().zip(&entries) {
+            prop_assert_eq!(share, bps_share(fee, bps as u64));
+        }
+        let paid: u128 = shares.iter().map(|&share| share as u128).sum();
+        prop_assert_eq!(paid + treasury as u128, fee as u128);
+    }
+
+    #[test]
+    fn collaborators_leave_the_creator_the_rest(amount: u64, shares in collaborator_bps()) {
+        let cuts = collaborator_cuts(amount, &shares).unwrap();
+        prop_assert_eq!(cuts.len(), shares.len() + 1);
+        prop_assert_eq!(cuts.iter().map(|&cut| cut as u128).sum::<u128>(), amount as u128);
+    }
+
+    #[test]
+    fn weighted_split_sums_to_amount(amount: u64, weights in prop::collection::vec(any::<u64>(), 1..=32)) {
+        let weights: Vec<u128> = weights.into_iter().map(u128::from).collect();
+        let parts = split_by_weight(amount, &weights).unwrap();
+        prop_assert_eq!(parts.len(), weights.len());
+        let total: u128 = weights.iter().sum();
+        if total == 0 {
+            prop_assert!(parts.iter().all(|&part| part == 0));
+            return Ok(());
+        }
+        prop_assert_eq!(parts.iter().map(|&part| part as u128).sum::<u128>(), amount as u128);
+        // Only the first of the largest weights gets more than its rounded-down share
+        let largest = weights.iter().enumerate().rev().max_by_key(|(_, &weight)| weight).unwrap().0;
+        for (i, (&part, &weight)) in parts.iter().zip(&weights).enumerate() {
+            let floor = (amount as u128 * weight / total) as u64;
+            if i == largest {
+                prop_assert!(part >= floor);
+            } else {
+                prop_assert_eq!(part, floor);
+            }
+        }
+    }
+
+    #[test]
+    fn weighted_split_ignores_order(amount: u64, weights in prop::collection::vec(0..=10_000u128, 1..=5)) {
+        let mut forward = split_by_weight(amount, &weights).unwrap();
+        let reversed_weights: Vec<u128> = weights.iter().rev().copied().collect();
+        let mut reversed = split_by_weight(amount, &reversed_weights).unwrap();
+        forward.sort_unstable();
+        reversed.sort_unstable();
+        prop_assert_eq!(forward, reversed);
+    }
+
+    #[test]
+    fn volume_window_only_grows_by_each_tip(
+        start in 0..i64::MAX / 2,
+        tips in prop::collection::vec((0..3 * VOLUME_BUCKET_SECS, 0..=u32::MAX as u64), 1..64),
+    ) {
+        let mut window = volume_window(start);
+        let mut now = start;
+        for (elapsed, amount) in tips {
+            now += elapsed;
+            let before = window.volume(now, VOLUME_WINDOW_BUCKETS);
+            window.record(now, amount).unwrap();
+            prop_assert_eq!(window.volume(now, VOLUME_WINDOW_BUCKETS), before + amount);
+            prop_assert!(window.volume(now, 24) <= window.volume(now, VOLUME_WINDOW_BUCKETS));
+        }
+    }
+
+    #[test]
+    fn leaderboard_stays_sorted(tips in prop::collection::vec((0..48u8, 1..=u32::MAX as u64), 1..128)) {
+        let creators: Vec<Pubkey> = (0..48).map(|_| Pubkey::new_unique()).collect();
+        let mut volumes = vec![0u64; creators.len()];
+        let mut board = leaderboard(0);
+        for (i, amount) in tips {
+            let i = i as usize;
+            volumes[i] += amount;
+            board.record(&creators[i], volumes[i]);
+            let entries = board.entries;
+            let sorted = entries.windows(2).all(|pair| { pair[0].volume } >= { pair[1].volume });
+            prop_assert!(sorted);
+            // A ranked creator's entry is their current volume, which only grows
+            for entry in entries.iter().filter(|e| { e.volume } > 0) {
+                let ranked = creators.iter().position(|c| *c == entry.creator).unwrap();
+                prop_assert_eq!({ entry.volume }, volumes[ranked]);
+            }
+        }
+    }
+}
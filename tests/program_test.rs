@@ -0,0 +1,57 @@
+//! End-to-end runs of the processor in a `ProgramTest` bank through the `testing` harness.
+
+use solana_sdk::{signature::Keypair, signer::Signer};
+use tip_program::{
+    instruction::{self, TipExtras},
+    state::FEE_UPDATE_DELAY_SLOTS,
+    testing::{self, assert_fee_bps, assert_initialized, assert_total_tips},
+    PROGRAM_ID,
+};
+
+#[tokio::test]
+async fn initialized_config_takes_a_tip_and_a_fee_update() {
+    let mut ctx = testing::start().await;
+    let admin = ctx.payer.pubkey();
+    let mint_authority = Keypair::new();
+    let (tipper, creator, treasury) = (Keypair::new(), Keypair::new(), Keypair::new());
+
+    let mint = testing::create_mint(&mut ctx, &mint_authority.pubkey(), 6).await.unwrap();
+    let tipper_token = testing::create_ata(&mut ctx, &tipper.pubkey(), &mint).await.unwrap();
+    let creator_token = testing::create_ata(&mut ctx, &creator.pubkey(), &mint).await.unwrap();
+    let treasury_token = testing::create_ata(&mut ctx, &treasury.pubkey(), &mint).await.unwrap();
+    testing::mint_to(&mut ctx, &mint, &tipper_token, &mint_authority, 1_000_000).await.unwrap();
+    testing::fund(&mut ctx, &tipper.pubkey(), 1_000_000_000).await.unwrap();
+
+    testing::initialize_config(&mut ctx, 0, &treasury_token, 250).await.unwrap();
+    testing::allow_mint(&mut ctx, 0, &mint).await.unwrap();
+    assert_initialized(&testing::config(&mut ctx, 0).await, &admin, &treasury_token, 250);
+
+    let tip = instruction::tip(
+        &PROGRAM_ID,
+        0,
+        &tipper.pubkey(),
+        &tipper_token,
+        &creator.pubkey(),
+        &creator_token,
+        &treasury_token,
+        &mint,
+        &spl_token::id(),
+        100_000,
+        "gm",
+        TipExtras::default(),
+    );
+    testing::process(&mut ctx, &[tip], &[&tipper]).await.unwrap();
+    assert_total_tips(&testing::config(&mut ctx, 0).await, 1);
+    assert_eq!(testing::token_balance(&mut ctx, &tipper_token).await, 900_000);
+    assert_eq!(testing::token_balance(&mut ctx, &creator_token).await, 97_500);
+    assert_eq!(testing::token_balance(&mut ctx, &treasury_token).await, 2_500);
+
+    let schedule = instruction::schedule_fee_update(&PROGRAM_ID, 0, &admin, 100);
+    testing::process(&mut ctx, &[schedule], &[]).await.unwrap();
+    assert_fee_bps(&testing::config(&mut ctx, 0).await, 250);
+    let slot = ctx.banks_client.get_root_slot().await.unwrap();
+    ctx.warp_to_slot(slot + FEE_UPDATE_DELAY_SLOTS + 1).unwrap();
+    let apply = instruction::apply_fee_update(&PROGRAM_ID, 0);
+    testing::process(&mut ctx, &[apply], &[]).await.unwrap();
+    assert_fee_bps(&testing::config(&mut ctx, 0).await, 100);
+}
@@ -0,0 +1,47 @@
+//! Round trips of the Solana Pay link and transaction a tip QR code produces.
+
+use proptest::prelude::*;
+use solana_program::{hash::Hash, pubkey::Pubkey};
+use tip_program::{
+    instruction::TipExtras,
+    solana_pay::{tip_transaction, TipRequest},
+};
+
+proptest! {
+    #[test]
+    fn link_query_round_trips(amount: u64, memo in "\\PC{0,80}", platform_id: u64) {
+        let request = TipRequest {
+            creator: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            token_program: spl_token::id(),
+            amount,
+            memo,
+            platform_id,
+        };
+        let link = request.link("https://example.com/api/tip");
+        prop_assert!(link.starts_with("solana:https%3A%2F%2Fexample.com%2Fapi%2Ftip%3F"));
+        prop_assert_eq!(TipRequest::from_query(&request.query()), Some(request));
+    }
+}
+
+#[test]
+fn transaction_is_unsigned_with_the_tipper_paying() {
+    let program_id = Pubkey::new_unique();
+    let tipper = Pubkey::new_unique();
+    let request = TipRequest {
+        creator: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        token_program: spl_token::id(),
+        amount: 1_000_000,
+        memo: "gm".into(),
+        platform_id: 0,
+    };
+    let treasury_token = Pubkey::new_unique();
+    let tx = tip_transaction(&program_id, &request, &tipper, &treasury_token, TipExtras::default(), &Hash::default());
+    assert_eq!(tx[0], 1);
+    assert!(tx[1..65].iter().all(|b| *b == 0));
+    // Message: 3 header bytes, the account key count, then the fee payer first
+    let message = &tx[65..];
+    assert_eq!(message[0], 1);
+    assert_eq!(&message[4..36], tipper.as_ref());
+}